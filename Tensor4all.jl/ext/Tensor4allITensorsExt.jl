@@ -19,6 +19,7 @@ This ensures deterministic matching for indices created in either system.
 - DenseF64 ↔ Dense{Float64}
 - DenseC64 ↔ Dense{ComplexF64}
 - DiagF64, DiagC64 → Error (not yet supported)
+- BlockSparseF64, BlockSparseC64 → Error (not yet supported)
 
 ## Memory Order
 
@@ -129,7 +130,7 @@ Base.convert(::Type{Tensor4all.Index}, idx::ITensors.Index) = Tensor4all.Index(i
 Convert a Tensor4all.Tensor to an ITensors.ITensor.
 
 Currently only supports DenseF64 and DenseC64 storage types.
-Diag storage will raise an error.
+Diag and BlockSparse storage will raise an error.
 """
 function ITensors.ITensor(t::Tensor4all.Tensor)
     kind = Tensor4all.storage_kind(t)
@@ -138,6 +139,9 @@ function ITensors.ITensor(t::Tensor4all.Tensor)
     if kind == Tensor4all.DiagF64 || kind == Tensor4all.DiagC64
         error("Diag storage not yet supported for ITensor conversion")
     end
+    if kind == Tensor4all.BlockSparseF64 || kind == Tensor4all.BlockSparseC64
+        error("BlockSparse storage not yet supported for ITensor conversion")
+    end
 
     # Get indices and convert
     t4a_inds = Tensor4all.indices(t)
@@ -289,6 +289,12 @@ const STORAGE_DENSE_F64 = Cint(0)
 const STORAGE_DENSE_C64 = Cint(1)
 const STORAGE_DIAG_F64 = Cint(2)
 const STORAGE_DIAG_C64 = Cint(3)
+const STORAGE_BLOCK_SPARSE_F64 = Cint(4)
+const STORAGE_BLOCK_SPARSE_C64 = Cint(5)
+const STORAGE_DENSE_F32 = Cint(6)
+const STORAGE_DENSE_C32 = Cint(7)
+const STORAGE_DIAG_F32 = Cint(8)
+const STORAGE_DIAG_C32 = Cint(9)
 
 # ============================================================================
 # Tensor lifecycle functions
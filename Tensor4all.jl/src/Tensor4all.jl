@@ -47,7 +47,8 @@ include("C_API.jl")
 # Re-export public API
 export Index, dim, tags, id, hastag
 export Tensor, rank, dims, indices, storage_kind, data
-export StorageKind, DenseF64, DenseC64, DiagF64, DiagC64
+export StorageKind, DenseF64, DenseC64, DiagF64, DiagC64, BlockSparseF64, BlockSparseC64
+export DenseF32, DenseC32, DiagF32, DiagC32
 
 """
     Index
@@ -217,6 +218,12 @@ Enum representing the storage type of a tensor.
     DenseC64 = 1
     DiagF64 = 2
     DiagC64 = 3
+    BlockSparseF64 = 4
+    BlockSparseC64 = 5
+    DenseF32 = 6
+    DenseC32 = 7
+    DiagF32 = 8
+    DiagC32 = 9
 end
 
 """
@@ -423,12 +430,15 @@ end
     data(t::Tensor) -> Array
 
 Get the tensor data as a column-major Julia array.
+
+Diagonal and block-sparse tensors are returned as dense arrays, and single-precision
+tensors as `Float32` or `ComplexF32` arrays.
 """
 function data(t::Tensor)
     kind = storage_kind(t)
     d = dims(t)
 
-    if kind == DenseF64
+    if kind in (DenseF64, DiagF64, BlockSparseF64, DenseF32, DiagF32)
         # Query length
         out_len = Ref{Csize_t}(0)
         status = C_API.t4a_tensor_get_data_f64(t.ptr, nothing, 0, out_len)
@@ -440,9 +450,12 @@ function data(t::Tensor)
         C_API.check_status(status)
 
         # Convert from row-major to column-major
+        if kind in (DenseF32, DiagF32)
+            return _row_to_column_major(Float32.(buf), d)
+        end
         return _row_to_column_major(buf, d)
 
-    elseif kind == DenseC64
+    elseif kind in (DenseC64, DiagC64, BlockSparseC64, DenseC32, DiagC32)
         # Query length
         out_len = Ref{Csize_t}(0)
         status = C_API.t4a_tensor_get_data_c64(t.ptr, nothing, nothing, 0, out_len)
@@ -456,6 +469,9 @@ function data(t::Tensor)
 
         # Combine and convert
         buf = [ComplexF64(r, i) for (r, i) in zip(buf_re, buf_im)]
+        if kind in (DenseC32, DiagC32)
+            return _row_to_column_major(ComplexF32.(buf), d)
+        end
         return _row_to_column_major(buf, d)
 
     else
@@ -19,6 +19,12 @@ ffi.cdef("""
         DenseC64 = 1,
         DiagF64 = 2,
         DiagC64 = 3,
+        BlockSparseF64 = 4,
+        BlockSparseC64 = 5,
+        DenseF32 = 6,
+        DenseC32 = 7,
+        DiagF32 = 8,
+        DiagC32 = 9,
     } t4a_storage_kind;
 
     // ========================================================================
@@ -68,6 +74,36 @@ ffi.cdef("""
         const double* data_im,
         size_t data_len
     );
+    t4a_tensor* t4a_tensor_new_dense_f32(
+        size_t rank,
+        const t4a_index** index_ptrs,
+        const size_t* dims,
+        const float* data,
+        size_t data_len
+    );
+    t4a_tensor* t4a_tensor_new_dense_c32(
+        size_t rank,
+        const t4a_index** index_ptrs,
+        const size_t* dims,
+        const float* data_re,
+        const float* data_im,
+        size_t data_len
+    );
+    t4a_tensor* t4a_tensor_new_diag_f64(
+        size_t rank,
+        const t4a_index** index_ptrs,
+        const size_t* dims,
+        const double* diag,
+        size_t diag_len
+    );
+    t4a_tensor* t4a_tensor_new_diag_c64(
+        size_t rank,
+        const t4a_index** index_ptrs,
+        const size_t* dims,
+        const double* diag_re,
+        const double* diag_im,
+        size_t diag_len
+    );
 
     // Accessors
     StatusCode t4a_tensor_get_rank(const t4a_tensor* ptr, size_t* out_rank);
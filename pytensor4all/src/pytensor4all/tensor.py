@@ -21,6 +21,27 @@ class StorageKind(IntEnum):
     DenseC64 = 1
     DiagF64 = 2
     DiagC64 = 3
+    BlockSparseF64 = 4
+    BlockSparseC64 = 5
+    DenseF32 = 6
+    DenseC32 = 7
+    DiagF32 = 8
+    DiagC32 = 9
+
+
+# NumPy dtype of the elements of each storage kind
+_DTYPES = {
+    StorageKind.DenseF64: np.dtype(np.float64),
+    StorageKind.DiagF64: np.dtype(np.float64),
+    StorageKind.BlockSparseF64: np.dtype(np.float64),
+    StorageKind.DenseC64: np.dtype(np.complex128),
+    StorageKind.DiagC64: np.dtype(np.complex128),
+    StorageKind.BlockSparseC64: np.dtype(np.complex128),
+    StorageKind.DenseF32: np.dtype(np.float32),
+    StorageKind.DiagF32: np.dtype(np.float32),
+    StorageKind.DenseC32: np.dtype(np.complex64),
+    StorageKind.DiagC32: np.dtype(np.complex64),
+}
 
 
 class Tensor:
@@ -52,7 +73,8 @@ class Tensor:
             List of indices, one for each dimension.
         data : np.ndarray
             NumPy array with shape matching the index dimensions.
-            Supports float64 and complex128 dtypes.
+            float32 and complex64 data is stored in single precision; other
+            data is stored as float64 or complex128.
 
         Raises
         ------
@@ -87,7 +109,19 @@ class Tensor:
             dims[i] = idx.dim
 
         # Ensure contiguous C-order array
-        if np.iscomplexobj(data):
+        if data.dtype == np.complex64:
+            data_re = np.ascontiguousarray(data.real)
+            data_im = np.ascontiguousarray(data.imag)
+
+            ptr = lib.t4a_tensor_new_dense_c32(
+                rank,
+                ffi.cast("const t4a_index**", index_ptrs),
+                dims,
+                ffi.cast("const float*", ffi.from_buffer(data_re)),
+                ffi.cast("const float*", ffi.from_buffer(data_im)),
+                data.size,
+            )
+        elif np.iscomplexobj(data):
             data = np.ascontiguousarray(data, dtype=np.complex128)
             data_re = np.ascontiguousarray(data.real)
             data_im = np.ascontiguousarray(data.imag)
@@ -100,6 +134,16 @@ class Tensor:
                 ffi.cast("const double*", ffi.from_buffer(data_im)),
                 data.size,
             )
+        elif data.dtype == np.float32:
+            data = np.ascontiguousarray(data)
+
+            ptr = lib.t4a_tensor_new_dense_f32(
+                rank,
+                ffi.cast("const t4a_index**", index_ptrs),
+                dims,
+                ffi.cast("const float*", ffi.from_buffer(data)),
+                data.size,
+            )
         else:
             data = np.ascontiguousarray(data, dtype=np.float64)
 
@@ -116,6 +160,72 @@ class Tensor:
 
         self._ptr = ptr
 
+    @classmethod
+    def diag(cls, indices: list[Index], values: NDArray) -> Tensor:
+        """Create a diagonal tensor.
+
+        Parameters
+        ----------
+        indices : list[Index]
+            List of indices, all with the same dimension.
+        values : np.ndarray
+            1D array of diagonal elements, stored as float64 or complex128.
+
+        Raises
+        ------
+        ValueError
+            If the index dimensions differ or don't match the number of values.
+        T4AError
+            If creation fails.
+        """
+        values = np.asarray(values)
+        if values.ndim != 1:
+            raise ValueError("Diagonal values must be a 1D array")
+        if not indices or any(idx.dim != values.size for idx in indices):
+            raise ValueError(
+                f"All indices must have dimension {values.size} (the number of diagonal values)"
+            )
+
+        lib = get_lib()
+        rank = len(indices)
+
+        index_ptrs = ffi.new(f"t4a_index*[{rank}]")
+        for i, idx in enumerate(indices):
+            index_ptrs[i] = idx._ptr
+
+        dims = ffi.new(f"size_t[{rank}]")
+        for i, idx in enumerate(indices):
+            dims[i] = idx.dim
+
+        if np.iscomplexobj(values):
+            values = np.ascontiguousarray(values, dtype=np.complex128)
+            values_re = np.ascontiguousarray(values.real)
+            values_im = np.ascontiguousarray(values.imag)
+
+            ptr = lib.t4a_tensor_new_diag_c64(
+                rank,
+                ffi.cast("const t4a_index**", index_ptrs),
+                dims,
+                ffi.cast("const double*", ffi.from_buffer(values_re)),
+                ffi.cast("const double*", ffi.from_buffer(values_im)),
+                values.size,
+            )
+        else:
+            values = np.ascontiguousarray(values, dtype=np.float64)
+
+            ptr = lib.t4a_tensor_new_diag_f64(
+                rank,
+                ffi.cast("const t4a_index**", index_ptrs),
+                dims,
+                ffi.cast("const double*", ffi.from_buffer(values)),
+                values.size,
+            )
+
+        if ptr == ffi.NULL:
+            raise T4AError("Failed to create diagonal Tensor")
+
+        return cls._from_ptr(ptr)
+
     @classmethod
     def _from_ptr(cls, ptr) -> Tensor:
         """Create a Tensor from an existing C pointer (internal use)."""
@@ -192,27 +302,29 @@ class Tensor:
     def dtype(self) -> np.dtype:
         """Get the NumPy dtype corresponding to the storage kind."""
         kind = self.storage_kind
-        if kind in (StorageKind.DenseF64, StorageKind.DiagF64):
-            return np.dtype(np.float64)
-        else:
-            return np.dtype(np.complex128)
+        try:
+            return _DTYPES[kind]
+        except KeyError:
+            raise NotImplementedError(f"Unsupported storage kind: {kind}") from None
 
     def to_numpy(self) -> NDArray:
         """Convert the tensor data to a NumPy array.
 
+        Diagonal and block-sparse tensors are converted to dense arrays.
+
         Returns
         -------
         np.ndarray
-            NumPy array with the tensor data in C order.
+            NumPy array with the tensor data in C order, of type ``self.dtype``.
         """
         lib = get_lib()
-        kind = self.storage_kind
+        dtype = self.dtype
         dims = self.dims
 
         # Query data length
         out_len = ffi.new("size_t*")
 
-        if kind == StorageKind.DenseF64:
+        if dtype.kind == "f":
             status = lib.t4a_tensor_get_data_f64(self._ptr, ffi.NULL, 0, out_len)
             check_status(status, "Failed to get data length")
 
@@ -224,9 +336,9 @@ class Tensor:
                 out_len,
             )
             check_status(status, "Failed to get f64 data")
-            return buf.reshape(dims)
+            return buf.astype(dtype, copy=False).reshape(dims)
 
-        elif kind == StorageKind.DenseC64:
+        else:
             status = lib.t4a_tensor_get_data_c64(
                 self._ptr, ffi.NULL, ffi.NULL, 0, out_len
             )
@@ -242,10 +354,7 @@ class Tensor:
                 out_len,
             )
             check_status(status, "Failed to get c64 data")
-            return (buf_re + 1j * buf_im).reshape(dims)
-
-        else:
-            raise NotImplementedError(f"Diagonal tensors not yet supported: {kind}")
+            return (buf_re + 1j * buf_im).astype(dtype, copy=False).reshape(dims)
 
     def clone(self) -> Tensor:
         """Create a copy of this tensor."""
@@ -108,6 +108,74 @@ class TestTensorData:
         np.testing.assert_array_equal(result, [[1, 2, 3], [4, 5, 6]])
 
 
+class TestTensorStorageKinds:
+    """Single-precision, diagonal and block-sparse storage tests."""
+
+    def test_float32(self):
+        """Test that float32 data is stored and returned in single precision."""
+        i = Index(2)
+        j = Index(2)
+        data = np.array([[1.5, -2.0], [0.25, 4.0]], dtype=np.float32)
+        t = Tensor([i, j], data)
+
+        assert t.storage_kind == StorageKind.DenseF32
+        assert t.dtype == np.float32
+        result = t.to_numpy()
+        assert result.dtype == np.float32
+        np.testing.assert_array_equal(result, data)
+
+    def test_complex64(self):
+        """Test that complex64 data is stored and returned in single precision."""
+        i = Index(2)
+        data = np.array([1 + 0.5j, 2 - 0.5j], dtype=np.complex64)
+        t = Tensor([i], data)
+
+        assert t.storage_kind == StorageKind.DenseC32
+        assert t.dtype == np.complex64
+        result = t.to_numpy()
+        assert result.dtype == np.complex64
+        np.testing.assert_array_equal(result, data)
+
+    def test_diag_f64(self):
+        """Test that a diagonal tensor is returned as a dense array."""
+        i = Index(3)
+        j = Index(3)
+        t = Tensor.diag([i, j], np.array([1.0, 2.0, 3.0]))
+
+        assert t.storage_kind == StorageKind.DiagF64
+        assert t.dtype == np.float64
+        np.testing.assert_array_equal(t.to_numpy(), np.diag([1.0, 2.0, 3.0]))
+
+    def test_diag_c64(self):
+        """Test converting a complex diagonal tensor to numpy."""
+        i = Index(2)
+        j = Index(2)
+        t = Tensor.diag([i, j], np.array([1 + 1j, 2 - 1j]))
+
+        assert t.storage_kind == StorageKind.DiagC64
+        assert t.dtype == np.complex128
+        np.testing.assert_array_equal(t.to_numpy(), np.diag([1 + 1j, 2 - 1j]))
+
+    def test_diag_dimension_mismatch(self):
+        """Test that a diagonal tensor needs equal index dimensions."""
+        with pytest.raises(ValueError):
+            Tensor.diag([Index(2), Index(3)], np.array([1.0, 2.0]))
+
+    @pytest.mark.parametrize(
+        "kind, dtype",
+        [
+            (StorageKind.BlockSparseF64, np.float64),
+            (StorageKind.BlockSparseC64, np.complex128),
+            (StorageKind.DiagF32, np.float32),
+            (StorageKind.DiagC32, np.complex64),
+        ],
+    )
+    def test_dtype_of_every_kind(self, monkeypatch, kind, dtype):
+        """Test that the dtype follows the element type of every storage kind."""
+        t = Tensor([Index(2)], np.zeros(2))
+        monkeypatch.setattr(Tensor, "storage_kind", property(lambda self: kind))
+        assert t.dtype == dtype
+
 class TestTensorIndices:
     """Index access tests."""
 
@@ -6,9 +6,11 @@ use std::panic::catch_unwind;
 use std::ptr;
 use std::sync::Arc;
 
-use num_complex::Complex64;
-use tensor4all_core_tensor::storage::{DenseStorageF64, DenseStorageC64};
-use tensor4all_core_tensor::Storage;
+use num_complex::{Complex32, Complex64};
+use tensor4all_core_tensor::storage::{
+    DenseStorageC32, DenseStorageC64, DenseStorageF32, DenseStorageF64,
+};
+use tensor4all_core_tensor::{ScalarType, Storage};
 
 use crate::types::{t4a_index, t4a_tensor, t4a_storage_kind, InternalIndex, InternalTensor};
 use crate::{StatusCode, T4A_SUCCESS, T4A_NULL_POINTER, T4A_INVALID_ARGUMENT, T4A_BUFFER_TOO_SMALL, T4A_INTERNAL_ERROR};
@@ -144,7 +146,38 @@ pub extern "C" fn t4a_tensor_get_storage_kind(
     result.unwrap_or(T4A_INTERNAL_ERROR)
 }
 
-/// Get the dense f64 data from a tensor in row-major order.
+/// Dense row-major storage of a tensor with elements of type `scalar_type`.
+fn dense_storage(tensor: &InternalTensor, scalar_type: ScalarType) -> Storage {
+    tensor.storage.to_dense_storage(&tensor.dims).to_scalar_type(scalar_type)
+}
+
+/// Read `rank` index handles and dimensions from C arrays.
+///
+/// Returns `None` if an index handle is NULL.
+fn read_indices_and_dims(
+    rank: libc::size_t,
+    index_ptrs: *const *const t4a_index,
+    dims: *const libc::size_t,
+) -> Option<(Vec<InternalIndex>, Vec<usize>)> {
+    let mut indices: Vec<InternalIndex> = Vec::with_capacity(rank);
+    for i in 0..rank {
+        let idx_ptr = unsafe { *index_ptrs.add(i) };
+        if idx_ptr.is_null() {
+            return None;
+        }
+        let idx = unsafe { &*idx_ptr };
+        indices.push(idx.inner().clone());
+    }
+    let dims_vec: Vec<usize> = (0..rank)
+        .map(|i| unsafe { *dims.add(i) })
+        .collect();
+    Some((indices, dims_vec))
+}
+
+/// Get the dense f64 data from a real tensor in row-major order.
+///
+/// Diag and BlockSparse storage is densified (missing elements are zero) and f32
+/// elements are converted to f64.
 ///
 /// # Arguments
 /// - `ptr`: Tensor handle
@@ -155,7 +188,7 @@ pub extern "C" fn t4a_tensor_get_storage_kind(
 /// # Returns
 /// - T4A_SUCCESS on success
 /// - T4A_BUFFER_TOO_SMALL if buffer is too small (out_len is still written)
-/// - T4A_INVALID_ARGUMENT if storage is not DenseF64
+/// - T4A_INVALID_ARGUMENT if the storage is complex
 ///
 /// # Safety
 /// - `ptr` must be a valid pointer to a t4a_tensor
@@ -173,11 +206,21 @@ pub extern "C" fn t4a_tensor_get_data_f64(
     }
 
     let result = catch_unwind(|| {
-        let tensor = unsafe { &*ptr };
+        let tensor = unsafe { &*ptr }.inner();
+        if tensor.storage.is_complex() {
+            return T4A_INVALID_ARGUMENT;
+        }
 
-        let data = match tensor.inner().storage.as_ref() {
+        let converted;
+        let data = match tensor.storage.as_ref() {
             Storage::DenseF64(ds) => ds.as_slice(),
-            _ => return T4A_INVALID_ARGUMENT,
+            _ => {
+                converted = dense_storage(tensor, ScalarType::F64);
+                match &converted {
+                    Storage::DenseF64(ds) => ds.as_slice(),
+                    _ => return T4A_INTERNAL_ERROR,
+                }
+            }
         };
 
         unsafe { *out_len = data.len() };
@@ -201,6 +244,9 @@ pub extern "C" fn t4a_tensor_get_data_f64(
 
 /// Get the dense complex64 data from a tensor in row-major order.
 ///
+/// Diag and BlockSparse storage is densified (missing elements are zero), and
+/// Complex32 and real elements are converted to Complex64.
+///
 /// # Arguments
 /// - `ptr`: Tensor handle
 /// - `buf_re`: Buffer to write real parts (if NULL, only out_len is written)
@@ -211,7 +257,6 @@ pub extern "C" fn t4a_tensor_get_data_f64(
 /// # Returns
 /// - T4A_SUCCESS on success
 /// - T4A_BUFFER_TOO_SMALL if buffer is too small (out_len is still written)
-/// - T4A_INVALID_ARGUMENT if storage is not DenseC64
 ///
 /// # Safety
 /// - `ptr` must be a valid pointer to a t4a_tensor
@@ -230,11 +275,18 @@ pub extern "C" fn t4a_tensor_get_data_c64(
     }
 
     let result = catch_unwind(|| {
-        let tensor = unsafe { &*ptr };
+        let tensor = unsafe { &*ptr }.inner();
 
-        let data = match tensor.inner().storage.as_ref() {
+        let converted;
+        let data = match tensor.storage.as_ref() {
             Storage::DenseC64(ds) => ds.as_slice(),
-            _ => return T4A_INVALID_ARGUMENT,
+            _ => {
+                converted = dense_storage(tensor, ScalarType::C64);
+                match &converted {
+                    Storage::DenseC64(ds) => ds.as_slice(),
+                    _ => return T4A_INTERNAL_ERROR,
+                }
+            }
         };
 
         unsafe { *out_len = data.len() };
@@ -288,21 +340,10 @@ pub extern "C" fn t4a_tensor_new_dense_f64(
     }
 
     let result = catch_unwind(|| {
-        // Extract indices
-        let mut indices: Vec<InternalIndex> = Vec::with_capacity(rank);
-        for i in 0..rank {
-            let idx_ptr = unsafe { *index_ptrs.add(i) };
-            if idx_ptr.is_null() {
-                return ptr::null_mut();
-            }
-            let idx = unsafe { &*idx_ptr };
-            indices.push(idx.inner().clone());
-        }
-
-        // Extract dimensions
-        let dims_vec: Vec<usize> = (0..rank)
-            .map(|i| unsafe { *dims.add(i) })
-            .collect();
+        // Extract indices and dimensions
+        let Some((indices, dims_vec)) = read_indices_and_dims(rank, index_ptrs, dims) else {
+            return ptr::null_mut();
+        };
 
         // Validate data length
         let expected_len: usize = dims_vec.iter().product();
@@ -358,21 +399,10 @@ pub extern "C" fn t4a_tensor_new_dense_c64(
     }
 
     let result = catch_unwind(|| {
-        // Extract indices
-        let mut indices: Vec<InternalIndex> = Vec::with_capacity(rank);
-        for i in 0..rank {
-            let idx_ptr = unsafe { *index_ptrs.add(i) };
-            if idx_ptr.is_null() {
-                return ptr::null_mut();
-            }
-            let idx = unsafe { &*idx_ptr };
-            indices.push(idx.inner().clone());
-        }
-
-        // Extract dimensions
-        let dims_vec: Vec<usize> = (0..rank)
-            .map(|i| unsafe { *dims.add(i) })
-            .collect();
+        // Extract indices and dimensions
+        let Some((indices, dims_vec)) = read_indices_and_dims(rank, index_ptrs, dims) else {
+            return ptr::null_mut();
+        };
 
         // Validate data length
         let expected_len: usize = dims_vec.iter().product();
@@ -399,10 +429,233 @@ pub extern "C" fn t4a_tensor_new_dense_c64(
     result.unwrap_or(ptr::null_mut())
 }
 
+/// Create a new dense f32 tensor from indices and data.
+///
+/// # Arguments
+/// - `rank`: Number of indices
+/// - `index_ptrs`: Array of t4a_index pointers (length = rank)
+/// - `dims`: Array of dimensions (length = rank)
+/// - `data`: Dense data in row-major order (length = product of dims)
+/// - `data_len`: Length of data array
+///
+/// # Returns
+/// - Pointer to new t4a_tensor on success
+/// - NULL on error
+///
+/// # Safety
+/// - All pointers must be valid
+/// - Caller owns the returned tensor and must call t4a_tensor_release
+#[no_mangle]
+pub extern "C" fn t4a_tensor_new_dense_f32(
+    rank: libc::size_t,
+    index_ptrs: *const *const t4a_index,
+    dims: *const libc::size_t,
+    data: *const libc::c_float,
+    data_len: libc::size_t,
+) -> *mut t4a_tensor {
+    if index_ptrs.is_null() || dims.is_null() || data.is_null() {
+        return ptr::null_mut();
+    }
+
+    let result = catch_unwind(|| {
+        // Extract indices and dimensions
+        let Some((indices, dims_vec)) = read_indices_and_dims(rank, index_ptrs, dims) else {
+            return ptr::null_mut();
+        };
+
+        // Validate data length
+        let expected_len: usize = dims_vec.iter().product();
+        if data_len != expected_len {
+            return ptr::null_mut();
+        }
+
+        // Copy data
+        let data_vec: Vec<f32> = unsafe {
+            std::slice::from_raw_parts(data, data_len).to_vec()
+        };
+
+        let storage = Arc::new(Storage::DenseF32(DenseStorageF32::from_vec(data_vec)));
+        let tensor = InternalTensor::new(indices, dims_vec, storage);
+
+        Box::into_raw(Box::new(t4a_tensor::new(tensor)))
+    });
+
+    result.unwrap_or(ptr::null_mut())
+}
+
+/// Create a new dense complex32 tensor from indices and data.
+///
+/// # Arguments
+/// - `rank`: Number of indices
+/// - `index_ptrs`: Array of t4a_index pointers (length = rank)
+/// - `dims`: Array of dimensions (length = rank)
+/// - `data_re`: Real parts of dense data in row-major order (length = product of dims)
+/// - `data_im`: Imaginary parts of dense data in row-major order (length = product of dims)
+/// - `data_len`: Length of data arrays
+///
+/// # Returns
+/// - Pointer to new t4a_tensor on success
+/// - NULL on error
+///
+/// # Safety
+/// - All pointers must be valid
+/// - Caller owns the returned tensor and must call t4a_tensor_release
+#[no_mangle]
+pub extern "C" fn t4a_tensor_new_dense_c32(
+    rank: libc::size_t,
+    index_ptrs: *const *const t4a_index,
+    dims: *const libc::size_t,
+    data_re: *const libc::c_float,
+    data_im: *const libc::c_float,
+    data_len: libc::size_t,
+) -> *mut t4a_tensor {
+    if index_ptrs.is_null() || dims.is_null() || data_re.is_null() || data_im.is_null() {
+        return ptr::null_mut();
+    }
+
+    let result = catch_unwind(|| {
+        // Extract indices and dimensions
+        let Some((indices, dims_vec)) = read_indices_and_dims(rank, index_ptrs, dims) else {
+            return ptr::null_mut();
+        };
+
+        // Validate data length
+        let expected_len: usize = dims_vec.iter().product();
+        if data_len != expected_len {
+            return ptr::null_mut();
+        }
+
+        // Copy data
+        let data_vec: Vec<Complex32> = (0..data_len)
+            .map(|i| unsafe {
+                Complex32::new(*data_re.add(i), *data_im.add(i))
+            })
+            .collect();
+
+        let storage = Arc::new(Storage::DenseC32(DenseStorageC32::from_vec(data_vec)));
+        let tensor = InternalTensor::new(indices, dims_vec, storage);
+
+        Box::into_raw(Box::new(t4a_tensor::new(tensor)))
+    });
+
+    result.unwrap_or(ptr::null_mut())
+}
+
+/// Create a new diagonal f64 tensor from indices and its diagonal.
+///
+/// # Arguments
+/// - `rank`: Number of indices
+/// - `index_ptrs`: Array of t4a_index pointers (length = rank)
+/// - `dims`: Array of dimensions (length = rank, all equal)
+/// - `diag`: Diagonal elements (length = the common dimension)
+/// - `diag_len`: Length of the diagonal array
+///
+/// # Returns
+/// - Pointer to new t4a_tensor on success
+/// - NULL on error (including unequal dimensions)
+///
+/// # Safety
+/// - All pointers must be valid
+/// - Caller owns the returned tensor and must call t4a_tensor_release
+#[no_mangle]
+pub extern "C" fn t4a_tensor_new_diag_f64(
+    rank: libc::size_t,
+    index_ptrs: *const *const t4a_index,
+    dims: *const libc::size_t,
+    diag: *const libc::c_double,
+    diag_len: libc::size_t,
+) -> *mut t4a_tensor {
+    if index_ptrs.is_null() || dims.is_null() || diag.is_null() {
+        return ptr::null_mut();
+    }
+
+    let result = catch_unwind(|| {
+        // Extract indices and dimensions
+        let Some((indices, dims_vec)) = read_indices_and_dims(rank, index_ptrs, dims) else {
+            return ptr::null_mut();
+        };
+
+        // All dimensions must equal the diagonal length
+        if dims_vec.is_empty() || dims_vec.iter().any(|&d| d != diag_len) {
+            return ptr::null_mut();
+        }
+
+        // Copy data
+        let diag_vec: Vec<f64> = unsafe {
+            std::slice::from_raw_parts(diag, diag_len).to_vec()
+        };
+
+        let storage = Arc::new(Storage::new_diag_f64(diag_vec));
+        let tensor = InternalTensor::new(indices, dims_vec, storage);
+
+        Box::into_raw(Box::new(t4a_tensor::new(tensor)))
+    });
+
+    result.unwrap_or(ptr::null_mut())
+}
+
+/// Create a new diagonal complex64 tensor from indices and its diagonal.
+///
+/// # Arguments
+/// - `rank`: Number of indices
+/// - `index_ptrs`: Array of t4a_index pointers (length = rank)
+/// - `dims`: Array of dimensions (length = rank, all equal)
+/// - `diag_re`: Real parts of the diagonal elements (length = the common dimension)
+/// - `diag_im`: Imaginary parts of the diagonal elements (length = the common dimension)
+/// - `diag_len`: Length of the diagonal arrays
+///
+/// # Returns
+/// - Pointer to new t4a_tensor on success
+/// - NULL on error (including unequal dimensions)
+///
+/// # Safety
+/// - All pointers must be valid
+/// - Caller owns the returned tensor and must call t4a_tensor_release
+#[no_mangle]
+pub extern "C" fn t4a_tensor_new_diag_c64(
+    rank: libc::size_t,
+    index_ptrs: *const *const t4a_index,
+    dims: *const libc::size_t,
+    diag_re: *const libc::c_double,
+    diag_im: *const libc::c_double,
+    diag_len: libc::size_t,
+) -> *mut t4a_tensor {
+    if index_ptrs.is_null() || dims.is_null() || diag_re.is_null() || diag_im.is_null() {
+        return ptr::null_mut();
+    }
+
+    let result = catch_unwind(|| {
+        // Extract indices and dimensions
+        let Some((indices, dims_vec)) = read_indices_and_dims(rank, index_ptrs, dims) else {
+            return ptr::null_mut();
+        };
+
+        // All dimensions must equal the diagonal length
+        if dims_vec.is_empty() || dims_vec.iter().any(|&d| d != diag_len) {
+            return ptr::null_mut();
+        }
+
+        // Copy data
+        let diag_vec: Vec<Complex64> = (0..diag_len)
+            .map(|i| unsafe {
+                Complex64::new(*diag_re.add(i), *diag_im.add(i))
+            })
+            .collect();
+
+        let storage = Arc::new(Storage::new_diag_c64(diag_vec));
+        let tensor = InternalTensor::new(indices, dims_vec, storage);
+
+        Box::into_raw(Box::new(t4a_tensor::new(tensor)))
+    });
+
+    result.unwrap_or(ptr::null_mut())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::index::*;
+    use tensor4all_core_tensor::BlockSparseStorageF64;
 
     #[test]
     fn test_tensor_lifecycle() {
@@ -556,4 +809,72 @@ mod tests {
         t4a_index_release(i);
         t4a_index_release(j);
     }
+
+    #[test]
+    fn test_tensor_get_data_converts_storage() {
+        let i = t4a_index_new(2);
+        let j = t4a_index_new(2);
+        let index_ptrs = [i as *const _, j as *const _];
+        let dims = [2_usize, 2_usize];
+        let mut out_len: usize = 0;
+        let mut kind = t4a_storage_kind::DenseF64;
+
+        // Diag storage is densified
+        let diag = [1.0, 2.0];
+        let tensor = t4a_tensor_new_diag_f64(2, index_ptrs.as_ptr(), dims.as_ptr(), diag.as_ptr(), 2);
+        assert!(!tensor.is_null());
+        assert_eq!(t4a_tensor_get_storage_kind(tensor as *const _, &mut kind), T4A_SUCCESS);
+        assert_eq!(kind, t4a_storage_kind::DiagF64);
+        let mut out_data = [0.0; 4];
+        assert_eq!(t4a_tensor_get_data_f64(tensor as *const _, out_data.as_mut_ptr(), 4, &mut out_len), T4A_SUCCESS);
+        assert_eq!(out_len, 4);
+        assert_eq!(out_data, [1.0, 0.0, 0.0, 2.0]);
+        t4a_tensor_release(tensor);
+
+        // Diagonal tensors need equal dimensions
+        let bad_dims = [2_usize, 3_usize];
+        assert!(t4a_tensor_new_diag_f64(2, index_ptrs.as_ptr(), bad_dims.as_ptr(), diag.as_ptr(), 2).is_null());
+
+        // f32 elements are converted to f64
+        let data_f32 = [1.5_f32, -2.0, 0.25, 4.0];
+        let tensor = t4a_tensor_new_dense_f32(2, index_ptrs.as_ptr(), dims.as_ptr(), data_f32.as_ptr(), 4);
+        assert_eq!(t4a_tensor_get_storage_kind(tensor as *const _, &mut kind), T4A_SUCCESS);
+        assert_eq!(kind, t4a_storage_kind::DenseF32);
+        assert_eq!(t4a_tensor_get_data_f64(tensor as *const _, out_data.as_mut_ptr(), 4, &mut out_len), T4A_SUCCESS);
+        assert_eq!(out_data, [1.5, -2.0, 0.25, 4.0]);
+        t4a_tensor_release(tensor);
+
+        // Complex32 elements are converted to Complex64, but can't be read as f64
+        let re = [1.0_f32, 2.0, 3.0, 4.0];
+        let im = [0.5_f32, -0.5, 0.0, 1.0];
+        let tensor = t4a_tensor_new_dense_c32(2, index_ptrs.as_ptr(), dims.as_ptr(), re.as_ptr(), im.as_ptr(), 4);
+        assert_eq!(t4a_tensor_get_storage_kind(tensor as *const _, &mut kind), T4A_SUCCESS);
+        assert_eq!(kind, t4a_storage_kind::DenseC32);
+        assert_eq!(
+            t4a_tensor_get_data_f64(tensor as *const _, ptr::null_mut(), 0, &mut out_len),
+            T4A_INVALID_ARGUMENT
+        );
+        let mut out_re = [0.0; 4];
+        let mut out_im = [0.0; 4];
+        assert_eq!(
+            t4a_tensor_get_data_c64(tensor as *const _, out_re.as_mut_ptr(), out_im.as_mut_ptr(), 4, &mut out_len),
+            T4A_SUCCESS
+        );
+        assert_eq!(out_re, [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(out_im, [0.5, -0.5, 0.0, 1.0]);
+        t4a_tensor_release(tensor);
+
+        // BlockSparse storage is densified, with zeros for the missing blocks
+        let mut blocks = BlockSparseStorageF64::new(vec![vec![1, 1], vec![2]]);
+        blocks.insert_block(vec![1, 0], vec![3.0, 4.0]);
+        let indices = vec![unsafe { &*i }.inner().clone(), unsafe { &*j }.inner().clone()];
+        let tensor = t4a_tensor::new(InternalTensor::new(indices, vec![2, 2], Arc::new(Storage::BlockSparseF64(blocks))));
+        assert_eq!(t4a_tensor_get_storage_kind(&tensor, &mut kind), T4A_SUCCESS);
+        assert_eq!(kind, t4a_storage_kind::BlockSparseF64);
+        assert_eq!(t4a_tensor_get_data_f64(&tensor, out_data.as_mut_ptr(), 4, &mut out_len), T4A_SUCCESS);
+        assert_eq!(out_data, [0.0, 0.0, 3.0, 4.0]);
+
+        t4a_index_release(i);
+        t4a_index_release(j);
+    }
 }
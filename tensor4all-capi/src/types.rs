@@ -77,6 +77,18 @@ pub enum t4a_storage_kind {
     DiagF64 = 2,
     /// Diagonal storage with Complex64 elements
     DiagC64 = 3,
+    /// Block-sparse storage with f64 elements
+    BlockSparseF64 = 4,
+    /// Block-sparse storage with Complex64 elements
+    BlockSparseC64 = 5,
+    /// Dense storage with f32 elements
+    DenseF32 = 6,
+    /// Dense storage with Complex32 elements
+    DenseC32 = 7,
+    /// Diagonal storage with f32 elements
+    DiagF32 = 8,
+    /// Diagonal storage with Complex32 elements
+    DiagC32 = 9,
 }
 
 impl t4a_storage_kind {
@@ -87,6 +99,12 @@ impl t4a_storage_kind {
             Storage::DenseC64(_) => Self::DenseC64,
             Storage::DiagF64(_) => Self::DiagF64,
             Storage::DiagC64(_) => Self::DiagC64,
+            Storage::BlockSparseF64(_) => Self::BlockSparseF64,
+            Storage::BlockSparseC64(_) => Self::BlockSparseC64,
+            Storage::DenseF32(_) => Self::DenseF32,
+            Storage::DenseC32(_) => Self::DenseC32,
+            Storage::DiagF32(_) => Self::DiagF32,
+            Storage::DiagC32(_) => Self::DiagC32,
         }
     }
 }
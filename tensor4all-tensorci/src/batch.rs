@@ -0,0 +1,125 @@
+//! Batched and parallel function evaluation
+//!
+//! TCI algorithms request function values in blocks (rows and columns of Pi
+//! matrices). A batch function `Fn(&[MultiIndex]) -> Vec<T>` receives such a
+//! block in a single call, which allows vectorized or parallel evaluation of
+//! expensive functions.
+
+use crate::error::{Result, TCIError};
+use crate::indexset::MultiIndex;
+use rayon::prelude::*;
+
+/// Wrap a point function into a batch function evaluated in parallel
+///
+/// The returned batch function distributes the multi-indices of each batch
+/// over the rayon thread pool. It can be passed as `batched_f` to
+/// [`crate::crossinterpolate1_with_batch`] and [`crate::crossinterpolate2`].
+///
+/// # Example
+///
+/// ```
+/// use tensor4all_tensorci::{parallel_batch, MultiIndex};
+///
+/// let batched_f = parallel_batch(|idx: &MultiIndex| (idx[0] * idx[1]) as f64);
+/// assert_eq!(batched_f(&[vec![2, 3], vec![1, 4]]), vec![6.0, 4.0]);
+/// ```
+pub fn parallel_batch<T, F>(f: F) -> impl Fn(&[MultiIndex]) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&MultiIndex) -> T + Sync,
+{
+    move |indices: &[MultiIndex]| indices.par_iter().map(&f).collect()
+}
+
+/// Batch function as a trait object
+type BatchFn<'a, T> = &'a dyn Fn(&[MultiIndex]) -> Vec<T>;
+
+/// Function evaluator dispatching to a batch function when available
+pub(crate) struct BatchEvaluator<'a, T> {
+    f: &'a dyn Fn(&MultiIndex) -> T,
+    batched_f: Option<BatchFn<'a, T>>,
+}
+
+impl<'a, T> BatchEvaluator<'a, T> {
+    pub(crate) fn new<F, B>(f: &'a F, batched_f: Option<&'a B>) -> Self
+    where
+        F: Fn(&MultiIndex) -> T,
+        B: Fn(&[MultiIndex]) -> Vec<T>,
+    {
+        Self {
+            f,
+            batched_f: batched_f.map(|b| b as BatchFn<'a, T>),
+        }
+    }
+
+    /// Evaluate the function at a single multi-index
+    pub(crate) fn eval(&self, idx: &MultiIndex) -> T {
+        (self.f)(idx)
+    }
+
+    /// Evaluate the function at many multi-indices
+    ///
+    /// Fails if the batch function returns the wrong number of values.
+    pub(crate) fn eval_batch(&self, indices: &[MultiIndex]) -> Result<Vec<T>> {
+        let values = self.eval_batch_unchecked(indices);
+        if values.len() != indices.len() {
+            return Err(TCIError::InvalidOperation {
+                message: format!(
+                    "Batch function returned {} values for {} indices",
+                    values.len(),
+                    indices.len()
+                ),
+            });
+        }
+        Ok(values)
+    }
+
+    /// Evaluate the function at many multi-indices, leaving the number of
+    /// returned values to be checked by the caller
+    pub(crate) fn eval_batch_unchecked(&self, indices: &[MultiIndex]) -> Vec<T> {
+        if indices.is_empty() {
+            return Vec::new();
+        }
+        match self.batched_f {
+            Some(batched_f) => batched_f(indices),
+            None => indices.iter().map(|idx| (self.f)(idx)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_batch_matches_serial() {
+        let f = |idx: &MultiIndex| idx.iter().map(|&i| i as f64).product::<f64>() + 1.0;
+        let indices: Vec<MultiIndex> = (0..100).map(|i| vec![i % 7, i % 5, i % 3]).collect();
+
+        let batched_f = parallel_batch(f);
+        let expected: Vec<f64> = indices.iter().map(f).collect();
+        assert_eq!(batched_f(&indices), expected);
+    }
+
+    #[test]
+    fn test_evaluator_prefers_batch_function() {
+        let f = |_: &MultiIndex| 0.0f64;
+        let batched_f = |indices: &[MultiIndex]| vec![1.0f64; indices.len()];
+
+        let eval = BatchEvaluator::new(&f, Some(&batched_f));
+        assert_eq!(eval.eval_batch(&[vec![0], vec![1]]).unwrap(), vec![1.0, 1.0]);
+        assert_eq!(eval.eval(&vec![0]), 0.0);
+
+        let eval = BatchEvaluator::new::<_, fn(&[MultiIndex]) -> Vec<f64>>(&f, None);
+        assert_eq!(eval.eval_batch(&[vec![0], vec![1]]).unwrap(), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_evaluator_rejects_wrong_batch_length() {
+        let f = |_: &MultiIndex| 0.0f64;
+        let batched_f = |_: &[MultiIndex]| vec![1.0f64];
+
+        let eval = BatchEvaluator::new(&f, Some(&batched_f));
+        assert!(eval.eval_batch(&[vec![0], vec![1]]).is_err());
+    }
+}
@@ -1,7 +1,27 @@
 //! Cached function wrapper for expensive function evaluations
+//!
+//! - `CachedFunction`: single-threaded cache with unbounded size
+//! - `ConcurrentCachedFunction`: thread-safe cache of a function of
+//!   multi-indices with an optional LRU size bound, compact keys, and
+//!   persistence to disk (with the `serde` feature)
 
-use std::collections::HashMap;
+use crate::error::{Result, TCIError};
+use crate::indexset::MultiIndex;
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "serde")]
+use std::fs::File;
 use std::hash::Hash;
+#[cfg(feature = "serde")]
+use std::io::{BufReader, BufWriter};
+#[cfg(feature = "serde")]
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 /// A wrapper that caches function evaluations
 ///
@@ -103,6 +123,400 @@ where
     }
 }
 
+/// Conversion of multi-indices into cache keys
+pub trait IndexEncoder: Send + Sync {
+    /// Key type stored in the cache
+    type Key: Clone + Eq + Hash + Send + Sync;
+
+    /// Encode a multi-index as a key
+    fn encode(&self, idx: &[usize]) -> Self::Key;
+
+    /// Parameters that determine the key of a multi-index
+    ///
+    /// Saved with a cache file; a cache can only be loaded by an encoder with the
+    /// same configuration. Empty for encoders whose keys don't depend on any
+    /// parameters.
+    fn config(&self) -> Vec<usize> {
+        Vec::new()
+    }
+}
+
+/// Use the multi-index itself as the key
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VecEncoder;
+
+impl IndexEncoder for VecEncoder {
+    type Key = MultiIndex;
+
+    fn encode(&self, idx: &[usize]) -> MultiIndex {
+        idx.to_vec()
+    }
+}
+
+/// Bit-pack multi-indices with a common local dimension into a `u128`
+///
+/// Each site uses `ceil(log2(local_dim))` bits, so e.g. quantics indices
+/// (local dimension 2) with up to 128 sites fit in a single key.
+///
+/// Encoding panics if a multi-index has the wrong length or a value outside
+/// `0..local_dim`, since such an index would collide with another key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuanticsEncoder {
+    nsites: usize,
+    local_dim: usize,
+    bits_per_site: u32,
+}
+
+impl QuanticsEncoder {
+    /// Create an encoder for `nsites` sites of dimension `local_dim`
+    pub fn new(nsites: usize, local_dim: usize) -> Result<Self> {
+        if local_dim == 0 {
+            return Err(TCIError::InvalidOperation {
+                message: "Local dimension must be positive".to_string(),
+            });
+        }
+        let bits_per_site = (usize::BITS - (local_dim - 1).leading_zeros()).max(1);
+        if nsites * bits_per_site as usize > 128 {
+            return Err(TCIError::InvalidOperation {
+                message: format!(
+                    "{} sites with {} bits each do not fit into 128 bits",
+                    nsites, bits_per_site
+                ),
+            });
+        }
+        Ok(Self {
+            nsites,
+            local_dim,
+            bits_per_site,
+        })
+    }
+
+    /// Number of sites
+    pub fn nsites(&self) -> usize {
+        self.nsites
+    }
+
+    /// Decode a key back into a multi-index
+    pub fn decode(&self, key: u128) -> MultiIndex {
+        let mask = (1u128 << self.bits_per_site) - 1;
+        (0..self.nsites)
+            .map(|p| ((key >> (p as u32 * self.bits_per_site)) & mask) as usize)
+            .collect()
+    }
+}
+
+impl IndexEncoder for QuanticsEncoder {
+    type Key = u128;
+
+    fn encode(&self, idx: &[usize]) -> u128 {
+        assert_eq!(
+            idx.len(),
+            self.nsites,
+            "QuanticsEncoder: multi-index has {} sites, expected {}",
+            idx.len(),
+            self.nsites
+        );
+        idx.iter().enumerate().fold(0u128, |key, (p, &i)| {
+            assert!(
+                i < self.local_dim,
+                "QuanticsEncoder: index {} at site {} is out of range 0..{}",
+                i,
+                p,
+                self.local_dim
+            );
+            key | ((i as u128) << (p as u32 * self.bits_per_site))
+        })
+    }
+
+    /// `[nsites, local_dim]`
+    fn config(&self) -> Vec<usize> {
+        vec![self.nsites, self.local_dim]
+    }
+}
+
+/// Hash map with optional least-recently-used eviction
+#[derive(Debug)]
+struct LruCache<K, V> {
+    map: HashMap<K, (V, u64)>,
+    order: BTreeMap<u64, K>,
+    tick: u64,
+    max_size: Option<usize>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LruCache<K, V> {
+    fn new(max_size: Option<usize>) -> Self {
+        Self {
+            map: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            max_size,
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        let tick = self.tick;
+        let (value, stamp) = self.map.get_mut(key)?;
+        if self.max_size.is_some() {
+            self.order.remove(stamp);
+            self.order.insert(tick, key.clone());
+            *stamp = tick;
+            self.tick += 1;
+        }
+        Some(value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.max_size == Some(0) {
+            return;
+        }
+        if let Some((_, stamp)) = self.map.get(&key) {
+            self.order.remove(stamp);
+        }
+        if self.max_size.is_some() {
+            self.order.insert(self.tick, key.clone());
+        }
+        self.map.insert(key, (value, self.tick));
+        self.tick += 1;
+
+        if let Some(max_size) = self.max_size {
+            while self.map.len() > max_size {
+                if let Some((_, oldest)) = self.order.pop_first() {
+                    self.map.remove(&oldest);
+                }
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+        self.order.clear();
+    }
+}
+
+#[cfg(feature = "serde")]
+/// Current version of the cache file format
+const CACHE_FILE_VERSION: u32 = 2;
+
+#[cfg(feature = "serde")]
+/// On-disk representation of a cache
+#[derive(Serialize, Deserialize)]
+struct CacheFile<K, V> {
+    version: u32,
+    /// [`IndexEncoder::config`] of the encoder that produced the keys
+    encoder: Vec<usize>,
+    entries: Vec<(K, V)>,
+}
+
+/// A thread-safe cached function of multi-indices
+///
+/// Evaluation takes `&self`, so a single cache can be shared between threads
+/// (e.g. inside a [`crate::parallel_batch`] function). The function itself is
+/// evaluated outside the lock; concurrent misses on the same key may evaluate
+/// it more than once. With a size bound, the least recently used entries are
+/// evicted first.
+///
+/// # Example
+///
+/// ```
+/// use tensor4all_tensorci::{ConcurrentCachedFunction, MultiIndex, QuanticsEncoder};
+///
+/// let encoder = QuanticsEncoder::new(40, 2).unwrap();
+/// let cf = ConcurrentCachedFunction::with_encoder(
+///     |idx: &MultiIndex| idx.iter().sum::<usize>() as f64,
+///     encoder,
+/// )
+/// .with_max_size(1_000_000);
+///
+/// let idx = vec![1; 40];
+/// assert_eq!(cf.eval(&idx), 40.0);
+/// assert_eq!(cf.eval(&idx), 40.0);
+/// assert_eq!(cf.num_evals(), 1);
+/// ```
+pub struct ConcurrentCachedFunction<V, F, E = VecEncoder>
+where
+    E: IndexEncoder,
+{
+    func: F,
+    encoder: E,
+    cache: Mutex<LruCache<E::Key, V>>,
+    num_evals: AtomicUsize,
+    num_cache_hits: AtomicUsize,
+}
+
+impl<V, F> ConcurrentCachedFunction<V, F, VecEncoder>
+where
+    V: Clone + Send + Sync,
+    F: Fn(&MultiIndex) -> V + Sync,
+{
+    /// Create a cache keyed by the multi-indices themselves
+    pub fn new(func: F) -> Self {
+        Self::with_encoder(func, VecEncoder)
+    }
+}
+
+impl<V, F, E> ConcurrentCachedFunction<V, F, E>
+where
+    V: Clone + Send + Sync,
+    F: Fn(&MultiIndex) -> V + Sync,
+    E: IndexEncoder,
+{
+    /// Create a cache with a custom key encoder
+    pub fn with_encoder(func: F, encoder: E) -> Self {
+        Self {
+            func,
+            encoder,
+            cache: Mutex::new(LruCache::new(None)),
+            num_evals: AtomicUsize::new(0),
+            num_cache_hits: AtomicUsize::new(0),
+        }
+    }
+
+    /// Bound the number of cached entries (least recently used are evicted)
+    pub fn with_max_size(self, max_size: usize) -> Self {
+        let mut cache = self.cache.into_inner().unwrap();
+        cache.max_size = Some(max_size);
+        let mut entries: Vec<(u64, E::Key, V)> = cache
+            .map
+            .drain()
+            .map(|(k, (v, stamp))| (stamp, k, v))
+            .collect();
+        entries.sort_by_key(|(stamp, _, _)| *stamp);
+        cache.order.clear();
+        for (_, k, v) in entries {
+            cache.insert(k, v);
+        }
+        Self {
+            cache: Mutex::new(cache),
+            ..self
+        }
+    }
+
+    /// Evaluate the function at a multi-index, using the cache if available
+    pub fn eval(&self, idx: &MultiIndex) -> V {
+        let key = self.encoder.encode(idx);
+        if let Some(value) = self.cache.lock().unwrap().get(&key) {
+            self.num_cache_hits.fetch_add(1, Ordering::Relaxed);
+            return value;
+        }
+
+        self.num_evals.fetch_add(1, Ordering::Relaxed);
+        let value = (self.func)(idx);
+        self.cache.lock().unwrap().insert(key, value.clone());
+        value
+    }
+
+    /// Evaluate the function at many multi-indices, computing misses in parallel
+    pub fn eval_batch(&self, indices: &[MultiIndex]) -> Vec<V> {
+        indices.par_iter().map(|idx| self.eval(idx)).collect()
+    }
+
+    /// Evaluate the function bypassing the cache
+    pub fn eval_no_cache(&self, idx: &MultiIndex) -> V {
+        (self.func)(idx)
+    }
+
+    /// Get the number of actual function evaluations
+    pub fn num_evals(&self) -> usize {
+        self.num_evals.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of cache hits
+    pub fn num_cache_hits(&self) -> usize {
+        self.num_cache_hits.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of cached entries
+    pub fn cache_size(&self) -> usize {
+        self.cache.lock().unwrap().len()
+    }
+
+    /// Check if a multi-index is cached
+    pub fn is_cached(&self, idx: &MultiIndex) -> bool {
+        let key = self.encoder.encode(idx);
+        self.cache.lock().unwrap().map.contains_key(&key)
+    }
+
+    /// Clear the cache
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    #[cfg(feature = "serde")]
+    /// Save all cached entries to a file
+    pub fn save_cache<P: AsRef<Path>>(&self, path: P) -> Result<()>
+    where
+        E::Key: Serialize,
+        V: Serialize,
+    {
+        let entries = {
+            let cache = self.cache.lock().unwrap();
+            let mut entries: Vec<(u64, E::Key, V)> = cache
+                .map
+                .iter()
+                .map(|(k, (v, stamp))| (*stamp, k.clone(), v.clone()))
+                .collect();
+            // Oldest first, so that reloading into a bounded cache keeps the most recent
+            entries.sort_by_key(|(stamp, _, _)| *stamp);
+            entries.into_iter().map(|(_, k, v)| (k, v)).collect()
+        };
+        let file = CacheFile {
+            version: CACHE_FILE_VERSION,
+            encoder: self.encoder.config(),
+            entries,
+        };
+        let writer = BufWriter::new(File::create(path)?);
+        bincode::serialize_into(writer, &file).map_err(|e| TCIError::Serialization {
+            message: e.to_string(),
+        })
+    }
+
+    #[cfg(feature = "serde")]
+    /// Load entries from a file written by [`Self::save_cache`]
+    ///
+    /// Loaded entries are added to the current cache. Returns the number of
+    /// entries read. Fails if the file was written with a differently configured
+    /// encoder, whose keys would refer to other multi-indices.
+    pub fn load_cache<P: AsRef<Path>>(&self, path: P) -> Result<usize>
+    where
+        E::Key: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let reader = BufReader::new(File::open(path)?);
+        let file: CacheFile<E::Key, V> =
+            bincode::deserialize_from(reader).map_err(|e| TCIError::Serialization {
+                message: e.to_string(),
+            })?;
+        if file.version != CACHE_FILE_VERSION {
+            return Err(TCIError::Serialization {
+                message: format!(
+                    "Unsupported cache file version {} (expected {})",
+                    file.version, CACHE_FILE_VERSION
+                ),
+            });
+        }
+        if file.encoder != self.encoder.config() {
+            return Err(TCIError::Serialization {
+                message: format!(
+                    "Cache file was written with encoder configuration {:?}, expected {:?}",
+                    file.encoder,
+                    self.encoder.config()
+                ),
+            });
+        }
+
+        let n = file.entries.len();
+        let mut cache = self.cache.lock().unwrap();
+        for (k, v) in file.entries {
+            cache.insert(k, v);
+        }
+        Ok(n)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,4 +560,126 @@ mod tests {
         cf.clear_cache();
         assert_eq!(cf.cache_size(), 0);
     }
+
+    #[test]
+    fn test_quantics_encoder_roundtrip() {
+        let encoder = QuanticsEncoder::new(128, 2).unwrap();
+        let idx: MultiIndex = (0..128).map(|p| (p * 7 + 3) % 2).collect();
+        assert_eq!(encoder.decode(encoder.encode(&idx)), idx);
+
+        let encoder = QuanticsEncoder::new(20, 5).unwrap();
+        let idx: MultiIndex = (0..20).map(|p| p % 5).collect();
+        assert_eq!(encoder.decode(encoder.encode(&idx)), idx);
+        assert_ne!(encoder.encode(&idx), encoder.encode(&[0; 20]));
+
+        assert!(QuanticsEncoder::new(129, 2).is_err());
+        assert!(QuanticsEncoder::new(43, 5).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 4")]
+    fn test_quantics_encoder_rejects_wrong_length() {
+        let encoder = QuanticsEncoder::new(4, 2).unwrap();
+        encoder.encode(&[0, 1, 0, 1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_quantics_encoder_rejects_out_of_range() {
+        // 5 fits into the 3 bits used per site but is not a valid local index
+        let encoder = QuanticsEncoder::new(4, 5).unwrap();
+        encoder.encode(&[0, 5, 0, 1]);
+    }
+
+    #[test]
+    fn test_concurrent_cached_function_threads() {
+        let cf = ConcurrentCachedFunction::new(|x: &MultiIndex| x[0] * x[1]);
+        let indices: Vec<MultiIndex> = (0..200).map(|i| vec![i % 10, i % 7]).collect();
+
+        let values = cf.eval_batch(&indices);
+        for (idx, v) in indices.iter().zip(values) {
+            assert_eq!(v, idx[0] * idx[1]);
+        }
+        assert_eq!(cf.cache_size(), 70);
+        assert_eq!(cf.num_evals() + cf.num_cache_hits(), 200);
+
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for idx in &indices {
+                        assert_eq!(cf.eval(idx), idx[0] * idx[1]);
+                    }
+                });
+            }
+        });
+        assert_eq!(cf.cache_size(), 70);
+    }
+
+    #[test]
+    fn test_concurrent_cached_function_lru() {
+        let cf = ConcurrentCachedFunction::new(|x: &MultiIndex| x[0]).with_max_size(2);
+        cf.eval(&vec![1]);
+        cf.eval(&vec![2]);
+        cf.eval(&vec![1]); // 1 is now more recent than 2
+        cf.eval(&vec![3]); // evicts 2
+
+        assert_eq!(cf.cache_size(), 2);
+        assert!(cf.is_cached(&vec![1]));
+        assert!(!cf.is_cached(&vec![2]));
+        assert!(cf.is_cached(&vec![3]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_concurrent_cached_function_save_load() {
+        let path = std::env::temp_dir().join(format!(
+            "tensor4all_tensorci_cache_{}.bin",
+            std::process::id()
+        ));
+        let encoder = QuanticsEncoder::new(10, 2).unwrap();
+        let f = |x: &MultiIndex| x.iter().sum::<usize>() as f64;
+
+        let cf = ConcurrentCachedFunction::with_encoder(f, encoder);
+        for i in 0..10 {
+            let mut idx = vec![0; 10];
+            idx[i] = 1;
+            cf.eval(&idx);
+        }
+        cf.save_cache(&path).unwrap();
+
+        let restored = ConcurrentCachedFunction::with_encoder(f, encoder);
+        assert_eq!(restored.load_cache(&path).unwrap(), 10);
+        assert_eq!(restored.cache_size(), 10);
+        let mut idx = vec![0; 10];
+        idx[3] = 1;
+        assert_eq!(restored.eval(&idx), 1.0);
+        assert_eq!(restored.num_evals(), 0);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(restored.load_cache(&path).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_concurrent_cached_function_load_rejects_other_encoder() {
+        let path = std::env::temp_dir().join(format!(
+            "tensor4all_tensorci_cache_encoder_{}.bin",
+            std::process::id()
+        ));
+        let f = |x: &MultiIndex| x.iter().sum::<usize>() as f64;
+
+        let cf = ConcurrentCachedFunction::with_encoder(f, QuanticsEncoder::new(10, 2).unwrap());
+        cf.eval(&vec![1; 10]);
+        cf.save_cache(&path).unwrap();
+
+        // Same key width, but the keys decode to other multi-indices
+        let other = ConcurrentCachedFunction::with_encoder(f, QuanticsEncoder::new(5, 4).unwrap());
+        assert!(other.load_cache(&path).is_err());
+        assert_eq!(other.cache_size(), 0);
+
+        let same = ConcurrentCachedFunction::with_encoder(f, QuanticsEncoder::new(10, 2).unwrap());
+        assert_eq!(same.load_cache(&path).unwrap(), 1);
+
+        std::fs::remove_file(&path).unwrap();
+    }
 }
@@ -0,0 +1,137 @@
+//! Versioned on-disk checkpoints of TCI states
+//!
+//! A checkpoint file starts with a header naming the stored type and the
+//! format version, followed by the bincode-encoded state.
+
+use crate::error::{Result, TCIError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use tensor4all_matrixci::util::{zeros, Matrix, Scalar};
+use tensor4all_tensortrain::{TTScalar, Tensor3};
+
+/// Current version of the checkpoint format
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+/// Header written before every checkpoint
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    kind: String,
+    version: u32,
+}
+
+/// Serializable form of a 3-leg tensor
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct TensorData<T> {
+    left_dim: usize,
+    site_dim: usize,
+    right_dim: usize,
+    data: Vec<T>,
+}
+
+impl<T: TTScalar> TensorData<T> {
+    pub(crate) fn from_tensor(tensor: &Tensor3<T>) -> Self {
+        Self {
+            left_dim: tensor.left_dim(),
+            site_dim: tensor.site_dim(),
+            right_dim: tensor.right_dim(),
+            data: tensor.as_left_matrix().0,
+        }
+    }
+
+    pub(crate) fn into_tensor(self) -> Result<Tensor3<T>> {
+        if self.data.len() != self.left_dim * self.site_dim * self.right_dim {
+            return Err(TCIError::Serialization {
+                message: "Tensor data does not match its shape".to_string(),
+            });
+        }
+        Ok(Tensor3::from_data(
+            self.data,
+            self.left_dim,
+            self.site_dim,
+            self.right_dim,
+        ))
+    }
+}
+
+/// Serializable form of a matrix
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct MatrixData<T> {
+    nrows: usize,
+    ncols: usize,
+    data: Vec<T>,
+}
+
+impl<T: Scalar> MatrixData<T> {
+    pub(crate) fn from_matrix(mat: &Matrix<T>) -> Self {
+        let data = (0..mat.nrows())
+            .flat_map(|i| (0..mat.ncols()).map(move |j| mat[[i, j]]))
+            .collect();
+        Self {
+            nrows: mat.nrows(),
+            ncols: mat.ncols(),
+            data,
+        }
+    }
+
+    pub(crate) fn into_matrix(self) -> Result<Matrix<T>> {
+        if self.data.len() != self.nrows * self.ncols {
+            return Err(TCIError::Serialization {
+                message: "Matrix data does not match its shape".to_string(),
+            });
+        }
+        let mut mat = zeros(self.nrows, self.ncols);
+        for i in 0..self.nrows {
+            for j in 0..self.ncols {
+                mat[[i, j]] = self.data[i * self.ncols + j];
+            }
+        }
+        Ok(mat)
+    }
+}
+
+fn serialization_error(e: bincode::Error) -> TCIError {
+    TCIError::Serialization {
+        message: e.to_string(),
+    }
+}
+
+/// Write a state of the given kind to a checkpoint file
+pub(crate) fn write_checkpoint<S: Serialize, P: AsRef<Path>>(
+    path: P,
+    kind: &str,
+    state: &S,
+) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let header = Header {
+        kind: kind.to_string(),
+        version: CHECKPOINT_VERSION,
+    };
+    bincode::serialize_into(&mut writer, &header).map_err(serialization_error)?;
+    bincode::serialize_into(&mut writer, state).map_err(serialization_error)
+}
+
+/// Read a state of the given kind from a checkpoint file
+pub(crate) fn read_checkpoint<S: DeserializeOwned, P: AsRef<Path>>(
+    path: P,
+    kind: &str,
+) -> Result<S> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let header: Header = bincode::deserialize_from(&mut reader).map_err(serialization_error)?;
+    if header.kind != kind {
+        return Err(TCIError::Serialization {
+            message: format!("Checkpoint contains {}, expected {}", header.kind, kind),
+        });
+    }
+    if header.version != CHECKPOINT_VERSION {
+        return Err(TCIError::Serialization {
+            message: format!(
+                "Unsupported checkpoint version {} (expected {})",
+                header.version, CHECKPOINT_VERSION
+            ),
+        });
+    }
+    bincode::deserialize_from(&mut reader).map_err(serialization_error)
+}
@@ -32,6 +32,14 @@ pub enum TCIError {
     #[error("Invalid operation: {message}")]
     InvalidOperation { message: String },
 
+    /// I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Serialization error
+    #[error("Serialization error: {message}")]
+    Serialization { message: String },
+
     /// Matrix CI error
     #[error("Matrix CI error: {0}")]
     MatrixCIError(#[from] tensor4all_matrixci::MatrixCIError),
@@ -0,0 +1,243 @@
+//! Global pivot search for TensorCI2
+//!
+//! Local two-site updates only explore the neighbourhood of the current
+//! pivots, so sharply localized features of the function can be missed.
+//! A global pivot finder compares the function against the current
+//! interpolant at points far from the pivots and proposes the multi-indices
+//! with large errors as new global pivots.
+
+use crate::error::{Result, TCIError};
+use crate::indexset::MultiIndex;
+use crate::tensorci2::TCI2Options;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tensor4all_matrixci::util::Scalar;
+use tensor4all_tensortrain::{TTCache, TTScalar, TensorTrain};
+
+/// Input to a global pivot search
+#[derive(Debug, Clone, Copy)]
+pub struct GlobalPivotSearchInput<'a, T: TTScalar> {
+    /// Local dimensions of the sites
+    pub local_dims: &'a [usize],
+    /// Current interpolant
+    pub tensor_train: &'a TensorTrain<T>,
+}
+
+/// Strategy for proposing global pivots to a TensorCI2
+pub trait GlobalPivotFinder<T: Scalar + TTScalar> {
+    /// Find multi-indices where the interpolant error exceeds the threshold
+    ///
+    /// # Arguments
+    /// * `input` - Current state of the interpolation
+    /// * `f` - Batch evaluation of the function being interpolated
+    /// * `abs_tol` - Absolute tolerance of the interpolation, already scaled
+    ///   by the largest sample value when errors are normalized
+    fn find_global_pivots(
+        &mut self,
+        input: &GlobalPivotSearchInput<T>,
+        f: &dyn Fn(&[MultiIndex]) -> Vec<T>,
+        abs_tol: f64,
+    ) -> Result<Vec<MultiIndex>>;
+}
+
+/// Random-start greedy local search for global pivots
+///
+/// Starting from `nsearch` random multi-indices, each site index is in turn
+/// replaced by the value maximizing the interpolation error until no further
+/// improvement is found. End points whose error exceeds
+/// `abs_tol * tol_margin` are returned, at most `max_nglobal_pivot` of them.
+/// The starting points, and the candidates for each site, are evaluated as
+/// one batch.
+#[derive(Debug, Clone)]
+pub struct DefaultGlobalPivotFinder {
+    /// Number of random starting points
+    pub nsearch: usize,
+    /// Maximum number of pivots returned per search
+    pub max_nglobal_pivot: usize,
+    /// Errors must exceed `abs_tol * tol_margin` to be accepted
+    pub tol_margin: f64,
+    rng: StdRng,
+}
+
+impl DefaultGlobalPivotFinder {
+    /// Create a new finder with a fixed default seed
+    pub fn new(nsearch: usize, max_nglobal_pivot: usize, tol_margin: f64) -> Self {
+        Self {
+            nsearch,
+            max_nglobal_pivot,
+            tol_margin,
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+
+    /// Create a finder from TCI2 options
+    pub fn from_options(options: &TCI2Options) -> Self {
+        Self::new(
+            options.nsearch,
+            options.max_nglobal_pivot,
+            options.tol_margin_global_search,
+        )
+    }
+
+    /// Reseed the random number generator
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+}
+
+impl Default for DefaultGlobalPivotFinder {
+    fn default() -> Self {
+        Self::new(5, 5, 10.0)
+    }
+}
+
+impl<T: Scalar + TTScalar> GlobalPivotFinder<T> for DefaultGlobalPivotFinder {
+    fn find_global_pivots(
+        &mut self,
+        input: &GlobalPivotSearchInput<T>,
+        f: &dyn Fn(&[MultiIndex]) -> Vec<T>,
+        abs_tol: f64,
+    ) -> Result<Vec<MultiIndex>> {
+        let n = input.local_dims.len();
+        let mut cache = TTCache::new(input.tensor_train);
+        let mut errors = |indices: &[MultiIndex]| -> Result<Vec<f64>> {
+            let values = f(indices);
+            if values.len() != indices.len() {
+                return Err(TCIError::InvalidOperation {
+                    message: format!(
+                        "Batch function returned {} values for {} indices",
+                        values.len(),
+                        indices.len()
+                    ),
+                });
+            }
+            let approx = cache.evaluate_many(indices)?;
+            Ok(values
+                .into_iter()
+                .zip(approx)
+                .map(|(v, a)| f64::sqrt(Scalar::abs_sq(v - a)))
+                .collect())
+        };
+
+        let starts: Vec<MultiIndex> = (0..self.nsearch)
+            .map(|_| {
+                input
+                    .local_dims
+                    .iter()
+                    .map(|&d| self.rng.gen_range(0..d))
+                    .collect()
+            })
+            .collect();
+        let start_errors = errors(&starts)?;
+
+        let mut pivots: Vec<MultiIndex> = Vec::new();
+        for (mut pivot, mut best) in starts.into_iter().zip(start_errors) {
+            if pivots.len() >= self.max_nglobal_pivot {
+                break;
+            }
+
+            // Greedy local search, one site at a time
+            for _ in 0..n {
+                let mut improved = false;
+                for p in 0..n {
+                    let candidates: Vec<MultiIndex> = (0..input.local_dims[p])
+                        .filter(|&s| s != pivot[p])
+                        .map(|s| {
+                            let mut candidate = pivot.clone();
+                            candidate[p] = s;
+                            candidate
+                        })
+                        .collect();
+                    if candidates.is_empty() {
+                        continue;
+                    }
+                    let candidate_errors = errors(&candidates)?;
+                    for (candidate, err) in candidates.into_iter().zip(candidate_errors) {
+                        if err > best {
+                            best = err;
+                            pivot = candidate;
+                            improved = true;
+                        }
+                    }
+                }
+                if !improved {
+                    break;
+                }
+            }
+
+            if best > abs_tol * self.tol_margin && !pivots.contains(&pivot) {
+                pivots.push(pivot);
+            }
+        }
+        Ok(pivots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bump(idx: &MultiIndex) -> f64 {
+        let dist2: usize = idx.iter().map(|&i| (i as isize - 2).pow(2) as usize).sum();
+        1.0 + (-(dist2 as f64)).exp()
+    }
+
+    #[test]
+    fn test_default_finder_finds_localized_feature() {
+        let local_dims = vec![4; 6];
+        let tt = TensorTrain::<f64>::constant(&local_dims, 1.0);
+        let input = GlobalPivotSearchInput {
+            local_dims: &local_dims,
+            tensor_train: &tt,
+        };
+
+        let mut finder = DefaultGlobalPivotFinder::new(10, 3, 10.0);
+        let f = |indices: &[MultiIndex]| indices.iter().map(bump).collect();
+        let pivots = finder.find_global_pivots(&input, &f, 1e-8).unwrap();
+        assert!(!pivots.is_empty());
+        assert!(pivots.len() <= 3);
+        for pivot in &pivots {
+            assert_eq!(pivot, &vec![2; 6]);
+        }
+    }
+
+    #[test]
+    fn test_default_finder_exact_interpolant() {
+        let local_dims = vec![3; 4];
+        let tt = TensorTrain::<f64>::constant(&local_dims, 2.0);
+        let input = GlobalPivotSearchInput {
+            local_dims: &local_dims,
+            tensor_train: &tt,
+        };
+
+        let mut finder = DefaultGlobalPivotFinder::default().with_seed(42);
+        let f = |indices: &[MultiIndex]| vec![2.0; indices.len()];
+        let pivots = finder.find_global_pivots(&input, &f, 1e-8).unwrap();
+        assert!(pivots.is_empty());
+    }
+
+    #[test]
+    fn test_default_finder_propagates_errors() {
+        let local_dims = vec![3; 4];
+        let tt = TensorTrain::<f64>::constant(&[3; 3], 1.0);
+        let input = GlobalPivotSearchInput {
+            local_dims: &local_dims,
+            tensor_train: &tt,
+        };
+
+        // The interpolant has fewer sites than the function
+        let mut finder = DefaultGlobalPivotFinder::default();
+        let f = |indices: &[MultiIndex]| vec![1.0; indices.len()];
+        assert!(finder.find_global_pivots(&input, &f, 1e-8).is_err());
+
+        // A batch function returning the wrong number of values
+        let tt = TensorTrain::<f64>::constant(&local_dims, 1.0);
+        let input = GlobalPivotSearchInput {
+            tensor_train: &tt,
+            ..input
+        };
+        let f = |_: &[MultiIndex]| vec![1.0];
+        assert!(finder.find_global_pivots(&input, &f, 1e-8).is_err());
+    }
+}
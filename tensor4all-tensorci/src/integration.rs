@@ -0,0 +1,292 @@
+//! Numerical integration with tensor cross interpolation
+//!
+//! A multivariate integrand is sampled on a tensor-product quadrature grid,
+//! compressed into a tensor train with TCI2, and integrated by contracting
+//! the tensor train with the quadrature weights of each dimension.
+
+use crate::error::{Result, TCIError};
+use crate::indexset::MultiIndex;
+use crate::tensorci2::{crossinterpolate2, TCI2Options};
+use tensor4all_matrixci::util::Scalar;
+use tensor4all_tensortrain::{AbstractTensorTrain, TTScalar};
+
+/// Nonnegative Gauss–Kronrod 15-point nodes on [-1, 1], in decreasing order
+///
+/// The rule is symmetric; the negative nodes are the mirror images of these.
+const GK15_NODES: [f64; 8] = [
+    0.9914553711208126,
+    0.9491079123427585,
+    0.8648644233597691,
+    0.7415311855993945,
+    0.5860872354676911,
+    0.4058451513773972,
+    0.20778495500789848,
+    0.0,
+];
+
+/// Gauss–Kronrod 15-point weights matching `GK15_NODES`
+const GK15_WEIGHTS: [f64; 8] = [
+    0.022935322010529224,
+    0.06309209262997856,
+    0.10479001032225019,
+    0.14065325971552592,
+    0.1690047266392679,
+    0.19035057806478542,
+    0.20443294007529889,
+    0.20948214108472782,
+];
+
+/// One-dimensional quadrature rule on [-1, 1]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuadratureRule {
+    /// 15-point Gauss–Kronrod rule
+    #[default]
+    GaussKronrod15,
+    /// Gauss–Legendre rule with the given number of nodes
+    GaussLegendre(usize),
+}
+
+impl QuadratureRule {
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        match self {
+            QuadratureRule::GaussKronrod15 => 15,
+            QuadratureRule::GaussLegendre(n) => *n,
+        }
+    }
+
+    /// Check if the rule has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Nodes (in increasing order) and weights on [-1, 1]
+    pub fn nodes_weights(&self) -> (Vec<f64>, Vec<f64>) {
+        match self {
+            QuadratureRule::GaussKronrod15 => {
+                let nodes = GK15_NODES
+                    .iter()
+                    .map(|&x| -x)
+                    .chain(GK15_NODES.iter().rev().skip(1).copied())
+                    .collect();
+                let weights = GK15_WEIGHTS
+                    .iter()
+                    .chain(GK15_WEIGHTS.iter().rev().skip(1))
+                    .copied()
+                    .collect();
+                (nodes, weights)
+            }
+            QuadratureRule::GaussLegendre(n) => gauss_legendre(*n),
+        }
+    }
+}
+
+/// Gauss–Legendre nodes and weights by Newton iteration on P_n
+fn gauss_legendre(n: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut nodes = vec![0.0; n];
+    let mut weights = vec![0.0; n];
+    for i in 0..n.div_ceil(2) {
+        // Initial guess (Chebyshev-like), refined by Newton's method
+        let mut x = -(std::f64::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        for _ in 0..100 {
+            let (p, dp) = legendre(n, x);
+            let dx = p / dp;
+            x -= dx;
+            if dx.abs() < 1e-15 {
+                break;
+            }
+        }
+        let (_, dp) = legendre(n, x);
+        let w = 2.0 / ((1.0 - x * x) * dp * dp);
+        nodes[i] = x;
+        nodes[n - 1 - i] = -x;
+        weights[i] = w;
+        weights[n - 1 - i] = w;
+    }
+    (nodes, weights)
+}
+
+/// Legendre polynomial P_n(x) and its derivative
+fn legendre(n: usize, x: f64) -> (f64, f64) {
+    let (mut p0, mut p1) = (1.0, x);
+    if n == 0 {
+        return (1.0, 0.0);
+    }
+    for k in 2..=n {
+        let k = k as f64;
+        let p2 = ((2.0 * k - 1.0) * x * p1 - (k - 1.0) * p0) / k;
+        p0 = p1;
+        p1 = p2;
+    }
+    let dp = n as f64 * (x * p1 - p0) / (x * x - 1.0);
+    (p1, dp)
+}
+
+/// Options for [`integrate`]
+#[derive(Debug, Clone, Default)]
+pub struct IntegrationOptions {
+    /// Quadrature rule used in every dimension
+    pub rule: QuadratureRule,
+    /// Options for the TCI2 run over the quadrature grid
+    pub tci_options: TCI2Options,
+}
+
+/// Integrate a function over a hyperrectangle
+///
+/// The integrand is interpolated with TCI2 on the tensor-product grid of the
+/// quadrature rule, and the resulting tensor train is contracted with the
+/// quadrature weights using [`AbstractTensorTrain::weighted_sum`].
+///
+/// # Arguments
+/// * `f` - Integrand, takes a point in the hyperrectangle
+/// * `a` - Lower bounds for each dimension
+/// * `b` - Upper bounds for each dimension
+/// * `options` - Quadrature rule and TCI2 options
+pub fn integrate<T, F>(f: F, a: &[f64], b: &[f64], options: &IntegrationOptions) -> Result<T>
+where
+    T: Scalar + TTScalar + Default + From<f64>,
+    F: Fn(&[f64]) -> T,
+{
+    if a.len() != b.len() {
+        return Err(TCIError::DimensionMismatch {
+            message: format!(
+                "Lower bounds ({}) and upper bounds ({}) must have the same length",
+                a.len(),
+                b.len()
+            ),
+        });
+    }
+    if a.is_empty() {
+        return Err(TCIError::Empty);
+    }
+    if options.rule.is_empty() {
+        return Err(TCIError::InvalidOperation {
+            message: "Quadrature rule must have at least one node".to_string(),
+        });
+    }
+
+    let (nodes, weights) = options.rule.nodes_weights();
+    let points: Vec<Vec<f64>> = a
+        .iter()
+        .zip(b)
+        .map(|(&lo, &hi)| {
+            nodes
+                .iter()
+                .map(|&x| 0.5 * (hi + lo) + 0.5 * (hi - lo) * x)
+                .collect()
+        })
+        .collect();
+    let site_weights: Vec<Vec<T>> = a
+        .iter()
+        .zip(b)
+        .map(|(&lo, &hi)| {
+            weights
+                .iter()
+                .map(|&w| T::from(0.5 * (hi - lo) * w))
+                .collect()
+        })
+        .collect();
+
+    let eval = |idx: &MultiIndex| {
+        let x: Vec<f64> = idx.iter().zip(&points).map(|(&i, p)| p[i]).collect();
+        f(&x)
+    };
+
+    // A single dimension is below the minimum size of TCI2: sum directly
+    if a.len() == 1 {
+        return Ok((0..nodes.len()).fold(T::zero(), |acc, i| {
+            acc + site_weights[0][i] * eval(&vec![i])
+        }));
+    }
+
+    let local_dims = vec![nodes.len(); a.len()];
+    let first_pivot = vec![nodes.len() / 2; a.len()];
+    let (tci, _ranks, _errors) = crossinterpolate2::<T, _, fn(&[MultiIndex]) -> Vec<T>>(
+        eval,
+        None,
+        local_dims,
+        vec![first_pivot],
+        options.tci_options.clone(),
+    )?;
+    let tt = tci.to_tensor_train()?;
+    Ok(tt.weighted_sum(&site_weights)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quadrature_rules_integrate_polynomials() {
+        for rule in [
+            QuadratureRule::GaussKronrod15,
+            QuadratureRule::GaussLegendre(1),
+            QuadratureRule::GaussLegendre(4),
+            QuadratureRule::GaussLegendre(7),
+        ] {
+            let (nodes, weights) = rule.nodes_weights();
+            assert_eq!(nodes.len(), rule.len());
+            assert!(nodes.windows(2).all(|w| w[0] < w[1]));
+
+            // Exact for x^k with k < n (all rules here are at least that accurate)
+            for k in 0..rule.len() {
+                let integral: f64 = nodes
+                    .iter()
+                    .zip(&weights)
+                    .map(|(x, w)| w * x.powi(k as i32))
+                    .sum();
+                let exact = if k % 2 == 0 {
+                    2.0 / (k as f64 + 1.0)
+                } else {
+                    0.0
+                };
+                assert!((integral - exact).abs() < 1e-13, "{:?}, k = {}", rule, k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_integrate_polynomial() {
+        // int_0^1 int_0^2 x y^2 dy dx = 1/2 * 8/3
+        let options = IntegrationOptions {
+            rule: QuadratureRule::GaussLegendre(3),
+            ..IntegrationOptions::default()
+        };
+        let result: f64 =
+            integrate(|x| x[0] * x[1] * x[1], &[0.0, 0.0], &[1.0, 2.0], &options).unwrap();
+        assert!((result - 4.0 / 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_integrate_gaussian() {
+        // int_{[-1,1]^4} exp(-|x|^2) dx = (sqrt(pi) erf(1))^4
+        let one_dim = 1.493_648_265_624_854_f64;
+        let result: f64 = integrate(
+            |x| (-x.iter().map(|v| v * v).sum::<f64>()).exp(),
+            &[-1.0; 4],
+            &[1.0; 4],
+            &IntegrationOptions::default(),
+        )
+        .unwrap();
+        assert!((result - one_dim.powi(4)).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_integrate_one_dimension() {
+        let result: f64 = integrate(
+            |x| x[0].cos(),
+            &[0.0],
+            &[1.0],
+            &IntegrationOptions::default(),
+        )
+        .unwrap();
+        assert!((result - 1.0f64.sin()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_integrate_invalid_bounds() {
+        let options = IntegrationOptions::default();
+        assert!(integrate(|_| 1.0f64, &[0.0, 0.0], &[1.0], &options).is_err());
+        assert!(integrate(|_| 1.0f64, &[], &[], &options).is_err());
+    }
+}
@@ -7,6 +7,14 @@
 //!
 //! - `TensorCI1`: One-site TCI algorithm
 //! - `crossinterpolate1`: Function to perform TCI1 interpolation
+//! - `TensorCI2`: Two-site TCI algorithm
+//! - `crossinterpolate2`: Function to perform TCI2 interpolation
+//! - `GlobalPivotFinder`: Pluggable global pivot search for TCI2
+//! - `integrate`: Quadrature of multivariate functions via TCI2
+//! - `parallel_batch`: Thread-parallel batch evaluation for TCI1 and TCI2
+//! - `TensorCI1::save` / `TensorCI2::save`: Versioned checkpoints; resume
+//!   TCI2 runs with `crossinterpolate2_from` (TCI1 checkpoints can be loaded
+//!   and evaluated, but not resumed). Requires the `serde` feature
 //!
 //! # Example
 //!
@@ -22,15 +30,33 @@
 //! println!("TCI rank: {}", tci.rank());
 //! ```
 
+pub mod batch;
 pub mod cached_function;
+#[cfg(feature = "serde")]
+pub mod checkpoint;
 pub mod error;
+pub mod global_pivot_finder;
 pub mod indexset;
+pub mod integration;
 pub mod tensorci1;
 pub mod tensorci2;
 
 // Re-export main types
-pub use cached_function::CachedFunction;
+pub use batch::parallel_batch;
+pub use cached_function::{
+    CachedFunction, ConcurrentCachedFunction, IndexEncoder, QuanticsEncoder, VecEncoder,
+};
 pub use error::{Result, TCIError};
+pub use global_pivot_finder::{
+    DefaultGlobalPivotFinder, GlobalPivotFinder, GlobalPivotSearchInput,
+};
 pub use indexset::{IndexSet, LocalIndex, MultiIndex};
-pub use tensorci1::{crossinterpolate1, SweepStrategy, TCI1Options, TensorCI1};
-pub use tensorci2::{crossinterpolate2, PivotSearchStrategy, TCI2Options, TensorCI2};
+pub use integration::{integrate, IntegrationOptions, QuadratureRule};
+pub use tensorci1::{
+    crossinterpolate1, crossinterpolate1_with_batch, SweepStrategy, TCI1Options, TensorCI1,
+};
+pub use tensorci2::{
+    crossinterpolate2, crossinterpolate2_from, crossinterpolate2_from_with_global_pivot_finder,
+    crossinterpolate2_with_global_pivot_finder, PivotSearchStrategy,
+    TCI2Options, TensorCI2,
+};
@@ -1,9 +1,18 @@
 //! TensorCI1 - One-site Tensor Cross Interpolation algorithm
 
+use crate::batch::BatchEvaluator;
+#[cfg(feature = "serde")]
+use crate::checkpoint::{read_checkpoint, write_checkpoint, MatrixData, TensorData};
 use crate::error::{Result, TCIError};
 use crate::indexset::{IndexSet, MultiIndex};
 use tensor4all_matrixci::util::{a_times_b_inv, zeros, Matrix, Scalar};
 use tensor4all_matrixci::{AbstractMatrixCI, MatrixACA};
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::path::Path;
 use tensor4all_tensortrain::{Tensor3, TensorTrain, TTScalar};
 
 /// Sweep strategy for TCI optimization
@@ -86,6 +95,23 @@ pub struct TensorCI1<T: Scalar + TTScalar> {
     max_sample_value: f64,
 }
 
+#[cfg(feature = "serde")]
+/// Serializable state of a TensorCI1
+#[derive(Serialize, Deserialize)]
+struct TCI1State<T> {
+    i_set: Vec<Vec<MultiIndex>>,
+    j_set: Vec<Vec<MultiIndex>>,
+    local_dims: Vec<usize>,
+    t_tensors: Vec<TensorData<T>>,
+    p_matrices: Vec<MatrixData<T>>,
+    pivot_errors: Vec<f64>,
+    max_sample_value: f64,
+}
+
+#[cfg(feature = "serde")]
+/// Kind tag of TensorCI1 checkpoints
+const TCI1_CHECKPOINT_KIND: &str = "TensorCI1";
+
 impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
     /// Create a new empty TensorCI1
     pub fn new(local_dims: Vec<usize>) -> Self {
@@ -312,6 +338,73 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
         &self.j_set[p]
     }
 
+    #[cfg(feature = "serde")]
+    /// Save the state (pivots, T and P tensors, errors) to a checkpoint file
+    ///
+    /// TCI1 checkpoints store the interpolation for later evaluation; they do
+    /// not store the Pi matrices and ACA state needed to continue sweeping, so
+    /// an interrupted TCI1 run cannot be resumed. Use TCI2 with
+    /// [`crate::crossinterpolate2_from`] for resumable runs.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let state = TCI1State {
+            i_set: self.i_set.iter().map(|s| s.values().to_vec()).collect(),
+            j_set: self.j_set.iter().map(|s| s.values().to_vec()).collect(),
+            local_dims: self.local_dims.clone(),
+            t_tensors: self.t_tensors.iter().map(TensorData::from_tensor).collect(),
+            p_matrices: self.p_matrices.iter().map(MatrixData::from_matrix).collect(),
+            pivot_errors: self.pivot_errors.clone(),
+            max_sample_value: self.max_sample_value,
+        };
+        write_checkpoint(path, TCI1_CHECKPOINT_KIND, &state)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Load a state written by [`TensorCI1::save`]
+    ///
+    /// The loaded interpolation can be evaluated and converted to a tensor
+    /// train, but not used to continue sweeping (see [`TensorCI1::save`]).
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self>
+    where
+        T: DeserializeOwned,
+    {
+        let state: TCI1State<T> = read_checkpoint(path, TCI1_CHECKPOINT_KIND)?;
+        let n = state.local_dims.len();
+        if state.i_set.len() != n
+            || state.j_set.len() != n
+            || state.t_tensors.len() != n
+            || state.p_matrices.len() != n
+            || state.pivot_errors.len() != n.saturating_sub(1)
+        {
+            return Err(TCIError::Serialization {
+                message: "Inconsistent TensorCI1 checkpoint".to_string(),
+            });
+        }
+
+        let mut tci = Self::new(state.local_dims);
+        tci.i_set = state.i_set.into_iter().map(IndexSet::from_vec).collect();
+        tci.j_set = state.j_set.into_iter().map(IndexSet::from_vec).collect();
+        tci.t_tensors = state
+            .t_tensors
+            .into_iter()
+            .map(TensorData::into_tensor)
+            .collect::<Result<_>>()?;
+        tci.p_matrices = state
+            .p_matrices
+            .into_iter()
+            .map(MatrixData::into_matrix)
+            .collect::<Result<_>>()?;
+        tci.pivot_errors = state.pivot_errors;
+        tci.max_sample_value = state.max_sample_value;
+        for p in 0..n {
+            tci.pi_i_set[p] = tci.get_pi_i_set(p);
+            tci.pi_j_set[p] = tci.get_pi_j_set(p);
+        }
+        Ok(tci)
+    }
+
     /// Build the Pi I set for site p
     /// PiIset[p] = { [i..., up] : i in Iset[p], up in 1..localdims[p] }
     fn get_pi_i_set(&self, p: usize) -> IndexSet<MultiIndex> {
@@ -342,31 +435,33 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
 
     /// Build the Pi matrix at bond p
     /// Pi[p][i, j] = f([PiIset[p][i]..., PiJset[p+1][j]...])
-    fn get_pi<F>(&mut self, p: usize, f: &F) -> Matrix<T>
-    where
-        F: Fn(&MultiIndex) -> T,
-    {
+    fn get_pi(&mut self, p: usize, f: &BatchEvaluator<T>) -> Result<Matrix<T>> {
         let i_set = &self.pi_i_set[p];
         let j_set = &self.pi_j_set[p + 1];
 
-        let mut pi = zeros(i_set.len(), j_set.len());
-        for (i, i_multi) in i_set.iter().enumerate() {
-            for (j, j_multi) in j_set.iter().enumerate() {
+        let mut indices = Vec::with_capacity(i_set.len() * j_set.len());
+        for i_multi in i_set.iter() {
+            for j_multi in j_set.iter() {
                 let mut full_idx = i_multi.clone();
                 full_idx.extend(j_multi.iter().cloned());
-                pi[[i, j]] = f(&full_idx);
+                indices.push(full_idx);
+            }
+        }
+        let values = f.eval_batch(&indices)?;
+
+        let mut pi = zeros(i_set.len(), j_set.len());
+        for i in 0..i_set.len() {
+            for j in 0..j_set.len() {
+                pi[[i, j]] = values[i * j_set.len() + j];
             }
         }
 
         self.update_max_sample_matrix(&pi);
-        pi
+        Ok(pi)
     }
 
     /// Update Pi rows at site p (after I set changed at p+1)
-    fn update_pi_rows<F>(&mut self, p: usize, f: &F)
-    where
-        F: Fn(&MultiIndex) -> T,
-    {
+    fn update_pi_rows(&mut self, p: usize, f: &BatchEvaluator<T>) -> Result<()> {
         let new_i_set = self.get_pi_i_set(p);
         // Clone the old set to avoid borrow issues
         let old_i_set: Vec<MultiIndex> = self.pi_i_set[p].iter().cloned().collect();
@@ -392,16 +487,22 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
         }
 
         // Compute new rows
+        let ncols = new_pi.ncols();
+        let mut indices = Vec::with_capacity(new_indices.len() * ncols);
         for i_multi in &new_indices {
+            for j_multi in self.pi_j_set[p + 1].iter() {
+                let mut full_idx = i_multi.clone();
+                full_idx.extend(j_multi.iter().cloned());
+                indices.push(full_idx);
+            }
+        }
+        let values = f.eval_batch(&indices)?;
+        self.update_max_sample(&values);
+        for (k, i_multi) in new_indices.iter().enumerate() {
             if let Some(new_i) = new_i_set.pos(i_multi) {
-                for (j, j_multi) in self.pi_j_set[p + 1].iter().enumerate() {
-                    let mut full_idx = i_multi.clone();
-                    full_idx.extend(j_multi.iter().cloned());
-                    new_pi[[new_i, j]] = f(&full_idx);
+                for j in 0..ncols {
+                    new_pi[[new_i, j]] = values[k * ncols + j];
                 }
-                // Update max sample
-                let row: Vec<T> = (0..new_pi.ncols()).map(|j| new_pi[[new_i, j]]).collect();
-                self.update_max_sample(&row);
             }
         }
 
@@ -420,13 +521,11 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
             .filter_map(|i| self.pi_i_set[p].pos(i))
             .collect();
         self.aca[p].set_rows(&t_p, &permutation);
+        Ok(())
     }
 
     /// Update Pi cols at site p (after J set changed at p)
-    fn update_pi_cols<F>(&mut self, p: usize, f: &F)
-    where
-        F: Fn(&MultiIndex) -> T,
-    {
+    fn update_pi_cols(&mut self, p: usize, f: &BatchEvaluator<T>) -> Result<()> {
         let new_j_set = self.get_pi_j_set(p + 1);
         // Clone the old set to avoid borrow issues
         let old_j_set: Vec<MultiIndex> = self.pi_j_set[p + 1].iter().cloned().collect();
@@ -452,16 +551,22 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
         }
 
         // Compute new columns
+        let nrows = new_pi.nrows();
+        let mut indices = Vec::with_capacity(new_indices.len() * nrows);
         for j_multi in &new_indices {
+            for i_multi in self.pi_i_set[p].iter() {
+                let mut full_idx = i_multi.clone();
+                full_idx.extend(j_multi.iter().cloned());
+                indices.push(full_idx);
+            }
+        }
+        let values = f.eval_batch(&indices)?;
+        self.update_max_sample(&values);
+        for (k, j_multi) in new_indices.iter().enumerate() {
             if let Some(new_j) = new_j_set.pos(j_multi) {
-                for (i, i_multi) in self.pi_i_set[p].iter().enumerate() {
-                    let mut full_idx = i_multi.clone();
-                    full_idx.extend(j_multi.iter().cloned());
-                    new_pi[[i, new_j]] = f(&full_idx);
+                for i in 0..nrows {
+                    new_pi[[i, new_j]] = values[k * nrows + i];
                 }
-                // Update max sample
-                let col: Vec<T> = (0..new_pi.nrows()).map(|i| new_pi[[i, new_j]]).collect();
-                self.update_max_sample(&col);
             }
         }
 
@@ -477,13 +582,11 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
             .filter_map(|j| self.pi_j_set[p + 1].pos(j))
             .collect();
         self.aca[p].set_cols(&t_mat, &permutation);
+        Ok(())
     }
 
     /// Add a pivot row at bond p
-    fn add_pivot_row<F>(&mut self, p: usize, new_i: usize, f: &F)
-    where
-        F: Fn(&MultiIndex) -> T,
-    {
+    fn add_pivot_row(&mut self, p: usize, new_i: usize, f: &BatchEvaluator<T>) -> Result<()> {
         // Add to ACA
         let _ = self.aca[p].add_pivot_row(&self.pi[p], new_i);
 
@@ -514,15 +617,13 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
 
         // Update adjacent Pi matrix if exists
         if p < self.len() - 2 {
-            self.update_pi_rows(p + 1, f);
+            self.update_pi_rows(p + 1, f)?;
         }
+        Ok(())
     }
 
     /// Add a pivot col at bond p
-    fn add_pivot_col<F>(&mut self, p: usize, new_j: usize, f: &F)
-    where
-        F: Fn(&MultiIndex) -> T,
-    {
+    fn add_pivot_col(&mut self, p: usize, new_j: usize, f: &BatchEvaluator<T>) -> Result<()> {
         // Add to ACA
         let _ = self.aca[p].add_pivot_col(&self.pi[p], new_j);
 
@@ -553,8 +654,9 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
 
         // Update adjacent Pi matrix if exists
         if p > 0 {
-            self.update_pi_cols(p - 1, f);
+            self.update_pi_cols(p - 1, f)?;
         }
+        Ok(())
     }
 
     /// Update P matrix at bond p from current I and J sets
@@ -577,12 +679,9 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
     }
 
     /// Add a pivot at bond p
-    fn add_pivot<F>(&mut self, p: usize, f: &F, tolerance: f64)
-    where
-        F: Fn(&MultiIndex) -> T,
-    {
+    fn add_pivot(&mut self, p: usize, f: &BatchEvaluator<T>, tolerance: f64) -> Result<()> {
         if p >= self.len() - 1 {
-            return;
+            return Ok(());
         }
 
         // Check if we've reached full rank
@@ -590,7 +689,7 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
         let pi_cols = self.pi[p].ncols();
         if self.aca[p].rank() >= pi_rows.min(pi_cols) {
             self.pivot_errors[p] = 0.0;
-            return;
+            return Ok(());
         }
 
         // Find new pivot using ACA
@@ -602,25 +701,23 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
                 self.pivot_errors[p] = error_val;
 
                 if error_val < tolerance {
-                    return;
+                    return Ok(());
                 }
 
                 // Add pivot column first, then row
-                self.add_pivot_col(p, new_j, f);
-                self.add_pivot_row(p, new_i, f);
+                self.add_pivot_col(p, new_j, f)?;
+                self.add_pivot_row(p, new_i, f)?;
             }
             Err(_) => {
                 self.pivot_errors[p] = 0.0;
             }
         }
+        Ok(())
     }
 
     /// Initialize from function with first pivot
-    fn initialize_from_pivot<F>(&mut self, f: &F, first_pivot: &MultiIndex) -> Result<()>
-    where
-        F: Fn(&MultiIndex) -> T,
-    {
-        let first_value = f(first_pivot);
+    fn initialize_from_pivot(&mut self, f: &BatchEvaluator<T>, first_pivot: &MultiIndex) -> Result<()> {
+        let first_value = f.eval(first_pivot);
         if Scalar::abs_sq(first_value) < 1e-30 {
             return Err(TCIError::InvalidPivot {
                 message: "First pivot must have non-zero function value".to_string(),
@@ -646,7 +743,7 @@ impl<T: Scalar + TTScalar + Default> TensorCI1<T> {
 
         // Build Pi matrices
         for p in 0..n - 1 {
-            self.pi[p] = self.get_pi(p, f);
+            self.pi[p] = self.get_pi(p, f)?;
         }
 
         // Initialize ACA and T tensors for each bond
@@ -834,6 +931,41 @@ where
     T: Scalar + TTScalar + Default,
     F: Fn(&MultiIndex) -> T,
 {
+    crossinterpolate1_with_batch::<T, F, fn(&[MultiIndex]) -> Vec<T>>(
+        f,
+        None,
+        local_dims,
+        first_pivot,
+        options,
+    )
+}
+
+/// Cross interpolate a function using TCI1 with optional batch evaluation
+///
+/// New entries of the Pi matrices are requested from `batched_f` in one call
+/// per update when it is given (see [`crate::parallel_batch`] for a
+/// thread-parallel batch function); `f` is then only used for the first
+/// pivot.
+///
+/// # Arguments
+/// * `f` - Function to interpolate, takes a multi-index and returns a value
+/// * `batched_f` - Optional batch evaluation function for efficiency
+/// * `local_dims` - Local dimensions for each site
+/// * `first_pivot` - Initial pivot point
+/// * `options` - Algorithm options
+pub fn crossinterpolate1_with_batch<T, F, B>(
+    f: F,
+    batched_f: Option<B>,
+    local_dims: Vec<usize>,
+    first_pivot: MultiIndex,
+    options: TCI1Options,
+) -> Result<(TensorCI1<T>, Vec<usize>, Vec<f64>)>
+where
+    T: Scalar + TTScalar + Default,
+    F: Fn(&MultiIndex) -> T,
+    B: Fn(&[MultiIndex]) -> Vec<T>,
+{
+    let f = BatchEvaluator::new(&f, batched_f.as_ref());
     if local_dims.len() != first_pivot.len() {
         return Err(TCIError::DimensionMismatch {
             message: format!(
@@ -856,11 +988,11 @@ where
         // Sweep
         if forward_sweep(options.sweep_strategy, iter) {
             for bond_index in 0..n - 1 {
-                tci.add_pivot(bond_index, &f, options.pivot_tolerance);
+                tci.add_pivot(bond_index, &f, options.pivot_tolerance)?;
             }
         } else {
             for bond_index in (0..n - 1).rev() {
-                tci.add_pivot(bond_index, &f, options.pivot_tolerance);
+                tci.add_pivot(bond_index, &f, options.pivot_tolerance)?;
             }
         }
 
@@ -1037,6 +1169,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_crossinterpolate1_with_batch_function() {
+        use std::cell::Cell;
+
+        let f = |idx: &MultiIndex| (idx[0] + 2 * idx[1] + idx[2] * idx[3] + 1) as f64;
+        let ncalls = Cell::new(0);
+        let batched_f = |indices: &[MultiIndex]| -> Vec<f64> {
+            ncalls.set(ncalls.get() + 1);
+            indices.iter().map(f).collect()
+        };
+        let local_dims = vec![3, 3, 3, 3];
+        let first_pivot = vec![1, 1, 1, 1];
+
+        let (tci_batch, _, errors_batch) = crossinterpolate1_with_batch(
+            f,
+            Some(batched_f),
+            local_dims.clone(),
+            first_pivot.clone(),
+            TCI1Options::default(),
+        )
+        .unwrap();
+        let (tci, _, errors) =
+            crossinterpolate1(f, local_dims, first_pivot, TCI1Options::default()).unwrap();
+
+        assert!(ncalls.get() > 0);
+        assert_eq!(tci_batch.rank(), tci.rank());
+        assert_eq!(errors_batch, errors);
+        for idx in [vec![0, 0, 0, 0], vec![2, 1, 2, 2], vec![1, 2, 0, 1]] {
+            assert!((tci_batch.evaluate(&idx).unwrap() - f(&idx)).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_crossinterpolate1_parallel_batch() {
+        let f = |idx: &MultiIndex| ((idx[0] + 1) * (idx[1] + 2) + idx[2]) as f64;
+        let (tci, _, _) = crossinterpolate1_with_batch(
+            f,
+            Some(crate::batch::parallel_batch(f)),
+            vec![4, 4, 4],
+            vec![1, 1, 1],
+            TCI1Options::default(),
+        )
+        .unwrap();
+
+        for idx in [vec![0, 0, 0], vec![3, 2, 1], vec![2, 3, 3]] {
+            assert!((tci.evaluate(&idx).unwrap() - f(&idx)).abs() < 1e-10);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tensorci1_save_load() {
+        let f = |idx: &MultiIndex| ((idx[0] + 1) * (idx[1] + 2) + idx[2]) as f64;
+        let (tci, _, _) =
+            crossinterpolate1(f, vec![3, 3, 3], vec![1, 1, 1], TCI1Options::default()).unwrap();
+        let path = std::env::temp_dir().join(format!(
+            "tensor4all_tensorci1_checkpoint_{}.bin",
+            std::process::id()
+        ));
+
+        tci.save(&path).unwrap();
+        let loaded = TensorCI1::<f64>::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.link_dims(), tci.link_dims());
+        assert_eq!(loaded.max_sample_value(), tci.max_sample_value());
+        assert_eq!(loaded.last_sweep_pivot_error(), tci.last_sweep_pivot_error());
+        for idx in [vec![0, 0, 0], vec![2, 1, 2], vec![1, 2, 0]] {
+            assert_eq!(loaded.evaluate(&idx).unwrap(), tci.evaluate(&idx).unwrap());
+        }
+        assert!(TensorCI1::<f64>::load(&path).is_err());
+    }
+
     #[test]
     fn test_crossinterpolate1_rank2_function() {
         // A rank-2 function: f(i, j) = i + j (not separable)
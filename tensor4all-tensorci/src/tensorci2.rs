@@ -1,13 +1,25 @@
 //! TensorCI2 - Two-site Tensor Cross Interpolation algorithm
 //!
 //! This implements the TCI2 algorithm which uses two-site updates for
-//! more efficient convergence. Unlike TCI1, it supports batch evaluation
-//! of function values through an explicit batch function parameter.
+//! more efficient convergence. Function values can be requested in batches
+//! through an explicit batch function parameter.
 
+use crate::batch::BatchEvaluator;
+#[cfg(feature = "serde")]
+use crate::checkpoint::{read_checkpoint, write_checkpoint, TensorData};
 use crate::error::{Result, TCIError};
+use crate::global_pivot_finder::{
+    DefaultGlobalPivotFinder, GlobalPivotFinder, GlobalPivotSearchInput,
+};
 use crate::indexset::MultiIndex;
-use tensor4all_matrixci::util::{zeros, Scalar};
+use tensor4all_matrixci::util::{a_times_b_inv, zeros, Matrix, Scalar};
 use tensor4all_matrixci::{AbstractMatrixCI, MatrixLUCI, RrLUOptions};
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::path::Path;
 use tensor4all_tensortrain::{Tensor3, TensorTrain, TTScalar};
 
 /// Options for TCI2 algorithm
@@ -25,10 +37,13 @@ pub struct TCI2Options {
     pub normalize_error: bool,
     /// Verbosity level
     pub verbosity: usize,
-    /// Number of global pivots to search per iteration
+    /// Maximum number of global pivots added per iteration (0 disables the
+    /// global pivot search)
     pub max_nglobal_pivot: usize,
-    /// Number of random searches for global pivots
+    /// Number of random starting points of the global pivot search
     pub nsearch: usize,
+    /// Global pivots must have errors above `tolerance * tol_margin_global_search`
+    pub tol_margin_global_search: f64,
 }
 
 impl Default for TCI2Options {
@@ -40,8 +55,9 @@ impl Default for TCI2Options {
             pivot_search: PivotSearchStrategy::Full,
             normalize_error: true,
             verbosity: 0,
-            max_nglobal_pivot: 5,
-            nsearch: 100,
+            max_nglobal_pivot: 0,
+            nsearch: 0,
+            tol_margin_global_search: 10.0,
         }
     }
 }
@@ -76,8 +92,31 @@ pub struct TensorCI2<T: Scalar + TTScalar> {
     bond_errors: Vec<f64>,
     /// Maximum sample value found
     max_sample_value: f64,
+    /// Normalized error after each iteration (half-sweep) so far
+    error_history: Vec<f64>,
+    /// Rank after each iteration (half-sweep) so far
+    rank_history: Vec<usize>,
 }
 
+#[cfg(feature = "serde")]
+/// Serializable state of a TensorCI2
+#[derive(Serialize, Deserialize)]
+struct TCI2State<T> {
+    i_set: Vec<Vec<MultiIndex>>,
+    j_set: Vec<Vec<MultiIndex>>,
+    local_dims: Vec<usize>,
+    site_tensors: Vec<TensorData<T>>,
+    pivot_errors: Vec<f64>,
+    bond_errors: Vec<f64>,
+    max_sample_value: f64,
+    error_history: Vec<f64>,
+    rank_history: Vec<usize>,
+}
+
+#[cfg(feature = "serde")]
+/// Kind tag of TensorCI2 checkpoints
+const TCI2_CHECKPOINT_KIND: &str = "TensorCI2";
+
 impl<T: Scalar + TTScalar + Default> TensorCI2<T> {
     /// Create a new empty TensorCI2
     pub fn new(local_dims: Vec<usize>) -> Result<Self> {
@@ -96,6 +135,8 @@ impl<T: Scalar + TTScalar + Default> TensorCI2<T> {
             pivot_errors: Vec::new(),
             bond_errors: vec![0.0; n.saturating_sub(1)],
             max_sample_value: 0.0,
+            error_history: Vec::new(),
+            rank_history: Vec::new(),
         })
     }
 
@@ -150,6 +191,16 @@ impl<T: Scalar + TTScalar + Default> TensorCI2<T> {
         &self.pivot_errors
     }
 
+    /// Get the normalized error after each iteration so far
+    pub fn error_history(&self) -> &[f64] {
+        &self.error_history
+    }
+
+    /// Get the rank after each iteration so far
+    pub fn rank_history(&self) -> &[usize] {
+        &self.rank_history
+    }
+
     /// Check if site tensors are available
     pub fn is_site_tensors_available(&self) -> bool {
         self.site_tensors.iter().all(|t| {
@@ -201,6 +252,66 @@ impl<T: Scalar + TTScalar + Default> TensorCI2<T> {
         Ok(())
     }
 
+    #[cfg(feature = "serde")]
+    /// Save the state (pivots, site tensors, errors, iteration history) to a
+    /// checkpoint file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let state = TCI2State {
+            i_set: self.i_set.clone(),
+            j_set: self.j_set.clone(),
+            local_dims: self.local_dims.clone(),
+            site_tensors: self.site_tensors.iter().map(TensorData::from_tensor).collect(),
+            pivot_errors: self.pivot_errors.clone(),
+            bond_errors: self.bond_errors.clone(),
+            max_sample_value: self.max_sample_value,
+            error_history: self.error_history.clone(),
+            rank_history: self.rank_history.clone(),
+        };
+        write_checkpoint(path, TCI2_CHECKPOINT_KIND, &state)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Load a state written by [`TensorCI2::save`]
+    ///
+    /// Use [`crossinterpolate2_from`] to continue the interpolation.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self>
+    where
+        T: DeserializeOwned,
+    {
+        let state: TCI2State<T> = read_checkpoint(path, TCI2_CHECKPOINT_KIND)?;
+        let n = state.local_dims.len();
+        if n < 2
+            || state.i_set.len() != n
+            || state.j_set.len() != n
+            || state.site_tensors.len() != n
+            || state.bond_errors.len() != n - 1
+            || state.error_history.len() != state.rank_history.len()
+        {
+            return Err(TCIError::Serialization {
+                message: "Inconsistent TensorCI2 checkpoint".to_string(),
+            });
+        }
+
+        Ok(Self {
+            i_set: state.i_set,
+            j_set: state.j_set,
+            local_dims: state.local_dims,
+            site_tensors: state
+                .site_tensors
+                .into_iter()
+                .map(TensorData::into_tensor)
+                .collect::<Result<_>>()?,
+            pivot_errors: state.pivot_errors,
+            bond_errors: state.bond_errors,
+            max_sample_value: state.max_sample_value,
+            error_history: state.error_history,
+            rank_history: state.rank_history,
+        })
+    }
+
     /// Invalidate all site tensors
     fn invalidate_site_tensors(&mut self) {
         for p in 0..self.len() {
@@ -208,6 +319,94 @@ impl<T: Scalar + TTScalar + Default> TensorCI2<T> {
         }
     }
 
+    /// Recompute all site tensors from the current pivots
+    ///
+    /// Site tensor p is `f(I_p ⊕ s, J_p) * f(I_{p+1}, J_p)^{-1}`; the last
+    /// site is `f(I_{L-1} ⊕ s)`. This requires `|I_{p+1}| == |J_p|` on every
+    /// bond, which holds after a full sweep.
+    pub fn fill_site_tensors<F>(&mut self, f: &F) -> Result<()>
+    where
+        F: Fn(&MultiIndex) -> T,
+    {
+        self.fill_site_tensors_with(&BatchEvaluator::new::<_, fn(&[MultiIndex]) -> Vec<T>>(
+            f, None,
+        ))
+    }
+
+    fn fill_site_tensors_with(&mut self, f: &BatchEvaluator<T>) -> Result<()> {
+        let n = self.len();
+        let mut tensors = Vec::with_capacity(n);
+        for p in 0..n {
+            let i_combined = self.kronecker_i(p);
+            let left_dim = self.i_set[p].len();
+            let site_dim = self.local_dims[p];
+            let right_dim = if p == n - 1 { 1 } else { self.j_set[p].len() };
+            if p < n - 1 && self.i_set[p + 1].len() != right_dim {
+                return Err(TCIError::InvalidOperation {
+                    message: format!(
+                        "Pivot matrix at bond {} is not square ({} x {})",
+                        p,
+                        self.i_set[p + 1].len(),
+                        right_dim
+                    ),
+                });
+            }
+
+            let j_set = if p == n - 1 { vec![Vec::new()] } else { self.j_set[p].clone() };
+            let pi = self.sample_matrix(f, &i_combined, &j_set)?;
+            let mat = if p < n - 1 {
+                let i_pivots = self.i_set[p + 1].clone();
+                let pivot = self.sample_matrix(f, &i_pivots, &j_set)?;
+                a_times_b_inv(&pi, &pivot)
+            } else {
+                pi
+            };
+
+            let mut tensor = Tensor3::zeros(left_dim, site_dim, right_dim);
+            for l in 0..left_dim {
+                for s in 0..site_dim {
+                    for r in 0..right_dim {
+                        tensor.set(l, s, r, mat[[l * site_dim + s, r]]);
+                    }
+                }
+            }
+            tensors.push(tensor);
+        }
+        self.site_tensors = tensors;
+        Ok(())
+    }
+
+    /// Evaluate `f(i ⊕ j)` for all pairs and update the maximum sample value
+    fn sample_matrix(
+        &mut self,
+        f: &BatchEvaluator<T>,
+        i_set: &[MultiIndex],
+        j_set: &[MultiIndex],
+    ) -> Result<Matrix<T>> {
+        let mut indices = Vec::with_capacity(i_set.len() * j_set.len());
+        for i_multi in i_set {
+            for j_multi in j_set {
+                let mut full_idx = i_multi.clone();
+                full_idx.extend(j_multi.iter().cloned());
+                indices.push(full_idx);
+            }
+        }
+        let values = f.eval_batch(&indices)?;
+
+        let mut mat = zeros(i_set.len(), j_set.len());
+        for i in 0..i_set.len() {
+            for j in 0..j_set.len() {
+                let value = values[i * j_set.len() + j];
+                let abs_val = f64::sqrt(Scalar::abs_sq(value));
+                if abs_val > self.max_sample_value {
+                    self.max_sample_value = abs_val;
+                }
+                mat[[i, j]] = value;
+            }
+        }
+        Ok(mat)
+    }
+
     /// Expand indices by Kronecker product with local dimension
     fn kronecker_i(&self, p: usize) -> Vec<MultiIndex> {
         let mut result = Vec::new();
@@ -247,6 +446,9 @@ impl<T: Scalar + TTScalar + Default> TensorCI2<T> {
 /// * `TensorCI2` - The constructed tensor cross interpolation
 /// * `Vec<usize>` - Ranks at each iteration
 /// * `Vec<f64>` - Errors at each iteration
+///
+/// If `options.max_nglobal_pivot > 0`, global pivots are searched with
+/// [`DefaultGlobalPivotFinder`] configured from `options`.
 pub fn crossinterpolate2<T, F, B>(
     f: F,
     batched_f: Option<B>,
@@ -258,6 +460,39 @@ where
     T: Scalar + TTScalar + Default,
     F: Fn(&MultiIndex) -> T,
     B: Fn(&[MultiIndex]) -> Vec<T>,
+{
+    let mut finder = DefaultGlobalPivotFinder::from_options(&options);
+    crossinterpolate2_with_global_pivot_finder(
+        f,
+        batched_f,
+        local_dims,
+        initial_pivots,
+        options,
+        &mut finder,
+    )
+}
+
+/// Cross interpolate a function using TCI2 with a custom global pivot finder
+///
+/// If `options.max_nglobal_pivot > 0`, the site tensors are recomputed from
+/// the pivots after each sweep and `finder` is asked for multi-indices where
+/// the interpolant is inaccurate; up to `options.max_nglobal_pivot` of them
+/// are added as global pivots before the next sweep. The finder evaluates the
+/// function through `batched_f` if given. See [`crossinterpolate2`] for the
+/// other arguments.
+pub fn crossinterpolate2_with_global_pivot_finder<T, F, B, G>(
+    f: F,
+    batched_f: Option<B>,
+    local_dims: Vec<usize>,
+    initial_pivots: Vec<MultiIndex>,
+    options: TCI2Options,
+    finder: &mut G,
+) -> Result<(TensorCI2<T>, Vec<usize>, Vec<f64>)>
+where
+    T: Scalar + TTScalar + Default,
+    F: Fn(&MultiIndex) -> T,
+    B: Fn(&[MultiIndex]) -> Vec<T>,
+    G: GlobalPivotFinder<T> + ?Sized,
 {
     if local_dims.len() < 2 {
         return Err(TCIError::DimensionMismatch {
@@ -275,13 +510,8 @@ where
     tci.add_global_pivots(&pivots)?;
 
     // Initialize max_sample_value
-    for pivot in &pivots {
-        let value = f(pivot);
-        let abs_val = f64::sqrt(Scalar::abs_sq(value));
-        if abs_val > tci.max_sample_value {
-            tci.max_sample_value = abs_val;
-        }
-    }
+    let evaluator = BatchEvaluator::new(&f, batched_f.as_ref());
+    tci.sample_matrix(&evaluator, &pivots, &[Vec::new()])?;
 
     if tci.max_sample_value < 1e-30 {
         return Err(TCIError::InvalidPivot {
@@ -289,35 +519,106 @@ where
         });
     }
 
+    let (ranks, errors) = optimize(&mut tci, &evaluator, &options, finder)?;
+    Ok((tci, ranks, errors))
+}
+
+/// Continue TCI2 sweeps from an existing state
+///
+/// Typically used with [`TensorCI2::load`] to resume an interrupted run from
+/// a checkpoint written by [`TensorCI2::save`]. The sweep direction follows
+/// on from the last completed iteration, and up to `options.max_iter`
+/// further iterations are run, so a run split into several calls performs
+/// the same sweeps as a single call. The returned ranks and errors cover the
+/// whole run, including the iterations before the checkpoint.
+///
+/// Global pivots are searched with a fresh [`DefaultGlobalPivotFinder`]
+/// configured from `options`; use
+/// [`crossinterpolate2_from_with_global_pivot_finder`] to pass another one.
+/// The state of the finder (e.g. its random number generator) is not part of
+/// the checkpoint.
+pub fn crossinterpolate2_from<T, F, B>(
+    tci: TensorCI2<T>,
+    f: F,
+    batched_f: Option<B>,
+    options: TCI2Options,
+) -> Result<(TensorCI2<T>, Vec<usize>, Vec<f64>)>
+where
+    T: Scalar + TTScalar + Default,
+    F: Fn(&MultiIndex) -> T,
+    B: Fn(&[MultiIndex]) -> Vec<T>,
+{
+    let mut finder = DefaultGlobalPivotFinder::from_options(&options);
+    crossinterpolate2_from_with_global_pivot_finder(tci, f, batched_f, options, &mut finder)
+}
+
+/// Continue TCI2 sweeps from an existing state with a custom global pivot finder
+///
+/// See [`crossinterpolate2_from`] and [`crossinterpolate2_with_global_pivot_finder`].
+pub fn crossinterpolate2_from_with_global_pivot_finder<T, F, B, G>(
+    tci: TensorCI2<T>,
+    f: F,
+    batched_f: Option<B>,
+    options: TCI2Options,
+    finder: &mut G,
+) -> Result<(TensorCI2<T>, Vec<usize>, Vec<f64>)>
+where
+    T: Scalar + TTScalar + Default,
+    F: Fn(&MultiIndex) -> T,
+    B: Fn(&[MultiIndex]) -> Vec<T>,
+    G: GlobalPivotFinder<T> + ?Sized,
+{
+    let mut tci = tci;
+    if tci.i_set.iter().any(|s| s.is_empty()) || tci.j_set.iter().any(|s| s.is_empty()) {
+        return Err(TCIError::InvalidPivot {
+            message: "TensorCI2 has no pivots to continue from".to_string(),
+        });
+    }
+
+    let evaluator = BatchEvaluator::new(&f, batched_f.as_ref());
+    let (ranks, errors) = optimize(&mut tci, &evaluator, &options, finder)?;
+    Ok((tci, ranks, errors))
+}
+
+/// Run TCI2 sweeps until convergence or `options.max_iter` further iterations
+///
+/// Returns the rank and error histories of the whole run.
+fn optimize<T, G>(
+    tci: &mut TensorCI2<T>,
+    evaluator: &BatchEvaluator<T>,
+    options: &TCI2Options,
+    finder: &mut G,
+) -> Result<(Vec<usize>, Vec<f64>)>
+where
+    T: Scalar + TTScalar + Default,
+    G: GlobalPivotFinder<T> + ?Sized,
+{
     let n = tci.len();
-    let mut errors = Vec::new();
-    let mut ranks = Vec::new();
+    let completed = tci.error_history.len();
 
-    // Main optimization loop
-    for iter in 0..options.max_iter {
+    // Main optimization loop, alternating direction with the completed sweeps
+    for iter in completed..completed + options.max_iter {
         let is_forward = iter % 2 == 0;
 
         // Sweep through bonds
         if is_forward {
             for b in 0..n - 1 {
                 update_pivots(
-                    &mut tci,
+                    tci,
                     b,
-                    &f,
-                    &batched_f,
+                    evaluator,
                     true, // left orthogonal in forward sweep
-                    &options,
+                    options,
                 )?;
             }
         } else {
             for b in (0..n - 1).rev() {
                 update_pivots(
-                    &mut tci,
+                    tci,
                     b,
-                    &f,
-                    &batched_f,
+                    evaluator,
                     false, // right orthogonal in backward sweep
-                    &options,
+                    options,
                 )?;
             }
         }
@@ -330,40 +631,66 @@ where
             error
         };
 
-        errors.push(error_normalized);
-        ranks.push(tci.rank());
+        tci.error_history.push(error_normalized);
+        tci.rank_history.push(tci.rank());
+
+        // Search for global pivots (not after the last sweep, which would
+        // leave the site tensors invalidated)
+        let mut nglobal = 0;
+        if options.max_nglobal_pivot > 0 && iter + 1 < completed + options.max_iter {
+            tci.fill_site_tensors_with(evaluator)?;
+            let abs_tol = if options.normalize_error {
+                options.tolerance * tci.max_sample_value
+            } else {
+                options.tolerance
+            };
+            let tt = tci.to_tensor_train()?;
+            let input = GlobalPivotSearchInput {
+                local_dims: &tci.local_dims,
+                tensor_train: &tt,
+            };
+            // The finder checks the number of returned values itself
+            let batch = |indices: &[MultiIndex]| evaluator.eval_batch_unchecked(indices);
+            let mut pivots = finder.find_global_pivots(&input, &batch, abs_tol)?;
+            pivots.truncate(options.max_nglobal_pivot);
+            nglobal = pivots.len();
+            if nglobal > 0 {
+                tci.add_global_pivots(&pivots)?;
+            }
+        }
 
         if options.verbosity > 0 {
             println!(
-                "iteration = {}, rank = {}, error = {:.2e}",
+                "iteration = {}, rank = {}, error = {:.2e}, nglobalpivot = {}",
                 iter + 1,
                 tci.rank(),
-                error_normalized
+                error_normalized,
+                nglobal
             );
         }
 
         // Check convergence
-        if error_normalized < options.tolerance {
+        if error_normalized < options.tolerance && nglobal == 0 {
             break;
         }
     }
 
-    Ok((tci, ranks, errors))
+    if !tci.is_site_tensors_available() {
+        tci.fill_site_tensors_with(evaluator)?;
+    }
+    Ok((tci.rank_history.clone(), tci.error_history.clone()))
 }
 
 /// Update pivots at bond b using LU-based cross interpolation
-fn update_pivots<T, F, B>(
+fn update_pivots<T>(
     tci: &mut TensorCI2<T>,
     b: usize,
-    f: &F,
-    batched_f: &Option<B>,
+    f: &BatchEvaluator<T>,
     left_orthogonal: bool,
     options: &TCI2Options,
 ) -> Result<()>
 where
     T: Scalar + TTScalar + Default,
-    F: Fn(&MultiIndex) -> T,
-    B: Fn(&[MultiIndex]) -> Vec<T>,
 {
     // Invalidate site tensors
     tci.invalidate_site_tensors();
@@ -377,51 +704,7 @@ where
     }
 
     // Build Pi matrix
-    let mut pi = zeros(i_combined.len(), j_combined.len());
-
-    // Use batch evaluation if available, otherwise use single evaluation
-    if let Some(ref batch_fn) = batched_f {
-        // Build all index pairs
-        let mut all_indices: Vec<MultiIndex> = Vec::with_capacity(i_combined.len() * j_combined.len());
-        for i_multi in &i_combined {
-            for j_multi in &j_combined {
-                let mut full_idx = i_multi.clone();
-                full_idx.extend(j_multi.iter().cloned());
-                all_indices.push(full_idx);
-            }
-        }
-
-        // Batch evaluate
-        let values = batch_fn(&all_indices);
-
-        // Fill Pi matrix
-        let mut idx = 0;
-        for i in 0..i_combined.len() {
-            for j in 0..j_combined.len() {
-                pi[[i, j]] = values[idx];
-                let abs_val = f64::sqrt(Scalar::abs_sq(values[idx]));
-                if abs_val > tci.max_sample_value {
-                    tci.max_sample_value = abs_val;
-                }
-                idx += 1;
-            }
-        }
-    } else {
-        // Single evaluation
-        for (i, i_multi) in i_combined.iter().enumerate() {
-            for (j, j_multi) in j_combined.iter().enumerate() {
-                let mut full_idx = i_multi.clone();
-                full_idx.extend(j_multi.iter().cloned());
-                let value = f(&full_idx);
-                pi[[i, j]] = value;
-
-                let abs_val = f64::sqrt(Scalar::abs_sq(value));
-                if abs_val > tci.max_sample_value {
-                    tci.max_sample_value = abs_val;
-                }
-            }
-        }
-    }
+    let pi = tci.sample_matrix(f, &i_combined, &j_combined)?;
 
     // Apply LU-based cross interpolation
     let lu_options = RrLUOptions {
@@ -491,6 +774,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tensor4all_tensortrain::AbstractTensorTrain;
 
     #[test]
     fn test_tensorci2_new() {
@@ -547,6 +831,162 @@ mod tests {
         assert_eq!(tci.len(), 2);
     }
 
+    #[test]
+    fn test_crossinterpolate2_parallel_batch() {
+        let f = |idx: &MultiIndex| ((idx[0] + 1) * (idx[1] + 2) + idx[2] * idx[3]) as f64;
+        let local_dims = vec![3, 3, 3, 3];
+        let options = TCI2Options {
+            tolerance: 1e-12,
+            ..TCI2Options::default()
+        };
+
+        let (tci, _ranks, _errors) = crossinterpolate2(
+            f,
+            Some(crate::batch::parallel_batch(f)),
+            local_dims,
+            vec![vec![1, 1, 1, 1]],
+            options,
+        )
+        .unwrap();
+
+        let tt = tci.to_tensor_train().unwrap();
+        for idx in [vec![0, 0, 0, 0], vec![2, 1, 2, 2], vec![1, 2, 0, 1]] {
+            assert!((tt.evaluate(&idx).unwrap() - f(&idx)).abs() < 1e-10);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tensorci2_checkpoint_resume() {
+        let f = |idx: &MultiIndex| 1.0 / (1.0 + idx.iter().sum::<usize>() as f64);
+        let local_dims = vec![4; 5];
+        let options = TCI2Options {
+            tolerance: 1e-10,
+            max_iter: 2,
+            ..TCI2Options::default()
+        };
+        let path = std::env::temp_dir().join(format!(
+            "tensor4all_tensorci2_checkpoint_{}.bin",
+            std::process::id()
+        ));
+
+        let (tci, _, _) = crossinterpolate2::<f64, _, fn(&[MultiIndex]) -> Vec<f64>>(
+            f,
+            None,
+            local_dims,
+            vec![vec![0; 5]],
+            options.clone(),
+        )
+        .unwrap();
+        tci.save(&path).unwrap();
+
+        let loaded = TensorCI2::<f64>::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.link_dims(), tci.link_dims());
+        assert_eq!(loaded.max_sample_value(), tci.max_sample_value());
+        let (tt, loaded_tt) = (tci.to_tensor_train().unwrap(), loaded.to_tensor_train().unwrap());
+        assert_eq!(loaded_tt.evaluate(&[1, 2, 3, 0, 1]).unwrap(), tt.evaluate(&[1, 2, 3, 0, 1]).unwrap());
+
+        // Continue sweeping from the loaded state
+        let options = TCI2Options {
+            max_iter: 20,
+            ..options
+        };
+        let (resumed, _, errors) =
+            crossinterpolate2_from::<f64, _, fn(&[MultiIndex]) -> Vec<f64>>(loaded, f, None, options)
+                .unwrap();
+        assert!(*errors.last().unwrap() < 1e-10);
+        let tt = resumed.to_tensor_train().unwrap();
+        for idx in [vec![0; 5], vec![3, 1, 2, 0, 3], vec![3; 5]] {
+            assert!((tt.evaluate(&idx).unwrap() - f(&idx)).abs() < 1e-8);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tensorci2_resume_matches_uninterrupted_run() {
+        // The rank limit keeps the error above the tolerance, so every iteration runs
+        let f = |idx: &MultiIndex| {
+            let x: usize = idx.iter().enumerate().map(|(p, &i)| (p % 3 + 1) * i * i).sum();
+            1.0 / (1.0 + x as f64).sqrt()
+        };
+        let local_dims = vec![4; 8];
+        let options = TCI2Options {
+            tolerance: 1e-14,
+            max_iter: 5,
+            max_bond_dim: 3,
+            ..TCI2Options::default()
+        };
+
+        let (full, full_ranks, full_errors) =
+            crossinterpolate2::<f64, _, fn(&[MultiIndex]) -> Vec<f64>>(
+                f,
+                None,
+                local_dims.clone(),
+                vec![vec![0; 8]],
+                options.clone(),
+            )
+            .unwrap();
+
+        // Stop after an odd number of iterations, so the resumed run must start backward
+        let path = std::env::temp_dir().join(format!(
+            "tensor4all_tensorci2_resume_{}.bin",
+            std::process::id()
+        ));
+        let (first, _, _) = crossinterpolate2::<f64, _, fn(&[MultiIndex]) -> Vec<f64>>(
+            f,
+            None,
+            local_dims,
+            vec![vec![0; 8]],
+            TCI2Options {
+                max_iter: 3,
+                ..options.clone()
+            },
+        )
+        .unwrap();
+        first.save(&path).unwrap();
+        let loaded = TensorCI2::<f64>::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.error_history(), first.error_history());
+
+        let (resumed, ranks, errors) =
+            crossinterpolate2_from::<f64, _, fn(&[MultiIndex]) -> Vec<f64>>(
+                loaded,
+                f,
+                None,
+                TCI2Options {
+                    max_iter: full_errors.len().saturating_sub(3),
+                    ..options
+                },
+            )
+            .unwrap();
+
+        assert_eq!(ranks, full_ranks);
+        assert_eq!(errors, full_errors);
+        assert_eq!(resumed.link_dims(), full.link_dims());
+        let (tt, full_tt) = (resumed.to_tensor_train().unwrap(), full.to_tensor_train().unwrap());
+        for idx in [vec![0; 8], vec![3, 1, 2, 0, 3, 1, 0, 2], vec![3; 8]] {
+            assert_eq!(tt.evaluate(&idx).unwrap(), full_tt.evaluate(&idx).unwrap());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tensorci2_load_rejects_other_checkpoints() {
+        let path = std::env::temp_dir().join(format!(
+            "tensor4all_tensorci2_wrong_kind_{}.bin",
+            std::process::id()
+        ));
+        let f = |idx: &MultiIndex| (idx[0] + idx[1] + 1) as f64;
+        let (tci1, _, _) =
+            crate::crossinterpolate1(f, vec![3, 3], vec![1, 1], crate::TCI1Options::default())
+                .unwrap();
+        tci1.save(&path).unwrap();
+
+        assert!(TensorCI2::<f64>::load(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_crossinterpolate2_rank2_function() {
         // f(i, j) = i + j
@@ -572,4 +1012,97 @@ mod tests {
         let final_error = errors.last().copied().unwrap_or(f64::INFINITY);
         assert!(final_error < 0.1, "Expected small error, got {}", final_error);
     }
+
+    #[test]
+    fn test_crossinterpolate2_global_pivots_find_localized_feature() {
+        // Smooth background plus a sharp bump far from the initial pivot
+        let f = |idx: &MultiIndex| {
+            let dist2: usize = idx.iter().map(|&i| (i as isize - 3).pow(2) as usize).sum();
+            1.0 + 10.0 * (-2.0 * dist2 as f64).exp()
+        };
+        let local_dims = vec![4; 5];
+        let options = TCI2Options {
+            tolerance: 1e-10,
+            max_nglobal_pivot: 5,
+            nsearch: 100,
+            ..TCI2Options::default()
+        };
+
+        let (tci, _ranks, _errors) = crossinterpolate2::<f64, _, fn(&[MultiIndex]) -> Vec<f64>>(
+            f,
+            None,
+            local_dims.clone(),
+            vec![vec![0; 5]],
+            options,
+        )
+        .unwrap();
+
+        let tt = tci.to_tensor_train().unwrap();
+        let peak = vec![3; 5];
+        assert!((tt.evaluate(&peak).unwrap() - f(&peak)).abs() < 1e-6);
+        assert!((tt.evaluate(&[0, 1, 2, 3, 0]).unwrap() - f(&vec![0, 1, 2, 3, 0])).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_crossinterpolate2_global_search_uses_batch_function() {
+        let g = |idx: &MultiIndex| {
+            let dist2: usize = idx.iter().map(|&i| (i as isize - 3).pow(2) as usize).sum();
+            1.0 + 10.0 * (-2.0 * dist2 as f64).exp()
+        };
+        let f = |_: &MultiIndex| -> f64 { panic!("point function must not be called") };
+        let batched_f = |indices: &[MultiIndex]| indices.iter().map(g).collect::<Vec<f64>>();
+        let options = TCI2Options {
+            tolerance: 1e-10,
+            max_nglobal_pivot: 5,
+            nsearch: 20,
+            ..TCI2Options::default()
+        };
+
+        let (tci, _ranks, _errors) =
+            crossinterpolate2(f, Some(batched_f), vec![4; 5], vec![vec![0; 5]], options).unwrap();
+
+        let tt = tci.to_tensor_train().unwrap();
+        let peak = vec![3; 5];
+        assert!((tt.evaluate(&peak).unwrap() - g(&peak)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_crossinterpolate2_custom_global_pivot_finder() {
+        struct FixedPivots(Vec<MultiIndex>);
+
+        impl GlobalPivotFinder<f64> for FixedPivots {
+            fn find_global_pivots(
+                &mut self,
+                _input: &GlobalPivotSearchInput<f64>,
+                _f: &dyn Fn(&[MultiIndex]) -> Vec<f64>,
+                _abs_tol: f64,
+            ) -> Result<Vec<MultiIndex>> {
+                Ok(std::mem::take(&mut self.0))
+            }
+        }
+
+        let f = |idx: &MultiIndex| if idx == &vec![2, 2, 2] { 5.0 } else { 1.0 };
+        let mut finder = FixedPivots(vec![vec![2, 2, 2]]);
+        let (tci, _ranks, _errors) = crossinterpolate2_with_global_pivot_finder::<
+            f64,
+            _,
+            fn(&[MultiIndex]) -> Vec<f64>,
+            _,
+        >(
+            f,
+            None,
+            vec![3, 3, 3],
+            vec![vec![0, 0, 0]],
+            TCI2Options {
+                max_nglobal_pivot: 5,
+                ..TCI2Options::default()
+            },
+            &mut finder,
+        )
+        .unwrap();
+
+        let tt = tci.to_tensor_train().unwrap();
+        assert!((tt.evaluate(&[2, 2, 2]).unwrap() - 5.0).abs() < 1e-8);
+        assert!((tt.evaluate(&[1, 0, 2]).unwrap() - 1.0).abs() < 1e-8);
+    }
 }
@@ -0,0 +1,431 @@
+//! Cached evaluation of tensor trains
+//!
+//! `TTCache` memoizes partial contractions of a tensor train over index
+//! prefixes (from the left) and suffixes (from the right), so that evaluating
+//! many multi-indices sharing prefixes or suffixes avoids recomputing the
+//! whole chain. Reference: `T4ATensorTrain.jl/src/cache.jl`.
+
+use crate::error::{Result, TensorTrainError};
+use crate::traits::{AbstractTensorTrain, TTScalar};
+use crate::types::{LocalIndex, MultiIndex, Tensor3};
+use std::collections::HashMap;
+
+/// Tensor train with memoized left and right partial contractions
+///
+/// By default the caches grow without bound. Use [`TTCache::with_max_size`]
+/// to bound the number of cached vectors on each side when evaluating very
+/// many points.
+#[derive(Debug, Clone)]
+pub struct TTCache<T: TTScalar> {
+    tensors: Vec<Tensor3<T>>,
+    /// cache_left[k]: prefix of length k -> vector over the right bond of site k - 1
+    cache_left: Vec<HashMap<MultiIndex, Vec<T>>>,
+    /// cache_right[k]: suffix starting at site k -> vector over the left bond of site k
+    cache_right: Vec<HashMap<MultiIndex, Vec<T>>>,
+    /// Maximum number of cached vectors per side (unbounded if `None`)
+    max_size: Option<usize>,
+}
+
+impl<T: TTScalar> TTCache<T> {
+    /// Create a cache for a tensor train
+    pub fn new<TT: AbstractTensorTrain<T>>(tt: &TT) -> Self {
+        let tensors = tt.site_tensors().to_vec();
+        let n = tensors.len();
+        Self {
+            tensors,
+            cache_left: (0..=n).map(|_| HashMap::new()).collect(),
+            cache_right: (0..=n).map(|_| HashMap::new()).collect(),
+            max_size: None,
+        }
+    }
+
+    /// Bound the number of cached partial contractions on each side
+    ///
+    /// When a side holds `max_size` vectors and a new one is computed, all
+    /// cached vectors of that side are dropped first. Evaluating points in
+    /// lexicographic order keeps the hit rate high under this policy.
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        if self.cache_size().0 > max_size {
+            self.cache_left.iter_mut().for_each(|c| c.clear());
+        }
+        if self.cache_size().1 > max_size {
+            self.cache_right.iter_mut().for_each(|c| c.clear());
+        }
+        self
+    }
+
+    /// Number of sites
+    pub fn len(&self) -> usize {
+        self.tensors.len()
+    }
+
+    /// Check if the tensor train is empty
+    pub fn is_empty(&self) -> bool {
+        self.tensors.is_empty()
+    }
+
+    /// Site dimensions
+    pub fn site_dims(&self) -> Vec<usize> {
+        self.tensors.iter().map(|t| t.site_dim()).collect()
+    }
+
+    /// Site tensors
+    pub fn site_tensors(&self) -> &[Tensor3<T>] {
+        &self.tensors
+    }
+
+    /// Number of cached (left, right) partial contractions
+    pub fn cache_size(&self) -> (usize, usize) {
+        (
+            self.cache_left.iter().map(|c| c.len()).sum(),
+            self.cache_right.iter().map(|c| c.len()).sum(),
+        )
+    }
+
+    /// Drop all cached partial contractions
+    pub fn clear_cache(&mut self) {
+        self.cache_left.iter_mut().for_each(|c| c.clear());
+        self.cache_right.iter_mut().for_each(|c| c.clear());
+    }
+
+    /// Insert into one side of the cache, clearing that side if it is full
+    fn insert_bounded(
+        cache: &mut [HashMap<MultiIndex, Vec<T>>],
+        max_size: Option<usize>,
+        k: usize,
+        key: MultiIndex,
+        value: Vec<T>,
+    ) {
+        if let Some(max_size) = max_size {
+            if max_size == 0 {
+                return;
+            }
+            if cache.iter().map(|c| c.len()).sum::<usize>() >= max_size {
+                cache.iter_mut().for_each(|c| c.clear());
+            }
+        }
+        cache[k].insert(key, value);
+    }
+
+    fn check_index(&self, site: usize, index: LocalIndex) -> Result<()> {
+        let max = self.tensors[site].site_dim();
+        if index >= max {
+            return Err(TensorTrainError::IndexOutOfBounds { site, index, max });
+        }
+        Ok(())
+    }
+
+    /// Contract the first `prefix.len()` sites at the given indices
+    ///
+    /// Returns a vector over the right bond of site `prefix.len() - 1`
+    /// (`[1]` for an empty prefix).
+    pub fn evaluate_left(&mut self, prefix: &[LocalIndex]) -> Result<Vec<T>> {
+        let k = prefix.len();
+        if k > self.len() {
+            return Err(TensorTrainError::IndexLengthMismatch {
+                expected: self.len(),
+                got: k,
+            });
+        }
+        if k == 0 {
+            return Ok(vec![T::one()]);
+        }
+        if let Some(v) = self.cache_left[k].get(prefix) {
+            return Ok(v.clone());
+        }
+        for (site, &index) in prefix.iter().enumerate() {
+            self.check_index(site, index)?;
+        }
+
+        // Longest cached prefix
+        let mut start = k - 1;
+        while start > 0 && !self.cache_left[start].contains_key(&prefix[..start]) {
+            start -= 1;
+        }
+        let mut vec = if start == 0 {
+            vec![T::one()]
+        } else {
+            self.cache_left[start][&prefix[..start]].clone()
+        };
+
+        for site in start..k {
+            let tensor = &self.tensors[site];
+            let s = prefix[site];
+            let mut next = vec![T::zero(); tensor.right_dim()];
+            for (l, &v) in vec.iter().enumerate() {
+                for (r, n) in next.iter_mut().enumerate() {
+                    *n = *n + v * *tensor.get(l, s, r);
+                }
+            }
+            vec = next;
+            Self::insert_bounded(
+                &mut self.cache_left,
+                self.max_size,
+                site + 1,
+                prefix[..=site].to_vec(),
+                vec.clone(),
+            );
+        }
+        Ok(vec)
+    }
+
+    /// Contract the last `suffix.len()` sites at the given indices
+    ///
+    /// Returns a vector over the left bond of site `len() - suffix.len()`
+    /// (`[1]` for an empty suffix).
+    pub fn evaluate_right(&mut self, suffix: &[LocalIndex]) -> Result<Vec<T>> {
+        let n = self.len();
+        let k = suffix.len();
+        if k > n {
+            return Err(TensorTrainError::IndexLengthMismatch {
+                expected: n,
+                got: k,
+            });
+        }
+        if k == 0 {
+            return Ok(vec![T::one()]);
+        }
+        let first = n - k;
+        if let Some(v) = self.cache_right[first].get(suffix) {
+            return Ok(v.clone());
+        }
+        for (offset, &index) in suffix.iter().enumerate() {
+            self.check_index(first + offset, index)?;
+        }
+
+        // Longest cached suffix
+        let mut end = 1;
+        while end < k && !self.cache_right[first + end].contains_key(&suffix[end..]) {
+            end += 1;
+        }
+        let mut vec = if end == k {
+            vec![T::one()]
+        } else {
+            self.cache_right[first + end][&suffix[end..]].clone()
+        };
+
+        for offset in (0..end).rev() {
+            let site = first + offset;
+            let tensor = &self.tensors[site];
+            let s = suffix[offset];
+            let mut next = vec![T::zero(); tensor.left_dim()];
+            for (l, n) in next.iter_mut().enumerate() {
+                for (r, &v) in vec.iter().enumerate() {
+                    *n = *n + *tensor.get(l, s, r) * v;
+                }
+            }
+            vec = next;
+            Self::insert_bounded(
+                &mut self.cache_right,
+                self.max_size,
+                site,
+                suffix[offset..].to_vec(),
+                vec.clone(),
+            );
+        }
+        Ok(vec)
+    }
+
+    /// Evaluate the tensor train at a multi-index using cached partial contractions
+    ///
+    /// The chain is split at the middle; both halves are memoized.
+    pub fn evaluate(&mut self, indices: &[LocalIndex]) -> Result<T> {
+        if indices.len() != self.len() {
+            return Err(TensorTrainError::IndexLengthMismatch {
+                expected: self.len(),
+                got: indices.len(),
+            });
+        }
+        if self.is_empty() {
+            return Err(TensorTrainError::Empty);
+        }
+        let mid = self.len() / 2;
+        let left = self.evaluate_left(&indices[..mid])?;
+        let right = self.evaluate_right(&indices[mid..])?;
+        Ok(left
+            .iter()
+            .zip(&right)
+            .fold(T::zero(), |acc, (&l, &r)| acc + l * r))
+    }
+
+    /// Evaluate the tensor train at many multi-indices
+    ///
+    /// The points are evaluated one after another on the calling thread;
+    /// prefixes and suffixes shared between points are reused through the
+    /// cache. For parallel evaluation, use one `TTCache` per thread.
+    pub fn evaluate_many(&mut self, indices: &[MultiIndex]) -> Result<Vec<T>> {
+        indices.iter().map(|idx| self.evaluate(idx)).collect()
+    }
+
+    /// Evaluate all combinations of left prefixes, free center sites, and right suffixes
+    ///
+    /// All prefixes must have the same length `nl` and all suffixes the same
+    /// length `nr`; the `len() - nl - nr` sites in between are enumerated in
+    /// full. The result is flattened row-major with shape
+    /// `(left.len(), d_nl, ..., d_{len-nr-1}, right.len())`.
+    pub fn evaluate_batch(&mut self, left: &[MultiIndex], right: &[MultiIndex]) -> Result<Vec<T>> {
+        let n = self.len();
+        let nl = left.first().map_or(0, |idx| idx.len());
+        let nr = right.first().map_or(0, |idx| idx.len());
+        if left.iter().any(|idx| idx.len() != nl) {
+            return Err(TensorTrainError::InvalidOperation {
+                message: "All left index sets must have the same length".to_string(),
+            });
+        }
+        if right.iter().any(|idx| idx.len() != nr) {
+            return Err(TensorTrainError::InvalidOperation {
+                message: "All right index sets must have the same length".to_string(),
+            });
+        }
+        if nl + nr > n {
+            return Err(TensorTrainError::IndexLengthMismatch {
+                expected: n,
+                got: nl + nr,
+            });
+        }
+
+        let right_vecs = right
+            .iter()
+            .map(|idx| self.evaluate_right(idx))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut result = Vec::new();
+        for prefix in left {
+            // Row vectors for every combination of center indices, in row-major order
+            let mut rows = vec![self.evaluate_left(prefix)?];
+            for site in nl..n - nr {
+                let tensor = &self.tensors[site];
+                let mut next_rows = Vec::with_capacity(rows.len() * tensor.site_dim());
+                for row in &rows {
+                    for s in 0..tensor.site_dim() {
+                        let mut next = vec![T::zero(); tensor.right_dim()];
+                        for (l, &v) in row.iter().enumerate() {
+                            for (r, x) in next.iter_mut().enumerate() {
+                                *x = *x + v * *tensor.get(l, s, r);
+                            }
+                        }
+                        next_rows.push(next);
+                    }
+                }
+                rows = next_rows;
+            }
+
+            for row in &rows {
+                for rv in &right_vecs {
+                    result.push(
+                        row.iter()
+                            .zip(rv)
+                            .fold(T::zero(), |acc, (&a, &b)| acc + a * b),
+                    );
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{all_indices, random_tt};
+
+    #[test]
+    fn test_evaluate_matches_tensor_train() {
+        let site_dims = [2, 3, 2, 2, 3];
+        let tt = random_tt(&site_dims, 3, 1);
+        let mut cache = TTCache::new(&tt);
+
+        let indices = all_indices(&site_dims);
+        let values = cache.evaluate_many(&indices).unwrap();
+        for (idx, value) in indices.iter().zip(values) {
+            assert!((value - tt.evaluate(idx).unwrap()).abs() < 1e-12);
+        }
+
+        // Cached partial contractions are reused on the second pass
+        let size = cache.cache_size();
+        cache.evaluate_many(&indices).unwrap();
+        assert_eq!(cache.cache_size(), size);
+
+        cache.clear_cache();
+        assert_eq!(cache.cache_size(), (0, 0));
+    }
+
+    #[test]
+    fn test_max_size_bounds_cache() {
+        let site_dims = [2, 3, 2, 2, 3];
+        let tt = random_tt(&site_dims, 3, 5);
+        let mut cache = TTCache::new(&tt).with_max_size(10);
+
+        for idx in all_indices(&site_dims) {
+            let value = cache.evaluate(&idx).unwrap();
+            assert!((value - tt.evaluate(&idx).unwrap()).abs() < 1e-12);
+            let (left, right) = cache.cache_size();
+            assert!(left <= 10 && right <= 10);
+        }
+
+        let mut empty = TTCache::new(&tt).with_max_size(0);
+        let idx = vec![1, 2, 0, 1, 2];
+        assert!((empty.evaluate(&idx).unwrap() - tt.evaluate(&idx).unwrap()).abs() < 1e-12);
+        assert_eq!(empty.cache_size(), (0, 0));
+    }
+
+    #[test]
+    fn test_evaluate_left_right() {
+        let site_dims = [2, 2, 2, 2];
+        let tt = random_tt(&site_dims, 2, 2);
+        let mut cache = TTCache::new(&tt);
+
+        for idx in all_indices(&site_dims) {
+            for split in 0..=site_dims.len() {
+                let left = cache.evaluate_left(&idx[..split]).unwrap();
+                let right = cache.evaluate_right(&idx[split..]).unwrap();
+                let value: f64 = left.iter().zip(&right).map(|(a, b)| a * b).sum();
+                assert!((value - tt.evaluate(&idx).unwrap()).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_errors() {
+        let tt = random_tt(&[2, 2, 2], 2, 3);
+        let mut cache = TTCache::new(&tt);
+        assert!(cache.evaluate(&[0, 0]).is_err());
+        assert!(cache.evaluate(&[0, 2, 0]).is_err());
+        assert!(cache.evaluate_left(&[0, 0, 0, 0]).is_err());
+        assert!(cache.evaluate_right(&[5]).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_batch() {
+        let site_dims = [2, 3, 2, 2, 2];
+        let tt = random_tt(&site_dims, 3, 4);
+        let mut cache = TTCache::new(&tt);
+
+        let left = vec![vec![0, 1], vec![1, 2]];
+        let right = vec![vec![0, 1], vec![1, 1], vec![0, 0]];
+        let values = cache.evaluate_batch(&left, &right).unwrap();
+        assert_eq!(values.len(), 2 * 2 * 3);
+
+        let mut pos = 0;
+        for l in &left {
+            for c in 0..2 {
+                for r in &right {
+                    let idx = vec![l[0], l[1], c, r[0], r[1]];
+                    assert!((values[pos] - tt.evaluate(&idx).unwrap()).abs() < 1e-12);
+                    pos += 1;
+                }
+            }
+        }
+
+        // No free sites, and empty prefixes
+        let values = cache
+            .evaluate_batch(&[vec![]], &[vec![0, 1, 0, 1, 1]])
+            .unwrap();
+        assert!((values[0] - tt.evaluate(&[0, 1, 0, 1, 1]).unwrap()).abs() < 1e-12);
+
+        assert!(cache
+            .evaluate_batch(&[vec![0], vec![0, 1]], &right)
+            .is_err());
+    }
+}
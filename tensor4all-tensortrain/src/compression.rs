@@ -1,9 +1,10 @@
 //! Compression algorithms for tensor trains
 
-use crate::error::Result;
+use crate::error::{Result, TensorTrainError};
 use crate::tensortrain::TensorTrain;
 use crate::traits::{AbstractTensorTrain, TTScalar};
 use crate::types::Tensor3;
+use faer::traits::ComplexField;
 use tensor4all_matrixci::util::{mat_mul, nrows, ncols, zeros, Matrix, Scalar};
 use tensor4all_matrixci::{rrlu, AbstractMatrixCI, MatrixLUCI, RrLUOptions};
 
@@ -15,7 +16,7 @@ pub enum CompressionMethod {
     LU,
     /// Cross interpolation based
     CI,
-    /// SVD decomposition (requires lapack feature)
+    /// Truncated SVD (optimal truncation in Frobenius norm)
     SVD,
 }
 
@@ -43,8 +44,30 @@ impl Default for CompressionOptions {
     }
 }
 
+/// Build a row-major matrix from flat data
+pub(crate) fn matrix_from_data<T: Scalar>(data: &[T], rows: usize, cols: usize) -> Matrix<T> {
+    let mut mat = zeros(rows, cols);
+    for i in 0..rows {
+        for j in 0..cols {
+            mat[[i, j]] = data[i * cols + j];
+        }
+    }
+    mat
+}
+
+/// Flatten a matrix into row-major data
+pub(crate) fn matrix_data<T: Scalar>(mat: &Matrix<T>) -> Vec<T> {
+    let mut data = Vec::with_capacity(nrows(mat) * ncols(mat));
+    for i in 0..nrows(mat) {
+        for j in 0..ncols(mat) {
+            data.push(mat[[i, j]]);
+        }
+    }
+    data
+}
+
 /// Convert Tensor3 to Matrix for factorization (left matrix view)
-fn tensor3_to_left_matrix<T: Scalar + Default>(tensor: &Tensor3<T>) -> Matrix<T> {
+pub(crate) fn tensor3_to_left_matrix<T: Scalar + Default>(tensor: &Tensor3<T>) -> Matrix<T> {
     let left_dim = tensor.left_dim();
     let site_dim = tensor.site_dim();
     let right_dim = tensor.right_dim();
@@ -63,7 +86,7 @@ fn tensor3_to_left_matrix<T: Scalar + Default>(tensor: &Tensor3<T>) -> Matrix<T>
 }
 
 /// Convert Tensor3 to Matrix for factorization (right matrix view)
-fn tensor3_to_right_matrix<T: Scalar + Default>(tensor: &Tensor3<T>) -> Matrix<T> {
+pub(crate) fn tensor3_to_right_matrix<T: Scalar + Default>(tensor: &Tensor3<T>) -> Matrix<T> {
     let left_dim = tensor.left_dim();
     let site_dim = tensor.site_dim();
     let right_dim = tensor.right_dim();
@@ -81,14 +104,105 @@ fn tensor3_to_right_matrix<T: Scalar + Default>(tensor: &Tensor3<T>) -> Matrix<T
     mat
 }
 
+/// Thin SVD of a matrix via faer: returns (U, singular values, V^H)
+pub(crate) fn svd<T: TTScalar + Scalar + ComplexField>(matrix: &Matrix<T>) -> Result<(Matrix<T>, Vec<f64>, Matrix<T>)> {
+    let m = nrows(matrix);
+    let n = ncols(matrix);
+    let a = faer::Mat::<T>::from_fn(m, n, |i, j| matrix[[i, j]]);
+    let decomp = a.thin_svd().map_err(|e| TensorTrainError::InvalidOperation {
+        message: format!("SVD did not converge: {:?}", e),
+    })?;
+
+    let k = m.min(n);
+    let u_ref = decomp.U();
+    let v_ref = decomp.V();
+    let s_ref = decomp.S().column_vector();
+
+    let mut u = zeros(m, k);
+    for i in 0..m {
+        for j in 0..k {
+            u[[i, j]] = u_ref[(i, j)];
+        }
+    }
+    let mut vt = zeros(k, n);
+    for i in 0..k {
+        for j in 0..n {
+            vt[[i, j]] = TTScalar::conj(v_ref[(j, i)]);
+        }
+    }
+    let singular_values = (0..k).map(|i| TTScalar::abs_sq(s_ref[i]).sqrt()).collect();
+    Ok((u, singular_values, vt))
+}
+
+/// Number of singular values to keep so that the discarded weight
+/// `sum_{i >= r} s_i^2` stays below `tolerance^2` (relative to `sum_i s_i^2`
+/// if `normalize_error` is set)
+pub(crate) fn svd_truncation_rank(
+    singular_values: &[f64],
+    tolerance: f64,
+    max_bond_dim: usize,
+    normalize_error: bool,
+) -> usize {
+    let total: f64 = singular_values.iter().map(|s| s * s).sum();
+    let scale = if normalize_error && total > 0.0 { total } else { 1.0 };
+    let threshold = tolerance * tolerance;
+
+    let mut rank = singular_values.len();
+    let mut discarded = 0.0;
+    while rank > 0 {
+        let s = singular_values[rank - 1];
+        if (discarded + s * s) / scale >= threshold {
+            break;
+        }
+        discarded += s * s;
+        rank -= 1;
+    }
+    rank.max(1).min(max_bond_dim).min(singular_values.len())
+}
+
+/// Left factor, right factor, rank, and full singular value spectrum
+pub(crate) type SvdFactors<T> = (Matrix<T>, Matrix<T>, usize, Vec<f64>);
+
+/// Truncated SVD factorization
+///
+/// Returns (left, right, rank, singular values) where the singular values are
+/// the full (untruncated) spectrum. The singular values are absorbed into the
+/// right factor if `left_orthogonal`, otherwise into the left factor.
+pub(crate) fn factorize_svd<T: TTScalar + Scalar + ComplexField>(
+    matrix: &Matrix<T>,
+    tolerance: f64,
+    max_bond_dim: usize,
+    normalize_error: bool,
+    left_orthogonal: bool,
+) -> Result<SvdFactors<T>> {
+    let (u, singular_values, vt) = svd(matrix)?;
+    let rank = svd_truncation_rank(&singular_values, tolerance, max_bond_dim, normalize_error);
+
+    let m = nrows(&u);
+    let n = ncols(&vt);
+    let mut left = zeros(m, rank);
+    let mut right = zeros(rank, n);
+    for j in 0..rank {
+        let s = T::from_f64_impl(singular_values[j]);
+        for i in 0..m {
+            left[[i, j]] = if left_orthogonal { u[[i, j]] } else { u[[i, j]] * s };
+        }
+        for i in 0..n {
+            right[[j, i]] = if left_orthogonal { s * vt[[j, i]] } else { vt[[j, i]] };
+        }
+    }
+    Ok((left, right, rank, singular_values))
+}
+
 /// Factorize a matrix into left and right factors
-fn factorize<T: TTScalar + Scalar>(
+pub(crate) fn factorize<T: TTScalar + Scalar + ComplexField>(
     matrix: &Matrix<T>,
     method: CompressionMethod,
     tolerance: f64,
     max_bond_dim: usize,
+    normalize_error: bool,
     left_orthogonal: bool,
-) -> (Matrix<T>, Matrix<T>, usize) {
+) -> Result<(Matrix<T>, Matrix<T>, usize)> {
     let reltol = if tolerance > 0.0 { tolerance } else { 1e-14 };
     let abstol = 0.0;
 
@@ -105,38 +219,59 @@ fn factorize<T: TTScalar + Scalar>(
             let left = lu.left(true);  // permuted
             let right = lu.right(true);  // permuted
             let npivots = lu.npivots();
-            (left, right, npivots)
+            Ok((left, right, npivots))
         }
         CompressionMethod::CI => {
             let luci = MatrixLUCI::from_matrix(matrix, Some(options));
             let left = luci.left();
             let right = luci.right();
             let npivots = luci.rank();
-            (left, right, npivots)
+            Ok((left, right, npivots))
         }
         CompressionMethod::SVD => {
-            // For SVD, we'd need a linear algebra library
-            // For now, fall back to LU
-            let lu = rrlu(matrix, Some(options));
-            let left = lu.left(true);
-            let right = lu.right(true);
-            let npivots = lu.npivots();
-            (left, right, npivots)
+            let (left, right, rank, _) =
+                factorize_svd(matrix, tolerance, max_bond_dim, normalize_error, left_orthogonal)?;
+            Ok((left, right, rank))
         }
     }
 }
 
-impl<T: TTScalar + Scalar + Default> TensorTrain<T> {
+impl<T: TTScalar + Scalar + Default + ComplexField> TensorTrain<T> {
     /// Compress the tensor train in-place using the specified method
     ///
     /// This performs a two-sweep compression:
     /// 1. Left-to-right sweep with left-orthogonal factorization (no truncation)
     /// 2. Right-to-left sweep with truncation
     pub fn compress(&mut self, options: &CompressionOptions) -> Result<()> {
+        self.compress_impl(options).map(|_| ())
+    }
+
+    /// Compress the tensor train in-place with truncated SVD and return the
+    /// singular value spectrum of each bond
+    ///
+    /// The returned vector has one entry per bond (`len() - 1` entries); entry `i`
+    /// holds the full, untruncated singular values (in descending order) of the
+    /// bond between sites `i` and `i + 1`, computed in canonical form.
+    /// `options.method` must be `CompressionMethod::SVD`.
+    pub fn compress_with_spectra(&mut self, options: &CompressionOptions) -> Result<Vec<Vec<f64>>> {
+        if options.method != CompressionMethod::SVD {
+            return Err(TensorTrainError::InvalidOperation {
+                message: format!(
+                    "singular value spectra require CompressionMethod::SVD, got {:?}",
+                    options.method
+                ),
+            });
+        }
+        self.compress_impl(options)
+    }
+
+    /// Two-sweep compression; returns per-bond singular values for SVD
+    fn compress_impl(&mut self, options: &CompressionOptions) -> Result<Vec<Vec<f64>>> {
         let n = self.len();
         if n <= 1 {
-            return Ok(());
+            return Ok(Vec::new());
         }
+        let mut spectra = vec![Vec::new(); n - 1];
 
         let tensors = self.site_tensors_mut();
 
@@ -154,8 +289,9 @@ impl<T: TTScalar + Scalar + Default> TensorTrain<T> {
                 options.method,
                 0.0,         // No truncation in left sweep
                 usize::MAX,  // No max bond dim in left sweep
+                options.normalize_error,
                 true,        // left orthogonal
-            );
+            )?;
 
             // Update current tensor
             let mut new_tensor = Tensor3::zeros(left_dim, site_dim, new_bond_dim);
@@ -202,13 +338,26 @@ impl<T: TTScalar + Scalar + Default> TensorTrain<T> {
             let mat = tensor3_to_right_matrix(&tensors[ell]);
 
             // Factorize with truncation
-            let (left_factor, right_factor, new_bond_dim) = factorize(
-                &mat,
-                options.method,
-                options.tolerance,
-                options.max_bond_dim,
-                false,  // right orthogonal
-            );
+            let (left_factor, right_factor, new_bond_dim) = if options.method == CompressionMethod::SVD {
+                let (left, right, rank, singular_values) = factorize_svd(
+                    &mat,
+                    options.tolerance,
+                    options.max_bond_dim,
+                    options.normalize_error,
+                    false,  // right orthogonal
+                )?;
+                spectra[ell - 1] = singular_values;
+                (left, right, rank)
+            } else {
+                factorize(
+                    &mat,
+                    options.method,
+                    options.tolerance,
+                    options.max_bond_dim,
+                    options.normalize_error,
+                    false,  // right orthogonal
+                )?
+            };
 
             // Update current tensor from right_factor
             let mut new_tensor = Tensor3::zeros(new_bond_dim, site_dim, right_dim);
@@ -243,7 +392,7 @@ impl<T: TTScalar + Scalar + Default> TensorTrain<T> {
             tensors[ell - 1] = new_prev_tensor;
         }
 
-        Ok(())
+        Ok(spectra)
     }
 
     /// Create a compressed copy of the tensor train
@@ -257,6 +406,7 @@ impl<T: TTScalar + Scalar + Default> TensorTrain<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{all_indices, random_tt};
 
     #[test]
     fn test_compress_constant() {
@@ -337,4 +487,127 @@ mod tests {
         let compressed_sum = tt_compressed.sum();
         assert!((original_sum - compressed_sum).abs() < original_sum.abs() * 0.1);
     }
+
+    #[test]
+    fn test_compress_svd_recovers_exact_rank() {
+        // tt + tt has bond dimension 4 but rank 2
+        let tt = random_tt(&[2, 3, 2, 2], 2, 1);
+        let doubled = tt.add(&tt).unwrap();
+        assert_eq!(doubled.rank(), 4);
+
+        let options = CompressionOptions {
+            method: CompressionMethod::SVD,
+            ..Default::default()
+        };
+        let compressed = doubled.compressed(&options).unwrap();
+        assert!(compressed.rank() <= 2);
+
+        for idx in all_indices(&[2, 3, 2, 2]) {
+            let expected = 2.0 * tt.evaluate(&idx).unwrap();
+            let actual = compressed.evaluate(&idx).unwrap();
+            assert!((expected - actual).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_compress_svd_spectra() {
+        let site_dims = [2, 2, 2, 2, 2];
+        let tt = random_tt(&site_dims, 4, 2);
+        let options = CompressionOptions {
+            method: CompressionMethod::SVD,
+            ..Default::default()
+        };
+
+        let mut compressed = tt.clone();
+        let spectra = compressed.compress_with_spectra(&options).unwrap();
+        assert_eq!(spectra.len(), site_dims.len() - 1);
+        for sv in &spectra {
+            assert!(!sv.is_empty());
+            assert!(sv.windows(2).all(|w| w[0] >= w[1]));
+        }
+
+        // Squared norm equals sum of squared singular values at every bond
+        let norm2 = tt.dot(&tt).unwrap();
+        for sv in &spectra {
+            let total: f64 = sv.iter().map(|s| s * s).sum();
+            assert!((total - norm2).abs() < 1e-10 * norm2);
+        }
+    }
+
+    #[test]
+    fn test_compress_svd_optimal_two_sites() {
+        // For two sites, truncated SVD error is the discarded singular weight
+        let tt = random_tt(&[4, 4], 4, 3);
+        let options = CompressionOptions {
+            method: CompressionMethod::SVD,
+            max_bond_dim: 2,
+            tolerance: 0.0,
+            ..Default::default()
+        };
+
+        let mut compressed = tt.clone();
+        let spectra = compressed.compress_with_spectra(&options).unwrap();
+        assert_eq!(compressed.rank(), 2);
+
+        let mut err2 = 0.0;
+        for idx in all_indices(&[4, 4]) {
+            let diff = tt.evaluate(&idx).unwrap() - compressed.evaluate(&idx).unwrap();
+            err2 += diff * diff;
+        }
+        let discarded: f64 = spectra[0][2..].iter().map(|s| s * s).sum();
+        assert!((err2 - discarded).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_compress_svd_tolerance() {
+        let tt = random_tt(&[2, 2, 2, 2, 2, 2], 4, 4);
+        let norm2 = tt.dot(&tt).unwrap();
+        let options = CompressionOptions {
+            method: CompressionMethod::SVD,
+            tolerance: 1e-1,
+            ..Default::default()
+        };
+        let compressed = tt.compressed(&options).unwrap();
+        assert!(compressed.rank() <= tt.rank());
+
+        let diff = tt.sub(&compressed).unwrap();
+        let err2 = diff.dot(&diff).unwrap();
+        // Per-bond errors accumulate at most linearly in the number of bonds
+        assert!(err2 <= 5.0 * 1e-2 * norm2);
+    }
+
+    #[test]
+    fn test_compress_svd_complex() {
+        use num_complex::Complex64;
+
+        let mut t0 = Tensor3::<Complex64>::zeros(1, 2, 2);
+        let mut t1 = Tensor3::<Complex64>::zeros(2, 2, 1);
+        for s in 0..2 {
+            for b in 0..2 {
+                t0.set(0, s, b, Complex64::new(1.0 + s as f64, b as f64));
+                t1.set(b, s, 0, Complex64::new(0.5, (s + b) as f64));
+            }
+        }
+        let tt = TensorTrain::new(vec![t0, t1]).unwrap();
+        let doubled = tt.add(&tt).unwrap();
+
+        let options = CompressionOptions {
+            method: CompressionMethod::SVD,
+            ..Default::default()
+        };
+        let compressed = doubled.compressed(&options).unwrap();
+        assert!(compressed.rank() <= 2);
+        for idx in all_indices(&[2, 2]) {
+            let expected = tt.evaluate(&idx).unwrap() * 2.0;
+            let actual = compressed.evaluate(&idx).unwrap();
+            assert!((expected - actual).norm() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_compress_with_spectra_requires_svd() {
+        let mut tt = TensorTrain::<f64>::constant(&[2, 2], 1.0);
+        let result = tt.compress_with_spectra(&CompressionOptions::default());
+        assert!(result.is_err());
+    }
 }
@@ -10,6 +10,7 @@ use crate::error::{Result, TensorTrainError};
 use crate::tensortrain::TensorTrain;
 use crate::traits::{AbstractTensorTrain, TTScalar};
 use crate::types::Tensor3;
+use faer::traits::ComplexField;
 use tensor4all_matrixci::util::{nrows, ncols, zeros, Matrix, Scalar};
 use tensor4all_matrixci::{AbstractMatrixCI, MatrixLUCI, RrLUOptions};
 
@@ -22,6 +23,8 @@ pub struct ContractionOptions {
     pub max_bond_dim: usize,
     /// Compression method (LU or CI)
     pub method: CompressionMethod,
+    /// Whether to normalize the error
+    pub normalize_error: bool,
 }
 
 impl Default for ContractionOptions {
@@ -30,6 +33,7 @@ impl Default for ContractionOptions {
             tolerance: 1e-12,
             max_bond_dim: usize::MAX,
             method: CompressionMethod::LU,
+            normalize_error: true,
         }
     }
 }
@@ -346,7 +350,10 @@ impl<T: TTScalar + Scalar + Default> TensorTrain<T> {
         &self,
         other: &Self,
         options: &CompressionOptions,
-    ) -> Result<Self> {
+    ) -> Result<Self>
+    where
+        T: ComplexField,
+    {
         let mut result = self.hadamard(other)?;
         result.compress(options)?;
         Ok(result)
@@ -567,6 +574,7 @@ mod tests {
             tolerance: 1e-12,
             max_bond_dim: 5,
             method: CompressionMethod::LU,
+            normalize_error: true,
         };
         let zipup_result = tt_a.hadamard_zipup(&tt_b, &options).unwrap();
 
@@ -3,8 +3,13 @@
 //! This crate provides tensor train (also known as Matrix Product State) algorithms,
 //! including:
 //! - `TensorTrain`: The main tensor train structure
+//! - `TensorTrain4` / `MPO`: Tensor trains with 4-leg cores (operators)
+//! - `SiteTensorTrain`: Site-canonical form with a movable orthogonality center
+//! - `VidalTensorTrain`: Vidal (Γ-Λ) canonical form with bond entanglement spectra
+//! - `TTCache`: Memoized partial contractions for repeated evaluation
 //! - Compression algorithms (LU, CI, SVD)
 //! - Arithmetic operations (add, subtract, scale)
+//! - MPO-MPO and MPO-MPS contraction (naive, zip-up, variational fit)
 //!
 //! # Example
 //!
@@ -24,17 +29,30 @@
 //! ```
 
 pub mod arithmetic;
+pub mod cache;
 pub mod compression;
 pub mod contraction;
 pub mod error;
+pub mod mpo;
+pub mod mpo_contraction;
+pub mod site_tensor_train;
 pub mod tensortrain;
 pub mod traits;
 pub mod types;
+pub mod vidal;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
 
 // Re-export main types
+pub use cache::TTCache;
 pub use compression::{CompressionMethod, CompressionOptions};
 pub use contraction::{dot, hadamard, hadamard_zipup, ContractionOptions};
 pub use error::{Result, TensorTrainError};
+pub use mpo::{TensorTrain4, MPO};
+pub use mpo_contraction::{contract_fit, contract_naive, contract_zipup, FitOptions, MPOOperand};
+pub use site_tensor_train::SiteTensorTrain;
 pub use tensortrain::TensorTrain;
 pub use traits::{AbstractTensorTrain, TTScalar};
-pub use types::{LocalIndex, MultiIndex, Tensor3};
+pub use types::{LocalIndex, MultiIndex, Tensor3, Tensor4};
+pub use vidal::VidalTensorTrain;
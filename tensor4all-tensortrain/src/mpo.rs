@@ -0,0 +1,288 @@
+//! Matrix product operator (tensor train with 4-leg cores)
+
+use crate::error::{Result, TensorTrainError};
+use crate::tensortrain::TensorTrain;
+use crate::traits::{AbstractTensorTrain, TTScalar};
+use crate::types::{LocalIndex, Tensor4};
+
+/// Tensor train with 4-leg site tensors (Matrix Product Operator)
+///
+/// Each site tensor has shape (left_bond, site_dim1, site_dim2, right_bond),
+/// where `site_dim1` is the output (row) index and `site_dim2` the input
+/// (column) index of the operator:
+///
+/// O[(i1, j1), ..., (iL, jL)] = W1[i1, j1] * W2[i2, j2] * ... * WL[iL, jL]
+#[derive(Debug, Clone)]
+pub struct TensorTrain4<T: TTScalar> {
+    /// Each tensor has shape (left_bond, site_dim1, site_dim2, right_bond)
+    tensors: Vec<Tensor4<T>>,
+}
+
+/// Matrix Product Operator
+pub type MPO<T> = TensorTrain4<T>;
+
+impl<T: TTScalar> TensorTrain4<T> {
+    /// Create a new operator from a list of 4D tensors
+    ///
+    /// The right_bond of tensor i must equal the left_bond of tensor i+1,
+    /// and the outer bonds must have dimension 1.
+    pub fn new(tensors: Vec<Tensor4<T>>) -> Result<Self> {
+        for i in 0..tensors.len().saturating_sub(1) {
+            if tensors[i].right_dim() != tensors[i + 1].left_dim() {
+                return Err(TensorTrainError::DimensionMismatch { site: i });
+            }
+        }
+
+        if !tensors.is_empty() && tensors[0].left_dim() != 1 {
+            return Err(TensorTrainError::InvalidOperation {
+                message: "First tensor must have left dimension 1".to_string(),
+            });
+        }
+
+        if !tensors.is_empty() && tensors.last().unwrap().right_dim() != 1 {
+            return Err(TensorTrainError::InvalidOperation {
+                message: "Last tensor must have right dimension 1".to_string(),
+            });
+        }
+
+        Ok(Self { tensors })
+    }
+
+    /// Create an operator from tensors without dimension validation
+    pub(crate) fn from_tensors_unchecked(tensors: Vec<Tensor4<T>>) -> Self {
+        Self { tensors }
+    }
+
+    /// Create the identity operator on the given site dimensions
+    pub fn identity(site_dims: &[usize]) -> Self {
+        let tensors = site_dims
+            .iter()
+            .map(|&d| {
+                let mut t = Tensor4::zeros(1, d, d, 1);
+                for s in 0..d {
+                    t.set(0, s, s, 0, T::one());
+                }
+                t
+            })
+            .collect();
+        Self { tensors }
+    }
+
+    /// Number of sites
+    pub fn len(&self) -> usize {
+        self.tensors.len()
+    }
+
+    /// Check if the operator has no sites
+    pub fn is_empty(&self) -> bool {
+        self.tensors.is_empty()
+    }
+
+    /// Get the site tensor at position i
+    pub fn site_tensor(&self, i: usize) -> &Tensor4<T> {
+        &self.tensors[i]
+    }
+
+    /// Get all site tensors
+    pub fn site_tensors(&self) -> &[Tensor4<T>] {
+        &self.tensors
+    }
+
+    /// Get mutable access to the site tensors
+    pub fn site_tensors_mut(&mut self) -> &mut [Tensor4<T>] {
+        &mut self.tensors
+    }
+
+    /// Site dimensions (site_dim1, site_dim2) for each site
+    pub fn site_dims(&self) -> Vec<(usize, usize)> {
+        self.tensors
+            .iter()
+            .map(|t| (t.site_dim1(), t.site_dim2()))
+            .collect()
+    }
+
+    /// Bond dimensions along the links between tensors
+    pub fn link_dims(&self) -> Vec<usize> {
+        if self.len() <= 1 {
+            return Vec::new();
+        }
+        self.tensors[1..].iter().map(|t| t.left_dim()).collect()
+    }
+
+    /// Maximum bond dimension
+    pub fn rank(&self) -> usize {
+        self.link_dims().into_iter().max().unwrap_or(1)
+    }
+
+    /// Evaluate the operator element at the given row and column indices
+    pub fn evaluate(&self, row: &[LocalIndex], col: &[LocalIndex]) -> Result<T> {
+        if row.len() != self.len() {
+            return Err(TensorTrainError::IndexLengthMismatch {
+                expected: self.len(),
+                got: row.len(),
+            });
+        }
+        if col.len() != self.len() {
+            return Err(TensorTrainError::IndexLengthMismatch {
+                expected: self.len(),
+                got: col.len(),
+            });
+        }
+        if self.is_empty() {
+            return Err(TensorTrainError::Empty);
+        }
+
+        let mut vec = vec![T::one()];
+        for (site, tensor) in self.tensors.iter().enumerate() {
+            let (i, j) = (row[site], col[site]);
+            if i >= tensor.site_dim1() {
+                return Err(TensorTrainError::IndexOutOfBounds {
+                    site,
+                    index: i,
+                    max: tensor.site_dim1(),
+                });
+            }
+            if j >= tensor.site_dim2() {
+                return Err(TensorTrainError::IndexOutOfBounds {
+                    site,
+                    index: j,
+                    max: tensor.site_dim2(),
+                });
+            }
+            let mut next = vec![T::zero(); tensor.right_dim()];
+            for (l, &v) in vec.iter().enumerate() {
+                for (r, n) in next.iter_mut().enumerate() {
+                    *n = *n + v * *tensor.get(l, i, j, r);
+                }
+            }
+            vec = next;
+        }
+        Ok(vec[0])
+    }
+
+    /// Multiply the operator by a scalar
+    pub fn scale(&mut self, factor: T) {
+        if let Some(tensor) = self.tensors.last_mut() {
+            for l in 0..tensor.left_dim() {
+                for s1 in 0..tensor.site_dim1() {
+                    for s2 in 0..tensor.site_dim2() {
+                        for r in 0..tensor.right_dim() {
+                            let val = *tensor.get(l, s1, s2, r);
+                            tensor.set(l, s1, s2, r, val * factor);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Create a scaled copy of the operator
+    pub fn scaled(&self, factor: T) -> Self {
+        let mut result = self.clone();
+        result.scale(factor);
+        result
+    }
+
+    /// Fuse the two site indices of each tensor into a tensor train
+    ///
+    /// The fused site index is `s1 * site_dim2 + s2`.
+    pub fn to_tensor_train(&self) -> TensorTrain<T> {
+        TensorTrain::from_tensors_unchecked(
+            self.tensors.iter().map(|t| t.fuse_site_indices()).collect(),
+        )
+    }
+
+    /// Build an operator from a tensor train by splitting each site index
+    ///
+    /// Inverse of [`TensorTrain4::to_tensor_train`].
+    pub fn from_tensor_train(tt: &TensorTrain<T>, site_dims: &[(usize, usize)]) -> Result<Self> {
+        if site_dims.len() != tt.len() {
+            return Err(TensorTrainError::IndexLengthMismatch {
+                expected: tt.len(),
+                got: site_dims.len(),
+            });
+        }
+        let mut tensors = Vec::with_capacity(tt.len());
+        for (site, (tensor, &(d1, d2))) in tt.site_tensors().iter().zip(site_dims).enumerate() {
+            if tensor.site_dim() != d1 * d2 {
+                return Err(TensorTrainError::DimensionMismatch { site });
+            }
+            tensors.push(Tensor4::from_fused(tensor, d1, d2));
+        }
+        Ok(Self { tensors })
+    }
+
+    /// View a tensor train as an operator with a trivial input index
+    ///
+    /// Each site tensor (l, s, r) becomes (l, s, 1, r).
+    pub fn from_mps(tt: &TensorTrain<T>) -> Self {
+        Self {
+            tensors: tt
+                .site_tensors()
+                .iter()
+                .map(|t| Tensor4::from_fused(t, t.site_dim(), 1))
+                .collect(),
+        }
+    }
+
+    /// Drop the trivial input index of an operator built by [`TensorTrain4::from_mps`]
+    pub fn to_mps(&self) -> Result<TensorTrain<T>> {
+        for (site, t) in self.tensors.iter().enumerate() {
+            if t.site_dim2() != 1 {
+                return Err(TensorTrainError::DimensionMismatch { site });
+            }
+        }
+        Ok(self.to_tensor_train())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_evaluate() {
+        let id = TensorTrain4::<f64>::identity(&[2, 3]);
+        assert_eq!(id.len(), 2);
+        assert_eq!(id.site_dims(), vec![(2, 2), (3, 3)]);
+        assert_eq!(id.evaluate(&[1, 2], &[1, 2]).unwrap(), 1.0);
+        assert_eq!(id.evaluate(&[1, 2], &[0, 2]).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_new_validates_dimensions() {
+        let t0 = Tensor4::<f64>::zeros(1, 2, 2, 3);
+        let t1 = Tensor4::<f64>::zeros(2, 2, 2, 1);
+        assert!(TensorTrain4::new(vec![t0, t1]).is_err());
+    }
+
+    #[test]
+    fn test_tensor_train_roundtrip() {
+        let mut mpo = TensorTrain4::<f64>::identity(&[2, 2]);
+        mpo.scale(3.0);
+        let tt = mpo.to_tensor_train();
+        assert_eq!(tt.site_dims(), vec![4, 4]);
+
+        let back = TensorTrain4::from_tensor_train(&tt, &mpo.site_dims()).unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_eq!(
+                    back.evaluate(&[i, j], &[i, j]).unwrap(),
+                    mpo.evaluate(&[i, j], &[i, j]).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_mps_roundtrip() {
+        let tt = TensorTrain::<f64>::constant(&[2, 3], 2.0);
+        let mpo = TensorTrain4::from_mps(&tt);
+        assert_eq!(mpo.site_dims(), vec![(2, 1), (3, 1)]);
+        assert_eq!(mpo.evaluate(&[1, 2], &[0, 0]).unwrap(), 2.0);
+
+        let back = mpo.to_mps().unwrap();
+        assert_eq!(back.evaluate(&[1, 2]).unwrap(), 2.0);
+        assert!(TensorTrain4::<f64>::identity(&[2]).to_mps().is_err());
+    }
+}
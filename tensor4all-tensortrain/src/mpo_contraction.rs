@@ -0,0 +1,733 @@
+//! Contraction of matrix product operators with MPOs and MPSs
+//!
+//! This module provides three algorithms for computing `A * B`, where `A` is an
+//! MPO and `B` is either an MPO or an MPS:
+//! - `contract_naive`: Exact contraction (bond dimensions multiply), then compression
+//! - `contract_zipup`: Contraction with on-the-fly factorization from left to right
+//! - `contract_fit`: Variational two-site fitting, starting from the zip-up result
+
+use crate::compression::{
+    factorize, factorize_svd, matrix_data, matrix_from_data, CompressionMethod, CompressionOptions,
+};
+use crate::contraction::ContractionOptions;
+use crate::error::{Result, TensorTrainError};
+use crate::mpo::TensorTrain4;
+use crate::tensortrain::TensorTrain;
+use crate::traits::TTScalar;
+use crate::types::{Tensor3, Tensor4};
+use faer::traits::ComplexField;
+use tensor4all_matrixci::util::{mat_mul, Matrix, Scalar};
+
+/// Right-hand operand of an MPO contraction (MPO or MPS)
+///
+/// An MPS is treated as an MPO whose input index has dimension 1.
+pub trait MPOOperand<T: TTScalar>: Sized {
+    /// Convert to an MPO
+    fn to_mpo(&self) -> TensorTrain4<T>;
+
+    /// Convert back from an MPO
+    fn from_mpo(mpo: TensorTrain4<T>) -> Result<Self>;
+}
+
+impl<T: TTScalar> MPOOperand<T> for TensorTrain4<T> {
+    fn to_mpo(&self) -> TensorTrain4<T> {
+        self.clone()
+    }
+
+    fn from_mpo(mpo: TensorTrain4<T>) -> Result<Self> {
+        Ok(mpo)
+    }
+}
+
+impl<T: TTScalar> MPOOperand<T> for TensorTrain<T> {
+    fn to_mpo(&self) -> TensorTrain4<T> {
+        TensorTrain4::from_mps(self)
+    }
+
+    fn from_mpo(mpo: TensorTrain4<T>) -> Result<Self> {
+        mpo.to_mps()
+    }
+}
+
+/// Options for variational fitting
+#[derive(Debug, Clone)]
+pub struct FitOptions {
+    /// Tolerance for SVD truncation (relative discarded weight per bond)
+    pub tolerance: f64,
+    /// Maximum bond dimension
+    pub max_bond_dim: usize,
+    /// Number of sweeps (each sweep goes left-to-right and back)
+    pub nsweeps: usize,
+}
+
+impl Default for FitOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-12,
+            max_bond_dim: usize::MAX,
+            nsweeps: 2,
+        }
+    }
+}
+
+/// Environment tensor with shape (c_bond, a_bond, b_bond), stored row-major
+struct Env<T> {
+    data: Vec<T>,
+    dims: (usize, usize, usize),
+}
+
+impl<T: TTScalar> Env<T> {
+    fn trivial() -> Self {
+        Self {
+            data: vec![T::one()],
+            dims: (1, 1, 1),
+        }
+    }
+}
+
+fn check_compatible<T: TTScalar>(a: &TensorTrain4<T>, b: &TensorTrain4<T>) -> Result<()> {
+    if a.len() != b.len() {
+        return Err(TensorTrainError::IndexLengthMismatch {
+            expected: a.len(),
+            got: b.len(),
+        });
+    }
+    if a.is_empty() {
+        return Err(TensorTrainError::Empty);
+    }
+    for (site, (ta, tb)) in a.site_tensors().iter().zip(b.site_tensors()).enumerate() {
+        if ta.site_dim2() != tb.site_dim1() {
+            return Err(TensorTrainError::DimensionMismatch { site });
+        }
+    }
+    Ok(())
+}
+
+/// Exact contraction of two site tensors over A's input and B's output index
+fn contract_site<T: TTScalar>(a: &Tensor4<T>, b: &Tensor4<T>) -> Tensor4<T> {
+    let (la, d1, dk, ra) = (a.left_dim(), a.site_dim1(), a.site_dim2(), a.right_dim());
+    let (lb, d2, rb) = (b.left_dim(), b.site_dim2(), b.right_dim());
+
+    let mut result = Tensor4::zeros(la * lb, d1, d2, ra * rb);
+    for ia in 0..la {
+        for ib in 0..lb {
+            for s1 in 0..d1 {
+                for s2 in 0..d2 {
+                    for ja in 0..ra {
+                        for jb in 0..rb {
+                            let mut sum = T::zero();
+                            for k in 0..dk {
+                                sum = sum + *a.get(ia, s1, k, ja) * *b.get(ib, k, s2, jb);
+                            }
+                            result.set(ia * lb + ib, s1, s2, ja * rb + jb, sum);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Contract a left environment (c, la, lb) with A and B
+///
+/// Returns data with shape (c, s1, s2, ra, rb).
+fn zip_left<T: TTScalar>(env: &Env<T>, a: &Tensor4<T>, b: &Tensor4<T>) -> Vec<T> {
+    let (dc, da, db) = env.dims;
+    let (d1, dk, ra) = (a.site_dim1(), a.site_dim2(), a.right_dim());
+    let (d2, rb) = (b.site_dim2(), b.right_dim());
+
+    // ea[c, lb, s1, k, ra] = sum_la env[c, la, lb] * A[la, s1, k, ra]
+    let mut ea = vec![T::zero(); dc * db * d1 * dk * ra];
+    for c in 0..dc {
+        for la in 0..da {
+            for lb in 0..db {
+                let e = env.data[(c * da + la) * db + lb];
+                for s1 in 0..d1 {
+                    for k in 0..dk {
+                        for r in 0..ra {
+                            let idx = (((c * db + lb) * d1 + s1) * dk + k) * ra + r;
+                            ea[idx] = ea[idx] + e * *a.get(la, s1, k, r);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // out[c, s1, s2, ra, rb] = sum_{lb, k} ea[c, lb, s1, k, ra] * B[lb, k, s2, rb]
+    let mut out = vec![T::zero(); dc * d1 * d2 * ra * rb];
+    for c in 0..dc {
+        for lb in 0..db {
+            for s1 in 0..d1 {
+                for k in 0..dk {
+                    for r1 in 0..ra {
+                        let x = ea[(((c * db + lb) * d1 + s1) * dk + k) * ra + r1];
+                        for s2 in 0..d2 {
+                            for r2 in 0..rb {
+                                let idx = (((c * d1 + s1) * d2 + s2) * ra + r1) * rb + r2;
+                                out[idx] = out[idx] + x * *b.get(lb, k, s2, r2);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Contract A and B with a right environment (c, ra, rb)
+///
+/// Returns data with shape (la, lb, s1, s2, c).
+fn zip_right<T: TTScalar>(a: &Tensor4<T>, b: &Tensor4<T>, env: &Env<T>) -> Vec<T> {
+    let (dc, ra, rb) = env.dims;
+    let (la, d1, dk) = (a.left_dim(), a.site_dim1(), a.site_dim2());
+    let (lb, d2) = (b.left_dim(), b.site_dim2());
+
+    // be[lb, k, s2, c, ra] = sum_rb B[lb, k, s2, rb] * env[c, ra, rb]
+    let mut be = vec![T::zero(); lb * dk * d2 * dc * ra];
+    for l in 0..lb {
+        for k in 0..dk {
+            for s2 in 0..d2 {
+                for c in 0..dc {
+                    for r1 in 0..ra {
+                        let mut sum = T::zero();
+                        for r2 in 0..rb {
+                            sum = sum + *b.get(l, k, s2, r2) * env.data[(c * ra + r1) * rb + r2];
+                        }
+                        be[(((l * dk + k) * d2 + s2) * dc + c) * ra + r1] = sum;
+                    }
+                }
+            }
+        }
+    }
+
+    // out[la, lb, s1, s2, c] = sum_{k, ra} A[la, s1, k, ra] * be[lb, k, s2, c, ra]
+    let mut out = vec![T::zero(); la * lb * d1 * d2 * dc];
+    for l1 in 0..la {
+        for s1 in 0..d1 {
+            for k in 0..dk {
+                for r1 in 0..ra {
+                    let x = *a.get(l1, s1, k, r1);
+                    for l2 in 0..lb {
+                        for s2 in 0..d2 {
+                            for c in 0..dc {
+                                let idx = (((l1 * lb + l2) * d1 + s1) * d2 + s2) * dc + c;
+                                out[idx] = out[idx] + x * be[(((l2 * dk + k) * d2 + s2) * dc + c) * ra + r1];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Extend a left environment by one site: conj(C) * env * A * B
+fn extend_left<T: TTScalar + Scalar>(
+    env: &Env<T>,
+    c: &Tensor3<T>,
+    a: &Tensor4<T>,
+    b: &Tensor4<T>,
+) -> Env<T> {
+    let z = zip_left(env, a, b);
+    let rows = c.left_dim() * c.site_dim();
+    let cols = a.right_dim() * b.right_dim();
+    let rc = c.right_dim();
+    let (cdata, _, _) = c.as_left_matrix();
+
+    let mut data = vec![T::zero(); rc * cols];
+    for i in 0..rows {
+        for j in 0..rc {
+            let x = cdata[i * rc + j].conj();
+            for k in 0..cols {
+                data[j * cols + k] = data[j * cols + k] + x * z[i * cols + k];
+            }
+        }
+    }
+    Env {
+        data,
+        dims: (rc, a.right_dim(), b.right_dim()),
+    }
+}
+
+/// Extend a right environment by one site: conj(C) * A * B * env
+fn extend_right<T: TTScalar + Scalar>(
+    env: &Env<T>,
+    c: &Tensor3<T>,
+    a: &Tensor4<T>,
+    b: &Tensor4<T>,
+) -> Env<T> {
+    let w = zip_right(a, b, env);
+    let rows = a.left_dim() * b.left_dim();
+    let cols = c.site_dim() * c.right_dim();
+    let lc = c.left_dim();
+    let (cdata, _, _) = c.as_right_matrix();
+
+    let mut data = vec![T::zero(); lc * rows];
+    for l in 0..lc {
+        for i in 0..rows {
+            let mut sum = T::zero();
+            for k in 0..cols {
+                sum = sum + cdata[l * cols + k].conj() * w[i * cols + k];
+            }
+            data[l * rows + i] = sum;
+        }
+    }
+    Env {
+        data,
+        dims: (lc, a.left_dim(), b.left_dim()),
+    }
+}
+
+/// Compress an MPO by fusing its site indices and compressing as a tensor train
+fn compress_mpo<T: TTScalar + Scalar + Default + ComplexField>(
+    mpo: TensorTrain4<T>,
+    options: &ContractionOptions,
+) -> Result<TensorTrain4<T>> {
+    let site_dims = mpo.site_dims();
+    let mut tt = mpo.to_tensor_train();
+    tt.compress(&CompressionOptions {
+        method: options.method,
+        tolerance: options.tolerance,
+        max_bond_dim: options.max_bond_dim,
+        normalize_error: options.normalize_error,
+    })?;
+    TensorTrain4::from_tensor_train(&tt, &site_dims)
+}
+
+fn contract_naive_mpo<T: TTScalar + Scalar + Default + ComplexField>(
+    a: &TensorTrain4<T>,
+    b: &TensorTrain4<T>,
+    options: &ContractionOptions,
+) -> Result<TensorTrain4<T>> {
+    check_compatible(a, b)?;
+    let tensors = a
+        .site_tensors()
+        .iter()
+        .zip(b.site_tensors())
+        .map(|(ta, tb)| contract_site(ta, tb))
+        .collect();
+    let result = TensorTrain4::from_tensors_unchecked(tensors);
+
+    if options.tolerance > 0.0 || options.max_bond_dim < usize::MAX {
+        compress_mpo(result, options)
+    } else {
+        Ok(result)
+    }
+}
+
+fn contract_zipup_mpo<T: TTScalar + Scalar + Default + ComplexField>(
+    a: &TensorTrain4<T>,
+    b: &TensorTrain4<T>,
+    options: &ContractionOptions,
+) -> Result<TensorTrain4<T>> {
+    check_compatible(a, b)?;
+    let n = a.len();
+    let mut tensors = Vec::with_capacity(n);
+    let mut env = Env::trivial();
+
+    for site in 0..n {
+        let ta = a.site_tensor(site);
+        let tb = b.site_tensor(site);
+        let (d1, d2) = (ta.site_dim1(), tb.site_dim2());
+        let dc = env.dims.0;
+        let z = zip_left(&env, ta, tb);
+
+        if site == n - 1 {
+            tensors.push(Tensor4::from_data(z, dc, d1, d2, 1));
+            break;
+        }
+
+        let (ra, rb) = (ta.right_dim(), tb.right_dim());
+        let mat = matrix_from_data(&z, dc * d1 * d2, ra * rb);
+        let (left, right, rank) = factorize(
+            &mat,
+            options.method,
+            options.tolerance,
+            options.max_bond_dim,
+            options.normalize_error,
+            true,  // left orthogonal
+        )?;
+
+        tensors.push(Tensor4::from_data(matrix_data(&left), dc, d1, d2, rank));
+        env = Env {
+            data: matrix_data(&right),
+            dims: (rank, ra, rb),
+        };
+    }
+
+    Ok(TensorTrain4::from_tensors_unchecked(tensors))
+}
+
+/// Optimal two-site tensor (lc * s_n, s_{n+1} * rc) for sites n, n+1
+fn two_site_tensor<T: TTScalar + Scalar>(
+    left_env: &Env<T>,
+    right_env: &Env<T>,
+    a: &TensorTrain4<T>,
+    b: &TensorTrain4<T>,
+    site: usize,
+) -> Matrix<T> {
+    let (a1, b1) = (a.site_tensor(site), b.site_tensor(site));
+    let (a2, b2) = (a.site_tensor(site + 1), b.site_tensor(site + 1));
+
+    let z = zip_left(left_env, a1, b1);
+    let w = zip_right(a2, b2, right_env);
+    let bond = a1.right_dim() * b1.right_dim();
+    let rows = left_env.dims.0 * a1.site_dim1() * b1.site_dim2();
+    let cols = a2.site_dim1() * b2.site_dim2() * right_env.dims.0;
+
+    mat_mul(
+        &matrix_from_data(&z, rows, bond),
+        &matrix_from_data(&w, bond, cols),
+    )
+}
+
+fn contract_fit_mpo<T: TTScalar + Scalar + Default + ComplexField>(
+    a: &TensorTrain4<T>,
+    b: &TensorTrain4<T>,
+    options: &FitOptions,
+) -> Result<TensorTrain4<T>> {
+    check_compatible(a, b)?;
+    let n = a.len();
+    if n == 1 {
+        return contract_naive_mpo(a, b, &ContractionOptions {
+            tolerance: 0.0,
+            max_bond_dim: usize::MAX,
+            method: CompressionMethod::SVD,
+            normalize_error: true,
+        });
+    }
+
+    let site_dims: Vec<(usize, usize)> = a
+        .site_tensors()
+        .iter()
+        .zip(b.site_tensors())
+        .map(|(ta, tb)| (ta.site_dim1(), tb.site_dim2()))
+        .collect();
+
+    // Initial guess from zip-up, fused to 3-leg tensors
+    let initial = contract_zipup_mpo(a, b, &ContractionOptions {
+        tolerance: options.tolerance,
+        max_bond_dim: options.max_bond_dim,
+        method: CompressionMethod::SVD,
+        normalize_error: true,
+    })?;
+    let mut c: Vec<Tensor3<T>> = initial
+        .site_tensors()
+        .iter()
+        .map(|t| t.fuse_site_indices())
+        .collect();
+
+    // Right-canonicalize so that the orthogonality center is at site 0
+    for site in (1..n).rev() {
+        let (l, s, r) = (c[site].left_dim(), c[site].site_dim(), c[site].right_dim());
+        let (data, _, _) = c[site].as_right_matrix();
+        let (left, right, rank, _) = factorize_svd(
+            &matrix_from_data(&data, l, s * r),
+            0.0,
+            usize::MAX,
+            true,
+            false,
+        )?;
+        c[site] = Tensor3::from_data(matrix_data(&right), rank, s, r);
+
+        let (pl, ps, pr) = (c[site - 1].left_dim(), c[site - 1].site_dim(), c[site - 1].right_dim());
+        let (pdata, _, _) = c[site - 1].as_left_matrix();
+        let prev = mat_mul(&matrix_from_data(&pdata, pl * ps, pr), &left);
+        c[site - 1] = Tensor3::from_data(matrix_data(&prev), pl, ps, rank);
+    }
+
+    // left_envs[i] covers sites < i, right_envs[i] covers sites >= i
+    let mut left_envs: Vec<Env<T>> = (0..=n).map(|_| Env::trivial()).collect();
+    let mut right_envs: Vec<Env<T>> = (0..=n).map(|_| Env::trivial()).collect();
+    for site in (2..n).rev() {
+        right_envs[site] = extend_right(&right_envs[site + 1], &c[site], a.site_tensor(site), b.site_tensor(site));
+    }
+
+    for _ in 0..options.nsweeps {
+        // Left-to-right: keep left factor orthogonal
+        for site in 0..n - 1 {
+            let local = two_site_tensor(&left_envs[site], &right_envs[site + 2], a, b, site);
+            let (left, right, rank, _) = factorize_svd(
+                &local,
+                options.tolerance,
+                options.max_bond_dim,
+                true,
+                true,
+            )?;
+            let lc = left_envs[site].dims.0;
+            let rc = right_envs[site + 2].dims.0;
+            let s1 = site_dims[site].0 * site_dims[site].1;
+            let s2 = site_dims[site + 1].0 * site_dims[site + 1].1;
+            c[site] = Tensor3::from_data(matrix_data(&left), lc, s1, rank);
+            c[site + 1] = Tensor3::from_data(matrix_data(&right), rank, s2, rc);
+            left_envs[site + 1] = extend_left(&left_envs[site], &c[site], a.site_tensor(site), b.site_tensor(site));
+        }
+
+        // Right-to-left: keep right factor orthogonal
+        for site in (0..n - 1).rev() {
+            let local = two_site_tensor(&left_envs[site], &right_envs[site + 2], a, b, site);
+            let (left, right, rank, _) = factorize_svd(
+                &local,
+                options.tolerance,
+                options.max_bond_dim,
+                true,
+                false,
+            )?;
+            let lc = left_envs[site].dims.0;
+            let rc = right_envs[site + 2].dims.0;
+            let s1 = site_dims[site].0 * site_dims[site].1;
+            let s2 = site_dims[site + 1].0 * site_dims[site + 1].1;
+            c[site] = Tensor3::from_data(matrix_data(&left), lc, s1, rank);
+            c[site + 1] = Tensor3::from_data(matrix_data(&right), rank, s2, rc);
+            right_envs[site + 1] = extend_right(&right_envs[site + 2], &c[site + 1], a.site_tensor(site + 1), b.site_tensor(site + 1));
+        }
+    }
+
+    let tensors = c
+        .iter()
+        .zip(&site_dims)
+        .map(|(t, &(d1, d2))| Tensor4::from_fused(t, d1, d2))
+        .collect();
+    Ok(TensorTrain4::from_tensors_unchecked(tensors))
+}
+
+/// Contract an MPO with an MPO or MPS exactly, then compress
+///
+/// The bond dimensions of the result are the products of the input bond
+/// dimensions. Compression is applied if `options.tolerance > 0` or
+/// `options.max_bond_dim` is finite.
+pub fn contract_naive<T, B>(a: &TensorTrain4<T>, b: &B, options: &ContractionOptions) -> Result<B>
+where
+    T: TTScalar + Scalar + Default + ComplexField,
+    B: MPOOperand<T>,
+{
+    B::from_mpo(contract_naive_mpo(a, &b.to_mpo(), options)?)
+}
+
+/// Contract an MPO with an MPO or MPS using the zip-up algorithm
+///
+/// Sweeps from left to right, factorizing the partially contracted tensor at
+/// each bond with `options.method`, so the full product is never formed.
+pub fn contract_zipup<T, B>(a: &TensorTrain4<T>, b: &B, options: &ContractionOptions) -> Result<B>
+where
+    T: TTScalar + Scalar + Default + ComplexField,
+    B: MPOOperand<T>,
+{
+    B::from_mpo(contract_zipup_mpo(a, &b.to_mpo(), options)?)
+}
+
+/// Contract an MPO with an MPO or MPS by variational fitting
+///
+/// Starts from the zip-up result and performs two-site sweeps, replacing each
+/// pair of tensors with the truncated SVD of the exact local projection.
+pub fn contract_fit<T, B>(a: &TensorTrain4<T>, b: &B, options: &FitOptions) -> Result<B>
+where
+    T: TTScalar + Scalar + Default + ComplexField,
+    B: MPOOperand<T>,
+{
+    B::from_mpo(contract_fit_mpo(a, &b.to_mpo(), options)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{all_indices, random_mpo};
+    use crate::traits::AbstractTensorTrain;
+    use num_complex::Complex64;
+    use rand::{Rng, SeedableRng};
+
+    /// Brute-force (A * B)[row, col]
+    fn product_element<T: TTScalar>(
+        a: &TensorTrain4<T>,
+        b: &TensorTrain4<T>,
+        row: &[usize],
+        col: &[usize],
+    ) -> T {
+        let inner: Vec<usize> = a.site_dims().iter().map(|d| d.1).collect();
+        let mut sum = T::zero();
+        for k in all_indices(&inner) {
+            sum = sum + a.evaluate(row, &k).unwrap() * b.evaluate(&k, col).unwrap();
+        }
+        sum
+    }
+
+    fn max_error(a: &TensorTrain4<f64>, b: &TensorTrain4<f64>, c: &TensorTrain4<f64>) -> f64 {
+        let rows: Vec<usize> = a.site_dims().iter().map(|d| d.0).collect();
+        let cols: Vec<usize> = b.site_dims().iter().map(|d| d.1).collect();
+        let mut err: f64 = 0.0;
+        for row in all_indices(&rows) {
+            for col in all_indices(&cols) {
+                let diff = product_element(a, b, &row, &col) - c.evaluate(&row, &col).unwrap();
+                err = err.max(diff.abs());
+            }
+        }
+        err
+    }
+
+    fn exact_options() -> ContractionOptions {
+        ContractionOptions {
+            tolerance: 0.0,
+            max_bond_dim: usize::MAX,
+            method: CompressionMethod::SVD,
+            normalize_error: true,
+        }
+    }
+
+    #[test]
+    fn test_contract_naive_mpo_mpo() {
+        let a = random_mpo(&[(2, 2), (2, 3), (2, 2)], 2, 1);
+        let b = random_mpo(&[(2, 2), (3, 2), (2, 2)], 3, 2);
+        let c = contract_naive(&a, &b, &exact_options()).unwrap();
+        assert_eq!(c.link_dims(), vec![6, 6]);
+        assert!(max_error(&a, &b, &c) < 1e-12);
+    }
+
+    #[test]
+    fn test_contract_naive_identity_mps() {
+        let mps = random_mpo(&[(2, 1), (3, 1), (2, 1)], 2, 3).to_mps().unwrap();
+        let id = TensorTrain4::identity(&[2, 3, 2]);
+        let result: TensorTrain<f64> = contract_naive(&id, &mps, &ContractionOptions::default()).unwrap();
+        for idx in all_indices(&[2, 3, 2]) {
+            let diff = result.evaluate(&idx).unwrap() - mps.evaluate(&idx).unwrap();
+            assert!(diff.abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_contract_dimension_mismatch() {
+        let a = random_mpo(&[(2, 2), (2, 2)], 2, 4);
+        let b = random_mpo(&[(3, 2), (2, 2)], 2, 5);
+        assert!(contract_naive(&a, &b, &exact_options()).is_err());
+        assert!(contract_zipup(&a, &b, &exact_options()).is_err());
+        assert!(contract_fit(&a, &b, &FitOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_contract_zipup_mpo_mpo() {
+        let a = random_mpo(&[(2, 2), (2, 2), (2, 2), (2, 2)], 2, 6);
+        let b = random_mpo(&[(2, 2), (2, 2), (2, 2), (2, 2)], 2, 7);
+        let options = ContractionOptions {
+            method: CompressionMethod::SVD,
+            ..Default::default()
+        };
+        let c = contract_zipup(&a, &b, &options).unwrap();
+        assert!(c.rank() <= 4);
+        assert!(max_error(&a, &b, &c) < 1e-10);
+    }
+
+    #[test]
+    fn test_contract_zipup_normalize_error() {
+        let a = random_mpo(&[(2, 2), (2, 2), (2, 2), (2, 2)], 2, 6);
+        let b = random_mpo(&[(2, 2), (2, 2), (2, 2), (2, 2)], 2, 7);
+        // Scale the first tensor, whose factorization carries the scale to the right
+        let mut tensors = a.site_tensors().to_vec();
+        let t = &mut tensors[0];
+        for l in 0..t.left_dim() {
+            for s1 in 0..t.site_dim1() {
+                for s2 in 0..t.site_dim2() {
+                    for r in 0..t.right_dim() {
+                        *t.get_mut(l, s1, s2, r) *= 1e-8;
+                    }
+                }
+            }
+        }
+        let small = TensorTrain4::from_tensors_unchecked(tensors);
+
+        let relative = ContractionOptions {
+            tolerance: 1e-6,
+            method: CompressionMethod::SVD,
+            ..Default::default()
+        };
+        let c = contract_zipup(&small, &b, &relative).unwrap();
+        assert_eq!(c.rank(), contract_zipup(&a, &b, &relative).unwrap().rank());
+
+        // The absolute tolerance exceeds all singular values
+        let absolute = ContractionOptions {
+            normalize_error: false,
+            ..relative
+        };
+        let c = contract_zipup(&small, &b, &absolute).unwrap();
+        assert_eq!(c.rank(), 1);
+    }
+
+    #[test]
+    fn test_contract_zipup_mps_lu() {
+        let a = random_mpo(&[(2, 2), (2, 2), (2, 2)], 2, 8);
+        let b = random_mpo(&[(2, 1), (2, 1), (2, 1)], 2, 9);
+        let mps = b.to_mps().unwrap();
+        let c: TensorTrain<f64> = contract_zipup(&a, &mps, &ContractionOptions::default()).unwrap();
+        assert!(max_error(&a, &b, &TensorTrain4::from_mps(&c)) < 1e-10);
+    }
+
+    #[test]
+    fn test_contract_fit_exact() {
+        let a = random_mpo(&[(2, 2), (2, 2), (2, 2), (2, 2)], 2, 10);
+        let b = random_mpo(&[(2, 1), (2, 1), (2, 1), (2, 1)], 3, 11);
+        let mps = b.to_mps().unwrap();
+        let c: TensorTrain<f64> = contract_fit(&a, &mps, &FitOptions::default()).unwrap();
+        assert!(max_error(&a, &b, &TensorTrain4::from_mps(&c)) < 1e-10);
+    }
+
+    #[test]
+    fn test_contract_fit_improves_zipup() {
+        let dims = [(2, 2); 5];
+        let a = random_mpo(&dims, 3, 12);
+        let b = random_mpo(&dims, 3, 13);
+        let exact = contract_naive(&a, &b, &exact_options()).unwrap().to_tensor_train();
+
+        let zipup = contract_zipup(&a, &b, &ContractionOptions {
+            tolerance: 0.0,
+            max_bond_dim: 3,
+            method: CompressionMethod::SVD,
+            normalize_error: true,
+        })
+        .unwrap();
+        let fit = contract_fit(&a, &b, &FitOptions {
+            tolerance: 0.0,
+            max_bond_dim: 3,
+            nsweeps: 4,
+        })
+        .unwrap();
+        assert!(fit.rank() <= 3);
+
+        let error = |c: &TensorTrain4<f64>| {
+            let diff = exact.sub(&c.to_tensor_train()).unwrap();
+            diff.dot(&diff).unwrap().sqrt()
+        };
+        assert!(error(&fit) <= error(&zipup) + 1e-12);
+    }
+
+    #[test]
+    fn test_contract_fit_complex() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(14);
+        let mut random_tensor = |l, d1, d2, r| {
+            let data = (0..l * d1 * d2 * r)
+                .map(|_| Complex64::new(rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5))
+                .collect();
+            Tensor4::from_data(data, l, d1, d2, r)
+        };
+        let a = TensorTrain4::new(vec![
+            random_tensor(1, 2, 2, 2),
+            random_tensor(2, 2, 2, 2),
+            random_tensor(2, 2, 2, 1),
+        ])
+        .unwrap();
+        let b = TensorTrain4::new(vec![
+            random_tensor(1, 2, 1, 2),
+            random_tensor(2, 2, 1, 2),
+            random_tensor(2, 2, 1, 1),
+        ])
+        .unwrap();
+
+        let c = contract_fit(&a, &b, &FitOptions::default()).unwrap();
+        for row in all_indices(&[2, 2, 2]) {
+            let expected = product_element(&a, &b, &row, &[0, 0, 0]);
+            let actual = c.evaluate(&row, &[0, 0, 0]).unwrap();
+            assert!((expected - actual).norm() < 1e-10);
+        }
+    }
+}
@@ -0,0 +1,441 @@
+//! Site-canonical (mixed-canonical) tensor train
+
+use crate::compression::{
+    factorize_svd, matrix_data, matrix_from_data, tensor3_to_left_matrix, tensor3_to_right_matrix,
+};
+use crate::error::{Result, TensorTrainError};
+use crate::tensortrain::TensorTrain;
+use crate::traits::{AbstractTensorTrain, TTScalar};
+use crate::types::{Tensor3, Tensor4};
+use faer::traits::ComplexField;
+use tensor4all_matrixci::util::{mat_mul, Scalar};
+
+/// Tensor train in site-canonical form
+///
+/// All tensors left of `center` are left-orthogonal and all tensors right of
+/// `center` are right-orthogonal, so the norm of the whole train is the norm
+/// of the center tensor and local updates at the center are well-conditioned.
+#[derive(Debug, Clone)]
+pub struct SiteTensorTrain<T: TTScalar> {
+    tt: TensorTrain<T>,
+    center: usize,
+}
+
+impl<T: TTScalar + Scalar + Default + ComplexField> SiteTensorTrain<T> {
+    /// Bring a tensor train into site-canonical form with the given center
+    pub fn new(tt: TensorTrain<T>, center: usize) -> Result<Self> {
+        if tt.is_empty() {
+            return Err(TensorTrainError::Empty);
+        }
+        if center >= tt.len() {
+            return Err(TensorTrainError::IndexOutOfBounds {
+                site: center,
+                index: center,
+                max: tt.len(),
+            });
+        }
+
+        let n = tt.len();
+        let mut stt = Self { tt, center: 0 };
+        for site in (1..n).rev() {
+            stt.shift_center_left(site)?;
+        }
+        for site in 0..center {
+            stt.shift_center_right(site)?;
+        }
+        stt.center = center;
+        Ok(stt)
+    }
+
+    /// Position of the orthogonality center
+    pub fn center(&self) -> usize {
+        self.center
+    }
+
+    /// The underlying tensor train
+    pub fn tensor_train(&self) -> &TensorTrain<T> {
+        &self.tt
+    }
+
+    /// Unwrap into the underlying tensor train
+    pub fn into_tensor_train(self) -> TensorTrain<T> {
+        self.tt
+    }
+
+    /// The tensor at the orthogonality center
+    pub fn center_tensor(&self) -> &Tensor3<T> {
+        self.tt.site_tensor(self.center)
+    }
+
+    /// Frobenius norm, computed from the center tensor only
+    pub fn norm(&self) -> f64 {
+        self.norm2().sqrt()
+    }
+
+    /// Squared Frobenius norm, computed from the center tensor only
+    pub fn norm2(&self) -> f64 {
+        let (data, _, _) = self.center_tensor().as_left_matrix();
+        data.iter().map(|&x| TTScalar::abs_sq(x)).sum()
+    }
+
+    /// Move the orthogonality center one site to the left
+    pub fn move_center_left(&mut self) -> Result<()> {
+        if self.center == 0 {
+            return Err(TensorTrainError::InvalidOperation {
+                message: "Orthogonality center is already at the first site".to_string(),
+            });
+        }
+        self.shift_center_left(self.center)?;
+        self.center -= 1;
+        Ok(())
+    }
+
+    /// Move the orthogonality center one site to the right
+    pub fn move_center_right(&mut self) -> Result<()> {
+        if self.center + 1 >= self.len() {
+            return Err(TensorTrainError::InvalidOperation {
+                message: "Orthogonality center is already at the last site".to_string(),
+            });
+        }
+        self.shift_center_right(self.center)?;
+        self.center += 1;
+        Ok(())
+    }
+
+    /// Move the orthogonality center to the given site
+    ///
+    /// Only the tensors between the old and new center are touched.
+    pub fn move_center(&mut self, site: usize) -> Result<()> {
+        if site >= self.len() {
+            return Err(TensorTrainError::IndexOutOfBounds {
+                site,
+                index: site,
+                max: self.len(),
+            });
+        }
+        while self.center < site {
+            self.move_center_right()?;
+        }
+        while self.center > site {
+            self.move_center_left()?;
+        }
+        Ok(())
+    }
+
+    /// Replace the tensor at the orthogonality center
+    ///
+    /// The bond dimensions must match the current center tensor.
+    pub fn set_center_tensor(&mut self, tensor: Tensor3<T>) -> Result<()> {
+        let current = self.center_tensor();
+        if tensor.left_dim() != current.left_dim()
+            || tensor.site_dim() != current.site_dim()
+            || tensor.right_dim() != current.right_dim()
+        {
+            return Err(TensorTrainError::DimensionMismatch { site: self.center });
+        }
+        let center = self.center;
+        self.tt.site_tensors_mut()[center] = tensor;
+        Ok(())
+    }
+
+    /// Contract the tensors at `site` and `site + 1` into a two-site tensor
+    ///
+    /// Returns a tensor of shape (left_dim, site_dim(site), site_dim(site + 1), right_dim).
+    pub fn two_site_tensor(&self, site: usize) -> Result<Tensor4<T>> {
+        self.check_bond(site)?;
+        let a = self.tt.site_tensor(site);
+        let b = self.tt.site_tensor(site + 1);
+        let prod = mat_mul(&tensor3_to_left_matrix(a), &tensor3_to_right_matrix(b));
+        Ok(Tensor4::from_data(
+            matrix_data(&prod),
+            a.left_dim(),
+            a.site_dim(),
+            b.site_dim(),
+            b.right_dim(),
+        ))
+    }
+
+    /// Replace the tensors at `site` and `site + 1` by a two-site tensor
+    ///
+    /// The orthogonality center must be at `site` or `site + 1`. The tensor is
+    /// split by truncated SVD; if the center was at `site` it moves to
+    /// `site + 1`, otherwise to `site`, so that sweeps keep their direction.
+    ///
+    /// # Arguments
+    /// * `site` - Left site of the pair
+    /// * `tensor` - Two-site tensor of shape (left_dim, site_dim(site), site_dim(site + 1), right_dim)
+    /// * `tolerance` - Relative truncation tolerance on the discarded weight
+    /// * `max_bond_dim` - Maximum bond dimension of the new bond
+    pub fn set_two_site_tensor(
+        &mut self,
+        site: usize,
+        tensor: &Tensor4<T>,
+        tolerance: f64,
+        max_bond_dim: usize,
+    ) -> Result<()> {
+        self.check_bond(site)?;
+        if self.center != site && self.center != site + 1 {
+            return Err(TensorTrainError::InvalidOperation {
+                message: format!(
+                    "Orthogonality center {} is not on sites ({}, {})",
+                    self.center,
+                    site,
+                    site + 1
+                ),
+            });
+        }
+        let a = self.tt.site_tensor(site);
+        let b = self.tt.site_tensor(site + 1);
+        if tensor.left_dim() != a.left_dim()
+            || tensor.site_dim1() != a.site_dim()
+            || tensor.site_dim2() != b.site_dim()
+            || tensor.right_dim() != b.right_dim()
+        {
+            return Err(TensorTrainError::DimensionMismatch { site });
+        }
+
+        let move_right = self.center == site;
+        let (l, s1, s2, r) = (
+            tensor.left_dim(),
+            tensor.site_dim1(),
+            tensor.site_dim2(),
+            tensor.right_dim(),
+        );
+        let (data, _, _) = tensor.fuse_site_indices().as_left_matrix();
+        let (left, right, rank, _) = factorize_svd(
+            &matrix_from_data(&data, l * s1, s2 * r),
+            tolerance,
+            max_bond_dim,
+            true,
+            move_right,
+        )?;
+
+        let tensors = self.tt.site_tensors_mut();
+        tensors[site] = Tensor3::from_data(matrix_data(&left), l, s1, rank);
+        tensors[site + 1] = Tensor3::from_data(matrix_data(&right), rank, s2, r);
+        self.center = if move_right { site + 1 } else { site };
+        Ok(())
+    }
+
+    fn check_bond(&self, site: usize) -> Result<()> {
+        if site + 1 >= self.len() {
+            return Err(TensorTrainError::IndexOutOfBounds {
+                site,
+                index: site,
+                max: self.len().saturating_sub(1),
+            });
+        }
+        Ok(())
+    }
+
+    /// Make tensor `site` left-orthogonal and absorb the rest into `site + 1`
+    fn shift_center_right(&mut self, site: usize) -> Result<()> {
+        let tensors = self.tt.site_tensors_mut();
+        let (l, s, _) = (tensors[site].left_dim(), tensors[site].site_dim(), tensors[site].right_dim());
+        let (left, right, rank, _) =
+            factorize_svd(&tensor3_to_left_matrix(&tensors[site]), 0.0, usize::MAX, true, true)?;
+        tensors[site] = Tensor3::from_data(matrix_data(&left), l, s, rank);
+
+        let next = &tensors[site + 1];
+        let (ns, nr) = (next.site_dim(), next.right_dim());
+        let contracted = mat_mul(&right, &tensor3_to_right_matrix(next));
+        tensors[site + 1] = Tensor3::from_data(matrix_data(&contracted), rank, ns, nr);
+        Ok(())
+    }
+
+    /// Make tensor `site` right-orthogonal and absorb the rest into `site - 1`
+    fn shift_center_left(&mut self, site: usize) -> Result<()> {
+        let tensors = self.tt.site_tensors_mut();
+        let (s, r) = (tensors[site].site_dim(), tensors[site].right_dim());
+        let (left, right, rank, _) =
+            factorize_svd(&tensor3_to_right_matrix(&tensors[site]), 0.0, usize::MAX, true, false)?;
+        tensors[site] = Tensor3::from_data(matrix_data(&right), rank, s, r);
+
+        let prev = &tensors[site - 1];
+        let (pl, ps) = (prev.left_dim(), prev.site_dim());
+        let contracted = mat_mul(&tensor3_to_left_matrix(prev), &left);
+        tensors[site - 1] = Tensor3::from_data(matrix_data(&contracted), pl, ps, rank);
+        Ok(())
+    }
+}
+
+impl<T: TTScalar> AbstractTensorTrain<T> for SiteTensorTrain<T> {
+    fn len(&self) -> usize {
+        self.tt.len()
+    }
+
+    fn site_tensor(&self, i: usize) -> &Tensor3<T> {
+        self.tt.site_tensor(i)
+    }
+
+    fn site_tensors(&self) -> &[Tensor3<T>] {
+        self.tt.site_tensors()
+    }
+}
+
+impl<T: TTScalar + Scalar + Default + ComplexField> From<SiteTensorTrain<T>> for TensorTrain<T> {
+    fn from(stt: SiteTensorTrain<T>) -> Self {
+        stt.into_tensor_train()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{all_indices, random_tt};
+    use num_complex::Complex64;
+    use rand::{Rng, SeedableRng};
+
+    /// Check the canonical form: left-orthogonal before, right-orthogonal after the center
+    fn assert_canonical<T: TTScalar + Scalar + Default + ComplexField>(stt: &SiteTensorTrain<T>) {
+        for site in 0..stt.len() {
+            let t = stt.site_tensor(site);
+            if site < stt.center() {
+                let m = tensor3_to_left_matrix(t);
+                for i in 0..t.right_dim() {
+                    for j in 0..t.right_dim() {
+                        let mut sum = T::zero();
+                        for k in 0..t.left_dim() * t.site_dim() {
+                            sum = sum + m[[k, i]].conj() * m[[k, j]];
+                        }
+                        let expected = if i == j { 1.0 } else { 0.0 };
+                        assert!((TTScalar::abs_sq(sum).sqrt() - expected).abs() < 1e-10);
+                    }
+                }
+            } else if site > stt.center() {
+                let m = tensor3_to_right_matrix(t);
+                for i in 0..t.left_dim() {
+                    for j in 0..t.left_dim() {
+                        let mut sum = T::zero();
+                        for k in 0..t.site_dim() * t.right_dim() {
+                            sum = sum + m[[i, k]] * m[[j, k]].conj();
+                        }
+                        let expected = if i == j { 1.0 } else { 0.0 };
+                        assert!((TTScalar::abs_sq(sum).sqrt() - expected).abs() < 1e-10);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_new_canonicalizes() {
+        let site_dims = [2, 3, 2, 2];
+        let tt = random_tt(&site_dims, 3, 1);
+        let stt = SiteTensorTrain::new(tt.clone(), 2).unwrap();
+        assert_eq!(stt.center(), 2);
+        assert_canonical(&stt);
+
+        for idx in all_indices(&site_dims) {
+            let diff = stt.evaluate(&idx).unwrap() - tt.evaluate(&idx).unwrap();
+            assert!(diff.abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_new_invalid_center() {
+        let tt = random_tt(&[2, 2], 2, 2);
+        assert!(SiteTensorTrain::new(tt, 2).is_err());
+    }
+
+    #[test]
+    fn test_move_center() {
+        let site_dims = [2, 2, 2, 2, 2];
+        let tt = random_tt(&site_dims, 3, 3);
+        let mut stt = SiteTensorTrain::new(tt.clone(), 0).unwrap();
+
+        for center in [4, 1, 3, 0] {
+            stt.move_center(center).unwrap();
+            assert_eq!(stt.center(), center);
+            assert_canonical(&stt);
+        }
+        assert!(stt.move_center_left().is_err());
+        assert!(stt.move_center(5).is_err());
+
+        for idx in all_indices(&site_dims) {
+            let diff = stt.evaluate(&idx).unwrap() - tt.evaluate(&idx).unwrap();
+            assert!(diff.abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_norm_from_center() {
+        let tt = random_tt(&[2, 3, 2, 2], 3, 4);
+        let norm2 = tt.dot(&tt).unwrap();
+        for center in 0..4 {
+            let stt = SiteTensorTrain::new(tt.clone(), center).unwrap();
+            assert!((stt.norm2() - norm2).abs() < 1e-10 * norm2);
+            assert!((stt.norm() - norm2.sqrt()).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_set_two_site_tensor_roundtrip() {
+        let site_dims = [2, 2, 2, 2];
+        let tt = random_tt(&site_dims, 2, 5);
+        let mut stt = SiteTensorTrain::new(tt.clone(), 0).unwrap();
+
+        // Sweep right, then left, writing back the unchanged two-site tensors
+        for site in 0..3 {
+            let two_site = stt.two_site_tensor(site).unwrap();
+            stt.set_two_site_tensor(site, &two_site, 1e-14, usize::MAX).unwrap();
+            assert_eq!(stt.center(), site + 1);
+            assert_canonical(&stt);
+        }
+        for site in (0..3).rev() {
+            let two_site = stt.two_site_tensor(site).unwrap();
+            stt.set_two_site_tensor(site, &two_site, 1e-14, usize::MAX).unwrap();
+            assert_eq!(stt.center(), site);
+            assert_canonical(&stt);
+        }
+
+        for idx in all_indices(&site_dims) {
+            let diff = stt.evaluate(&idx).unwrap() - tt.evaluate(&idx).unwrap();
+            assert!(diff.abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_set_two_site_tensor_truncates() {
+        let tt = random_tt(&[2, 2, 2, 2], 4, 6);
+        let mut stt = SiteTensorTrain::new(tt, 1).unwrap();
+        let two_site = stt.two_site_tensor(1).unwrap();
+        stt.set_two_site_tensor(1, &two_site, 0.0, 1).unwrap();
+        assert_eq!(stt.link_dim(1), 1);
+        assert_canonical(&stt);
+
+        let wrong = Tensor4::zeros(1, 2, 2, 1);
+        assert!(stt.set_two_site_tensor(1, &wrong, 0.0, 1).is_err());
+
+        // Center (now at site 2) must be inside the pair
+        let two_site = stt.two_site_tensor(0).unwrap();
+        assert!(stt.set_two_site_tensor(0, &two_site, 0.0, 1).is_err());
+    }
+
+    #[test]
+    fn test_complex_canonical() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut random_tensor = |l, d, r| {
+            let data = (0..l * d * r)
+                .map(|_| Complex64::new(rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5))
+                .collect();
+            Tensor3::from_data(data, l, d, r)
+        };
+        let tt = TensorTrain::new(vec![
+            random_tensor(1, 2, 2),
+            random_tensor(2, 2, 3),
+            random_tensor(3, 2, 1),
+        ])
+        .unwrap();
+        let mut stt = SiteTensorTrain::new(tt.clone(), 1).unwrap();
+        assert_canonical(&stt);
+        stt.move_center(2).unwrap();
+        assert_canonical(&stt);
+
+        let norm2: f64 = all_indices(&[2, 2, 2])
+            .iter()
+            .map(|idx| tt.evaluate(idx).unwrap().norm_sqr())
+            .sum();
+        assert!((stt.norm2() - norm2).abs() < 1e-10 * norm2);
+    }
+}
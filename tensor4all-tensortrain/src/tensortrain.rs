@@ -227,6 +227,31 @@ mod tests {
         assert!((sum - 12.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_tensortrain_weighted_sum() {
+        let mut t0 = Tensor3::<f64>::zeros(1, 2, 1);
+        t0.set(0, 0, 0, 1.0);
+        t0.set(0, 1, 0, 2.0);
+
+        let mut t1 = Tensor3::<f64>::zeros(1, 3, 1);
+        t1.set(0, 0, 0, 1.0);
+        t1.set(0, 1, 0, 2.0);
+        t1.set(0, 2, 0, 3.0);
+
+        let tt = TensorTrain::new(vec![t0, t1]).unwrap();
+
+        // (0.5 * 1 + 0.25 * 2) * (1 * 1 + 0 * 2 + 2 * 3) = 1 * 7
+        let weights = vec![vec![0.5, 0.25], vec![1.0, 0.0, 2.0]];
+        assert!((tt.weighted_sum(&weights).unwrap() - 7.0).abs() < 1e-10);
+
+        // Unit weights reproduce the plain sum
+        let ones = vec![vec![1.0; 2], vec![1.0; 3]];
+        assert!((tt.weighted_sum(&ones).unwrap() - tt.sum()).abs() < 1e-10);
+
+        assert!(tt.weighted_sum(&weights[..1]).is_err());
+        assert!(tt.weighted_sum(&[vec![1.0; 3], vec![1.0; 3]]).is_err());
+    }
+
     #[test]
     fn test_tensortrain_reverse() {
         let mut t0 = Tensor3::<f64>::zeros(1, 2, 1);
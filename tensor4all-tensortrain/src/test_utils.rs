@@ -0,0 +1,58 @@
+//! Shared fixtures for unit tests
+
+use crate::mpo::TensorTrain4;
+use crate::tensortrain::TensorTrain;
+use crate::types::{Tensor3, Tensor4};
+use rand::{Rng, SeedableRng};
+
+/// Random real tensor train with uniform bond dimension and entries in [-0.5, 0.5)
+pub(crate) fn random_tt(site_dims: &[usize], bond_dim: usize, seed: u64) -> TensorTrain<f64> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    let n = site_dims.len();
+    let tensors = site_dims
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| {
+            let left = if i == 0 { 1 } else { bond_dim };
+            let right = if i == n - 1 { 1 } else { bond_dim };
+            let data = (0..left * d * right).map(|_| rng.gen::<f64>() - 0.5).collect();
+            Tensor3::from_data(data, left, d, right)
+        })
+        .collect();
+    TensorTrain::new(tensors).unwrap()
+}
+
+/// Random real MPO with site dimensions `(d1, d2)` per site and uniform bond dimension
+pub(crate) fn random_mpo(dims: &[(usize, usize)], bond_dim: usize, seed: u64) -> TensorTrain4<f64> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    let n = dims.len();
+    let tensors = dims
+        .iter()
+        .enumerate()
+        .map(|(i, &(d1, d2))| {
+            let l = if i == 0 { 1 } else { bond_dim };
+            let r = if i == n - 1 { 1 } else { bond_dim };
+            let data = (0..l * d1 * d2 * r).map(|_| rng.gen::<f64>() - 0.5).collect();
+            Tensor4::from_data(data, l, d1, d2, r)
+        })
+        .collect();
+    TensorTrain4::new(tensors).unwrap()
+}
+
+/// All multi-indices of a tensor with the given site dimensions, in row-major order
+pub(crate) fn all_indices(site_dims: &[usize]) -> Vec<Vec<usize>> {
+    let mut result = vec![vec![]];
+    for &d in site_dims {
+        result = result
+            .into_iter()
+            .flat_map(|idx| {
+                (0..d).map(move |s| {
+                    let mut next = idx.clone();
+                    next.push(s);
+                    next
+                })
+            })
+            .collect();
+    }
+    result
+}
@@ -1,6 +1,6 @@
 //! Abstract traits for tensor train objects
 
-use crate::error::Result;
+use crate::error::{Result, TensorTrainError};
 use crate::types::{LocalIndex, Tensor3};
 use num_traits::{One, Zero};
 
@@ -240,4 +240,40 @@ pub trait AbstractTensorTrain<T: TTScalar>: Sized {
 
         current[0]
     }
+
+    /// Sum over all indices with a weight vector per site
+    ///
+    /// Computes `sum_{i1..iL} w1[i1] * ... * wL[iL] * T[i1, ..., iL]`, e.g. a
+    /// quadrature rule applied to a tensor train sampled on the nodes.
+    fn weighted_sum(&self, weights: &[Vec<T>]) -> Result<T> {
+        if weights.len() != self.len() {
+            return Err(TensorTrainError::IndexLengthMismatch {
+                expected: self.len(),
+                got: weights.len(),
+            });
+        }
+        if self.is_empty() {
+            return Ok(T::zero());
+        }
+
+        let mut current = vec![T::one()];
+        for (site, w) in weights.iter().enumerate() {
+            let tensor = self.site_tensor(site);
+            if w.len() != tensor.site_dim() {
+                return Err(TensorTrainError::DimensionMismatch { site });
+            }
+            let mut next = vec![T::zero(); tensor.right_dim()];
+            for (l, &c) in current.iter().enumerate() {
+                for (s, &ws) in w.iter().enumerate() {
+                    let cw = c * ws;
+                    for (r, n) in next.iter_mut().enumerate() {
+                        *n = *n + cw * *tensor.get(l, s, r);
+                    }
+                }
+            }
+            current = next;
+        }
+
+        Ok(current[0])
+    }
 }
@@ -111,3 +111,116 @@ impl<T: Clone + Default + num_traits::Zero> Default for Tensor3<T> {
         Self::zeros(1, 1, 1)
     }
 }
+
+/// A 4D tensor represented as a flat Vec with shape information
+/// Shape is (left_dim, site_dim1, site_dim2, right_dim)
+///
+/// Used as the site tensor of a matrix product operator, where `site_dim1`
+/// is the output (row) index and `site_dim2` the input (column) index.
+#[derive(Debug, Clone)]
+pub struct Tensor4<T> {
+    data: Vec<T>,
+    left_dim: usize,
+    site_dim1: usize,
+    site_dim2: usize,
+    right_dim: usize,
+}
+
+impl<T: Clone + Default> Tensor4<T> {
+    /// Create a new tensor filled with default values
+    pub fn zeros(left_dim: usize, site_dim1: usize, site_dim2: usize, right_dim: usize) -> Self {
+        Self {
+            data: vec![T::default(); left_dim * site_dim1 * site_dim2 * right_dim],
+            left_dim,
+            site_dim1,
+            site_dim2,
+            right_dim,
+        }
+    }
+
+    /// Create from flat data with shape
+    pub fn from_data(
+        data: Vec<T>,
+        left_dim: usize,
+        site_dim1: usize,
+        site_dim2: usize,
+        right_dim: usize,
+    ) -> Self {
+        assert_eq!(data.len(), left_dim * site_dim1 * site_dim2 * right_dim);
+        Self {
+            data,
+            left_dim,
+            site_dim1,
+            site_dim2,
+            right_dim,
+        }
+    }
+
+    /// Get the left (bond) dimension
+    pub fn left_dim(&self) -> usize {
+        self.left_dim
+    }
+
+    /// Get the first (output) site dimension
+    pub fn site_dim1(&self) -> usize {
+        self.site_dim1
+    }
+
+    /// Get the second (input) site dimension
+    pub fn site_dim2(&self) -> usize {
+        self.site_dim2
+    }
+
+    /// Get the right (bond) dimension
+    pub fn right_dim(&self) -> usize {
+        self.right_dim
+    }
+
+    fn offset(&self, l: usize, s1: usize, s2: usize, r: usize) -> usize {
+        ((l * self.site_dim1 + s1) * self.site_dim2 + s2) * self.right_dim + r
+    }
+
+    /// Get element at (left, site1, site2, right)
+    pub fn get(&self, l: usize, s1: usize, s2: usize, r: usize) -> &T {
+        &self.data[self.offset(l, s1, s2, r)]
+    }
+
+    /// Get mutable element at (left, site1, site2, right)
+    pub fn get_mut(&mut self, l: usize, s1: usize, s2: usize, r: usize) -> &mut T {
+        let idx = self.offset(l, s1, s2, r);
+        &mut self.data[idx]
+    }
+
+    /// Set element at (left, site1, site2, right)
+    pub fn set(&mut self, l: usize, s1: usize, s2: usize, r: usize, value: T) {
+        let idx = self.offset(l, s1, s2, r);
+        self.data[idx] = value;
+    }
+
+    /// Fuse the two site indices into one: (left, site1 * site2, right)
+    ///
+    /// The fused index is `s1 * site_dim2 + s2`.
+    pub fn fuse_site_indices(&self) -> Tensor3<T> {
+        Tensor3::from_data(
+            self.data.clone(),
+            self.left_dim,
+            self.site_dim1 * self.site_dim2,
+            self.right_dim,
+        )
+    }
+
+    /// Split the site index of a 3D tensor into (site_dim1, site_dim2)
+    ///
+    /// Inverse of [`Tensor4::fuse_site_indices`].
+    pub fn from_fused(tensor: &Tensor3<T>, site_dim1: usize, site_dim2: usize) -> Self {
+        assert_eq!(tensor.site_dim(), site_dim1 * site_dim2);
+        let (data, _, _) = tensor.as_left_matrix();
+        Self::from_data(data, tensor.left_dim(), site_dim1, site_dim2, tensor.right_dim())
+    }
+}
+
+impl<T: Clone + Default + num_traits::Zero> Default for Tensor4<T> {
+    fn default() -> Self {
+        Self::zeros(1, 1, 1, 1)
+    }
+}
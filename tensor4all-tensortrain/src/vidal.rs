@@ -0,0 +1,501 @@
+//! Vidal (Γ-Λ) canonical form of tensor trains
+
+use crate::compression::{
+    matrix_data, svd, svd_truncation_rank, tensor3_to_left_matrix, tensor3_to_right_matrix,
+};
+use crate::error::{Result, TensorTrainError};
+use crate::site_tensor_train::SiteTensorTrain;
+use crate::tensortrain::TensorTrain;
+use crate::traits::{AbstractTensorTrain, TTScalar};
+use crate::types::Tensor3;
+use faer::traits::ComplexField;
+use tensor4all_matrixci::util::{mat_mul, ncols, nrows, zeros, Matrix, Scalar};
+
+/// Tensor train in Vidal canonical form
+///
+/// The tensor is stored as Γ1 Λ1 Γ2 Λ2 ... Λ(L-1) ΓL, where each Λi is a
+/// diagonal matrix of the Schmidt (singular) values across bond i. The Λs are
+/// not normalized: the sum of λ² on every bond is the squared norm of the
+/// tensor.
+///
+/// Λ(i-1) Γi is a left and Γi Λi a right isometry, taking the Λs outside the
+/// chain as 1. The exceptions are Γ1 Λ1 and Λ(L-1) ΓL, which carry the norm;
+/// they are isometries too only if the tensor has norm 1, in which case the
+/// Λs are the entanglement spectra.
+#[derive(Debug, Clone)]
+pub struct VidalTensorTrain<T: TTScalar> {
+    /// Γ tensors, shape (left_bond, site_dim, right_bond)
+    gammas: Vec<Tensor3<T>>,
+    /// Singular values on the L - 1 inner bonds, in descending order
+    lambdas: Vec<Vec<f64>>,
+}
+
+/// Rank kept on a bond: truncation by discarded weight, and singular values
+/// below machine precision (relative to the largest) are always dropped so
+/// that Λ can be inverted.
+fn vidal_rank(singular_values: &[f64], tolerance: f64, max_bond_dim: usize) -> usize {
+    let rank = svd_truncation_rank(singular_values, tolerance, max_bond_dim, true);
+    let cutoff = singular_values.first().copied().unwrap_or(0.0) * f64::EPSILON;
+    let nonzero = singular_values[..rank].iter().take_while(|&&s| s > cutoff).count();
+    nonzero.max(1)
+}
+
+fn inverse(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0 / x
+    } else {
+        0.0
+    }
+}
+
+/// Scale the left and right bonds of a tensor by diagonal weights
+fn scale_bonds<T: TTScalar + Scalar + Default + ComplexField>(tensor: &Tensor3<T>, left: &[f64], right: &[f64]) -> Tensor3<T> {
+    let mut result = tensor.clone();
+    for (l, &wl) in left.iter().enumerate() {
+        for s in 0..tensor.site_dim() {
+            for (r, &wr) in right.iter().enumerate() {
+                let w = T::from_f64_impl(wl * wr);
+                result.set(l, s, r, *tensor.get(l, s, r) * w);
+            }
+        }
+    }
+    result
+}
+
+impl<T: TTScalar + Scalar + Default + ComplexField> VidalTensorTrain<T> {
+    /// Convert a tensor train into Vidal form
+    ///
+    /// The train is right-canonicalized and then swept from left to right
+    /// with SVDs; each bond is truncated with `tolerance` (relative discarded
+    /// weight) and `max_bond_dim`. Use `tolerance = 0` for an exact conversion.
+    pub fn from_tensor_train(tt: &TensorTrain<T>, tolerance: f64, max_bond_dim: usize) -> Result<Self> {
+        if tt.is_empty() {
+            return Err(TensorTrainError::Empty);
+        }
+        let n = tt.len();
+        let tensors = SiteTensorTrain::new(tt.clone(), 0)?.into_tensor_train();
+
+        let mut gammas = Vec::with_capacity(n);
+        let mut lambdas: Vec<Vec<f64>> = Vec::with_capacity(n - 1);
+        let mut center = tensors.site_tensor(0).clone();
+        let mut prev_lambda = vec![1.0];
+
+        for site in 0..n - 1 {
+            let (l, s) = (center.left_dim(), center.site_dim());
+            let (u, singular_values, vt) = svd(&tensor3_to_left_matrix(&center))?;
+            let rank = vidal_rank(&singular_values, tolerance, max_bond_dim);
+
+            let mut gamma = Tensor3::zeros(l, s, rank);
+            for il in 0..l {
+                let w = T::from_f64_impl(inverse(prev_lambda[il]));
+                for is in 0..s {
+                    for m in 0..rank {
+                        gamma.set(il, is, m, u[[il * s + is, m]] * w);
+                    }
+                }
+            }
+            gammas.push(gamma);
+
+            // Absorb Λ V^H into the next tensor
+            let lambda = singular_values[..rank].to_vec();
+            let next = tensors.site_tensor(site + 1);
+            let mut sv = zeros(rank, ncols(&vt));
+            for m in 0..rank {
+                let w = T::from_f64_impl(lambda[m]);
+                for j in 0..ncols(&vt) {
+                    sv[[m, j]] = vt[[m, j]] * w;
+                }
+            }
+            let contracted = mat_mul(&sv, &tensor3_to_right_matrix(next));
+            center = Tensor3::from_data(matrix_data(&contracted), rank, next.site_dim(), next.right_dim());
+
+            prev_lambda = lambda.clone();
+            lambdas.push(lambda);
+        }
+
+        let inv_left: Vec<f64> = prev_lambda.iter().map(|&x| inverse(x)).collect();
+        gammas.push(scale_bonds(&center, &inv_left, &[1.0]));
+
+        Ok(Self { gammas, lambdas })
+    }
+
+    /// Convert back to a tensor train by absorbing each Λ into the Γ on its left
+    ///
+    /// Every Γi Λi is a right isometry, so the result is right-canonical with
+    /// the orthogonality center (and the norm) on the first site.
+    pub fn to_tensor_train(&self) -> TensorTrain<T> {
+        let n = self.len();
+        let tensors = (0..n)
+            .map(|site| {
+                let gamma = &self.gammas[site];
+                scale_bonds(gamma, &vec![1.0; gamma.left_dim()], &self.right_lambda(site))
+            })
+            .collect();
+        TensorTrain::from_tensors_unchecked(tensors)
+    }
+
+    /// Number of sites
+    pub fn len(&self) -> usize {
+        self.gammas.len()
+    }
+
+    /// Check if there are no sites
+    pub fn is_empty(&self) -> bool {
+        self.gammas.is_empty()
+    }
+
+    /// The Γ tensor at a site
+    pub fn gamma(&self, site: usize) -> &Tensor3<T> {
+        &self.gammas[site]
+    }
+
+    /// All Γ tensors
+    pub fn gammas(&self) -> &[Tensor3<T>] {
+        &self.gammas
+    }
+
+    /// Singular values on bond `bond` (between sites `bond` and `bond + 1`)
+    pub fn lambda(&self, bond: usize) -> &[f64] {
+        &self.lambdas[bond]
+    }
+
+    /// Singular values on all inner bonds
+    pub fn lambdas(&self) -> &[Vec<f64>] {
+        &self.lambdas
+    }
+
+    /// Bond dimensions along the links between tensors
+    pub fn link_dims(&self) -> Vec<usize> {
+        self.lambdas.iter().map(|l| l.len()).collect()
+    }
+
+    /// Von Neumann entanglement entropy across a bond
+    ///
+    /// S = -sum_i p_i ln p_i with p_i = λ_i^2 / sum_j λ_j^2.
+    pub fn entanglement_entropy(&self, bond: usize) -> f64 {
+        let lambda = &self.lambdas[bond];
+        let total: f64 = lambda.iter().map(|x| x * x).sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        lambda
+            .iter()
+            .map(|x| x * x / total)
+            .filter(|&p| p > 0.0)
+            .map(|p| -p * p.ln())
+            .sum()
+    }
+
+    /// Entanglement entropy across every inner bond
+    pub fn entanglement_entropies(&self) -> Vec<f64> {
+        (0..self.lambdas.len())
+            .map(|bond| self.entanglement_entropy(bond))
+            .collect()
+    }
+
+    /// Apply a gate acting on a single site
+    ///
+    /// `gate` has shape (site_dim, site_dim) with `gate[[s_new, s_old]]`.
+    /// The canonical form is preserved if the gate is unitary.
+    pub fn apply_single_site_gate(&mut self, site: usize, gate: &Matrix<T>) -> Result<()> {
+        self.check_site(site)?;
+        let gamma = &self.gammas[site];
+        let d = gamma.site_dim();
+        if nrows(gate) != d || ncols(gate) != d {
+            return Err(TensorTrainError::DimensionMismatch { site });
+        }
+
+        let mut result = Tensor3::zeros(gamma.left_dim(), d, gamma.right_dim());
+        for l in 0..gamma.left_dim() {
+            for r in 0..gamma.right_dim() {
+                for s_new in 0..d {
+                    let mut sum = T::zero();
+                    for s_old in 0..d {
+                        sum = sum + gate[[s_new, s_old]] * *gamma.get(l, s_old, r);
+                    }
+                    result.set(l, s_new, r, sum);
+                }
+            }
+        }
+        self.gammas[site] = result;
+        Ok(())
+    }
+
+    /// Apply a gate acting on sites `site` and `site + 1` (TEBD update)
+    ///
+    /// `gate` has shape (d1 * d2, d1 * d2) with row index `s1_new * d2 + s2_new`
+    /// and column index `s1_old * d2 + s2_old`. The updated bond is truncated
+    /// with `tolerance` and `max_bond_dim`. The canonical form of the other
+    /// bonds is preserved if the gate is unitary.
+    pub fn apply_two_site_gate(
+        &mut self,
+        site: usize,
+        gate: &Matrix<T>,
+        tolerance: f64,
+        max_bond_dim: usize,
+    ) -> Result<()> {
+        self.check_site(site)?;
+        self.check_site(site + 1)?;
+        let (g1, g2) = (&self.gammas[site], &self.gammas[site + 1]);
+        let (l, d1, m, d2, r) = (g1.left_dim(), g1.site_dim(), g1.right_dim(), g2.site_dim(), g2.right_dim());
+        if nrows(gate) != d1 * d2 || ncols(gate) != d1 * d2 {
+            return Err(TensorTrainError::DimensionMismatch { site });
+        }
+
+        // theta[l, s1, s2, r] = ΛL Γ1 Λ Γ2 ΛR
+        let left_lambda = self.left_lambda(site);
+        let right_lambda = self.right_lambda(site + 1);
+        let a = scale_bonds(g1, &left_lambda, &self.lambdas[site]);
+        let b = scale_bonds(g2, &vec![1.0; m], &right_lambda);
+        let theta = mat_mul(&tensor3_to_left_matrix(&a), &tensor3_to_right_matrix(&b));
+
+        // Apply the gate on the physical indices
+        let mut updated = zeros(l * d1, d2 * r);
+        for il in 0..l {
+            for ir in 0..r {
+                for s_new in 0..d1 * d2 {
+                    let mut sum = T::zero();
+                    for s_old in 0..d1 * d2 {
+                        let (o1, o2) = (s_old / d2, s_old % d2);
+                        sum = sum + gate[[s_new, s_old]] * theta[[il * d1 + o1, o2 * r + ir]];
+                    }
+                    let (n1, n2) = (s_new / d2, s_new % d2);
+                    updated[[il * d1 + n1, n2 * r + ir]] = sum;
+                }
+            }
+        }
+
+        let (u, singular_values, vt) = svd(&updated)?;
+        let rank = vidal_rank(&singular_values, tolerance, max_bond_dim);
+
+        let mut gamma1 = Tensor3::zeros(l, d1, rank);
+        for il in 0..l {
+            for s1 in 0..d1 {
+                for k in 0..rank {
+                    gamma1.set(il, s1, k, u[[il * d1 + s1, k]]);
+                }
+            }
+        }
+        let mut gamma2 = Tensor3::zeros(rank, d2, r);
+        for k in 0..rank {
+            for s2 in 0..d2 {
+                for ir in 0..r {
+                    gamma2.set(k, s2, ir, vt[[k, s2 * r + ir]]);
+                }
+            }
+        }
+
+        let inv_left: Vec<f64> = left_lambda.iter().map(|&x| inverse(x)).collect();
+        let inv_right: Vec<f64> = right_lambda.iter().map(|&x| inverse(x)).collect();
+        self.gammas[site] = scale_bonds(&gamma1, &inv_left, &vec![1.0; rank]);
+        self.gammas[site + 1] = scale_bonds(&gamma2, &vec![1.0; rank], &inv_right);
+        self.lambdas[site] = singular_values[..rank].to_vec();
+        Ok(())
+    }
+
+    fn check_site(&self, site: usize) -> Result<()> {
+        if site >= self.len() {
+            return Err(TensorTrainError::IndexOutOfBounds {
+                site,
+                index: site,
+                max: self.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Λ on the left bond of a site (trivial at the boundary)
+    fn left_lambda(&self, site: usize) -> Vec<f64> {
+        if site == 0 {
+            vec![1.0]
+        } else {
+            self.lambdas[site - 1].clone()
+        }
+    }
+
+    /// Λ on the right bond of a site (trivial at the boundary)
+    fn right_lambda(&self, site: usize) -> Vec<f64> {
+        if site + 1 == self.len() {
+            vec![1.0]
+        } else {
+            self.lambdas[site].clone()
+        }
+    }
+}
+
+impl<T: TTScalar + Scalar + Default + ComplexField> From<&VidalTensorTrain<T>> for TensorTrain<T> {
+    fn from(vtt: &VidalTensorTrain<T>) -> Self {
+        vtt.to_tensor_train()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::{CompressionMethod, CompressionOptions};
+    use crate::test_utils::{all_indices, random_tt};
+    use tensor4all_matrixci::util::eye;
+
+    #[test]
+    fn test_roundtrip() {
+        let site_dims = [2, 3, 2, 2];
+        let tt = random_tt(&site_dims, 3, 1);
+        let vtt = VidalTensorTrain::from_tensor_train(&tt, 0.0, usize::MAX).unwrap();
+        assert_eq!(vtt.len(), 4);
+        assert_eq!(vtt.lambdas().len(), 3);
+
+        let back = vtt.to_tensor_train();
+        for idx in all_indices(&site_dims) {
+            let diff = back.evaluate(&idx).unwrap() - tt.evaluate(&idx).unwrap();
+            assert!(diff.abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_to_tensor_train_is_right_canonical() {
+        let tt = random_tt(&[2, 3, 2, 2], 3, 7);
+        let vtt = VidalTensorTrain::from_tensor_train(&tt, 0.0, usize::MAX).unwrap();
+        let back = vtt.to_tensor_train();
+
+        for site in 1..back.len() {
+            let t = back.site_tensor(site);
+            let m = tensor3_to_right_matrix(t);
+            for i in 0..t.left_dim() {
+                for j in 0..t.left_dim() {
+                    let sum: f64 = (0..ncols(&m)).map(|k| m[[i, k]] * m[[j, k]]).sum();
+                    let expected = if i == j { 1.0 } else { 0.0 };
+                    assert!((sum - expected).abs() < 1e-10);
+                }
+            }
+        }
+
+        let (first, _, _) = back.site_tensor(0).as_left_matrix();
+        let norm2: f64 = first.iter().map(|x| x * x).sum();
+        let expected = tt.dot(&tt).unwrap();
+        assert!((norm2 - expected).abs() < 1e-10 * expected);
+    }
+
+    #[test]
+    fn test_lambdas_match_svd_spectra() {
+        let tt = random_tt(&[2, 2, 2, 2, 2], 3, 2);
+        let vtt = VidalTensorTrain::from_tensor_train(&tt, 0.0, usize::MAX).unwrap();
+
+        let mut compressed = tt.clone();
+        let spectra = compressed
+            .compress_with_spectra(&CompressionOptions {
+                method: CompressionMethod::SVD,
+                tolerance: 0.0,
+                ..Default::default()
+            })
+            .unwrap();
+
+        for (bond, lambda) in vtt.lambdas().iter().enumerate() {
+            for (i, &x) in lambda.iter().enumerate() {
+                assert!((x - spectra[bond][i]).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lambdas_carry_norm() {
+        let tt = random_tt(&[2, 3, 2, 2], 3, 5);
+        let norm2 = tt.dot(&tt).unwrap();
+        let vtt = VidalTensorTrain::from_tensor_train(&tt, 0.0, usize::MAX).unwrap();
+        for lambda in vtt.lambdas() {
+            let sum: f64 = lambda.iter().map(|x| x * x).sum();
+            assert!((sum - norm2).abs() < 1e-10 * norm2);
+        }
+
+        // With norm 1, Γ1 Λ1 is a right isometry
+        let normalized = tt.scaled(1.0 / norm2.sqrt());
+        let vtt = VidalTensorTrain::from_tensor_train(&normalized, 0.0, usize::MAX).unwrap();
+        let first = scale_bonds(vtt.gamma(0), &[1.0], vtt.lambda(0));
+        let (data, _, _) = first.as_left_matrix();
+        let sum: f64 = data.iter().map(|x| x * x).sum();
+        assert!((sum - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_entanglement_entropy() {
+        // Product state: zero entropy
+        let product = TensorTrain::<f64>::constant(&[2, 2, 2], 1.0);
+        let vtt = VidalTensorTrain::from_tensor_train(&product, 0.0, usize::MAX).unwrap();
+        assert_eq!(vtt.link_dims(), vec![1, 1]);
+        for s in vtt.entanglement_entropies() {
+            assert!(s.abs() < 1e-12);
+        }
+
+        // GHZ state |000> + |111>: entropy ln 2 on every bond
+        let mut t0 = Tensor3::<f64>::zeros(1, 2, 2);
+        let mut t1 = Tensor3::<f64>::zeros(2, 2, 2);
+        let mut t2 = Tensor3::<f64>::zeros(2, 2, 1);
+        for s in 0..2 {
+            t0.set(0, s, s, 1.0);
+            t1.set(s, s, s, 1.0);
+            t2.set(s, s, 0, 1.0);
+        }
+        let ghz = TensorTrain::new(vec![t0, t1, t2]).unwrap();
+        let vtt = VidalTensorTrain::from_tensor_train(&ghz, 0.0, usize::MAX).unwrap();
+        for s in vtt.entanglement_entropies() {
+            assert!((s - 2.0f64.ln()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_truncation() {
+        let tt = random_tt(&[2, 2, 2, 2, 2, 2], 4, 3);
+        let vtt = VidalTensorTrain::from_tensor_train(&tt, 0.0, 2).unwrap();
+        assert!(vtt.link_dims().iter().all(|&d| d <= 2));
+    }
+
+    #[test]
+    fn test_two_site_swap_gate() {
+        let site_dims = [2, 2, 2, 2];
+        let tt = random_tt(&site_dims, 2, 4);
+        let mut vtt = VidalTensorTrain::from_tensor_train(&tt, 0.0, usize::MAX).unwrap();
+
+        // SWAP gate on sites 1 and 2
+        let mut swap = zeros(4, 4);
+        for a in 0..2 {
+            for b in 0..2 {
+                swap[[b * 2 + a, a * 2 + b]] = 1.0;
+            }
+        }
+        vtt.apply_two_site_gate(1, &swap, 0.0, usize::MAX).unwrap();
+
+        let result = vtt.to_tensor_train();
+        for idx in all_indices(&site_dims) {
+            let swapped = vec![idx[0], idx[2], idx[1], idx[3]];
+            let diff = result.evaluate(&idx).unwrap() - tt.evaluate(&swapped).unwrap();
+            assert!(diff.abs() < 1e-10);
+        }
+
+        // Unitary gates keep the canonical form: Λ on the updated bond is the SVD spectrum
+        let recomputed = VidalTensorTrain::from_tensor_train(&result, 0.0, usize::MAX).unwrap();
+        for (x, y) in vtt.lambda(1).iter().zip(recomputed.lambda(1)) {
+            assert!((x - y).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_single_site_gate() {
+        let site_dims = [2, 3, 2];
+        let tt = random_tt(&site_dims, 2, 5);
+        let mut vtt = VidalTensorTrain::from_tensor_train(&tt, 0.0, usize::MAX).unwrap();
+
+        let identity: Matrix<f64> = eye(3);
+        vtt.apply_single_site_gate(1, &identity).unwrap();
+        let mut flip = zeros(2, 2);
+        flip[[0, 1]] = 1.0;
+        flip[[1, 0]] = 1.0;
+        vtt.apply_single_site_gate(0, &flip).unwrap();
+        assert!(vtt.apply_single_site_gate(0, &identity).is_err());
+
+        let result = vtt.to_tensor_train();
+        for idx in all_indices(&site_dims) {
+            let flipped = vec![1 - idx[0], idx[1], idx[2]];
+            let diff = result.evaluate(&idx).unwrap() - tt.evaluate(&flipped).unwrap();
+            assert!(diff.abs() < 1e-10);
+        }
+    }
+}
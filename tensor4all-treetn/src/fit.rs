@@ -0,0 +1,391 @@
+//! Variational fitting of tree tensor networks.
+//!
+//! Finds a `TreeTN` with bounded bond dimension that maximizes the overlap with a
+//! target given as a sum of networks, or as an operator network applied to a state,
+//! without forming the target explicitly (DMRG-style two-site sweeps).
+//!
+//! The fitted network is kept in canonical form with respect to a moving
+//! orthogonality center. For each term of the target, an environment tensor is kept
+//! for every directed edge `(a, b)`: the contraction of the subtree at `a` (on the far
+//! side from `b`) with the conjugate of the fitted network. In canonical form, the
+//! optimal tensor on two neighboring nodes is the contraction of the target with the
+//! environments of all other edges, which is then split by a truncated SVD.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use anyhow::{Context, Result};
+use num_complex::Complex64;
+use petgraph::stable_graph::{EdgeIndex, NodeIndex};
+use tensor4all::index::{DynId, Index, NoSymmSpace, Symmetry};
+use tensor4all::{contract_network, svd_with, Storage, SvdOptions, TensorDynLen};
+use crate::treetn::TreeTN;
+
+/// Options for [`fit_sum`] and [`fit_apply`].
+#[derive(Debug, Clone, Copy)]
+pub struct FitOptions {
+    /// Truncation applied to each bond after a two-site update.
+    /// `max_rank` is the maximum bond dimension of the result.
+    pub truncation: SvdOptions,
+    /// Number of sweeps. Each sweep updates every edge twice
+    /// (once moving away from the root and once moving back).
+    pub nsweeps: usize,
+}
+
+impl Default for FitOptions {
+    fn default() -> Self {
+        Self {
+            truncation: SvdOptions::default(),
+            nsweeps: 2,
+        }
+    }
+}
+
+impl FitOptions {
+    /// Set the maximum bond dimension of the result.
+    pub fn with_max_rank(mut self, max_rank: usize) -> Self {
+        self.truncation.max_rank = Some(max_rank);
+        self
+    }
+
+    /// Set the truncation options for the bonds.
+    pub fn with_truncation(mut self, truncation: SvdOptions) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
+    /// Set the number of sweeps.
+    pub fn with_nsweeps(mut self, nsweeps: usize) -> Self {
+        self.nsweeps = nsweeps;
+        self
+    }
+}
+
+/// One term of the fit target: the contraction of one or more networks with the
+/// same topology, together with its environments.
+struct Term<Id, Symm, V> {
+    /// Node tensors of each network (with shared bond indices), by vertex name.
+    networks: Vec<HashMap<V, TensorDynLen<Id, Symm>>>,
+    /// Environment of each directed edge `(a, b)`, by vertex names.
+    envs: HashMap<(V, V), TensorDynLen<Id, Symm>>,
+}
+
+/// Fit a TreeTN to the sum of `terms` (ITensors.jl's `add(...; alg = "fit")`).
+///
+/// Starting from `init`, which must have the same topology and site indices as the
+/// terms, the network is optimized by `options.nsweeps` two-site sweeps starting and
+/// ending at `root`. The bond dimension of the result is limited by
+/// `options.truncation`. This avoids the direct sum of [`TreeTN::add`], whose bond
+/// dimension grows with the number of terms.
+///
+/// The returned network is orthogonalized towards `root`.
+///
+/// # Errors
+/// Returns an error if:
+/// - `terms` is empty
+/// - The networks are not trees with the same vertex names and edges
+/// - `root` does not exist in the graph
+/// - The site indices of the terms don't match those of `init`
+/// - An SVD fails or a tensor storage type is not DenseF64 or DenseC64
+pub fn fit_sum<Id, Symm, V>(
+    terms: &[TreeTN<Id, Symm, V>],
+    init: TreeTN<Id, Symm, V>,
+    root: &V,
+    options: &FitOptions,
+) -> Result<TreeTN<Id, Symm, V>>
+where
+    Id: Clone + Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    for (k, term) in terms.iter().enumerate() {
+        if !init.site_index_network().is_compatible(term.site_index_network()) {
+            return Err(anyhow::anyhow!("fit_sum: site indices of term {} don't match those of init", k));
+        }
+    }
+    let products: Vec<Vec<&TreeTN<Id, Symm, V>>> = terms.iter().map(|tn| vec![tn]).collect();
+    fit_products(&products, init, root, options).context("fit_sum: fitting failed")
+}
+
+/// Fit a TreeTN to an operator network applied to a state.
+///
+/// `op` is a network with the same topology as `state`, whose node tensors carry the
+/// site indices of `state` (input) and the site indices of the result (output); the
+/// bonds of `op` and `state` must have distinct IDs. `init` must have the output site
+/// indices. See [`fit_sum`] for the sweeps and truncation.
+///
+/// # Errors
+/// Returns an error under the same conditions as [`fit_sum`], if the site indices
+/// of `init` at some vertex are not those of `op` minus those of `state`, or if a
+/// bond of `op` and a bond of `state` share an ID.
+pub fn fit_apply<Id, Symm, V>(
+    op: &TreeTN<Id, Symm, V>,
+    state: &TreeTN<Id, Symm, V>,
+    init: TreeTN<Id, Symm, V>,
+    root: &V,
+    options: &FitOptions,
+) -> Result<TreeTN<Id, Symm, V>>
+where
+    Id: Clone + Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    for name in init.site_index_network().node_names() {
+        let (Some(out_sites), Some(op_sites), Some(in_sites)) =
+            (init.site_space(name), op.site_space(name), state.site_space(name))
+        else {
+            return Err(anyhow::anyhow!("fit_apply: node {:?} is missing from op or state", name));
+        };
+        let expected: HashSet<_> = op_sites.difference(in_sites).cloned().collect();
+        if *out_sites != expected {
+            return Err(anyhow::anyhow!(
+                "fit_apply: site indices of init at node {:?} are not those of op minus those of state",
+                name
+            ));
+        }
+    }
+    let op_bonds = bond_ids(op)?;
+    if bond_ids(state)?.iter().any(|id| op_bonds.contains(id)) {
+        return Err(anyhow::anyhow!("fit_apply: op and state have bonds with the same ID"));
+    }
+    fit_products(&[vec![op, state]], init, root, options).context("fit_apply: fitting failed")
+}
+
+/// IDs of the bond indices of `tn`, on both ends of every edge.
+fn bond_ids<Id, Symm, V>(tn: &TreeTN<Id, Symm, V>) -> Result<HashSet<Id>>
+where
+    Id: Clone + Hash + Eq,
+    Symm: Clone + Symmetry,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    let mut ids = HashSet::new();
+    for node in tn.node_indices() {
+        for (edge, _) in tn.edges_for_node(node) {
+            let conn = tn.connection(edge)
+                .ok_or_else(|| anyhow::anyhow!("Connection not found"))?;
+            ids.insert(conn.index_source.id.clone());
+            ids.insert(conn.index_target.id.clone());
+        }
+    }
+    Ok(ids)
+}
+
+/// Fit `x` to the sum over `products` of the contraction of the networks in each product.
+fn fit_products<Id, Symm, V>(
+    products: &[Vec<&TreeTN<Id, Symm, V>>],
+    mut x: TreeTN<Id, Symm, V>,
+    root: &V,
+    options: &FitOptions,
+) -> Result<TreeTN<Id, Symm, V>>
+where
+    Id: Clone + Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    if products.is_empty() {
+        return Err(anyhow::anyhow!("The fit target must have at least one term"));
+    }
+    x.validate_tree().context("graph must be a tree")?;
+    let root_node = x.node_index(root)
+        .ok_or_else(|| anyhow::anyhow!("Node {:?} not found in graph", root))?;
+
+    let mut terms = Vec::with_capacity(products.len());
+    for product in products {
+        let mut networks = Vec::with_capacity(product.len());
+        for &tn in product {
+            x.check_same_topology(tn)?;
+            let mut tensors = HashMap::new();
+            for node in tn.node_indices() {
+                let name = tn.node_name(node)
+                    .ok_or_else(|| anyhow::anyhow!("Node name not found for {:?}", node))?;
+                tensors.insert(name.clone(), tn.tensor_with_shared_bonds(node)?);
+            }
+            networks.push(tensors);
+        }
+        terms.push(Term { networks, envs: HashMap::new() });
+    }
+
+    if x.node_count() == 1 {
+        let target = local_target(&x, &terms, &[root_node])?;
+        x.replace_tensor(root_node, target)?;
+        x.set_ortho_region([root.clone()])?;
+        return Ok(x);
+    }
+
+    let mut tour = Vec::new();
+    x.depth_first_tour(root_node, None, &mut tour);
+
+    // Canonicalize towards root (leaves first), which also gives the fitted network
+    // fresh bond indices, and build the environments pointing towards root.
+    for &(from, to, edge, away_from_root) in &tour {
+        if !away_from_root {
+            x.move_ortho_center(from, to, edge, None)
+                .context("failed to canonicalize towards root")?;
+            update_envs(&x, &mut terms, from, to)?;
+        }
+    }
+
+    // Each update moves the center from `from` to `to`. The environments needed by
+    // an update are those pointing towards the two nodes, which are up to date
+    // because the center has not been in their subtrees since they were computed.
+    for _ in 0..options.nsweeps {
+        for &(from, to, edge, _) in &tour {
+            two_site_update(&mut x, &terms, from, to, edge, &options.truncation)?;
+            update_envs(&x, &mut terms, from, to)?;
+        }
+    }
+
+    x.set_ortho_region([root.clone()])?;
+    Ok(x)
+}
+
+/// Names of `node` and of its neighbors outside `region`.
+fn outer_neighbors<Id, Symm, V>(
+    x: &TreeTN<Id, Symm, V>,
+    node: NodeIndex,
+    region: &[NodeIndex],
+) -> Result<(V, Vec<V>)>
+where
+    Id: Clone + Hash + Eq,
+    Symm: Clone + Symmetry,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    let name = |n: NodeIndex| {
+        x.node_name(n)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Node name not found for {:?}", n))
+    };
+    let neighbors = x.edges_for_node(node)
+        .into_iter()
+        .filter(|(_, nb)| !region.contains(nb))
+        .map(|(_, nb)| name(nb))
+        .collect::<Result<Vec<_>>>()?;
+    Ok((name(node)?, neighbors))
+}
+
+/// Contract the target with the environments of all edges pointing into `region`.
+///
+/// The result has the indices of the fitted network on `region`, except for the
+/// bonds inside `region`.
+fn local_target<Id, Symm, V>(
+    x: &TreeTN<Id, Symm, V>,
+    terms: &[Term<Id, Symm, V>],
+    region: &[NodeIndex],
+) -> Result<TensorDynLen<Id, Symm>>
+where
+    Id: Clone + Hash + Eq,
+    Symm: Clone + Symmetry,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    let mut target: Option<TensorDynLen<Id, Symm>> = None;
+    for term in terms {
+        let mut tensors = Vec::new();
+        for &node in region {
+            let (name, neighbors) = outer_neighbors(x, node, region)?;
+            for network in &term.networks {
+                tensors.push(network[&name].clone());
+            }
+            for neighbor in neighbors {
+                tensors.push(term.envs[&(neighbor, name.clone())].clone());
+            }
+        }
+        let contracted = contract_network(&tensors)?;
+        target = Some(match target {
+            None => contracted,
+            Some(sum) => sum.add(&contracted)
+                .context("terms of the fit target have different site indices")?,
+        });
+    }
+    target.ok_or_else(|| anyhow::anyhow!("The fit target must have at least one term"))
+}
+
+/// Recompute the environments of the edge `(from, to)` after the tensor at `from` changed.
+fn update_envs<Id, Symm, V>(
+    x: &TreeTN<Id, Symm, V>,
+    terms: &mut [Term<Id, Symm, V>],
+    from: NodeIndex,
+    to: NodeIndex,
+) -> Result<()>
+where
+    Id: Clone + Hash + Eq,
+    Symm: Clone + Symmetry,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    let (name, neighbors) = outer_neighbors(x, from, &[from, to])?;
+    let to_name = x.node_name(to)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Node name not found for {:?}", to))?;
+    let x_dag = x.tensor_with_shared_bonds(from)?.dag();
+
+    for term in terms.iter_mut() {
+        let mut tensors = vec![x_dag.clone()];
+        for network in &term.networks {
+            tensors.push(network[&name].clone());
+        }
+        for neighbor in &neighbors {
+            tensors.push(term.envs[&(neighbor.clone(), name.clone())].clone());
+        }
+        let env = contract_network(&tensors)?;
+        term.envs.insert((name.clone(), to_name.clone()), env);
+    }
+    Ok(())
+}
+
+/// Optimize the tensors on `from` and `to` and move the orthogonality center to `to`.
+fn two_site_update<Id, Symm, V>(
+    x: &mut TreeTN<Id, Symm, V>,
+    terms: &[Term<Id, Symm, V>],
+    from: NodeIndex,
+    to: NodeIndex,
+    edge: EdgeIndex,
+    truncation: &SvdOptions,
+) -> Result<()>
+where
+    Id: Clone + Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    let target = local_target(x, terms, &[from, to])?;
+
+    let tensor_from = x.tensor(from)
+        .ok_or_else(|| anyhow::anyhow!("Tensor not found for node {:?}", from))?;
+    let left_inds: Vec<Index<Id, Symm>> = target.indices
+        .iter()
+        .filter(|idx| tensor_from.indices.iter().any(|i| i.id == idx.id))
+        .cloned()
+        .collect();
+    if left_inds.is_empty() || left_inds.len() == target.indices.len() {
+        return Err(anyhow::anyhow!(
+            "Cannot split the two-site tensor on nodes {:?} and {:?}: each node needs at least one index besides their bond",
+            from,
+            to
+        ));
+    }
+
+    let (u, _, _) = match target.storage.as_ref() {
+        Storage::DenseF64(_) => svd_with::<Id, Symm, f64>(&target, &left_inds, truncation)
+            .map_err(|e| anyhow::anyhow!("SVD failed: {}", e))?,
+        Storage::DenseC64(_) => svd_with::<Id, Symm, Complex64>(&target, &left_inds, truncation)
+            .map_err(|e| anyhow::anyhow!("SVD failed: {}", e))?,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unsupported storage type for SVD (only DenseF64 and DenseC64 are supported)"
+            ));
+        }
+    };
+    // U^† T = S V^† carries the new bond and the indices of `to`
+    let remainder = u.conj().contract(&target);
+    let new_bond_index = u.indices
+        .last()
+        .ok_or_else(|| anyhow::anyhow!("U has no indices"))?
+        .clone();
+
+    x.replace_edge_bond(edge, new_bond_index.clone(), new_bond_index.clone())?;
+    x.replace_tensor(from, u)?;
+    x.replace_tensor(to, remainder)?;
+    x.set_edge_ortho_towards(edge, Some(new_bond_index))?;
+    let to_name = x.node_name(to)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Node name not found for {:?}", to))?;
+    x.set_ortho_region([to_name])?;
+    Ok(())
+}
@@ -1,10 +1,16 @@
 pub mod connection;
+pub mod fit;
 pub mod named_graph;
+pub mod operator;
 pub mod site_index_network;
+pub mod tci;
 pub mod treetn;
 
 pub use connection::Connection;
+pub use fit::{fit_apply, fit_sum, FitOptions};
 pub use named_graph::NamedGraph;
+pub use operator::{apply, apply_fit, OperatorTerm, TreeTNO};
 pub use site_index_network::SiteIndexNetwork;
+pub use tci::{crossinterpolate_tree, TreeTCIOptions};
 pub use treetn::{TreeTN, TreeTopology, decompose_tensor_to_treetn};
 
@@ -0,0 +1,429 @@
+//! Tree tensor network operators (TTNO).
+//!
+//! A [`TreeTNO`] is a `TreeTN` whose nodes carry pairs of site indices: an input
+//! index, shared with the states the operator acts on, and an output index. Operators
+//! are built from sums of products of local operators ([`OperatorTerm`]) on a
+//! [`SiteIndexNetwork`], and applied to states with [`apply`] or [`apply_fit`], which
+//! return states on the same site indices as the input.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
+use anyhow::{Context, Result};
+use petgraph::stable_graph::NodeIndex;
+use tensor4all::index::{DynId, Index, NoSymmSpace, Symmetry};
+use tensor4all::index_ops::sim;
+use tensor4all::{contract_network, AnyScalar, Storage, StorageScalar, SvdOptions, TensorDynLen};
+use crate::fit::{fit_apply, FitOptions};
+use crate::site_index_network::SiteIndexNetwork;
+use crate::treetn::TreeTN;
+
+/// Number of terms [`TreeTNO::from_terms`] adds before compressing the partial sum.
+const TERMS_PER_TRUNCATION: usize = 16;
+
+/// One term of an operator: a coefficient times a product of local operators.
+///
+/// Each factor acts on one site index. Its matrix is dense with shape
+/// `(dim, dim)` in row-major order, indexed as `matrix[output * dim + input]`.
+/// Sites without a factor are acted on by the identity.
+#[derive(Debug, Clone)]
+pub struct OperatorTerm<Id, Symm = NoSymmSpace>
+where
+    Id: Clone + Hash + Eq,
+    Symm: Clone + Symmetry,
+{
+    /// Coefficient of the term.
+    pub coefficient: AnyScalar,
+    /// Local operators as `(site index, matrix)` pairs.
+    pub factors: Vec<(Index<Id, Symm>, Arc<Storage>)>,
+}
+
+impl<Id, Symm> OperatorTerm<Id, Symm>
+where
+    Id: Clone + Hash + Eq,
+    Symm: Clone + Symmetry,
+{
+    /// Create a term with the given coefficient and no factors (a multiple of the identity).
+    pub fn new(coefficient: impl Into<AnyScalar>) -> Self {
+        Self {
+            coefficient: coefficient.into(),
+            factors: Vec::new(),
+        }
+    }
+
+    /// Add a local operator acting on `site`.
+    pub fn with_factor(mut self, site: Index<Id, Symm>, matrix: Arc<Storage>) -> Self {
+        self.factors.push((site, matrix));
+        self
+    }
+}
+
+/// Tree tensor network operator.
+///
+/// Wraps a `TreeTN` whose site space at each node consists of pairs of input and
+/// output indices. The input indices are the site indices of the states the operator
+/// acts on; the output indices are created with the operator.
+#[derive(Clone)]
+pub struct TreeTNO<Id, Symm = NoSymmSpace, V = NodeIndex>
+where
+    Id: Clone + Hash + Eq,
+    Symm: Clone + Symmetry,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    /// The operator network, with both input and output indices as site indices.
+    network: TreeTN<Id, Symm, V>,
+    /// `(input, output)` site index pairs, by vertex name.
+    site_pairs: HashMap<V, Vec<(Index<Id, Symm>, Index<Id, Symm>)>>,
+}
+
+impl<Id, Symm, V> TreeTNO<Id, Symm, V>
+where
+    Id: Clone + Hash + Eq,
+    Symm: Clone + Symmetry,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    /// Build an operator from a sum of terms on the sites of `sites`.
+    ///
+    /// Each term is a product operator with bond dimension 1. The terms are summed
+    /// with [`TreeTN::add`] and the sum is compressed with [`TreeTN::truncate`] using
+    /// `options` after every 16 terms and at the end, so the bond dimension of the
+    /// operator is the rank of the sum rather than the number of terms.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - `sites` or `terms` is empty, or `sites` is not a tree
+    /// - A factor acts on an index that is not a site of `sites`, or two factors of
+    ///   the same term act on the same site
+    /// - A factor matrix is not dense or has the wrong size
+    pub fn from_terms(
+        sites: &SiteIndexNetwork<V, Id, Symm>,
+        terms: &[OperatorTerm<Id, Symm>],
+        options: &SvdOptions,
+    ) -> Result<Self>
+    where
+        Id: Ord + From<DynId>,
+        Symm: From<NoSymmSpace>,
+        V: Ord,
+    {
+        let names: Vec<V> = sites.node_names().into_iter().cloned().collect();
+        let root = names.first()
+            .ok_or_else(|| anyhow::anyhow!("Cannot build an operator on an empty site index network"))?
+            .clone();
+        if terms.is_empty() {
+            return Err(anyhow::anyhow!("An operator must have at least one term"));
+        }
+
+        let mut site_pairs: HashMap<V, Vec<(Index<Id, Symm>, Index<Id, Symm>)>> = HashMap::new();
+        for name in &names {
+            let pairs = sites.site_space(name)
+                .map(|space| space.iter().map(|s| (s.clone(), sim(s))).collect())
+                .unwrap_or_default();
+            site_pairs.insert(name.clone(), pairs);
+        }
+
+        let mut edges = Vec::with_capacity(sites.edge_count());
+        for edge in sites.graph().edge_indices() {
+            let (a, b) = sites.graph().edge_endpoints(edge)
+                .ok_or_else(|| anyhow::anyhow!("Edge has no endpoints"))?;
+            let name = |n: NodeIndex| {
+                sites.node_name(n)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("Node name not found for {:?}", n))
+            };
+            edges.push((name(a)?, name(b)?));
+        }
+
+        let mut network: Option<TreeTN<Id, Symm, V>> = None;
+        let mut added_since_truncation = 0;
+        for (k, term) in terms.iter().enumerate() {
+            let product = product_operator(&names, &edges, &site_pairs, term)
+                .with_context(|| format!("from_terms: invalid term {}", k))?;
+            let mut sum = match network {
+                None => product,
+                Some(sum) => sum.add(product).context("from_terms: failed to add terms")?,
+            };
+            added_since_truncation += 1;
+            if added_since_truncation == TERMS_PER_TRUNCATION && k + 1 < terms.len() {
+                sum = sum.truncate(&root, options).context("from_terms: failed to compress the operator")?;
+                added_since_truncation = 0;
+            }
+            network = Some(sum);
+        }
+        let network = network
+            .ok_or_else(|| anyhow::anyhow!("An operator must have at least one term"))?
+            .truncate(&root, options)
+            .context("from_terms: failed to compress the operator")?;
+
+        Ok(Self { network, site_pairs })
+    }
+
+    /// Get the operator network.
+    ///
+    /// Its site indices are the input and output indices of all nodes.
+    pub fn as_treetn(&self) -> &TreeTN<Id, Symm, V> {
+        &self.network
+    }
+
+    /// Get the `(input, output)` site index pairs of a node.
+    pub fn site_pairs(&self, node_name: &V) -> Option<&[(Index<Id, Symm>, Index<Id, Symm>)]> {
+        self.site_pairs.get(node_name).map(|pairs| pairs.as_slice())
+    }
+
+    /// Get the output index paired with the input site index `input`.
+    pub fn output_index(&self, input: &Index<Id, Symm>) -> Option<&Index<Id, Symm>> {
+        self.site_pairs.values()
+            .flatten()
+            .find(|(i, _)| i.id == input.id)
+            .map(|(_, o)| o)
+    }
+
+    /// `(input, output)` pairs of all nodes.
+    fn all_pairs(&self) -> Vec<(Index<Id, Symm>, Index<Id, Symm>)> {
+        self.site_pairs.values().flatten().cloned().collect()
+    }
+
+    /// Check that the input indices of the operator are the site indices of `state`.
+    fn check_inputs(&self, state: &TreeTN<Id, Symm, V>) -> Result<()> {
+        self.network.check_same_topology(state)?;
+        for (name, pairs) in &self.site_pairs {
+            let inputs: HashSet<&Index<Id, Symm>> = pairs.iter().map(|(i, _)| i).collect();
+            let state_sites: HashSet<&Index<Id, Symm>> = state.site_space(name)
+                .map(|space| space.iter().collect())
+                .unwrap_or_default();
+            if inputs != state_sites {
+                return Err(anyhow::anyhow!(
+                    "Site indices of the state at node {:?} don't match the operator inputs",
+                    name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Build the product operator of one term, with bond dimension 1.
+fn product_operator<Id, Symm, V>(
+    names: &[V],
+    edges: &[(V, V)],
+    site_pairs: &HashMap<V, Vec<(Index<Id, Symm>, Index<Id, Symm>)>>,
+    term: &OperatorTerm<Id, Symm>,
+) -> Result<TreeTN<Id, Symm, V>>
+where
+    Id: Clone + Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    let mut factors: HashMap<Id, &Arc<Storage>> = HashMap::new();
+    for (site, matrix) in &term.factors {
+        if !site_pairs.values().flatten().any(|(i, _)| i.id == site.id) {
+            return Err(anyhow::anyhow!("Factor acts on an index that is not a site index"));
+        }
+        if !matches!(matrix.as_ref(), Storage::DenseF64(_) | Storage::DenseC64(_)) {
+            return Err(anyhow::anyhow!("Factor matrices must be DenseF64 or DenseC64"));
+        }
+        if matrix.len() != site.size() * site.size() {
+            return Err(anyhow::anyhow!(
+                "Factor matrix has {} elements, expected {}",
+                matrix.len(),
+                site.size() * site.size()
+            ));
+        }
+        if factors.insert(site.id.clone(), matrix).is_some() {
+            return Err(anyhow::anyhow!("Two factors act on the same site"));
+        }
+    }
+
+    let bonds: Vec<Index<Id, Symm>> = edges.iter()
+        .map(|_| Index::new_link(1).map_err(|e| anyhow::anyhow!("Failed to create bond index: {:?}", e)))
+        .collect::<Result<_>>()?;
+
+    let mut tn = TreeTN::new();
+    let mut nodes = HashMap::new();
+    for (k, name) in names.iter().enumerate() {
+        let mut tensors = Vec::new();
+        for (input, output) in &site_pairs[name] {
+            let d = input.size();
+            let matrix = match factors.get(&input.id) {
+                Some(&matrix) => matrix.clone(),
+                None => f64::dense_storage((0..d * d).map(|x| if x % (d + 1) == 0 { 1.0 } else { 0.0 }).collect()),
+            };
+            tensors.push(TensorDynLen::from_indices(vec![output.clone(), input.clone()], matrix));
+        }
+        for ((a, b), bond) in edges.iter().zip(&bonds) {
+            if a == name || b == name {
+                tensors.push(TensorDynLen::from_indices(vec![bond.clone()], f64::dense_storage(vec![1.0])));
+            }
+        }
+        let mut tensor = contract_network(&tensors)?;
+        if k == 0 {
+            tensor = &tensor * term.coefficient.clone();
+        }
+        nodes.insert(name.clone(), tn.add_tensor_with_vertex(name.clone(), tensor)?);
+    }
+    for ((a, b), bond) in edges.iter().zip(&bonds) {
+        tn.connect(nodes[a], bond, nodes[b], bond)?;
+    }
+    Ok(tn)
+}
+
+/// Apply an operator to a state, `op |state⟩`, and compress the result.
+///
+/// The operator and the state are contracted node by node, fusing their bonds, which
+/// gives a network whose bond dimensions are the products of those of `op` and
+/// `state`. This network is then truncated with `options`, orthogonalizing it towards
+/// `root`. The result has the site indices of `state`.
+///
+/// For large bond dimensions, [`apply_fit`] avoids forming the product network.
+///
+/// # Errors
+/// Returns an error if:
+/// - `op` and `state` don't have the same vertex names and edges
+/// - The site indices of `state` are not the input indices of `op`
+/// - `root` does not exist in the graph
+/// - A tensor storage type is not DenseF64 or DenseC64
+pub fn apply<Id, Symm, V>(
+    op: &TreeTNO<Id, Symm, V>,
+    state: &TreeTN<Id, Symm, V>,
+    root: &V,
+    options: &SvdOptions,
+) -> Result<TreeTN<Id, Symm, V>>
+where
+    Id: Clone + Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    op.check_inputs(state).context("apply: operator and state are not compatible")?;
+    let network = &op.network;
+
+    // Give the bonds of `state` fresh IDs, so they can't be confused with bonds of `op`
+    let mut state_bonds: HashMap<Id, Index<Id, Symm>> = HashMap::new();
+    for node in state.node_indices() {
+        for (edge, _) in state.edges_for_node(node) {
+            let conn = state.connection(edge)
+                .ok_or_else(|| anyhow::anyhow!("Connection not found"))?;
+            state_bonds.entry(conn.index_source.id.clone())
+                .or_insert_with(|| sim(&conn.index_source));
+        }
+    }
+    let outputs: HashMap<Id, Index<Id, Symm>> = op.all_pairs()
+        .into_iter()
+        .map(|(input, output)| (output.id, input))
+        .collect();
+
+    let mut result = TreeTN::new();
+    let mut nodes = HashMap::new();
+    let mut fused_bonds = HashMap::new();
+    for node in network.node_indices() {
+        let name = network.node_name(node)
+            .ok_or_else(|| anyhow::anyhow!("Node name not found for {:?}", node))?;
+        let state_node = state.node_index(name)
+            .ok_or_else(|| anyhow::anyhow!("Node {:?} not found in state", name))?;
+
+        let mut tensor_state = state.tensor_with_shared_bonds(state_node)?;
+        for idx in tensor_state.indices.iter_mut() {
+            if let Some(renamed) = state_bonds.get(&idx.id) {
+                *idx = renamed.clone();
+            }
+        }
+        let tensor = contract_network(&[network.tensor_with_shared_bonds(node)?, tensor_state])
+            .with_context(|| format!("apply: failed to contract node {:?}", name))?;
+
+        // Fuse the bonds of the operator and the state on each edge into a single bond
+        let mut bond_pairs = Vec::new();
+        for (edge, neighbor) in network.edges_for_node(node) {
+            let neighbor_name = network.node_name(neighbor)
+                .ok_or_else(|| anyhow::anyhow!("Node name not found for {:?}", neighbor))?;
+            let op_bond = network.connection(edge)
+                .ok_or_else(|| anyhow::anyhow!("Connection not found"))?
+                .index_source
+                .clone();
+            let state_edge = state.edges_for_node(state_node)
+                .into_iter()
+                .find(|&(_, nb)| state.node_name(nb) == Some(neighbor_name))
+                .map(|(e, _)| e)
+                .ok_or_else(|| anyhow::anyhow!("Edge to {:?} not found in state", neighbor_name))?;
+            let state_bond = state_bonds[&state.connection(state_edge)
+                .ok_or_else(|| anyhow::anyhow!("Connection not found"))?
+                .index_source
+                .id]
+                .clone();
+            if !fused_bonds.contains_key(&edge) {
+                let fused = Index::new_link(op_bond.size() * state_bond.size())
+                    .map_err(|e| anyhow::anyhow!("Failed to create bond index: {:?}", e))?;
+                fused_bonds.insert(edge, (node, neighbor, fused));
+            }
+            bond_pairs.push((op_bond, state_bond, fused_bonds[&edge].2.clone()));
+        }
+        let mut tensor = fuse_bonds(&tensor, &bond_pairs);
+
+        // The output indices of the operator become the site indices of the result
+        for idx in tensor.indices.iter_mut() {
+            if let Some(input) = outputs.get(&idx.id) {
+                *idx = input.clone();
+            }
+        }
+        nodes.insert(node, result.add_tensor_with_vertex(name.clone(), tensor)?);
+    }
+
+    for (a, b, fused) in fused_bonds.values() {
+        result.connect(nodes[a], fused, nodes[b], fused)?;
+    }
+
+    result.truncate(root, options).context("apply: failed to truncate the result")
+}
+
+/// Apply an operator to a state by variational fitting.
+///
+/// Uses [`fit_apply`] starting from a copy of `state`, so the product network of
+/// [`apply`] is never formed. The bond dimension of the result is limited by
+/// `options.truncation`. The result has the site indices of `state` and is
+/// orthogonalized towards `root`.
+///
+/// # Errors
+/// Returns an error under the same conditions as [`apply`] and [`fit_apply`].
+pub fn apply_fit<Id, Symm, V>(
+    op: &TreeTNO<Id, Symm, V>,
+    state: &TreeTN<Id, Symm, V>,
+    root: &V,
+    options: &FitOptions,
+) -> Result<TreeTN<Id, Symm, V>>
+where
+    Id: Clone + Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    op.check_inputs(state).context("apply_fit: operator and state are not compatible")?;
+    let pairs = op.all_pairs();
+
+    let mut init = state.clone();
+    init.replace_site_indices(&pairs)?;
+    let mut result = fit_apply(&op.network, state, init, root, options)?;
+
+    let reversed: Vec<_> = pairs.into_iter().map(|(input, output)| (output, input)).collect();
+    result.replace_site_indices(&reversed)?;
+    Ok(result)
+}
+
+/// Replace each pair of bond indices `(a, b)` of `tensor` by a single index `ab`.
+///
+/// With row-major storage, adjacent indices `a, b` fuse into `ab = a * dim(b) + b`.
+fn fuse_bonds<Id, Symm>(
+    tensor: &TensorDynLen<Id, Symm>,
+    bond_pairs: &[(Index<Id, Symm>, Index<Id, Symm>, Index<Id, Symm>)],
+) -> TensorDynLen<Id, Symm>
+where
+    Id: Clone + Hash + Eq,
+    Symm: Clone + Symmetry,
+{
+    let is_bond = |idx: &Index<Id, Symm>| bond_pairs.iter().any(|(a, b, _)| a.id == idx.id || b.id == idx.id);
+    let mut order: Vec<Index<Id, Symm>> = tensor.indices.iter().filter(|idx| !is_bond(idx)).cloned().collect();
+    let mut fused_indices = order.clone();
+    for (a, b, ab) in bond_pairs {
+        order.push(a.clone());
+        order.push(b.clone());
+        fused_indices.push(ab.clone());
+    }
+    let permuted = tensor.permute_indices(&order);
+    let dims = fused_indices.iter().map(|idx| idx.size()).collect();
+    TensorDynLen::new(fused_indices, dims, permuted.storage)
+}
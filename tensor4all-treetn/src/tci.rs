@@ -0,0 +1,462 @@
+//! Tree tensor cross interpolation (tree TCI).
+//!
+//! Builds a `TreeTN` approximation of a function of many discrete variables by
+//! sampling it, generalizing TCI2 from tensor trains to tree topologies.
+//!
+//! Removing an edge `(a, b)` splits the tree into two subtrees. The edge carries two
+//! pivot sets of equal size: partial multi-indices on the sites of `a`'s subtree and
+//! on the sites of `b`'s subtree. An edge is updated by sampling the function on the
+//! rows obtained by combining the local indices of `a` with the pivots coming into
+//! `a` from its other neighbors, the columns obtained likewise from `b`, and picking
+//! new pivots by a rank-revealing LU decomposition of this matrix.
+//!
+//! The network is assembled in the `T P^{-1}` form: the tensor of a node is the
+//! function evaluated with its neighbors' subtrees fixed to the incoming pivots, and
+//! the inverse of the pivot matrix of each edge is absorbed into the node away from
+//! the root.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use anyhow::{Context, Result};
+use tensor4all::index::{DynId, Index, NoSymmSpace, Symmetry};
+use tensor4all::{StorageScalar, TensorDynLen};
+use tensor4all_matrixci::util::{a_times_b_inv, Matrix, Scalar};
+use tensor4all_matrixci::{AbstractMatrixCI, MatrixLUCI, RrLUOptions};
+use crate::treetn::{TreeTN, TreeTopology};
+
+/// Options for [`crossinterpolate_tree`].
+#[derive(Debug, Clone)]
+pub struct TreeTCIOptions {
+    /// Tolerance for convergence (relative, see `normalize_error`)
+    pub tolerance: f64,
+    /// Maximum number of iterations (sweeps over all edges)
+    pub max_iter: usize,
+    /// Maximum bond dimension
+    pub max_bond_dim: usize,
+    /// Whether to normalize error by max sample value
+    pub normalize_error: bool,
+    /// Verbosity level
+    pub verbosity: usize,
+}
+
+impl Default for TreeTCIOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-8,
+            max_iter: 20,
+            max_bond_dim: 50,
+            normalize_error: true,
+            verbosity: 0,
+        }
+    }
+}
+
+/// Cross interpolate a function into a TreeTN.
+///
+/// # Arguments
+/// * `f` - Function to interpolate, takes a multi-index (one value per site) and returns a value
+/// * `sites` - Site indices; `sites[p]` is the index of argument `p` of `f`
+/// * `topology` - Tree topology; the positions of each node refer to `sites`, and
+///   each node must have at least one site. The network is rooted at the first node
+///   of `topology.edges[0]`
+/// * `initial_pivots` - Initial pivot points (default: the all-zero multi-index)
+/// * `options` - Algorithm options
+///
+/// # Returns
+/// * `TreeTN` - The interpolating network, with node names from `topology`
+/// * `Vec<usize>` - Maximum bond dimension at each iteration
+/// * `Vec<f64>` - Errors at each iteration
+///
+/// # Errors
+/// Returns an error if:
+/// - The topology is not a tree, a node has no sites, or the positions don't cover
+///   each site exactly once
+/// - The function vanishes on all initial pivots
+pub fn crossinterpolate_tree<T, F, Id, Symm, V>(
+    f: F,
+    sites: &[Index<Id, Symm>],
+    topology: &TreeTopology<V>,
+    initial_pivots: Vec<Vec<usize>>,
+    options: &TreeTCIOptions,
+) -> Result<(TreeTN<Id, Symm, V>, Vec<usize>, Vec<f64>)>
+where
+    T: Scalar + StorageScalar,
+    F: Fn(&[usize]) -> T,
+    Id: Clone + Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    V: Clone + Hash + Eq + Send + Sync + std::fmt::Debug,
+{
+    topology.validate()?;
+    // Number the vertices in order of appearance in `topology.edges`, so that the root
+    // (vertex 0) doesn't depend on the iteration order of `topology.nodes`
+    let mut names: Vec<V> = Vec::with_capacity(topology.nodes.len());
+    let mut vertex: HashMap<&V, usize> = HashMap::new();
+    for name in topology.edges.iter().flat_map(|(a, b)| [a, b]).chain(topology.nodes.keys()) {
+        if !vertex.contains_key(name) {
+            vertex.insert(name, names.len());
+            names.push(name.clone());
+        }
+    }
+
+    let mut positions = vec![Vec::new(); names.len()];
+    let mut seen = vec![false; sites.len()];
+    for (v, name) in names.iter().enumerate() {
+        if topology.nodes[name].is_empty() {
+            return Err(anyhow::anyhow!("Node {:?} has no sites", name));
+        }
+        for &p in &topology.nodes[name] {
+            if p >= sites.len() || seen[p] {
+                return Err(anyhow::anyhow!(
+                    "Node {:?} refers to site {} which is out of range or already assigned",
+                    name,
+                    p
+                ));
+            }
+            seen[p] = true;
+            positions[v].push(p);
+        }
+    }
+    if let Some(p) = seen.iter().position(|&s| !s) {
+        return Err(anyhow::anyhow!("Site {} is not assigned to any node", p));
+    }
+
+    let edges: Vec<(usize, usize)> = topology.edges.iter()
+        .map(|(a, b)| (vertex[a], vertex[b]))
+        .collect();
+    let local_dims: Vec<usize> = sites.iter().map(|s| s.size()).collect();
+    let mut tci = TreeTCI::new(positions, local_dims, &edges)?;
+
+    let pivots = if initial_pivots.is_empty() {
+        vec![vec![0; sites.len()]]
+    } else {
+        initial_pivots
+    };
+    tci.add_pivots(&f, &pivots)?;
+    if tci.max_sample_value < 1e-30 {
+        return Err(anyhow::anyhow!("Initial pivots have zero function values"));
+    }
+
+    let (ranks, errors) = tci.optimize(&f, options);
+    let tensors = tci.node_tensors(&f)?;
+
+    // Assemble the network: one bond index per edge, shared by both nodes
+    let bonds: Vec<Index<Id, Symm>> = edges.iter()
+        .map(|&(a, b)| {
+            Index::new_link(tci.pivots[&(a, b)].len())
+                .map_err(|e| anyhow::anyhow!("Failed to create bond index: {:?}", e))
+        })
+        .collect::<Result<_>>()?;
+    let mut tn = TreeTN::new();
+    let mut nodes = Vec::with_capacity(names.len());
+    for (v, (data, neighbors)) in tensors.into_iter().enumerate() {
+        let mut indices: Vec<Index<Id, Symm>> = tci.positions[v].iter().map(|&p| sites[p].clone()).collect();
+        for c in neighbors {
+            let e = edges.iter().position(|&(a, b)| (a, b) == (v, c) || (a, b) == (c, v))
+                .ok_or_else(|| anyhow::anyhow!("Edge not found"))?;
+            indices.push(bonds[e].clone());
+        }
+        let tensor = TensorDynLen::from_indices(indices, T::dense_storage(data));
+        nodes.push(tn.add_tensor_with_vertex(names[v].clone(), tensor)?);
+    }
+    for (&(a, b), bond) in edges.iter().zip(&bonds) {
+        tn.connect(nodes[a], bond, nodes[b], bond)
+            .context("crossinterpolate_tree: failed to connect nodes")?;
+    }
+    Ok((tn, ranks, errors))
+}
+
+/// State of a tree TCI, with vertices numbered `0..n`.
+struct TreeTCI<T> {
+    /// Site positions of each vertex
+    positions: Vec<Vec<usize>>,
+    /// Dimension of each site
+    local_dims: Vec<usize>,
+    /// Neighbors of each vertex
+    neighbors: Vec<Vec<usize>>,
+    /// Site positions on `a`'s side of each directed edge `(a, b)`
+    sides: HashMap<(usize, usize), Vec<usize>>,
+    /// Pivots on `a`'s side of each directed edge `(a, b)`, as full-length multi-indices
+    /// whose entries outside `sides[&(a, b)]` are ignored
+    pivots: HashMap<(usize, usize), Vec<Vec<usize>>>,
+    /// Last pivot error of each edge `(a, b)` with `a < b`
+    bond_errors: HashMap<(usize, usize), f64>,
+    /// Largest absolute value sampled so far
+    max_sample_value: f64,
+    /// Function values sampled so far
+    cache: HashMap<Vec<usize>, T>,
+}
+
+impl<T: Scalar> TreeTCI<T> {
+    fn new(positions: Vec<Vec<usize>>, local_dims: Vec<usize>, edges: &[(usize, usize)]) -> Result<Self> {
+        let mut neighbors = vec![Vec::new(); positions.len()];
+        for &(a, b) in edges {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+        // With n - 1 edges, a connected graph is a tree
+        let mut visited = vec![false; positions.len()];
+        let mut stack = vec![0];
+        while let Some(v) = stack.pop() {
+            if !std::mem::replace(&mut visited[v], true) {
+                stack.extend(&neighbors[v]);
+            }
+        }
+        if visited.contains(&false) {
+            return Err(anyhow::anyhow!("Topology is not connected"));
+        }
+
+        let mut tci = Self {
+            positions,
+            local_dims,
+            neighbors,
+            sides: HashMap::new(),
+            pivots: HashMap::new(),
+            bond_errors: HashMap::new(),
+            max_sample_value: 0.0,
+            cache: HashMap::new(),
+        };
+        for &(a, b) in edges {
+            for (from, to) in [(a, b), (b, a)] {
+                let side = tci.side_positions(from, to);
+                tci.sides.insert((from, to), side);
+            }
+        }
+        Ok(tci)
+    }
+
+    /// Site positions of the subtree at `a` when the edge to `b` is removed.
+    fn side_positions(&self, a: usize, b: usize) -> Vec<usize> {
+        let mut result = Vec::new();
+        let mut stack = vec![(a, b)];
+        while let Some((v, parent)) = stack.pop() {
+            result.extend(&self.positions[v]);
+            stack.extend(self.neighbors[v].iter().filter(|&&c| c != parent).map(|&c| (c, v)));
+        }
+        result
+    }
+
+    fn evaluate<F: Fn(&[usize]) -> T>(&mut self, f: &F, index: &[usize]) -> T {
+        if let Some(&value) = self.cache.get(index) {
+            return value;
+        }
+        let value = f(index);
+        self.max_sample_value = self.max_sample_value.max(value.abs_sq().sqrt());
+        self.cache.insert(index.to_vec(), value);
+        value
+    }
+
+    /// Use the restrictions of `pivots` as initial pivots of every edge.
+    ///
+    /// Both sides of an edge get the same pivots, so that the pivot sets have equal
+    /// sizes. A pivot is skipped on an edge if its restriction to either side
+    /// duplicates one already taken.
+    fn add_pivots<F: Fn(&[usize]) -> T>(&mut self, f: &F, pivots: &[Vec<usize>]) -> Result<()> {
+        for pivot in pivots {
+            if pivot.len() != self.local_dims.len() || pivot.iter().zip(&self.local_dims).any(|(&i, &d)| i >= d) {
+                return Err(anyhow::anyhow!("Invalid initial pivot {:?}", pivot));
+            }
+            self.evaluate(f, pivot);
+        }
+        let edges: Vec<(usize, usize)> = self.sides.keys().copied().filter(|&(a, b)| a < b).collect();
+        for (a, b) in edges {
+            let (side_a, side_b) = (&self.sides[&(a, b)], &self.sides[&(b, a)]);
+            let same_on = |side: &[usize], p: &[usize], q: &[usize]| side.iter().all(|&s| p[s] == q[s]);
+            let mut edge_pivots: Vec<Vec<usize>> = Vec::new();
+            for pivot in pivots {
+                if !edge_pivots.iter().any(|p| same_on(side_a, p, pivot) || same_on(side_b, p, pivot)) {
+                    edge_pivots.push(pivot.clone());
+                }
+            }
+            self.pivots.insert((a, b), edge_pivots.clone());
+            self.pivots.insert((b, a), edge_pivots);
+        }
+        Ok(())
+    }
+
+    /// Multi-indices on `a`'s side of the edge `(a, b)`: each local index of `a`
+    /// combined with each pivot coming into `a` from its other neighbors.
+    ///
+    /// The local index varies slowest, followed by the neighbors in order.
+    fn kronecker(&self, a: usize, b: usize) -> Vec<Vec<usize>> {
+        let mut result = vec![vec![0; self.local_dims.len()]];
+        for &p in &self.positions[a] {
+            result = result.into_iter()
+                .flat_map(|index| {
+                    (0..self.local_dims[p]).map(move |i| {
+                        let mut index = index.clone();
+                        index[p] = i;
+                        index
+                    })
+                })
+                .collect();
+        }
+        for &c in self.neighbors[a].iter().filter(|&&c| c != b) {
+            let side = &self.sides[&(c, a)];
+            let incoming = &self.pivots[&(c, a)];
+            result = result.into_iter()
+                .flat_map(|index| {
+                    incoming.iter().map(move |pivot| {
+                        let mut index = index.clone();
+                        for &s in side {
+                            index[s] = pivot[s];
+                        }
+                        index
+                    })
+                })
+                .collect();
+        }
+        result
+    }
+
+    /// Combine a multi-index on `a`'s side of the edge `(a, b)` with one on `b`'s side.
+    fn join(&self, a: usize, b: usize, left: &[usize], right: &[usize]) -> Vec<usize> {
+        let mut index = left.to_vec();
+        for &s in &self.sides[&(b, a)] {
+            index[s] = right[s];
+        }
+        index
+    }
+
+    /// Update the pivots of the edge `(a, b)`.
+    fn update_edge<F: Fn(&[usize]) -> T>(&mut self, f: &F, a: usize, b: usize, options: &TreeTCIOptions) {
+        let rows = self.kronecker(a, b);
+        let cols = self.kronecker(b, a);
+        let mut pi = Matrix::from_elem(rows.len(), cols.len(), T::zero());
+        for (i, row) in rows.iter().enumerate() {
+            for (j, col) in cols.iter().enumerate() {
+                let index = self.join(a, b, row, col);
+                pi[[i, j]] = self.evaluate(f, &index);
+            }
+        }
+
+        let lu_options = RrLUOptions {
+            max_rank: options.max_bond_dim,
+            rel_tol: options.tolerance,
+            abs_tol: 0.0,
+            left_orthogonal: true,
+        };
+        let luci = MatrixLUCI::from_matrix(&pi, Some(lu_options));
+        if luci.rank() == 0 {
+            return;
+        }
+        self.pivots.insert((a, b), luci.row_indices().iter().map(|&i| rows[i].clone()).collect());
+        self.pivots.insert((b, a), luci.col_indices().iter().map(|&j| cols[j].clone()).collect());
+        let error = luci.pivot_errors().last().copied().unwrap_or(0.0);
+        self.bond_errors.insert((a.min(b), a.max(b)), error);
+    }
+
+    /// Edges in depth-first order from vertex 0, oriented away from it.
+    fn edge_order(&self) -> Vec<(usize, usize)> {
+        let mut order = Vec::new();
+        let mut stack = vec![(0, usize::MAX)];
+        while let Some((v, parent)) = stack.pop() {
+            for &c in self.neighbors[v].iter().rev().filter(|&&c| c != parent) {
+                stack.push((c, v));
+            }
+            if parent != usize::MAX {
+                order.push((parent, v));
+            }
+        }
+        order
+    }
+
+    /// Run sweeps until convergence or `options.max_iter` iterations.
+    fn optimize<F: Fn(&[usize]) -> T>(&mut self, f: &F, options: &TreeTCIOptions) -> (Vec<usize>, Vec<f64>) {
+        let order = self.edge_order();
+        let mut ranks = Vec::new();
+        let mut errors = Vec::new();
+        let mut bond_dims: Vec<usize> = order.iter().map(|e| self.pivots[e].len()).collect();
+
+        for iter in 0..options.max_iter {
+            // Alternate between sweeping away from and towards vertex 0
+            if iter % 2 == 0 {
+                for &(a, b) in &order {
+                    self.update_edge(f, a, b, options);
+                }
+            } else {
+                for &(a, b) in order.iter().rev() {
+                    self.update_edge(f, b, a, options);
+                }
+            }
+
+            let error = self.bond_errors.values().copied().fold(0.0, f64::max);
+            let error_normalized = if options.normalize_error && self.max_sample_value > 0.0 {
+                error / self.max_sample_value
+            } else {
+                error
+            };
+            let new_bond_dims: Vec<usize> = order.iter().map(|e| self.pivots[e].len()).collect();
+            let rank = new_bond_dims.iter().copied().max().unwrap_or(0);
+            errors.push(error_normalized);
+            ranks.push(rank);
+
+            if options.verbosity > 0 {
+                println!("iteration = {}, rank = {}, error = {:.2e}", iter + 1, rank, error_normalized);
+            }
+            // The error of a sweep only covers the pivots available to it, so the bond
+            // dimensions must also have stopped growing
+            let converged = error_normalized < options.tolerance && new_bond_dims == bond_dims;
+            bond_dims = new_bond_dims;
+            if converged {
+                break;
+            }
+        }
+        (ranks, errors)
+    }
+
+    /// Node tensors in the `T P^{-1}` form, rooted at vertex 0.
+    ///
+    /// Returns, for each vertex, the row-major data of its tensor and the neighbors in
+    /// the order of the bond axes; the site axes come first, in the order of `positions`.
+    fn node_tensors<F: Fn(&[usize]) -> T>(&mut self, f: &F) -> Result<Vec<(Vec<T>, Vec<usize>)>> {
+        let mut parents = vec![None; self.positions.len()];
+        for (a, b) in self.edge_order() {
+            parents[b] = Some(a);
+        }
+
+        let mut result = Vec::with_capacity(self.positions.len());
+        for v in 0..self.positions.len() {
+            // The bond to the parent is the last axis
+            let mut neighbors: Vec<usize> = self.neighbors[v].iter().copied().filter(|&c| Some(c) != parents[v]).collect();
+            let Some(p) = parents[v] else {
+                let data = self.kronecker(v, usize::MAX).iter().map(|index| self.evaluate(f, index)).collect();
+                result.push((data, neighbors));
+                continue;
+            };
+            neighbors.push(p);
+
+            // T[(local, children), j] with j running over the pivots from the parent's side
+            let rows = self.kronecker(v, p);
+            let from_parent = self.pivots[&(p, v)].clone();
+            let to_parent = self.pivots[&(v, p)].clone();
+            if from_parent.len() != to_parent.len() {
+                return Err(anyhow::anyhow!("Pivot sets of edge ({}, {}) have different sizes", v, p));
+            }
+            let mut t = Matrix::from_elem(rows.len(), from_parent.len(), T::zero());
+            for (i, row) in rows.iter().enumerate() {
+                for (j, col) in from_parent.iter().enumerate() {
+                    let index = self.join(v, p, row, col);
+                    t[[i, j]] = self.evaluate(f, &index);
+                }
+            }
+            // P[i, j] with i running over the pivots on this side
+            let mut pivot_matrix = Matrix::from_elem(to_parent.len(), from_parent.len(), T::zero());
+            for (i, row) in to_parent.iter().enumerate() {
+                for (j, col) in from_parent.iter().enumerate() {
+                    let index = self.join(v, p, row, col);
+                    pivot_matrix[[i, j]] = self.evaluate(f, &index);
+                }
+            }
+
+            let tp = a_times_b_inv(&t, &pivot_matrix);
+            let mut data = Vec::with_capacity(tp.nrows() * tp.ncols());
+            for i in 0..tp.nrows() {
+                for j in 0..tp.ncols() {
+                    data.push(tp[[i, j]]);
+                }
+            }
+            result.push((data, neighbors));
+        }
+        Ok(result)
+    }
+}
@@ -4,10 +4,10 @@ use std::collections::VecDeque;
 use std::collections::HashSet;
 use std::collections::HashMap;
 use std::hash::Hash;
-use tensor4all::TensorDynLen;
+use tensor4all::{contract_network, AnyScalar, SvdOptions, TensorDynLen};
 use tensor4all::Storage;
 use tensor4all::index::{Index, NoSymmSpace, Symmetry, DynId};
-use tensor4all::index_ops::common_inds;
+use tensor4all::index_ops::{common_inds, sim};
 use crate::connection::Connection;
 use crate::named_graph::NamedGraph;
 use crate::site_index_network::SiteIndexNetwork;
@@ -593,6 +593,16 @@ where
             .collect()
     }
 
+    /// Get the NodeIndex of a vertex by its name.
+    pub fn node_index(&self, node_name: &V) -> Option<NodeIndex> {
+        self.graph.node_index(node_name)
+    }
+
+    /// Get the name of a vertex by its NodeIndex.
+    pub fn node_name(&self, node: NodeIndex) -> Option<&V> {
+        self.graph.node_name(node)
+    }
+
     /// Get a reference to the orthogonalization region (using node names).
     ///
     /// When empty, the network is not orthogonalized.
@@ -731,6 +741,43 @@ where
         self.site_index_network.site_space_mut(node_name)
     }
 
+    /// Replace site indices, given as `(old, new)` pairs matched by ID.
+    ///
+    /// Both the node tensors and the site index network are updated.
+    ///
+    /// # Errors
+    /// Returns an error if an `old` index is not a site index of the network, or if
+    /// `old` and `new` have different dimensions.
+    pub fn replace_site_indices(&mut self, replacements: &[(Index<Id, Symm>, Index<Id, Symm>)]) -> Result<()> {
+        for (old, new) in replacements {
+            if old.size() != new.size() {
+                return Err(anyhow::anyhow!(
+                    "Dimension mismatch: cannot replace index of size {} by index of size {}",
+                    old.size(),
+                    new.size()
+                ));
+            }
+            let node_name = self.site_index_network.node_names()
+                .into_iter()
+                .find(|name| self.site_space(name).map_or(false, |s| s.iter().any(|i| i.id == old.id)))
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Site index not found in network"))?;
+            let node = self.graph.node_index(&node_name)
+                .ok_or_else(|| anyhow::anyhow!("Node {:?} not found", node_name))?;
+
+            let tensor = self.tensor_mut(node)
+                .ok_or_else(|| anyhow::anyhow!("Tensor not found for node {:?}", node))?;
+            for idx in tensor.indices.iter_mut().filter(|idx| idx.id == old.id) {
+                *idx = new.clone();
+            }
+            if let Some(site_space) = self.site_space_mut(&node_name) {
+                site_space.retain(|i| i.id != old.id);
+                site_space.insert(new.clone());
+            }
+        }
+        Ok(())
+    }
+
     /// Check if two TreeTN can be added together.
     ///
     /// Two TreeTN can be added if:
@@ -1091,13 +1138,14 @@ where
     /// Contract the TreeTN to a single dense tensor.
     ///
     /// This method contracts all tensors in the network into a single tensor
-    /// containing all physical indices. The contraction is performed using
-    /// a tree-aware order (post-order from leaves to root) with edge-based
-    /// contraction that uses Connection information to identify which indices
-    /// to contract, rather than relying on index ID matching.
+    /// containing all physical indices. Bonds are identified by the Connection
+    /// information rather than by index ID matching, and the pairwise contraction
+    /// order is chosen by [`contract_network`] to keep intermediate tensors small.
     ///
     /// # Returns
-    /// A single tensor representing the full contraction of the network.
+    /// A single tensor representing the full contraction of the network. Its indices
+    /// are the site indices of all nodes, ordered by vertex name (and by position
+    /// within each node's tensor).
     ///
     /// # Errors
     /// Returns an error if:
@@ -1125,77 +1173,203 @@ where
         self.validate_tree()
             .context("contract_to_tensor: graph must be a tree")?;
 
-        // Choose a deterministic root (minimum vertex name)
-        let root_name = self.graph.graph().node_indices()
-            .filter_map(|idx| self.graph.node_name(idx).cloned())
-            .min()
-            .ok_or_else(|| anyhow::anyhow!("No nodes found"))?;
-        let root = self.graph.node_index(&root_name)
-            .ok_or_else(|| anyhow::anyhow!("Root node not found"))?;
-
-        // Build post-order traversal (leaves first, root last)
-        let post_order = self.post_order_dfs(root);
-
-        // Track which nodes have been contracted and their resulting tensors
-        // For each node in post-order, we contract its tensor with all
-        // already-contracted children, using the edge's Connection info.
-        let mut contracted: HashMap<NodeIndex, TensorDynLen<Id, Symm>> = HashMap::new();
+        // Visit nodes in a deterministic order (sorted by vertex name)
+        let mut nodes: Vec<(V, NodeIndex)> = self.graph.graph().node_indices()
+            .filter_map(|idx| self.graph.node_name(idx).cloned().map(|name| (name, idx)))
+            .collect();
+        nodes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut tensors = Vec::with_capacity(nodes.len());
+        let mut site_indices = Vec::new();
+        for (_, node) in &nodes {
+            let tensor = self.tensor_with_shared_bonds(*node)?;
+            let bond_ids: Vec<&Id> = self.edges_for_node(*node)
+                .into_iter()
+                .filter_map(|(edge, _)| self.connection(edge))
+                .map(|conn| &conn.index_source.id)
+                .collect();
+            site_indices.extend(
+                tensor.indices.iter()
+                    .filter(|idx| !bond_ids.contains(&&idx.id))
+                    .cloned(),
+            );
+            tensors.push(tensor);
+        }
 
-        for node in post_order {
-            let mut current = self.tensor(node)
-                .ok_or_else(|| anyhow::anyhow!("Tensor not found for node {:?}", node))?
-                .clone();
+        // Contract in an optimized order, then order the site indices by vertex name
+        let result = contract_network(&tensors)
+            .context("contract_to_tensor: failed to contract network")?;
+        Ok(result.permute_indices(&site_indices))
+    }
 
-            // Find all edges to already-contracted neighbors (children in the rooted tree)
-            for (edge, neighbor) in self.edges_for_node(node) {
-                if let Some(child_tensor) = contracted.remove(&neighbor) {
-                    // Determine which index belongs to which node
-                    // edge_index_for_node returns the index for a given node on this edge
-                    let idx_current = self.edge_index_for_node(edge, node)?.clone();
-                    let idx_child = self.edge_index_for_node(edge, neighbor)?.clone();
-
-                    // Contract current tensor with child tensor along the bond indices
-                    // We use contract_pairs which takes explicit index pairs
-                    current = current.contract_pairs(&child_tensor, &[(idx_current, idx_child)])
-                        .context("Failed to contract along edge")?;
+    /// Inner product `⟨self, other⟩ = Σ conj(self) * other` of two networks.
+    ///
+    /// The networks are contracted edge by edge from the leaves towards a root, so the
+    /// full tensors are never formed: each step contracts the tensors of both networks
+    /// at one node with the environments coming from its children. The cost is linear
+    /// in the number of nodes.
+    ///
+    /// The networks must be compatible (see [`SiteIndexNetwork::is_compatible`]): same
+    /// vertex names, topology and site indices. Their bond indices may be arbitrary,
+    /// since the bonds of `other` are renamed internally.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The networks are empty or not compatible
+    /// - The graph is not a tree
+    /// - Tensor contraction fails
+    pub fn inner(&self, other: &Self) -> Result<AnyScalar>
+    where
+        Id: From<DynId>,
+    {
+        if self.node_count() == 0 {
+            return Err(anyhow::anyhow!("Cannot compute the inner product of empty TreeTNs"));
+        }
+        if !self.site_index_network.is_compatible(&other.site_index_network) {
+            return Err(anyhow::anyhow!("Site index networks are not compatible"))
+                .context("inner: networks must have the same topology and site indices");
+        }
+        self.check_same_topology(other)
+            .context("inner: networks must have the same topology and site indices")?;
+        self.validate_tree()
+            .context("inner: graph must be a tree")?;
+
+        // Give the bonds of `other` fresh IDs, so they can't be confused with bonds of `self`
+        let mut renamed_bonds: HashMap<Id, Index<Id, Symm>> = HashMap::new();
+        for e in other.graph.graph().edge_indices() {
+            let conn = other.connection(e)
+                .ok_or_else(|| anyhow::anyhow!("Connection not found"))?;
+            renamed_bonds.insert(conn.index_source.id.clone(), sim(&conn.index_source));
+        }
+
+        // Pair of tensors (conj(self), other) at a node of `self`
+        let node_pair = |node: NodeIndex| -> Result<[TensorDynLen<Id, Symm>; 2]> {
+            let name = self.node_name(node)
+                .ok_or_else(|| anyhow::anyhow!("Node name not found for {:?}", node))?;
+            let other_node = other.node_index(name)
+                .ok_or_else(|| anyhow::anyhow!("Node {:?} not found in other network", name))?;
+            let mut tensor_other = other.tensor_with_shared_bonds(other_node)?;
+            for idx in tensor_other.indices.iter_mut() {
+                if let Some(renamed) = renamed_bonds.get(&idx.id) {
+                    *idx = renamed.clone();
                 }
             }
+            Ok([self.tensor_with_shared_bonds(node)?.dag(), tensor_other])
+        };
 
-            // Store the contracted result for this node
-            contracted.insert(node, current);
+        let root = self.graph.graph().node_indices().next()
+            .ok_or_else(|| anyhow::anyhow!("No nodes found"))?;
+        let mut tour = Vec::new();
+        self.depth_first_tour(root, None, &mut tour);
+
+        // Environments of the edges towards root, computed leaves first
+        let mut envs: HashMap<NodeIndex, Vec<TensorDynLen<Id, Symm>>> = HashMap::new();
+        for &(child, parent, _, away_from_root) in &tour {
+            if away_from_root {
+                continue;
+            }
+            let mut tensors = envs.remove(&child).unwrap_or_default();
+            tensors.extend(node_pair(child)?);
+            let env = contract_network(&tensors)
+                .context("inner: failed to contract environment")?;
+            envs.entry(parent).or_default().push(env);
         }
 
-        // The root's tensor (last in post-order) is the final result
-        contracted.remove(&root)
-            .ok_or_else(|| anyhow::anyhow!("Contraction produced no result"))
+        let mut tensors = envs.remove(&root).unwrap_or_default();
+        tensors.extend(node_pair(root)?);
+        let result = contract_network(&tensors)
+            .context("inner: failed to contract root")?;
+        Ok(result.sum())
     }
 
-    /// Perform a post-order DFS traversal starting from the given root.
+    /// Frobenius norm of the network, computed from [`TreeTN::inner`].
     ///
-    /// Returns nodes in post-order (children before parents, leaves first).
-    fn post_order_dfs(&self, root: NodeIndex) -> Vec<NodeIndex> {
-        let g = self.graph.graph();
-        let mut result = Vec::new();
-        let mut visited = HashSet::new();
-        let mut stack = vec![(root, false)]; // (node, processed)
-
-        while let Some((node, processed)) = stack.pop() {
-            if processed {
-                result.push(node);
-            } else if !visited.contains(&node) {
-                visited.insert(node);
-                stack.push((node, true)); // Push back for post-processing
-
-                // Push unvisited neighbors
-                for neighbor in g.neighbors(node) {
-                    if !visited.contains(&neighbor) {
-                        stack.push((neighbor, false));
-                    }
+    /// The ortho metadata is not used: it is not updated when tensors are modified
+    /// through [`TreeTN::tensor_mut`] or [`TreeTN::replace_tensor`].
+    ///
+    /// # Errors
+    /// Returns an error under the same conditions as [`TreeTN::inner`].
+    pub fn norm(&self) -> Result<f64>
+    where
+        Id: From<DynId>,
+    {
+        Ok(self.inner(self)?.real().max(0.0).sqrt())
+    }
+
+    /// Frobenius distance `‖self - other‖` between two networks.
+    ///
+    /// Computed as `sqrt(‖self‖² + ‖other‖² - 2 Re⟨self, other⟩)` without forming the
+    /// difference. Because of cancellation, distances much smaller than the norms
+    /// are only accurate to about `sqrt(ε) * ‖self‖`.
+    ///
+    /// # Errors
+    /// Returns an error under the same conditions as [`TreeTN::inner`].
+    pub fn distance(&self, other: &Self) -> Result<f64>
+    where
+        Id: From<DynId>,
+    {
+        let norm_self = self.norm()?;
+        let norm_other = other.norm()?;
+        let overlap = self.inner(other)?.real();
+        let dist_sq = norm_self * norm_self + norm_other * norm_other - 2.0 * overlap;
+        Ok(dist_sq.max(0.0).sqrt())
+    }
+
+    /// Check that `other` has the same vertex names and edges as `self`.
+    pub(crate) fn check_same_topology(&self, other: &Self) -> Result<()> {
+        if self.node_count() != other.node_count() || self.edge_count() != other.edge_count() {
+            return Err(anyhow::anyhow!(
+                "Topology mismatch: {} nodes and {} edges vs {} nodes and {} edges",
+                self.node_count(),
+                self.edge_count(),
+                other.node_count(),
+                other.edge_count()
+            ));
+        }
+        for node in self.node_indices() {
+            let name = self.node_name(node)
+                .ok_or_else(|| anyhow::anyhow!("Node name not found for {:?}", node))?;
+            let other_node = other.node_index(name)
+                .ok_or_else(|| anyhow::anyhow!("Topology mismatch: node {:?} not found", name))?;
+            for (_, neighbor) in self.edges_for_node(node) {
+                let neighbor_name = self.node_name(neighbor)
+                    .ok_or_else(|| anyhow::anyhow!("Node name not found for {:?}", neighbor))?;
+                let connected = other.edges_for_node(other_node)
+                    .into_iter()
+                    .any(|(_, nb)| other.node_name(nb) == Some(neighbor_name));
+                if !connected {
+                    return Err(anyhow::anyhow!(
+                        "Topology mismatch: no edge between {:?} and {:?}",
+                        name,
+                        neighbor_name
+                    ));
                 }
             }
         }
+        Ok(())
+    }
 
-        result
+    /// Get the tensor of a node with each bond index replaced by the source-side index
+    /// of its connection.
+    ///
+    /// The two sides of a bond may carry indices with different IDs. After renaming,
+    /// the tensors of neighboring nodes share their bond indices by ID, so that network
+    /// contractions pair them up.
+    pub(crate) fn tensor_with_shared_bonds(&self, node: NodeIndex) -> Result<TensorDynLen<Id, Symm>> {
+        let mut tensor = self.tensor(node)
+            .ok_or_else(|| anyhow::anyhow!("Tensor not found for node {:?}", node))?
+            .clone();
+        for (edge, _) in self.edges_for_node(node) {
+            let idx_node = self.edge_index_for_node(edge, node)?;
+            let shared = &self.connection(edge)
+                .ok_or_else(|| anyhow::anyhow!("Connection not found"))?
+                .index_source;
+            let pos = tensor.indices.iter()
+                .position(|idx| idx.id == idx_node.id)
+                .ok_or_else(|| anyhow::anyhow!("Bond index not found in tensor of node {:?}", node))?;
+            tensor.indices[pos] = shared.clone();
+        }
+        Ok(tensor)
     }
 
     /// Validate that `ortho_region` and edge `ortho_towards` are consistent.
@@ -1592,6 +1766,177 @@ where
 
         Ok(self)
     }
+
+    /// Truncate the bond dimensions of the network by SVD.
+    ///
+    /// The network is first canonicalized towards `root` with QR decompositions (leaves
+    /// first). The orthogonality center is then moved through the whole tree in
+    /// depth-first order: each time it crosses an edge away from `root`, the bond is
+    /// truncated by an SVD of the current center tensor, and each time it returns it is
+    /// moved back by QR. Every truncation is thus performed in canonical form, where the
+    /// discarded singular values are the error of the whole network.
+    ///
+    /// `options` controls the truncation of each bond as described in [`SvdOptions`]:
+    /// `max_rank` bounds the bond dimension, and `rtol`, `cutoff` and `atol` bound the
+    /// discarded weight.
+    ///
+    /// The returned network is orthogonalized towards `root`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The graph is not a tree
+    /// - `root` does not exist in the graph
+    /// - A QR or SVD decomposition fails
+    /// - Tensor storage types are not DenseF64 or DenseC64
+    pub fn truncate(mut self, root: &V, options: &SvdOptions) -> Result<Self>
+    where
+        Id: From<DynId>,
+        Symm: From<NoSymmSpace>,
+    {
+        self.validate_tree()
+            .context("truncate: graph must be a tree")?;
+
+        let root_node = self.graph.node_index(root)
+            .ok_or_else(|| anyhow::anyhow!("Node {:?} not found in graph", root))
+            .context("truncate: root must be a valid node")?;
+
+        let mut tour = Vec::new();
+        self.depth_first_tour(root_node, None, &mut tour);
+
+        // 1. Canonicalize towards root. The moves back towards root visit every
+        //    edge after all edges of the subtree below it (leaves first).
+        for &(from, to, edge, away_from_root) in &tour {
+            if !away_from_root {
+                self.move_ortho_center(from, to, edge, None)
+                    .context("truncate: failed to canonicalize towards root")?;
+            }
+        }
+
+        // 2. Truncation sweep
+        for &(from, to, edge, away_from_root) in &tour {
+            let truncation = if away_from_root { Some(options) } else { None };
+            self.move_ortho_center(from, to, edge, truncation)
+                .context("truncate: failed to truncate bond")?;
+        }
+
+        self.set_ortho_region([root.clone()])
+            .context("truncate: failed to set ortho_region")?;
+        Ok(self)
+    }
+
+    /// Collect the moves of the orthogonality center for a depth-first traversal of the
+    /// subtree at `node` (excluding `parent`) that starts and ends at `node`.
+    ///
+    /// Each move is `(from, to, edge, away_from_root)`.
+    pub(crate) fn depth_first_tour(
+        &self,
+        node: NodeIndex,
+        parent: Option<NodeIndex>,
+        tour: &mut Vec<(NodeIndex, NodeIndex, EdgeIndex, bool)>,
+    ) {
+        for (edge, child) in self.edges_for_node(node) {
+            if Some(child) == parent {
+                continue;
+            }
+            tour.push((node, child, edge, true));
+            self.depth_first_tour(child, Some(node), tour);
+            tour.push((child, node, edge, false));
+        }
+    }
+
+    /// Move the orthogonality center from `from` to its neighbor `to` across `edge`.
+    ///
+    /// The tensor at `from` is factorized with the bond to `to` on the right side. The
+    /// isometry stays at `from` and the remainder is absorbed into `to`. With
+    /// `truncation = None` the factorization is a QR decomposition; otherwise it is an
+    /// SVD truncated according to the options, and the remainder is `U^† A = S V^†`.
+    pub(crate) fn move_ortho_center(
+        &mut self,
+        from: NodeIndex,
+        to: NodeIndex,
+        edge: EdgeIndex,
+        truncation: Option<&SvdOptions>,
+    ) -> Result<()>
+    where
+        Id: From<DynId>,
+        Symm: From<NoSymmSpace>,
+    {
+        use tensor4all::{qr, svd_with};
+
+        let bond_from = self.edge_index_for_node(edge, from)?.clone();
+        let bond_to = self.edge_index_for_node(edge, to)?.clone();
+
+        let tensor_from = self
+            .tensor(from)
+            .ok_or_else(|| anyhow::anyhow!("Tensor not found for node {:?}", from))?;
+
+        let left_inds: Vec<Index<Id, Symm>> = tensor_from
+            .indices
+            .iter()
+            .filter(|idx| idx.id != bond_from.id)
+            .cloned()
+            .collect();
+        if left_inds.is_empty() || left_inds.len() == tensor_from.indices.len() {
+            return Err(anyhow::anyhow!(
+                "Cannot factorize node {:?}: need at least one left index and at least one right index",
+                from
+            ))
+            .context("move_ortho_center: invalid tensor rank");
+        }
+
+        let (isometry, remainder) = match truncation {
+            None => match tensor_from.storage.as_ref() {
+                Storage::DenseF64(_) => qr::<Id, Symm, f64>(tensor_from, &left_inds)
+                    .map_err(|e| anyhow::anyhow!("QR decomposition failed: {}", e))?,
+                Storage::DenseC64(_) => qr::<Id, Symm, Complex64>(tensor_from, &left_inds)
+                    .map_err(|e| anyhow::anyhow!("QR decomposition failed: {}", e))?,
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Unsupported storage type for QR decomposition (only DenseF64 and DenseC64 are supported)"
+                    ));
+                }
+            },
+            Some(options) => {
+                let (u, _, _) = match tensor_from.storage.as_ref() {
+                    Storage::DenseF64(_) => svd_with::<Id, Symm, f64>(tensor_from, &left_inds, options)
+                        .map_err(|e| anyhow::anyhow!("SVD failed: {}", e))?,
+                    Storage::DenseC64(_) => svd_with::<Id, Symm, Complex64>(tensor_from, &left_inds, options)
+                        .map_err(|e| anyhow::anyhow!("SVD failed: {}", e))?,
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "Unsupported storage type for SVD (only DenseF64 and DenseC64 are supported)"
+                        ));
+                    }
+                };
+                // Contracting the left indices gives the remainder [new bond, bond_from]
+                let remainder = u.conj().contract(tensor_from);
+                (u, remainder)
+            }
+        };
+
+        let tensor_to = self
+            .tensor(to)
+            .ok_or_else(|| anyhow::anyhow!("Tensor not found for node {:?}", to))?;
+        let updated_tensor_to = tensor_to
+            .contract_pairs(&remainder, &[(bond_to, bond_from)])
+            .context("move_ortho_center: failed to absorb remainder into neighbor")?;
+
+        // The new bond index is always the last index of the isometry.
+        let new_bond_index = isometry
+            .indices
+            .last()
+            .ok_or_else(|| anyhow::anyhow!("Isometry has no indices"))?
+            .clone();
+
+        // Update the connection bond indices FIRST, so replace_tensor validation matches.
+        self.replace_edge_bond(edge, new_bond_index.clone(), new_bond_index.clone())?;
+        self.replace_tensor(from, isometry)?;
+        self.replace_tensor(to, updated_tensor_to)?;
+
+        let ortho_towards_index = self.edge_index_for_node(edge, to)?.clone();
+        self.set_edge_ortho_towards(edge, Some(ortho_towards_index))?;
+        Ok(())
+    }
 }
 
 impl<Id, Symm, V> Default for TreeTN<Id, Symm, V>
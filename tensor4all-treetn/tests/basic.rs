@@ -1,6 +1,6 @@
-use tensor4all_treetn::{Connection, TreeTN, TreeTopology};
+use tensor4all_treetn::{apply, apply_fit, crossinterpolate_tree, fit_apply, fit_sum, Connection, FitOptions, OperatorTerm, TreeTCIOptions, TreeTN, TreeTNO, TreeTopology};
 use tensor4all::index::{DefaultIndex as Index, DynId};
-use tensor4all::{TensorDynLen, Storage};
+use tensor4all::{SvdOptions, TensorDynLen, Storage};
 use tensor4all::NoSymmSpace;
 use tensor4all::storage::{DenseStorageF64, DenseStorageC64};
 use std::sync::Arc;
@@ -1155,17 +1155,17 @@ fn test_treetn_contract_two_nodes() {
     // Connect via j1 and j2
     tn.connect(n1, &j1, n2, &j2).unwrap();
 
-    // Contract to tensor - currently this performs simple sequential contraction
-    // which may not eliminate bond indices if they have different IDs
-    // For this test, we just verify it runs without errors and produces valid output
+    // Bond indices j1 and j2 have different IDs but are contracted via the connection
     let result = tn.contract_to_tensor().unwrap();
-
-    // The result should have some dimensions (implementation-dependent)
-    assert!(!result.dims.is_empty());
-
-    // Verify total size is consistent
-    let total_size: usize = result.dims.iter().product();
-    assert!(total_size > 0);
+    assert_eq!(result.dims, vec![2, 4]);
+    assert_eq!(result.indices[0].id, i.id);
+    assert_eq!(result.indices[1].id, k.id);
+
+    // Each element is a sum of 3 ones
+    match result.storage.as_ref() {
+        Storage::DenseF64(dense) => assert!(dense.as_slice().iter().all(|&x| x == 3.0)),
+        _ => panic!("Expected DenseF64 storage"),
+    }
 }
 
 #[test]
@@ -1572,4 +1572,606 @@ fn test_treetn_add_two_nodes_multi_physical_permuted() {
     assert_treetn_add_correctness(tn_a, tn_b, "test_treetn_add_two_nodes_multi_physical_permuted");
 }
 
-// Note: Complex64 two-nodes correctness test is generated by the macro (test_treetn_add_two_nodes_c64)
\ No newline at end of file
+// Note: Complex64 two-nodes correctness test is generated by the macro (test_treetn_add_two_nodes_c64)
+// ============================================================================
+// TreeTN::truncate tests
+// ============================================================================
+
+/// Star-shaped network: center vertex 1 (site `s1`) connected to leaves 0, 2 and 3
+/// (sites `leaf_sites`), with fresh bond indices of dimension 2.
+fn star_treetn(
+    s1: &Index<DynId>,
+    leaf_sites: &[Index<DynId>; 3],
+    offset: usize,
+) -> TreeTN<DynId, NoSymmSpace, usize> {
+    let value = |x: usize| (((x + offset) * 37) % 11) as f64 - 5.0;
+    let bonds: Vec<Index<DynId>> = (0..3).map(|_| Index::new_dyn(2)).collect();
+    let mut tn: TreeTN<DynId, _, usize> = TreeTN::new();
+
+    let center_data: Vec<f64> = (0..16).map(value).collect();
+    let center: TensorDynLen<DynId> = TensorDynLen::new(
+        vec![s1.clone(), bonds[0].clone(), bonds[1].clone(), bonds[2].clone()],
+        vec![2, 2, 2, 2],
+        Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(center_data))),
+    );
+    let n1 = tn.add_tensor_with_vertex(1, center).unwrap();
+
+    for (k, (site, bond)) in leaf_sites.iter().zip(&bonds).enumerate() {
+        let leaf_data: Vec<f64> = (0..6).map(|x| value(x + 16 * (k + 1))).collect();
+        let leaf: TensorDynLen<DynId> = TensorDynLen::new(
+            vec![site.clone(), bond.clone()],
+            vec![3, 2],
+            Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(leaf_data))),
+        );
+        let vertex = if k == 0 { 0 } else { k + 1 };
+        let n = tn.add_tensor_with_vertex(vertex, leaf).unwrap();
+        tn.connect(n1, bond, n, bond).unwrap();
+    }
+    tn
+}
+
+fn max_bond_dim(tn: &TreeTN<DynId, NoSymmSpace, usize>) -> usize {
+    tn.node_indices()
+        .into_iter()
+        .flat_map(|node| tn.edges_for_node(node))
+        .map(|(edge, _)| tn.connection(edge).unwrap().bond_dim())
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn test_treetn_truncate_after_add() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let tn = star_treetn(&s1, &leaf_sites, 0);
+    let expected = &tn.contract_to_tensor().unwrap() * 2.0;
+
+    // Adding a network to itself doubles the bond dimensions without changing the rank
+    let other = star_treetn(&s1, &leaf_sites, 0);
+    let tn_sum = tn.add(other).unwrap();
+    assert_eq!(max_bond_dim(&tn_sum), 4);
+
+    let truncated = tn_sum.truncate(&1, &SvdOptions::default()).unwrap();
+    assert_eq!(max_bond_dim(&truncated), 2);
+    assert!(truncated.ortho_region().contains(&1));
+    assert!(truncated.validate_ortho_consistency().is_ok());
+
+    let actual = truncated.contract_to_tensor().unwrap();
+    assert!(actual.isapprox(&expected, 0.0, 1e-10));
+}
+
+#[test]
+fn test_treetn_truncate_max_rank() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let tn_a = star_treetn(&s1, &leaf_sites, 0);
+    let tn_b = star_treetn(&s1, &leaf_sites, 5);
+    let exact = tn_a.contract_to_tensor().unwrap().add(&tn_b.contract_to_tensor().unwrap()).unwrap();
+
+    let tn_sum = tn_a.add(tn_b).unwrap();
+    let truncated = tn_sum.truncate(&0, &SvdOptions::default().with_max_rank(1)).unwrap();
+    assert_eq!(max_bond_dim(&truncated), 1);
+    assert!(truncated.ortho_region().contains(&0));
+    assert!(truncated.validate_ortho_consistency().is_ok());
+
+    // The truncated network is a worse approximation than the exact sum,
+    // but no worse than the zero network
+    let approx = truncated.contract_to_tensor().unwrap();
+    let error = approx.sub(&exact).unwrap().norm();
+    assert!(error > 1e-8);
+    assert!(error < exact.norm());
+}
+
+#[test]
+fn test_treetn_truncate_invalid_root() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let tn = star_treetn(&s1, &leaf_sites, 0);
+    assert!(tn.truncate(&7, &SvdOptions::default()).is_err());
+}
+
+// ============================================================================
+// Variational fitting tests
+// ============================================================================
+
+/// Star-shaped operator network matching `star_treetn`: each node maps the input
+/// site indices to the output site indices, with bonds of dimension 2.
+fn star_operator(
+    s1: &Index<DynId>,
+    s1_out: &Index<DynId>,
+    leaf_sites: &[Index<DynId>; 3],
+    leaf_out: &[Index<DynId>; 3],
+) -> TreeTN<DynId, NoSymmSpace, usize> {
+    let value = |x: usize| ((x * 17) % 7) as f64 - 3.0;
+    let bonds: Vec<Index<DynId>> = (0..3).map(|_| Index::new_dyn(2)).collect();
+    let mut op: TreeTN<DynId, _, usize> = TreeTN::new();
+
+    let center: TensorDynLen<DynId> = TensorDynLen::new(
+        vec![s1_out.clone(), s1.clone(), bonds[0].clone(), bonds[1].clone(), bonds[2].clone()],
+        vec![2, 2, 2, 2, 2],
+        Arc::new(Storage::DenseF64(DenseStorageF64::from_vec((0..32).map(value).collect()))),
+    );
+    let n1 = op.add_tensor_with_vertex(1, center).unwrap();
+
+    for k in 0..3 {
+        let leaf_data: Vec<f64> = (0..18).map(|x| value(x + 32 * (k + 1))).collect();
+        let leaf: TensorDynLen<DynId> = TensorDynLen::new(
+            vec![leaf_out[k].clone(), leaf_sites[k].clone(), bonds[k].clone()],
+            vec![3, 3, 2],
+            Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(leaf_data))),
+        );
+        let vertex = if k == 0 { 0 } else { k + 1 };
+        let n = op.add_tensor_with_vertex(vertex, leaf).unwrap();
+        op.connect(n1, &bonds[k], n, &bonds[k]).unwrap();
+    }
+    op
+}
+
+#[test]
+fn test_fit_sum_exact() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let terms: Vec<_> = [0, 3, 8].iter().map(|&offset| star_treetn(&s1, &leaf_sites, offset)).collect();
+
+    let mut exact = terms[0].contract_to_tensor().unwrap();
+    for tn in &terms[1..] {
+        exact = exact.add(&tn.contract_to_tensor().unwrap()).unwrap();
+    }
+
+    let init = star_treetn(&s1, &leaf_sites, 1);
+    let fitted = fit_sum(&terms, init, &1, &FitOptions::default()).unwrap();
+    assert!(fitted.ortho_region().contains(&1));
+    assert!(fitted.validate_ortho_consistency().is_ok());
+    // A leaf bond never needs more than the leaf dimension
+    assert_eq!(max_bond_dim(&fitted), 3);
+
+    let actual = fitted.contract_to_tensor().unwrap();
+    assert!(actual.isapprox(&exact, 0.0, 1e-10));
+}
+
+#[test]
+fn test_fit_sum_max_rank() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let terms: Vec<_> = [0, 3, 8].iter().map(|&offset| star_treetn(&s1, &leaf_sites, offset)).collect();
+
+    let mut exact = terms[0].contract_to_tensor().unwrap();
+    for tn in &terms[1..] {
+        exact = exact.add(&tn.contract_to_tensor().unwrap()).unwrap();
+    }
+
+    let init = terms[0].clone();
+    let options = FitOptions::default().with_max_rank(1).with_nsweeps(3);
+    let fitted = fit_sum(&terms, init, &0, &options).unwrap();
+    assert_eq!(max_bond_dim(&fitted), 1);
+
+    let error = fitted.contract_to_tensor().unwrap().sub(&exact).unwrap().norm();
+    assert!(error < exact.norm());
+}
+
+#[test]
+fn test_fit_apply() {
+    let s1 = Index::new_dyn(2);
+    let s1_out = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let leaf_out = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+
+    let state = star_treetn(&s1, &leaf_sites, 0);
+    let op = star_operator(&s1, &s1_out, &leaf_sites, &leaf_out);
+    let exact = op.contract_to_tensor().unwrap().contract(&state.contract_to_tensor().unwrap());
+
+    let init = star_treetn(&s1_out, &leaf_out, 2);
+    let fitted = fit_apply(&op, &state, init, &2, &FitOptions::default()).unwrap();
+    assert!(fitted.ortho_region().contains(&2));
+
+    let actual = fitted.contract_to_tensor().unwrap();
+    assert!(actual.isapprox(&exact, 0.0, 1e-10));
+}
+
+#[test]
+fn test_fit_sum_topology_mismatch() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let terms = vec![star_treetn(&s1, &leaf_sites, 0)];
+
+    let mut single: TreeTN<DynId, _, usize> = TreeTN::new();
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(
+        vec![s1.clone()],
+        vec![2],
+        Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(vec![1.0, 2.0]))),
+    );
+    single.add_tensor_with_vertex(1, tensor).unwrap();
+
+    assert!(fit_sum(&terms, single, &1, &FitOptions::default()).is_err());
+    assert!(fit_sum(&[], star_treetn(&s1, &leaf_sites, 0), &1, &FitOptions::default()).is_err());
+}
+
+#[test]
+fn test_fit_sum_site_mismatch() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let other_sites = [leaf_sites[0].clone(), leaf_sites[1].clone(), Index::new_dyn(3)];
+    let terms = vec![star_treetn(&s1, &leaf_sites, 0), star_treetn(&s1, &other_sites, 3)];
+
+    let init = star_treetn(&s1, &leaf_sites, 1);
+    assert!(fit_sum(&terms, init, &1, &FitOptions::default()).is_err());
+}
+
+#[test]
+fn test_fit_apply_site_mismatch() {
+    let s1 = Index::new_dyn(2);
+    let s1_out = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let leaf_out = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+
+    let state = star_treetn(&s1, &leaf_sites, 0);
+    let op = star_operator(&s1, &s1_out, &leaf_sites, &leaf_out);
+
+    // init carries the input site indices instead of the output ones
+    let init = star_treetn(&s1, &leaf_sites, 2);
+    assert!(fit_apply(&op, &state, init, &2, &FitOptions::default()).is_err());
+
+    // One output site index is wrong
+    let wrong_out = [leaf_out[0].clone(), leaf_out[1].clone(), Index::new_dyn(3)];
+    let init = star_treetn(&s1_out, &wrong_out, 2);
+    assert!(fit_apply(&op, &state, init, &2, &FitOptions::default()).is_err());
+}
+
+// ============================================================================
+// TreeTN inner product tests
+// ============================================================================
+
+#[test]
+fn test_treetn_inner_matches_dense() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let tn_a = star_treetn(&s1, &leaf_sites, 0);
+    let tn_b = star_treetn(&s1, &leaf_sites, 4);
+    let dense_a = tn_a.contract_to_tensor().unwrap();
+    let dense_b = tn_b.contract_to_tensor().unwrap();
+
+    let expected = dense_a.inner(&dense_b).unwrap().real();
+    let actual = tn_a.inner(&tn_b).unwrap().real();
+    assert!((actual - expected).abs() < 1e-10 * expected.abs().max(1.0));
+
+    // A network shares its bond indices with itself
+    let expected_norm_sq = dense_a.norm() * dense_a.norm();
+    let actual_norm_sq = tn_a.inner(&tn_a).unwrap().real();
+    assert!((actual_norm_sq - expected_norm_sq).abs() < 1e-10 * expected_norm_sq);
+}
+
+#[test]
+fn test_treetn_norm_and_distance() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let tn_a = star_treetn(&s1, &leaf_sites, 0);
+    let tn_b = star_treetn(&s1, &leaf_sites, 4);
+    let dense_a = tn_a.contract_to_tensor().unwrap();
+    let dense_b = tn_b.contract_to_tensor().unwrap();
+
+    let expected_norm = dense_a.norm();
+    assert!((tn_a.norm().unwrap() - expected_norm).abs() < 1e-10 * expected_norm);
+
+    let canonical = tn_a.clone().truncate(&2, &SvdOptions::default()).unwrap();
+    assert!((canonical.norm().unwrap() - expected_norm).abs() < 1e-10 * expected_norm);
+
+    // Modifying a tensor away from the center leaves the ortho metadata stale
+    let mut scaled = canonical.clone();
+    let leaf = scaled.node_index(&0).unwrap();
+    let tensor = scaled.tensor_mut(leaf).unwrap();
+    *tensor = &*tensor * 3.0;
+    let expected_scaled = scaled.contract_to_tensor().unwrap().norm();
+    assert!((scaled.norm().unwrap() - expected_scaled).abs() < 1e-10 * expected_scaled);
+
+    let expected_distance = dense_a.sub(&dense_b).unwrap().norm();
+    let actual_distance = tn_a.distance(&tn_b).unwrap();
+    assert!((actual_distance - expected_distance).abs() < 1e-8 * expected_distance);
+    assert!(tn_a.distance(&canonical).unwrap() < 1e-6 * expected_norm);
+}
+
+#[test]
+fn test_treetn_inner_incompatible() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let tn = star_treetn(&s1, &leaf_sites, 0);
+
+    // Different site indices
+    let other_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let other = star_treetn(&s1, &other_sites, 0);
+    assert!(tn.inner(&other).is_err());
+    assert!(tn.distance(&other).is_err());
+
+    let empty: TreeTN<DynId, NoSymmSpace, usize> = TreeTN::new();
+    assert!(empty.inner(&empty).is_err());
+}
+
+// ============================================================================
+// TreeTNO tests
+// ============================================================================
+
+fn dense_matrix(data: Vec<f64>) -> Arc<Storage> {
+    Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data)))
+}
+
+/// Apply `op` to `state` by full contraction, returning a tensor on the input site indices.
+fn apply_dense(
+    op: &TreeTNO<DynId, NoSymmSpace, usize>,
+    state: &TreeTN<DynId, NoSymmSpace, usize>,
+) -> TensorDynLen<DynId> {
+    let dense_op = op.as_treetn().contract_to_tensor().unwrap();
+    let mut applied = dense_op.contract(&state.contract_to_tensor().unwrap());
+    for idx in applied.indices.iter_mut() {
+        for vertex in 0..4 {
+            for (input, output) in op.site_pairs(&vertex).unwrap() {
+                if output.id == idx.id {
+                    *idx = input.clone();
+                }
+            }
+        }
+    }
+    applied
+}
+
+#[test]
+fn test_treetno_from_terms() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let state = star_treetn(&s1, &leaf_sites, 0);
+    let dense_state = state.contract_to_tensor().unwrap();
+
+    // 3 * identity
+    let identity = TreeTNO::from_terms(
+        state.site_index_network(),
+        &[OperatorTerm::new(3.0)],
+        &SvdOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(max_bond_dim(identity.as_treetn()), 1);
+    assert!(apply_dense(&identity, &state).isapprox(&(&dense_state * 3.0), 0.0, 1e-12));
+
+    // 2 * X on the center site
+    let x = dense_matrix(vec![0.0, 1.0, 1.0, 0.0]);
+    let op = TreeTNO::from_terms(
+        state.site_index_network(),
+        &[OperatorTerm::new(2.0).with_factor(s1.clone(), x.clone())],
+        &SvdOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(op.site_pairs(&1).unwrap().len(), 1);
+    let s1_out = op.output_index(&s1).unwrap().clone();
+    let mut expected = TensorDynLen::new(vec![s1_out, s1.clone()], vec![2, 2], x).contract(&dense_state);
+    for idx in expected.indices.iter_mut() {
+        if idx.id == op.output_index(&s1).unwrap().id {
+            *idx = s1.clone();
+        }
+    }
+    let expected = &expected * 2.0;
+    assert!(apply_dense(&op, &state).isapprox(&expected, 0.0, 1e-12));
+}
+
+#[test]
+fn test_treetno_sum_is_compressed() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let state = star_treetn(&s1, &leaf_sites, 0);
+    let n = dense_matrix(vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0]);
+
+    // Sum of local terms: each bond needs dimension 2
+    let terms: Vec<_> = leaf_sites
+        .iter()
+        .map(|site| OperatorTerm::new(1.0).with_factor(site.clone(), n.clone()))
+        .collect();
+    let op = TreeTNO::from_terms(state.site_index_network(), &terms, &SvdOptions::default()).unwrap();
+    assert_eq!(max_bond_dim(op.as_treetn()), 2);
+
+    let mut expected = apply_dense(
+        &TreeTNO::from_terms(state.site_index_network(), &terms[..1], &SvdOptions::default()).unwrap(),
+        &state,
+    );
+    for term in &terms[1..] {
+        let single = TreeTNO::from_terms(state.site_index_network(), &[term.clone()], &SvdOptions::default()).unwrap();
+        expected = expected.add(&apply_dense(&single, &state)).unwrap();
+    }
+    assert!(apply_dense(&op, &state).isapprox(&expected, 0.0, 1e-10));
+}
+
+#[test]
+fn test_treetno_many_terms() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let state = star_treetn(&s1, &leaf_sites, 0);
+    let n = dense_matrix(vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0]);
+
+    // More terms than are added between two compressions of the partial sum
+    let terms: Vec<_> = (0..40)
+        .map(|k| OperatorTerm::new((k + 1) as f64).with_factor(leaf_sites[k % 3].clone(), n.clone()))
+        .collect();
+    let op = TreeTNO::from_terms(state.site_index_network(), &terms, &SvdOptions::default()).unwrap();
+    assert_eq!(max_bond_dim(op.as_treetn()), 2);
+
+    let collected: Vec<_> = (0..3)
+        .map(|leaf| {
+            let coefficient: usize = (0..40).filter(|k| k % 3 == leaf).map(|k| k + 1).sum();
+            OperatorTerm::new(coefficient as f64).with_factor(leaf_sites[leaf].clone(), n.clone())
+        })
+        .collect();
+    let expected = TreeTNO::from_terms(state.site_index_network(), &collected, &SvdOptions::default()).unwrap();
+    assert!(apply_dense(&op, &state).isapprox(&apply_dense(&expected, &state), 0.0, 1e-10));
+}
+
+#[test]
+fn test_treetno_apply() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let state = star_treetn(&s1, &leaf_sites, 0);
+    let x = dense_matrix(vec![0.0, 1.0, 1.0, 0.0]);
+    let n = dense_matrix(vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0]);
+    let terms = vec![
+        OperatorTerm::new(1.0).with_factor(s1.clone(), x),
+        OperatorTerm::new(0.5)
+            .with_factor(leaf_sites[0].clone(), n.clone())
+            .with_factor(leaf_sites[2].clone(), n),
+    ];
+    let op = TreeTNO::from_terms(state.site_index_network(), &terms, &SvdOptions::default()).unwrap();
+    let expected = apply_dense(&op, &state);
+
+    let result = apply(&op, &state, &1, &SvdOptions::default()).unwrap();
+    assert!(result.can_add(&state));
+    assert!(result.ortho_region().contains(&1));
+    assert!(result.contract_to_tensor().unwrap().isapprox(&expected, 0.0, 1e-10));
+
+    let fitted = apply_fit(&op, &state, &0, &FitOptions::default()).unwrap();
+    assert!(fitted.can_add(&state));
+    assert!(fitted.contract_to_tensor().unwrap().isapprox(&expected, 0.0, 1e-10));
+
+    // Truncated application
+    let truncated = apply(&op, &state, &1, &SvdOptions::default().with_max_rank(1)).unwrap();
+    assert_eq!(max_bond_dim(&truncated), 1);
+}
+
+#[test]
+fn test_treetno_apply_incompatible_state() {
+    let s1 = Index::new_dyn(2);
+    let leaf_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let state = star_treetn(&s1, &leaf_sites, 0);
+    let op = TreeTNO::from_terms(state.site_index_network(), &[OperatorTerm::new(1.0)], &SvdOptions::default()).unwrap();
+
+    let other_sites = [Index::new_dyn(3), Index::new_dyn(3), Index::new_dyn(3)];
+    let other = star_treetn(&s1, &other_sites, 0);
+    assert!(apply(&op, &other, &1, &SvdOptions::default()).is_err());
+    assert!(apply_fit(&op, &other, &1, &FitOptions::default()).is_err());
+
+    // A factor on an index that is not a site index
+    let stranger = OperatorTerm::new(1.0).with_factor(Index::new_dyn(2), dense_matrix(vec![1.0, 0.0, 0.0, 1.0]));
+    assert!(TreeTNO::from_terms(state.site_index_network(), &[stranger], &SvdOptions::default()).is_err());
+}
+
+#[test]
+fn test_fit_apply_shared_bond_ids() {
+    let sites = [Index::new_dyn(2), Index::new_dyn(2)];
+    let out = [Index::new_dyn(2), Index::new_dyn(2)];
+    // Two-node network with the given site indices at each node
+    let pair = |site_lists: [Vec<Index<DynId>>; 2], bond: &Index<DynId>| {
+        let mut tn: TreeTN<DynId, _, usize> = TreeTN::new();
+        let mut nodes = Vec::new();
+        for (v, mut indices) in site_lists.into_iter().enumerate() {
+            indices.push(bond.clone());
+            let dims = vec![2; indices.len()];
+            let data: Vec<f64> = (0..1 << indices.len()).map(|x| 1.0 + (x % 3) as f64).collect();
+            let tensor = TensorDynLen::new(indices, dims, Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data))));
+            nodes.push(tn.add_tensor_with_vertex(v, tensor).unwrap());
+        }
+        tn.connect(nodes[0], bond, nodes[1], bond).unwrap();
+        tn
+    };
+    let state_bond = Index::new_dyn(2);
+    let state = pair([vec![sites[0].clone()], vec![sites[1].clone()]], &state_bond);
+    let op_sites = |k: usize| vec![out[k].clone(), sites[k].clone()];
+    let init = || pair([vec![out[0].clone()], vec![out[1].clone()]], &Index::new_dyn(2));
+
+    let op = pair([op_sites(0), op_sites(1)], &Index::new_dyn(2));
+    assert!(fit_apply(&op, &state, init(), &0, &FitOptions::default()).is_ok());
+
+    let op = pair([op_sites(0), op_sites(1)], &state_bond);
+    assert!(fit_apply(&op, &state, init(), &0, &FitOptions::default()).is_err());
+}
+
+// ============================================================================
+// Tree TCI tests
+// ============================================================================
+
+/// Star topology: center 0 (site 0) with leaves 1, 2 and 3 (sites 1-2, 3 and 4-5).
+fn star_topology() -> TreeTopology<usize> {
+    let mut nodes: HashMap<usize, Vec<usize>> = HashMap::new();
+    nodes.insert(0, vec![0]);
+    nodes.insert(1, vec![1, 2]);
+    nodes.insert(2, vec![3]);
+    nodes.insert(3, vec![4, 5]);
+    TreeTopology::new(nodes, vec![(0, 1), (2, 0), (0, 3)])
+}
+
+/// Dense tensor of `f` on `sites`, with argument `p` of `f` on `sites[p]`.
+fn dense_function(f: impl Fn(&[usize]) -> f64, sites: &[Index<DynId>]) -> TensorDynLen<DynId> {
+    let dims: Vec<usize> = sites.iter().map(|s| s.size()).collect();
+    let total: usize = dims.iter().product();
+    let data: Vec<f64> = (0..total)
+        .map(|mut k| {
+            let mut x = vec![0; dims.len()];
+            for p in (0..dims.len()).rev() {
+                x[p] = k % dims[p];
+                k /= dims[p];
+            }
+            f(&x)
+        })
+        .collect();
+    TensorDynLen::new(
+        sites.to_vec(),
+        dims,
+        Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data))),
+    )
+}
+
+#[test]
+fn test_crossinterpolate_tree_low_rank() {
+    let sites: Vec<Index<DynId>> = [2, 3, 2, 4, 2, 3].iter().map(|&d| Index::new_dyn(d)).collect();
+    // cos(a + b) + c has rank 3 across every cut
+    let f = |x: &[usize]| (x.iter().enumerate().map(|(p, &v)| 0.2 * (p + 1) as f64 * v as f64).sum::<f64>()).cos() + 0.1;
+
+    let (tn, ranks, errors) =
+        crossinterpolate_tree(f, &sites, &star_topology(), vec![], &TreeTCIOptions::default()).unwrap();
+    assert_eq!(*ranks.last().unwrap(), 3);
+    assert!(*errors.last().unwrap() < 1e-8);
+    assert_eq!(max_bond_dim(&tn), 3);
+    assert!(tn.validate_tree().is_ok());
+
+    let expected = dense_function(f, &sites);
+    assert!(tn.contract_to_tensor().unwrap().isapprox(&expected, 1e-10, 0.0));
+}
+
+#[test]
+fn test_crossinterpolate_tree_max_bond_dim() {
+    let sites: Vec<Index<DynId>> = [2, 3, 2, 4, 2, 3].iter().map(|&d| Index::new_dyn(d)).collect();
+    let f = |x: &[usize]| 1.0 / (1.0 + x.iter().sum::<usize>() as f64);
+    let expected = dense_function(f, &sites);
+
+    let (tn, _, _) = crossinterpolate_tree(f, &sites, &star_topology(), vec![], &TreeTCIOptions::default()).unwrap();
+    assert!(tn.contract_to_tensor().unwrap().isapprox(&expected, 1e-10, 0.0));
+
+    let options = TreeTCIOptions { max_bond_dim: 2, max_iter: 4, ..Default::default() };
+    let (truncated, ranks, errors) = crossinterpolate_tree(f, &sites, &star_topology(), vec![], &options).unwrap();
+    assert_eq!(ranks.len(), 4);
+    assert!(*errors.last().unwrap() > 1e-8);
+    assert_eq!(max_bond_dim(&truncated), 2);
+    let error = truncated.contract_to_tensor().unwrap().sub(&expected).unwrap().norm();
+    assert!(error < 0.1 * expected.norm());
+}
+
+#[test]
+fn test_crossinterpolate_tree_invalid_input() {
+    let sites: Vec<Index<DynId>> = [2, 3, 2, 4, 2, 3].iter().map(|&d| Index::new_dyn(d)).collect();
+    let f = |x: &[usize]| 1.0 + x[0] as f64;
+    let options = TreeTCIOptions::default();
+
+    assert!(crossinterpolate_tree(f, &sites, &star_topology(), vec![], &options).is_ok());
+    // The topology refers to site 5, which is out of range
+    assert!(crossinterpolate_tree(f, &sites[..5], &star_topology(), vec![], &options).is_err());
+
+    // The function vanishes on the initial pivot
+    let g = |x: &[usize]| x[0] as f64;
+    assert!(crossinterpolate_tree(g, &sites, &star_topology(), vec![], &options).is_err());
+    assert!(crossinterpolate_tree(g, &sites, &star_topology(), vec![vec![1, 0, 0, 0, 0, 0]], &options).is_ok());
+}
+
+#[test]
+fn test_crossinterpolate_tree_duplicate_initial_pivots() {
+    let sites: Vec<Index<DynId>> = [2, 3, 2, 4, 2, 3].iter().map(|&d| Index::new_dyn(d)).collect();
+    let f = |x: &[usize]| (1.0 + x[0] as f64) * (1.0 + x[3] as f64);
+    // Both pivots agree on every site except site 0, so they coincide on the leaf side of every edge
+    let pivots = vec![vec![0; 6], vec![1, 0, 0, 0, 0, 0]];
+    let options = TreeTCIOptions { max_iter: 0, ..Default::default() };
+
+    let (tn, ranks, _) = crossinterpolate_tree(f, &sites, &star_topology(), pivots, &options).unwrap();
+    assert!(ranks.is_empty());
+    assert_eq!(max_bond_dim(&tn), 1);
+    assert!(tn.contract_to_tensor().unwrap().isapprox(&dense_function(f, &sites), 1e-12, 0.0));
+}
@@ -6,6 +6,8 @@ use rand::Rng;
 ///
 /// Uses UInt128 for extremely low collision probability (see design.md for analysis).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct DynId(pub u128);
 
 /// Trait for symmetry information (quantum number space).
@@ -18,12 +20,20 @@ pub trait Symmetry: Clone + PartialEq + Eq + std::hash::Hash {
     /// For no symmetry, this is just the dimension.
     /// For quantum number spaces, this is the sum of all block dimensions.
     fn total_dim(&self) -> usize;
+
+    /// Return the dual space (ITensors.jl's `dag`).
+    ///
+    /// Spaces without a direction are their own dual.
+    fn dag(&self) -> Self {
+        self.clone()
+    }
 }
 
 /// No symmetry space (corresponds to ITensors.jl's `Index{Int}`).
 ///
 /// This represents a simple index with no quantum number structure.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoSymmSpace {
     dim: usize,
 }
@@ -51,12 +61,16 @@ impl Symmetry for NoSymmSpace {
 /// - `Id = DynId` for ITensors-like runtime identity
 /// - `Id = ZST marker type` for compile-time-known identity
 /// - `Symm = NoSymmSpace` for no symmetry (default, corresponds to `Index{Int}` in ITensors.jl)
-/// - `Symm = QNSpace` (future) for quantum number spaces (corresponds to `Index{QNBlocks}` in ITensors.jl)
+/// - `Symm = QNSpace` for quantum number spaces (corresponds to `Index{QNBlocks}` in ITensors.jl)
 /// - `Tags = DefaultTagSet` for tags (default, max 4 tags, each max 16 characters)
 ///
 /// **Equality**: Two `Index` values are considered equal if and only if their `id` fields match.
 /// Tags are not used for equality comparison.
+///
+/// With the `serde` feature, an index serializes its `id`, `symm` and `tags`,
+/// so deserialized indices keep their identity.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Index<Id, Symm = NoSymmSpace, Tags = DefaultTagSet> {
     pub id: Id,
     pub symm: Symm,
@@ -99,6 +113,21 @@ where
     }
 }
 
+impl<Id: Clone, Symm: Symmetry, Tags: Clone> Index<Id, Symm, Tags> {
+    /// Return the index with its space replaced by the dual space (ITensors.jl's `dag`).
+    ///
+    /// For quantum number indices this reverses the direction; indices without
+    /// symmetry are returned unchanged. The ID is kept, so the result still matches
+    /// `self` in contractions.
+    pub fn dag(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            symm: self.symm.dag(),
+            tags: self.tags.clone(),
+        }
+    }
+}
+
 impl<Id, Tags> Index<Id, NoSymmSpace, Tags>
 where
     Tags: Default,
@@ -198,6 +227,23 @@ where
     }
 }
 
+impl<Symm: Symmetry, Tags> Index<DynId, Symm, Tags>
+where
+    Tags: Default,
+{
+    /// Create a new index with a generated dynamic ID and the given symmetry space.
+    ///
+    /// This is the dynamic-ID constructor for spaces other than `NoSymmSpace`,
+    /// e.g. quantum number spaces.
+    pub fn new_dyn_with_space(symm: Symm) -> Self {
+        Self {
+            id: DynId(generate_id()),
+            symm,
+            tags: Tags::default(),
+        }
+    }
+}
+
 // Equality and Hash implementations: only compare by `id`
 impl<Id: PartialEq, Symm, Tags> PartialEq for Index<Id, Symm, Tags> {
     fn eq(&self, other: &Self) -> bool {
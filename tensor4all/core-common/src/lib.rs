@@ -1,5 +1,6 @@
 pub mod index;
 pub mod index_ops;
+pub mod qn;
 pub mod smallstring;
 pub mod tagset;
 
@@ -10,6 +11,7 @@ pub use index_ops::{
     sim, sim_owned, replaceinds, replaceinds_in_place, ReplaceIndsError, unique_inds,
     noncommon_inds, union_inds, hasind, hasinds, hascommoninds, common_inds, check_unique_indices,
 };
+pub use qn::{Arrow, QN, QNError, QNSpace, MAX_QN_SECTORS};
 pub use smallstring::{SmallString, SmallStringError};
 pub use tagset::{DefaultTagSet, Tag, TagSet, TagSetError, TagSetLike, TagSetIterator};
 
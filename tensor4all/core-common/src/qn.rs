@@ -0,0 +1,355 @@
+use crate::index::{Index, NoSymmSpace, Symmetry};
+use std::ops::{Add, Neg, Sub};
+
+/// Maximum number of sectors in a quantum number (matching ITensors.jl's `maxQNs`).
+pub const MAX_QN_SECTORS: usize = 4;
+
+/// Error type for quantum number operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QNError {
+    TooManySectors { actual: usize, max: usize },
+    InvalidModulus { sector: usize },
+    SectorMismatch,
+}
+
+impl std::fmt::Display for QNError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QNError::TooManySectors { actual, max } => {
+                write!(f, "Too many QN sectors: {} (max {})", actual, max)
+            }
+            QNError::InvalidModulus { sector } => {
+                write!(f, "Invalid modulus in QN sector {}", sector)
+            }
+            QNError::SectorMismatch => write!(f, "QN sectors do not match"),
+        }
+    }
+}
+
+impl std::error::Error for QNError {}
+
+/// Abelian quantum number.
+///
+/// A quantum number consists of up to [`MAX_QN_SECTORS`] sectors, each of which is
+/// either a U(1) charge (modulus 1, as in ITensors.jl) or a Z_n charge (modulus n ≥ 2).
+/// Z_n values are always kept in the range `0..n`.
+///
+/// Quantum numbers combine by addition; `-q` is the charge flowing in the opposite direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QN {
+    values: [i64; MAX_QN_SECTORS],
+    moduli: [u32; MAX_QN_SECTORS],
+    nsectors: usize,
+}
+
+impl QN {
+    /// Create a quantum number from `(value, modulus)` pairs.
+    ///
+    /// Use modulus 1 for U(1) sectors and modulus n ≥ 2 for Z_n sectors.
+    pub fn new(sectors: &[(i64, u32)]) -> Result<Self, QNError> {
+        if sectors.len() > MAX_QN_SECTORS {
+            return Err(QNError::TooManySectors {
+                actual: sectors.len(),
+                max: MAX_QN_SECTORS,
+            });
+        }
+        let mut qn = Self::default();
+        for (s, &(value, modulus)) in sectors.iter().enumerate() {
+            if modulus == 0 {
+                return Err(QNError::InvalidModulus { sector: s });
+            }
+            qn.moduli[s] = modulus;
+            qn.values[s] = reduce(value, modulus);
+        }
+        qn.nsectors = sectors.len();
+        Ok(qn)
+    }
+
+    /// Create a quantum number with a single U(1) sector.
+    pub fn u1(value: i64) -> Self {
+        Self::new(&[(value, 1)]).expect("a single U(1) sector is always valid")
+    }
+
+    /// Create a quantum number with a single Z_n sector.
+    ///
+    /// # Panics
+    /// Panics if `modulus` is zero.
+    pub fn zn(value: i64, modulus: u32) -> Self {
+        Self::new(&[(value, modulus)]).expect("modulus must be nonzero")
+    }
+
+    /// Get the number of sectors.
+    pub fn nsectors(&self) -> usize {
+        self.nsectors
+    }
+
+    /// Get the value of a sector.
+    pub fn value(&self, sector: usize) -> Option<i64> {
+        (sector < self.nsectors).then(|| self.values[sector])
+    }
+
+    /// Get the modulus of a sector (1 for U(1)).
+    pub fn modulus(&self, sector: usize) -> Option<u32> {
+        (sector < self.nsectors).then(|| self.moduli[sector])
+    }
+
+    /// Check if all sector values are zero.
+    ///
+    /// A quantum number without sectors is also zero.
+    pub fn is_zero(&self) -> bool {
+        self.values[..self.nsectors].iter().all(|&v| v == 0)
+    }
+
+    /// Return the zero quantum number with the same sectors.
+    pub fn zero_like(&self) -> Self {
+        Self {
+            values: [0; MAX_QN_SECTORS],
+            ..*self
+        }
+    }
+
+    /// Add two quantum numbers, checking that their sectors match.
+    ///
+    /// A quantum number without sectors acts as the identity.
+    pub fn try_add(&self, other: &Self) -> Result<Self, QNError> {
+        if self.nsectors == 0 {
+            return Ok(*other);
+        }
+        if other.nsectors == 0 {
+            return Ok(*self);
+        }
+        if self.nsectors != other.nsectors
+            || self.moduli[..self.nsectors] != other.moduli[..other.nsectors]
+        {
+            return Err(QNError::SectorMismatch);
+        }
+        let mut result = *self;
+        for s in 0..self.nsectors {
+            result.values[s] = reduce(self.values[s] + other.values[s], self.moduli[s]);
+        }
+        Ok(result)
+    }
+}
+
+impl Default for QN {
+    /// The quantum number without sectors (neutral element).
+    fn default() -> Self {
+        Self {
+            values: [0; MAX_QN_SECTORS],
+            moduli: [1; MAX_QN_SECTORS],
+            nsectors: 0,
+        }
+    }
+}
+
+/// Serialized as a sequence of `(value, modulus)` sector pairs.
+#[cfg(feature = "serde")]
+impl serde::Serialize for QN {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..self.nsectors).map(|s| (self.values[s], self.moduli[s])))
+    }
+}
+
+/// Sectors go through [`QN::new`], so the number of sectors and the moduli are
+/// checked and Z_n values are reduced.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for QN {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let sectors = Vec::<(i64, u32)>::deserialize(deserializer)?;
+        QN::new(&sectors).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Reduce a value into `0..modulus` for Z_n sectors; U(1) values are unchanged.
+fn reduce(value: i64, modulus: u32) -> i64 {
+    if modulus > 1 {
+        value.rem_euclid(modulus as i64)
+    } else {
+        value
+    }
+}
+
+/// # Panics
+/// Panics if the sectors of the two quantum numbers don't match.
+impl Add for QN {
+    type Output = QN;
+
+    fn add(self, rhs: QN) -> QN {
+        self.try_add(&rhs).expect("QN sectors must match for addition")
+    }
+}
+
+impl Neg for QN {
+    type Output = QN;
+
+    fn neg(self) -> QN {
+        let mut result = self;
+        for s in 0..self.nsectors {
+            result.values[s] = reduce(-self.values[s], self.moduli[s]);
+        }
+        result
+    }
+}
+
+/// # Panics
+/// Panics if the sectors of the two quantum numbers don't match.
+impl Sub for QN {
+    type Output = QN;
+
+    fn sub(self, rhs: QN) -> QN {
+        self + (-rhs)
+    }
+}
+
+impl std::fmt::Display for QN {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "QN(")?;
+        for s in 0..self.nsectors {
+            if s > 0 {
+                write!(f, ", ")?;
+            }
+            if self.moduli[s] > 1 {
+                write!(f, "{} mod {}", self.values[s], self.moduli[s])?;
+            } else {
+                write!(f, "{}", self.values[s])?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+/// Direction of an index carrying quantum numbers (corresponds to ITensors.jl's `Arrow`).
+///
+/// Quantum numbers of `Out` indices contribute with a positive sign to the flux of a
+/// tensor, those of `In` indices with a negative sign. Contracted indices are expected
+/// to have opposite directions (see [`Index::dag`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Arrow {
+    In,
+    #[default]
+    Out,
+}
+
+impl Arrow {
+    /// Return the opposite direction.
+    pub fn reverse(self) -> Self {
+        match self {
+            Arrow::In => Arrow::Out,
+            Arrow::Out => Arrow::In,
+        }
+    }
+
+    /// Sign of the direction (+1 for `Out`, -1 for `In`).
+    pub fn sign(self) -> i64 {
+        match self {
+            Arrow::In => -1,
+            Arrow::Out => 1,
+        }
+    }
+}
+
+/// Quantum number space (corresponds to ITensors.jl's `Index{QNBlocks}`).
+///
+/// The space is a direct sum of blocks, each labeled by a quantum number, together
+/// with an index direction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QNSpace {
+    blocks: Vec<(QN, usize)>,
+    dir: Arrow,
+}
+
+impl QNSpace {
+    /// Create a new QN space from `(qn, block dimension)` pairs and a direction.
+    pub fn new(blocks: Vec<(QN, usize)>, dir: Arrow) -> Self {
+        Self { blocks, dir }
+    }
+
+    /// Get the blocks as `(qn, block dimension)` pairs.
+    pub fn blocks(&self) -> &[(QN, usize)] {
+        &self.blocks
+    }
+
+    /// Get the number of blocks.
+    pub fn nblocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Get the quantum number of a block.
+    pub fn qn(&self, block: usize) -> QN {
+        self.blocks[block].0
+    }
+
+    /// Get the dimension of a block.
+    pub fn block_dim(&self, block: usize) -> usize {
+        self.blocks[block].1
+    }
+
+    /// Get the dimensions of all blocks.
+    pub fn block_dims(&self) -> Vec<usize> {
+        self.blocks.iter().map(|&(_, d)| d).collect()
+    }
+
+    /// Get the offset of a block within the full index range.
+    pub fn block_offset(&self, block: usize) -> usize {
+        self.blocks[..block].iter().map(|&(_, d)| d).sum()
+    }
+
+    /// Find the block containing index value `i` and the position within that block.
+    pub fn block_of(&self, i: usize) -> Option<(usize, usize)> {
+        let mut offset = 0;
+        for (b, &(_, d)) in self.blocks.iter().enumerate() {
+            if i < offset + d {
+                return Some((b, i - offset));
+            }
+            offset += d;
+        }
+        None
+    }
+
+    /// Get the direction.
+    pub fn dir(&self) -> Arrow {
+        self.dir
+    }
+
+    /// Return the same space with the direction reversed (ITensors.jl's `dag`).
+    pub fn dag(&self) -> Self {
+        Self {
+            blocks: self.blocks.clone(),
+            dir: self.dir.reverse(),
+        }
+    }
+
+    /// Quantum number of a block including the sign of the direction.
+    pub fn flux_of_block(&self, block: usize) -> QN {
+        match self.dir {
+            Arrow::Out => self.qn(block),
+            Arrow::In => -self.qn(block),
+        }
+    }
+}
+
+impl Symmetry for QNSpace {
+    fn total_dim(&self) -> usize {
+        self.blocks.iter().map(|&(_, d)| d).sum()
+    }
+
+    fn dag(&self) -> Self {
+        QNSpace::dag(self)
+    }
+}
+
+/// A space without symmetry is a single block with the zero quantum number.
+impl From<NoSymmSpace> for QNSpace {
+    fn from(space: NoSymmSpace) -> Self {
+        Self::new(vec![(QN::default(), space.dim())], Arrow::Out)
+    }
+}
+
+impl<Id: Clone, Tags: Clone> Index<Id, QNSpace, Tags> {
+    /// Get the direction of the index.
+    pub fn dir(&self) -> Arrow {
+        self.symm.dir()
+    }
+}
@@ -113,3 +113,18 @@ impl<const MAX_LEN: usize> std::fmt::Display for SmallString<MAX_LEN> {
     }
 }
 
+
+#[cfg(feature = "serde")]
+impl<const MAX_LEN: usize> serde::Serialize for SmallString<MAX_LEN> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const MAX_LEN: usize> serde::Deserialize<'de> for SmallString<MAX_LEN> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(|e| serde::de::Error::custom(format!("{:?}", e)))
+    }
+}
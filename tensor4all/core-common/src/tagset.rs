@@ -311,6 +311,30 @@ impl<const MAX_TAGS: usize, const MAX_TAG_LEN: usize> PartialEq for TagSet<MAX_T
 
 impl<const MAX_TAGS: usize, const MAX_TAG_LEN: usize> Eq for TagSet<MAX_TAGS, MAX_TAG_LEN> {}
 
+/// Serialized as a sequence of tag strings.
+#[cfg(feature = "serde")]
+impl<const MAX_TAGS: usize, const MAX_TAG_LEN: usize> serde::Serialize for TagSet<MAX_TAGS, MAX_TAG_LEN> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|tag| tag.as_str()))
+    }
+}
+
+/// Tags are re-added one by one, so capacity and length limits are checked
+/// and the sorted order is restored.
+#[cfg(feature = "serde")]
+impl<'de, const MAX_TAGS: usize, const MAX_TAG_LEN: usize> serde::Deserialize<'de> for TagSet<MAX_TAGS, MAX_TAG_LEN> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tags = Vec::<String>::deserialize(deserializer)?;
+        let mut tagset = Self::new();
+        for tag in &tags {
+            tagset
+                .add_tag(tag)
+                .map_err(|e| serde::de::Error::custom(format!("{:?}", e)))?;
+        }
+        Ok(tagset)
+    }
+}
+
 /// Default tag type (max 16 characters, matching ITensors.jl's `SmallString`).
 pub type Tag = SmallString<16>;
 
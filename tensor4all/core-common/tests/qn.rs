@@ -0,0 +1,91 @@
+use tensor4all_core_common::index::{Index, DynId};
+use tensor4all_core_common::qn::{Arrow, QN, QNError, QNSpace};
+use tensor4all_core_common::tagset::DefaultTagSet;
+
+type QNIndex = Index<DynId, QNSpace, DefaultTagSet>;
+
+#[test]
+fn test_qn_u1_arithmetic() {
+    let a = QN::u1(2);
+    let b = QN::u1(-3);
+    assert_eq!(a + b, QN::u1(-1));
+    assert_eq!(a - b, QN::u1(5));
+    assert_eq!(-a, QN::u1(-2));
+    assert!((a + (-a)).is_zero());
+    assert_eq!(a.zero_like(), QN::u1(0));
+}
+
+#[test]
+fn test_qn_zn_wraps() {
+    let a = QN::zn(2, 3);
+    assert_eq!(a + a, QN::zn(1, 3));
+    assert_eq!(-a, QN::zn(1, 3));
+    assert_eq!(QN::zn(-1, 3).value(0), Some(2));
+    assert!((a + a + a).is_zero());
+}
+
+#[test]
+fn test_qn_multiple_sectors() {
+    let a = QN::new(&[(1, 1), (1, 2)]).unwrap();
+    let b = QN::new(&[(-1, 1), (1, 2)]).unwrap();
+    assert!((a + b).is_zero());
+    assert_eq!(a.nsectors(), 2);
+    assert_eq!(a.modulus(1), Some(2));
+    assert_eq!(a.value(2), None);
+}
+
+#[test]
+fn test_qn_errors() {
+    assert_eq!(QN::u1(1).try_add(&QN::zn(1, 2)), Err(QNError::SectorMismatch));
+    assert!(matches!(
+        QN::new(&[(0, 1); 5]),
+        Err(QNError::TooManySectors { actual: 5, max: 4 })
+    ));
+    assert_eq!(QN::new(&[(0, 0)]), Err(QNError::InvalidModulus { sector: 0 }));
+}
+
+#[test]
+fn test_qn_without_sectors_is_identity() {
+    let a = QN::u1(3);
+    assert_eq!(QN::default() + a, a);
+    assert_eq!(a + QN::default(), a);
+    assert!(QN::default().is_zero());
+}
+
+#[test]
+fn test_qn_space_blocks() {
+    let space = QNSpace::new(vec![(QN::u1(0), 1), (QN::u1(1), 2), (QN::u1(2), 1)], Arrow::Out);
+    assert_eq!(space.nblocks(), 3);
+    assert_eq!(space.block_dims(), vec![1, 2, 1]);
+    assert_eq!(space.block_offset(2), 3);
+    assert_eq!(space.block_of(0), Some((0, 0)));
+    assert_eq!(space.block_of(2), Some((1, 1)));
+    assert_eq!(space.block_of(3), Some((2, 0)));
+    assert_eq!(space.block_of(4), None);
+
+    let dagged = space.dag();
+    assert_eq!(dagged.dir(), Arrow::In);
+    assert_eq!(space.flux_of_block(1), QN::u1(1));
+    assert_eq!(dagged.flux_of_block(1), QN::u1(-1));
+}
+
+#[test]
+fn test_qn_index() {
+    let space = QNSpace::new(vec![(QN::u1(0), 2), (QN::u1(1), 3)], Arrow::Out);
+    let i = QNIndex::new_dyn_with_space(space);
+    assert_eq!(i.size(), 5);
+    assert_eq!(i.dir(), Arrow::Out);
+
+    // dag keeps the identity and flips the direction
+    let i_dag = i.dag();
+    assert_eq!(i_dag, i);
+    assert_eq!(i_dag.dir(), Arrow::In);
+}
+
+#[test]
+fn test_qn_link_index_from_no_symm() {
+    let link = QNIndex::new_link(4).unwrap();
+    assert_eq!(link.size(), 4);
+    assert_eq!(link.symm.nblocks(), 1);
+    assert!(link.symm.qn(0).is_zero());
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "serde")]
+
+use tensor4all_core_common::index::{DefaultIndex as Index, DynId, NoSymmSpace};
+use tensor4all_core_common::qn::QN;
+use tensor4all_core_common::tagset::DefaultTagSet;
+
+#[test]
+fn test_dyn_id_roundtrip() {
+    let id = DynId(u128::MAX - 7);
+    let json = serde_json::to_string(&id).unwrap();
+    let restored: DynId = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, id);
+}
+
+#[test]
+fn test_tagset_roundtrip() {
+    let tags = DefaultTagSet::from_str("Site,n=1,Link").unwrap();
+    let json = serde_json::to_string(&tags).unwrap();
+    assert_eq!(json, r#"["Link","Site","n=1"]"#);
+
+    let restored: DefaultTagSet = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, tags);
+}
+
+#[test]
+fn test_tagset_deserialize_sorts_and_validates() {
+    let restored: DefaultTagSet = serde_json::from_str(r#"["b","a"]"#).unwrap();
+    assert_eq!(restored.get(0).unwrap().as_str(), "a");
+    assert_eq!(restored.get(1).unwrap().as_str(), "b");
+
+    // Too many tags
+    assert!(serde_json::from_str::<DefaultTagSet>(r#"["a","b","c","d","e"]"#).is_err());
+    // Tag too long
+    assert!(serde_json::from_str::<DefaultTagSet>(r#"["abcdefghijklmnopq"]"#).is_err());
+}
+
+#[test]
+fn test_index_roundtrip_preserves_id() {
+    let idx: Index<DynId, NoSymmSpace> = Index::new_dyn_with_tag(5, "Site").unwrap();
+    let json = serde_json::to_string(&idx).unwrap();
+    let restored: Index<DynId, NoSymmSpace> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored.id, idx.id);
+    assert_eq!(restored.size(), 5);
+    assert_eq!(restored.tags(), idx.tags());
+    assert!(restored.tags().has_tag("Site"));
+}
+
+#[test]
+fn test_qn_roundtrip() {
+    let qn = QN::new(&[(-3, 1), (2, 4)]).unwrap();
+    let json = serde_json::to_string(&qn).unwrap();
+    assert_eq!(json, "[[-3,1],[2,4]]");
+
+    let restored: QN = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, qn);
+    assert_eq!(serde_json::from_str::<QN>("[]").unwrap(), QN::default());
+}
+
+#[test]
+fn test_qn_deserialize_validates() {
+    // Z_n values are reduced, so equal charges compare equal
+    let restored: QN = serde_json::from_str("[[5,3]]").unwrap();
+    assert_eq!(restored, QN::zn(2, 3));
+
+    // Too many sectors
+    assert!(serde_json::from_str::<QN>("[[0,1],[0,1],[0,1],[0,1],[0,1]]").is_err());
+    // Zero modulus
+    assert!(serde_json::from_str::<QN>("[[1,0]]").is_err());
+    // The in-memory layout is not accepted
+    assert!(serde_json::from_str::<QN>(r#"{"values":[0,0,0,0],"moduli":[1,1,1,1],"nsectors":9}"#).is_err());
+}
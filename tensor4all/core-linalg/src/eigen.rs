@@ -0,0 +1,258 @@
+use faer::{MatRef, Side};
+use num_complex::Complex64;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tensor4all_core_common::index::{DynId, Index, NoSymmSpace, Symmetry};
+use tensor4all_core_common::index_ops::sim;
+use tensor4all_core_common::tagset::DefaultTagSet;
+use tensor4all_core_tensor::{unfold_split, Storage, StorageScalar, TensorDynLen};
+use thiserror::Error;
+
+/// Error type for eigendecomposition and matrix functions in tensor4all-linalg.
+#[derive(Debug, Error)]
+pub enum EigenError {
+    #[error("Eigendecomposition failed: {0}")]
+    ComputationError(#[from] anyhow::Error),
+    #[error("Matrix is not Hermitian: max |A - A^H| = {0}")]
+    NotHermitian(f64),
+    #[error("Matrix is not positive semidefinite: smallest eigenvalue = {0}")]
+    NotPositiveSemidefinite(f64),
+    #[error("Matrix is not positive definite: smallest eigenvalue = {0}")]
+    NotPositiveDefinite(f64),
+    #[error("Matrix has non-finite entries")]
+    NotFinite,
+}
+
+/// Relative tolerance for the Hermiticity check and for treating eigenvalues as zero.
+pub(crate) const HERMITIAN_RTOL: f64 = 1e-10;
+
+/// Scalar types supported by eigendecomposition and matrix functions.
+///
+/// Computations are carried out in `Complex64`; real results are recovered by
+/// dropping the (vanishing) imaginary part.
+pub trait EigenScalar: StorageScalar + Into<Complex64> {
+    /// Convert from `Complex64`, dropping the imaginary part for real types.
+    fn from_c64(z: Complex64) -> Self;
+}
+
+impl EigenScalar for f64 {
+    fn from_c64(z: Complex64) -> Self {
+        z.re
+    }
+}
+
+impl EigenScalar for Complex64 {
+    fn from_c64(z: Complex64) -> Self {
+        z
+    }
+}
+
+/// A tensor unfolded into a square matrix with paired row and column indices.
+pub(crate) struct SquareUnfolding<Id, Symm> {
+    /// Row-major matrix data (n×n).
+    pub(crate) data: Vec<Complex64>,
+    /// Matrix dimension.
+    pub(crate) n: usize,
+    /// Row indices (`left_inds`).
+    pub(crate) left_indices: Vec<Index<Id, Symm>>,
+    /// Column indices (`right_inds`), in the given order.
+    pub(crate) right_indices: Vec<Index<Id, Symm>>,
+}
+
+/// Check that `left_inds` and `right_inds` pair up and cover all indices of the tensor.
+fn validate_pairing<Id, Symm>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    right_inds: &[Index<Id, Symm>],
+) -> anyhow::Result<()>
+where
+    Id: std::hash::Hash + Eq,
+    Symm: Symmetry,
+{
+    anyhow::ensure!(
+        left_inds.len() == right_inds.len(),
+        "left_inds and right_inds must have the same length, got {} and {}",
+        left_inds.len(),
+        right_inds.len()
+    );
+    anyhow::ensure!(
+        left_inds.len() + right_inds.len() == t.indices.len(),
+        "left_inds and right_inds must cover all {} indices of the tensor",
+        t.indices.len()
+    );
+    for (k, (l, r)) in left_inds.iter().zip(right_inds).enumerate() {
+        anyhow::ensure!(
+            l.size() == r.size(),
+            "Paired indices must have the same dimension: left_inds[{}] has {}, right_inds[{}] has {}",
+            k,
+            l.size(),
+            k,
+            r.size()
+        );
+    }
+    let tensor_ids: HashSet<_> = t.indices.iter().map(|idx| &idx.id).collect();
+    let mut seen = HashSet::new();
+    for idx in left_inds.iter().chain(right_inds) {
+        anyhow::ensure!(tensor_ids.contains(&idx.id), "Index not found in tensor");
+        anyhow::ensure!(seen.insert(&idx.id), "Duplicate index in left_inds/right_inds");
+    }
+    Ok(())
+}
+
+/// Unfold a tensor into a square matrix with rows `left_inds` and columns `right_inds`.
+///
+/// `left_inds[k]` is paired with `right_inds[k]` and both must have the same dimension.
+/// Together they must cover all indices of the tensor.
+pub(crate) fn unfold_square<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    right_inds: &[Index<Id, Symm>],
+) -> Result<SquareUnfolding<Id, Symm>, EigenError>
+where
+    Id: Clone + std::hash::Hash + Eq,
+    Symm: Clone + Symmetry,
+    T: EigenScalar,
+{
+    validate_pairing(t, left_inds, right_inds)?;
+
+    // Bring the right indices into the given order, then unfold
+    let order: Vec<Index<Id, Symm>> = left_inds.iter().chain(right_inds).cloned().collect();
+    let permuted = t.permute_indices(&order);
+    let (matrix, _, m, n, left_indices, right_indices) = unfold_split::<Id, T, Symm>(&permuted, left_inds)
+        .map_err(|e| anyhow::anyhow!("Failed to unfold tensor: {}", e))?;
+    debug_assert_eq!(m, n);
+
+    let mut data = Vec::with_capacity(n * n);
+    for i in 0..n {
+        for j in 0..n {
+            data.push(matrix[[i, j]].into());
+        }
+    }
+    Ok(SquareUnfolding {
+        data,
+        n,
+        left_indices,
+        right_indices,
+    })
+}
+
+/// Check that a row-major n×n matrix is Hermitian within `HERMITIAN_RTOL`.
+pub(crate) fn check_hermitian(a: &[Complex64], n: usize) -> Result<(), EigenError> {
+    let scale = a.iter().map(|z| z.norm()).fold(0.0, f64::max).max(1.0);
+    let mut deviation: f64 = 0.0;
+    for i in 0..n {
+        for j in i..n {
+            deviation = deviation.max((a[i * n + j] - a[j * n + i].conj()).norm());
+        }
+    }
+    if deviation > HERMITIAN_RTOL * scale {
+        return Err(EigenError::NotHermitian(deviation));
+    }
+    Ok(())
+}
+
+/// Diagonalize a Hermitian row-major n×n matrix with faer's self-adjoint eigensolver.
+///
+/// # Returns
+/// A tuple `(eigenvalues, vectors)` where the eigenvalues are sorted in descending order
+/// and `vectors` is the row-major n×n unitary matrix whose k-th column is the
+/// eigenvector of the k-th eigenvalue.
+pub(crate) fn hermitian_eigen(a: &[Complex64], n: usize) -> Result<(Vec<f64>, Vec<Complex64>), EigenError> {
+    // faer does not accept empty matrices
+    if n == 0 {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let evd = MatRef::from_row_major_slice(a, n, n)
+        .self_adjoint_eigen(Side::Lower)
+        .map_err(|e| anyhow::anyhow!("Self-adjoint eigendecomposition failed: {:?}", e))?;
+    let (s, u) = (evd.S().column_vector(), evd.U());
+
+    // faer sorts the eigenvalues in ascending order; reverse them (as in ITensors.jl)
+    let eigenvalues = (0..n).rev().map(|k| s[k].re).collect();
+    let mut vectors = Vec::with_capacity(n * n);
+    for i in 0..n {
+        vectors.extend((0..n).rev().map(|k| u[(i, k)]));
+    }
+    Ok((eigenvalues, vectors))
+}
+
+/// Compute the eigendecomposition of a Hermitian tensor viewed as a matrix, returning (D, U).
+///
+/// This mimics ITensors.jl's `eigen(A, Lis, Ris)`: the tensor is viewed as a matrix with
+/// rows `left_inds` and columns `right_inds`, where `left_inds[k]` is paired with
+/// `right_inds[k]` (typically the primed and unprimed version of the same site index).
+/// The decomposition satisfies
+/// \[ A = U' * D * U^H \]
+/// where `U'` is `U` with `right_inds` replaced by `left_inds`. Equivalently, contracting
+/// `A` with `U` over `right_inds` gives `U' * D`.
+///
+/// # Arguments
+/// * `t` - Input tensor with DenseF64 or DenseC64 storage
+/// * `left_inds` - Row indices of the matrix
+/// * `right_inds` - Column indices of the matrix, paired with `left_inds`
+///
+/// # Returns
+/// A tuple `(D, U)` where:
+/// - `D` is a r×r diagonal tensor with indices `[bond_index, sim(bond_index)]` holding the
+///   (real) eigenvalues in descending order
+/// - `U` is a tensor with indices `[right_inds..., bond_index]` whose columns are the
+///   orthonormal eigenvectors
+///
+/// # Errors
+/// Returns `EigenError` if:
+/// - `left_inds` and `right_inds` don't pair up (different lengths or dimensions) or
+///   don't cover all indices of the tensor
+/// - Storage is not DenseF64 or DenseC64
+/// - The matrix is not Hermitian
+/// - The eigendecomposition does not converge
+pub fn eigen<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    right_inds: &[Index<Id, Symm>],
+) -> Result<(TensorDynLen<Id, Symm>, TensorDynLen<Id, Symm>), EigenError>
+where
+    Id: Clone + std::hash::Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    T: EigenScalar,
+{
+    let SquareUnfolding {
+        data,
+        n,
+        right_indices,
+        ..
+    } = unfold_square::<Id, Symm, T>(t, left_inds, right_inds)?;
+    check_hermitian(&data, n)?;
+    let (eigenvalues, vectors) = hermitian_eigen(&data, n)?;
+
+    // Create bond index with "Link" tag
+    let bond_index: Index<Id, Symm, DefaultTagSet> = Index::new_link(n)
+        .map_err(|e| anyhow::anyhow!("Failed to create Link index: {:?}", e))?;
+
+    // Create D tensor: [bond_index, sim(bond_index)] (diagonal, real)
+    let d_indices = vec![bond_index.clone(), sim(&bond_index)];
+    let d = TensorDynLen::from_indices(d_indices, Arc::new(Storage::new_diag_f64(eigenvalues)));
+
+    // Create U tensor: [right_inds..., bond_index]
+    let mut u_indices = right_indices;
+    u_indices.push(bond_index);
+    let u_storage = T::dense_storage(vectors.into_iter().map(T::from_c64).collect());
+    let u = TensorDynLen::from_indices(u_indices, u_storage);
+
+    Ok((d, u))
+}
+
+/// Compute the eigendecomposition of a complex Hermitian tensor, returning (D, U).
+///
+/// This is a convenience wrapper around the generic `eigen` function for `Complex64` tensors.
+#[inline]
+pub fn eigen_c64<Id, Symm>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    right_inds: &[Index<Id, Symm>],
+) -> Result<(TensorDynLen<Id, Symm>, TensorDynLen<Id, Symm>), EigenError>
+where
+    Id: Clone + std::hash::Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+{
+    eigen::<Id, Symm, Complex64>(t, left_inds, right_inds)
+}
@@ -1,10 +1,22 @@
 mod backend;
+pub mod eigen;
+pub mod lq;
+mod matrix;
+pub mod matrix_functions;
+pub mod polar;
 pub mod qr;
+pub mod rsvd;
 pub mod svd;
 
+pub use eigen::{eigen, eigen_c64, EigenError, EigenScalar};
+pub use lq::{lq, lq_c64, lq_with};
+pub use matrix_functions::{exp, inv_sqrt, sqrt};
+pub use polar::{polar, polar_c64};
 pub use qr::{
     default_qr_rtol, qr, qr_c64, qr_with, set_default_qr_rtol, QrError, QrOptions,
 };
+pub use rsvd::{rsvd, rsvd_c64, RsvdOptions};
 pub use svd::{
-    default_svd_rtol, set_default_svd_rtol, svd, svd_c64, svd_with, SvdError, SvdOptions,
+    default_svd_rtol, set_default_svd_rtol, svd, svd_c64, svd_truncated, svd_with, SvdError,
+    SvdOptions, SvdResult,
 };
@@ -0,0 +1,144 @@
+use mdarray::DSlice;
+use num_complex::{Complex64, ComplexFloat};
+use tensor4all_core_common::index::{DynId, Index, NoSymmSpace, Symmetry};
+use tensor4all_core_common::tagset::DefaultTagSet;
+use tensor4all_core_tensor::{unfold_split, StorageScalar, TensorDynLen};
+
+use crate::backend::qr_backend;
+use crate::matrix::{adjoint, matrix_from_vec, matrix_to_vec};
+use crate::qr::{compute_retained_rank_qr, default_qr_rtol, QrError, QrOptions};
+use faer_traits::ComplexField;
+
+/// Compute LQ decomposition of a tensor with arbitrary rank, returning (L, Q).
+///
+/// This function uses the global default QR rtol for truncation.
+/// See `lq_with` for per-call rtol control.
+///
+/// For an unfolded matrix A (m×n), we return L (m×k) and Q (k×n) with k = min(m, n),
+/// where L is lower triangular and Q has orthonormal rows. The decomposition is computed
+/// from the QR decomposition of \(A^H\).
+///
+/// # Arguments
+/// * `t` - Input tensor with DenseF64 or DenseC64 storage
+/// * `left_inds` - Indices to place on the left (row) side of the unfolded matrix
+///
+/// # Returns
+/// A tuple `(L, Q)` where:
+/// - `L` is a tensor with indices `[left_inds..., bond_index]` and dimensions `[left_dims..., r]`
+/// - `Q` is a tensor with indices `[bond_index, right_inds...]` and dimensions `[r, right_dims...]`
+/// where `r` is the retained rank (≤ min(m, n)) determined by rtol truncation.
+///
+/// # Errors
+/// Returns `QrError` under the same conditions as [`crate::qr`].
+#[allow(private_bounds)]
+pub fn lq<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+) -> Result<(TensorDynLen<Id, Symm>, TensorDynLen<Id, Symm>), QrError>
+where
+    Id: Clone + std::hash::Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    T: StorageScalar + ComplexFloat + ComplexField + Default + From<<T as ComplexFloat>::Real>,
+    <T as ComplexFloat>::Real: Into<f64> + 'static,
+{
+    lq_with::<Id, Symm, T>(t, left_inds, &QrOptions::default())
+}
+
+/// Compute LQ decomposition of a tensor with arbitrary rank, returning (L, Q).
+///
+/// This function allows per-call control of the truncation tolerance via `QrOptions`.
+/// If `options.rtol` is `None`, uses the global default QR rtol.
+///
+/// Truncation is performed based on L's diagonal elements: rows of Q with |L[i, i]| < rtol
+/// are truncated.
+///
+/// # Arguments
+/// * `t` - Input tensor with DenseF64 or DenseC64 storage
+/// * `left_inds` - Indices to place on the left (row) side of the unfolded matrix
+/// * `options` - QR options including rtol for truncation control
+///
+/// # Returns
+/// A tuple `(L, Q)` where:
+/// - `L` is a tensor with indices `[left_inds..., bond_index]` and dimensions `[left_dims..., r]`
+/// - `Q` is a tensor with indices `[bond_index, right_inds...]` and dimensions `[r, right_dims...]`
+/// where `r` is the retained rank (≤ min(m, n)) determined by rtol truncation.
+///
+/// # Errors
+/// Returns `QrError` if:
+/// - The tensor rank is < 2
+/// - Storage is not DenseF64 or DenseC64
+/// - `left_inds` is empty or contains all indices
+/// - `left_inds` contains indices not in the tensor or duplicates
+/// - The QR computation fails
+/// - `options.rtol` is invalid (not finite or negative)
+#[allow(private_bounds)]
+pub fn lq_with<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    options: &QrOptions,
+) -> Result<(TensorDynLen<Id, Symm>, TensorDynLen<Id, Symm>), QrError>
+where
+    Id: Clone + std::hash::Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    T: StorageScalar + ComplexFloat + ComplexField + Default + From<<T as ComplexFloat>::Real>,
+    <T as ComplexFloat>::Real: Into<f64> + 'static,
+{
+    // Determine rtol to use
+    let rtol = options.rtol.unwrap_or_else(default_qr_rtol);
+    if !rtol.is_finite() || rtol < 0.0 {
+        return Err(QrError::InvalidRtol(rtol));
+    }
+
+    // Unfold tensor into matrix (returns DTensor<T, 2>)
+    let (a_tensor, _, m, n, left_indices, right_indices) = unfold_split::<Id, T, Symm>(t, left_inds)
+        .map_err(|e| anyhow::anyhow!("Failed to unfold tensor: {}", e))
+        .map_err(QrError::ComputationError)?;
+    let k = m.min(n);
+
+    // QR of the adjoint: A^H = Q' R'  =>  A = R'^H Q'^H
+    let a_vec = matrix_to_vec(&a_tensor, m, n);
+    let mut ah_tensor = matrix_from_vec(adjoint(&a_vec, m, n), n, m);
+    let ah_slice: &mut DSlice<T, 2> = ah_tensor.as_mut();
+    let (q_full, r_full) = qr_backend(ah_slice);
+
+    // Compute retained rank based on rtol truncation (R' is k×m)
+    let r = compute_retained_rank_qr(&r_full, k, m, rtol);
+
+    // L = (first r rows of R')^H: m×r
+    let l_vec = adjoint(&matrix_to_vec(&r_full, r, m), r, m);
+
+    // Q = (first r columns of Q')^H: r×n
+    let q_vec = adjoint(&matrix_to_vec(&q_full, n, r), n, r);
+
+    // Create bond index with "Link" tag (dimension r, not k)
+    let bond_index: Index<Id, Symm, DefaultTagSet> = Index::new_link(r)
+        .map_err(|e| anyhow::anyhow!("Failed to create Link index: {:?}", e))
+        .map_err(QrError::ComputationError)?;
+
+    // Create L tensor: [left_inds..., bond_index]
+    let mut l_indices = left_indices.clone();
+    l_indices.push(bond_index.clone());
+    let l = TensorDynLen::from_indices(l_indices, T::dense_storage(l_vec));
+
+    // Create Q tensor: [bond_index, right_inds...]
+    let mut q_indices = vec![bond_index.clone()];
+    q_indices.extend_from_slice(&right_indices);
+    let q = TensorDynLen::from_indices(q_indices, T::dense_storage(q_vec));
+
+    Ok((l, q))
+}
+
+/// Compute LQ decomposition of a complex tensor with arbitrary rank, returning (L, Q).
+///
+/// This is a convenience wrapper around the generic `lq` function for `Complex64` tensors.
+#[inline]
+pub fn lq_c64<Id, Symm>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+) -> Result<(TensorDynLen<Id, Symm>, TensorDynLen<Id, Symm>), QrError>
+where
+    Id: Clone + std::hash::Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+{
+    lq::<Id, Symm, Complex64>(t, left_inds)
+}
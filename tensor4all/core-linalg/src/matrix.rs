@@ -0,0 +1,102 @@
+//! Small dense matrix helpers on row-major `Vec` data.
+//!
+//! These are used by factorizations that combine several backend calls
+//! (LQ, polar decomposition, randomized SVD).
+
+use faer::{Accum, MatMut, MatRef};
+use faer_traits::{ComplexField, Conjugate};
+use mdarray::{DTensor, Rank};
+use num_complex::ComplexFloat;
+
+/// Convert an `f64` into the scalar type `T`.
+pub(crate) fn from_f64<T>(x: f64) -> T
+where
+    T: ComplexFloat + From<<T as ComplexFloat>::Real>,
+    <T as ComplexFloat>::Real: From<f64>,
+{
+    <T as From<<T as ComplexFloat>::Real>>::from(<<T as ComplexFloat>::Real as From<f64>>::from(x))
+}
+
+/// Create a `DTensor<T, 2>` (m×n) from row-major data.
+pub(crate) fn matrix_from_vec<T>(data: Vec<T>, m: usize, n: usize) -> DTensor<T, 2> {
+    debug_assert_eq!(data.len(), m * n);
+    let tensor_1d = mdarray::Tensor::<T, Rank<1>>::from(data);
+    tensor_1d.into_shape([m, n])
+}
+
+/// Copy a `DTensor<T, 2>` into row-major data, keeping the leading `rows`×`cols` block.
+pub(crate) fn matrix_to_vec<T: Copy>(a: &DTensor<T, 2>, rows: usize, cols: usize) -> Vec<T> {
+    let mut data = Vec::with_capacity(rows * cols);
+    for i in 0..rows {
+        for j in 0..cols {
+            data.push(a[[i, j]]);
+        }
+    }
+    data
+}
+
+/// Conjugate transpose of a row-major m×n matrix (returns n×m).
+pub(crate) fn adjoint<T: ComplexFloat>(a: &[T], m: usize, n: usize) -> Vec<T> {
+    let mut result = Vec::with_capacity(m * n);
+    for j in 0..n {
+        for i in 0..m {
+            result.push(a[i * n + j].conj());
+        }
+    }
+    result
+}
+
+/// Multiply a row-major m×k matrix by a row-major k×n matrix.
+pub(crate) fn matmul<T>(a: &[T], b: &[T], m: usize, k: usize, n: usize) -> Vec<T>
+where
+    T: ComplexFloat + ComplexField + Default,
+{
+    gemm(MatRef::from_row_major_slice(a, m, k), MatRef::from_row_major_slice(b, k, n))
+}
+
+/// Multiply the adjoint of a row-major k×m matrix by a row-major k×n matrix.
+///
+/// Computes the m×n product A^H B without forming A^H.
+pub(crate) fn adjoint_matmul<T>(a: &[T], b: &[T], k: usize, m: usize, n: usize) -> Vec<T>
+where
+    T: ComplexFloat + ComplexField + Default,
+{
+    gemm(MatRef::from_row_major_slice(a, k, m).adjoint(), MatRef::from_row_major_slice(b, k, n))
+}
+
+/// Multiply two faer matrix views with faer's GEMM into row-major data.
+fn gemm<T, L>(lhs: MatRef<'_, L>, rhs: MatRef<'_, T>) -> Vec<T>
+where
+    T: ComplexFloat + ComplexField + Default,
+    L: Conjugate<Canonical = T>,
+{
+    let (m, n) = (lhs.nrows(), rhs.ncols());
+    let mut c = vec![T::default(); m * n];
+    faer::linalg::matmul::matmul(
+        MatMut::from_row_major_slice_mut(&mut c, m, n),
+        Accum::Replace,
+        lhs,
+        rhs,
+        T::one(),
+        faer::get_global_parallelism(),
+    );
+    c
+}
+
+/// Thin QR decomposition of a row-major m×n matrix with m ≥ n.
+///
+/// # Returns
+/// A tuple `(Q, R)` of the row-major m×n factor Q with orthonormal columns and the
+/// row-major n×n upper triangular factor R, such that the input equals `Q * R`.
+pub(crate) fn thin_qr<T: ComplexField + Copy>(a: &[T], m: usize, n: usize) -> (Vec<T>, Vec<T>) {
+    debug_assert!(m >= n);
+    let qr = MatRef::from_row_major_slice(a, m, n).qr();
+    (to_row_major(qr.compute_thin_Q().as_ref()), to_row_major(qr.thin_R()))
+}
+
+/// Copy a faer matrix view into row-major data.
+fn to_row_major<T: Copy>(a: MatRef<'_, T>) -> Vec<T> {
+    (0..a.nrows())
+        .flat_map(|i| (0..a.ncols()).map(move |j| a[(i, j)]))
+        .collect()
+}
@@ -0,0 +1,222 @@
+use faer::{Accum, Mat, MatMut, MatRef};
+use num_complex::Complex64;
+use tensor4all_core_common::index::{Index, Symmetry};
+use tensor4all_core_tensor::TensorDynLen;
+
+use crate::eigen::{
+    check_hermitian, hermitian_eigen, unfold_square, EigenError, EigenScalar, SquareUnfolding,
+    HERMITIAN_RTOL,
+};
+
+/// Maximum number of Taylor terms in the matrix exponential.
+const MAX_TAYLOR_TERMS: usize = 40;
+
+/// Multiply two row-major n×n matrices.
+fn matmul(a: &[Complex64], b: &[Complex64], n: usize) -> Vec<Complex64> {
+    let mut c = vec![Complex64::new(0.0, 0.0); n * n];
+    faer::linalg::matmul::matmul(
+        MatMut::from_row_major_slice_mut(&mut c, n, n),
+        Accum::Replace,
+        MatRef::from_row_major_slice(a, n, n),
+        MatRef::from_row_major_slice(b, n, n),
+        Complex64::new(1.0, 0.0),
+        faer::get_global_parallelism(),
+    );
+    c
+}
+
+/// Maximum absolute column sum (1-norm) of a row-major n×n matrix.
+fn norm1(a: &[Complex64], n: usize) -> f64 {
+    (0..n)
+        .map(|j| (0..n).map(|i| a[i * n + j].norm()).sum::<f64>())
+        .fold(0.0, f64::max)
+}
+
+/// Matrix exponential of a row-major n×n matrix by scaling and squaring with a Taylor series.
+///
+/// Fails for matrices with non-finite entries or norm, for which the number of
+/// squarings would be unbounded.
+fn expm(a: &[Complex64], n: usize) -> Result<Vec<Complex64>, EigenError> {
+    // The norm alone misses NaN entries, and overflows for huge finite ones
+    let norm = norm1(a, n);
+    if !norm.is_finite() || a.iter().any(|z| !z.is_finite()) {
+        return Err(EigenError::NotFinite);
+    }
+    // Scale so that ||A / 2^s||_1 <= 1/2
+    let squarings = if norm > 0.5 {
+        (norm / 0.5).log2().ceil() as i32
+    } else {
+        0
+    };
+    let scale = 0.5_f64.powi(squarings);
+    let scaled: Vec<Complex64> = a.iter().map(|&z| z * scale).collect();
+
+    let mut result = vec![Complex64::new(0.0, 0.0); n * n];
+    for i in 0..n {
+        result[i * n + i] = Complex64::new(1.0, 0.0);
+    }
+    let mut term = result.clone();
+    for k in 1..=MAX_TAYLOR_TERMS {
+        term = matmul(&term, &scaled, n);
+        for z in term.iter_mut() {
+            *z /= k as f64;
+        }
+        for (r, &t) in result.iter_mut().zip(&term) {
+            *r += t;
+        }
+        if norm1(&term, n) <= f64::EPSILON * norm1(&result, n) {
+            break;
+        }
+    }
+
+    for _ in 0..squarings {
+        result = matmul(&result, &result, n);
+    }
+    Ok(result)
+}
+
+/// Build V * diag(f) * V^H from row-major eigenvectors V (n×n).
+fn from_eigen(vectors: &[Complex64], values: &[f64], n: usize) -> Vec<Complex64> {
+    let v = MatRef::from_row_major_slice(vectors, n, n);
+    let scaled = Mat::from_fn(n, n, |i, k| v[(i, k)] * values[k]);
+    let mut result = vec![Complex64::new(0.0, 0.0); n * n];
+    faer::linalg::matmul::matmul(
+        MatMut::from_row_major_slice_mut(&mut result, n, n),
+        Accum::Replace,
+        scaled.as_ref(),
+        v.adjoint(),
+        Complex64::new(1.0, 0.0),
+        faer::get_global_parallelism(),
+    );
+    result
+}
+
+/// Fold row-major matrix data back into a tensor with indices `[left_inds..., right_inds...]`.
+fn fold<Id, Symm, T>(unfolding: SquareUnfolding<Id, Symm>, data: Vec<Complex64>) -> TensorDynLen<Id, Symm>
+where
+    Id: Clone + std::hash::Hash + Eq,
+    Symm: Clone + Symmetry,
+    T: EigenScalar,
+{
+    let mut indices = unfolding.left_indices;
+    indices.extend(unfolding.right_indices);
+    let storage = T::dense_storage(data.into_iter().map(T::from_c64).collect());
+    TensorDynLen::from_indices(indices, storage)
+}
+
+/// Apply a function to the eigenvalues of a Hermitian tensor viewed as a matrix.
+fn hermitian_function<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    right_inds: &[Index<Id, Symm>],
+    f: impl FnOnce(&[f64]) -> Result<Vec<f64>, EigenError>,
+) -> Result<TensorDynLen<Id, Symm>, EigenError>
+where
+    Id: Clone + std::hash::Hash + Eq,
+    Symm: Clone + Symmetry,
+    T: EigenScalar,
+{
+    let unfolding = unfold_square::<Id, Symm, T>(t, left_inds, right_inds)?;
+    let n = unfolding.n;
+    check_hermitian(&unfolding.data, n)?;
+    let (eigenvalues, vectors) = hermitian_eigen(&unfolding.data, n)?;
+    let values = f(&eigenvalues)?;
+    let data = from_eigen(&vectors, &values, n);
+    Ok(fold::<Id, Symm, T>(unfolding, data))
+}
+
+/// Compute the matrix exponential of a tensor viewed as a matrix.
+///
+/// This mimics ITensors.jl's `exp(A, Lis, Ris)`: the tensor is viewed as a matrix with
+/// rows `left_inds` and columns `right_inds`, where `left_inds[k]` is paired with
+/// `right_inds[k]`. The matrix does not need to be Hermitian; the exponential is computed
+/// by scaling and squaring with a Taylor series.
+///
+/// To build a time-evolution gate exp(-iτH) from a real Hamiltonian, multiply the tensor
+/// by the complex scalar first and call this function with `T = Complex64`.
+///
+/// # Returns
+/// A tensor with indices `[left_inds..., right_inds...]`.
+///
+/// # Errors
+/// Returns `EigenError` if:
+/// - `left_inds` and `right_inds` don't pair up (different lengths or dimensions) or
+///   don't cover all indices of the tensor
+/// - Storage is not DenseF64 or DenseC64 (matching `T`)
+/// - The tensor has non-finite entries
+pub fn exp<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    right_inds: &[Index<Id, Symm>],
+) -> Result<TensorDynLen<Id, Symm>, EigenError>
+where
+    Id: Clone + std::hash::Hash + Eq,
+    Symm: Clone + Symmetry,
+    T: EigenScalar,
+{
+    let unfolding = unfold_square::<Id, Symm, T>(t, left_inds, right_inds)?;
+    let data = expm(&unfolding.data, unfolding.n)?;
+    Ok(fold::<Id, Symm, T>(unfolding, data))
+}
+
+/// Compute the square root of a Hermitian positive semidefinite tensor viewed as a matrix.
+///
+/// The tensor is viewed as a matrix as in [`exp`]. Eigenvalues that are negative only
+/// by rounding (relative to the largest eigenvalue) are treated as zero.
+///
+/// # Returns
+/// A tensor with indices `[left_inds..., right_inds...]`.
+///
+/// # Errors
+/// Returns `EigenError` if the indices don't pair up, the storage type doesn't match `T`,
+/// the matrix is not Hermitian, or it has a negative eigenvalue.
+pub fn sqrt<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    right_inds: &[Index<Id, Symm>],
+) -> Result<TensorDynLen<Id, Symm>, EigenError>
+where
+    Id: Clone + std::hash::Hash + Eq,
+    Symm: Clone + Symmetry,
+    T: EigenScalar,
+{
+    hermitian_function::<Id, Symm, T>(t, left_inds, right_inds, |eigenvalues| {
+        let scale = eigenvalues.iter().map(|x| x.abs()).fold(0.0, f64::max);
+        let min = eigenvalues.iter().copied().fold(f64::INFINITY, f64::min);
+        if min < -HERMITIAN_RTOL * scale {
+            return Err(EigenError::NotPositiveSemidefinite(min));
+        }
+        Ok(eigenvalues.iter().map(|&x| x.max(0.0).sqrt()).collect())
+    })
+}
+
+/// Compute the inverse square root of a Hermitian positive definite tensor viewed as a matrix.
+///
+/// The tensor is viewed as a matrix as in [`exp`].
+///
+/// # Returns
+/// A tensor with indices `[left_inds..., right_inds...]`.
+///
+/// # Errors
+/// Returns `EigenError` if the indices don't pair up, the storage type doesn't match `T`,
+/// the matrix is not Hermitian, or it has an eigenvalue that is not positive
+/// (relative to the largest eigenvalue).
+pub fn inv_sqrt<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    right_inds: &[Index<Id, Symm>],
+) -> Result<TensorDynLen<Id, Symm>, EigenError>
+where
+    Id: Clone + std::hash::Hash + Eq,
+    Symm: Clone + Symmetry,
+    T: EigenScalar,
+{
+    hermitian_function::<Id, Symm, T>(t, left_inds, right_inds, |eigenvalues| {
+        let scale = eigenvalues.iter().map(|x| x.abs()).fold(0.0, f64::max);
+        let min = eigenvalues.iter().copied().fold(f64::INFINITY, f64::min);
+        if min <= HERMITIAN_RTOL * scale {
+            return Err(EigenError::NotPositiveDefinite(min));
+        }
+        Ok(eigenvalues.iter().map(|&x| 1.0 / x.sqrt()).collect())
+    })
+}
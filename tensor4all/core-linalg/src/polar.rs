@@ -0,0 +1,101 @@
+use mdarray::DSlice;
+use num_complex::{Complex64, ComplexFloat};
+use tensor4all_core_common::index::{DynId, Index, NoSymmSpace, Symmetry};
+use tensor4all_core_common::index_ops::sim;
+use tensor4all_core_tensor::{unfold_split, StorageScalar, TensorDynLen};
+
+use crate::backend::svd_backend;
+use crate::matrix::{adjoint, from_f64, matmul};
+use crate::svd::{extract_usv_from_svd_decomp, SvdError};
+use faer_traits::ComplexField;
+
+/// Compute the polar decomposition of a tensor with arbitrary rank, returning (U, P).
+///
+/// For an unfolded matrix A (m×n) with rows `left_inds` and columns `right_inds`,
+/// the polar decomposition is
+/// \[ A = U * P \]
+/// where U (m×n) has orthonormal columns (or rows if m < n) and P (n×n) is Hermitian
+/// positive semidefinite. Both factors are computed from the SVD \(A = W Σ V^H\) as
+/// \(U = W V^H\) and \(P = V Σ V^H\). No truncation is performed.
+///
+/// # Arguments
+/// * `t` - Input tensor with DenseF64 or DenseC64 storage
+/// * `left_inds` - Indices to place on the left (row) side of the unfolded matrix
+///
+/// # Returns
+/// A tuple `(U, P)` where:
+/// - `U` is a tensor with indices `[left_inds..., sim(right_inds)...]`
+/// - `P` is a tensor with indices `[sim(right_inds)..., right_inds...]`
+/// so that contracting `U` and `P` over the new indices gives back `t`.
+///
+/// # Errors
+/// Returns `SvdError` if:
+/// - The tensor rank is < 2
+/// - Storage is not DenseF64 or DenseC64
+/// - `left_inds` is empty or contains all indices
+/// - `left_inds` contains indices not in the tensor or duplicates
+/// - The SVD computation fails
+#[allow(private_bounds)]
+pub fn polar<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+) -> Result<(TensorDynLen<Id, Symm>, TensorDynLen<Id, Symm>), SvdError>
+where
+    Id: Clone + std::hash::Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    T: StorageScalar + ComplexFloat + ComplexField + Default + From<<T as ComplexFloat>::Real>,
+    <T as ComplexFloat>::Real: Into<f64> + From<f64> + 'static,
+{
+    // Unfold tensor into matrix (returns DTensor<T, 2>)
+    let (mut a_tensor, _, m, n, left_indices, right_indices) = unfold_split::<Id, T, Symm>(t, left_inds)
+        .map_err(|e| anyhow::anyhow!("Failed to unfold tensor: {}", e))
+        .map_err(SvdError::ComputationError)?;
+    let k = m.min(n);
+
+    let a_slice: &mut DSlice<T, 2> = a_tensor.as_mut();
+    let decomp = svd_backend(a_slice).map_err(SvdError::ComputationError)?;
+    let (w_vec, s_vec, v_vec) = extract_usv_from_svd_decomp(decomp, m, n, k);
+
+    // U = W V^H (m×n)
+    let vh_vec = adjoint(&v_vec, n, k);
+    let u_vec = matmul(&w_vec, &vh_vec, m, k, n);
+
+    // P = V Σ V^H (n×n)
+    let mut v_sigma = v_vec;
+    for row in v_sigma.chunks_mut(k) {
+        for (x, &s) in row.iter_mut().zip(&s_vec) {
+            *x = *x * from_f64::<T>(s);
+        }
+    }
+    let p_vec = matmul(&v_sigma, &vh_vec, n, k, n);
+
+    // New indices connecting U and P
+    let new_indices: Vec<Index<Id, Symm>> = right_indices.iter().map(sim).collect();
+
+    // Create U tensor: [left_inds..., sim(right_inds)...]
+    let mut u_indices = left_indices;
+    u_indices.extend(new_indices.iter().cloned());
+    let u = TensorDynLen::from_indices(u_indices, T::dense_storage(u_vec));
+
+    // Create P tensor: [sim(right_inds)..., right_inds...]
+    let mut p_indices = new_indices;
+    p_indices.extend(right_indices);
+    let p = TensorDynLen::from_indices(p_indices, T::dense_storage(p_vec));
+
+    Ok((u, p))
+}
+
+/// Compute the polar decomposition of a complex tensor, returning (U, P).
+///
+/// This is a convenience wrapper around the generic `polar` function for `Complex64` tensors.
+#[inline]
+pub fn polar_c64<Id, Symm>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+) -> Result<(TensorDynLen<Id, Symm>, TensorDynLen<Id, Symm>), SvdError>
+where
+    Id: Clone + std::hash::Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+{
+    polar::<Id, Symm, Complex64>(t, left_inds)
+}
@@ -80,7 +80,7 @@ pub fn set_default_qr_rtol(rtol: f64) -> Result<(), QrError> {
 ///
 /// # Returns
 /// The retained rank `r` (at least 1, at most k)
-fn compute_retained_rank_qr<T>(r_full: &DTensor<T, 2>, k: usize, n: usize, rtol: f64) -> usize
+pub(crate) fn compute_retained_rank_qr<T>(r_full: &DTensor<T, 2>, k: usize, n: usize, rtol: f64) -> usize
 where
     T: ComplexFloat,
     <T as ComplexFloat>::Real: Into<f64>,
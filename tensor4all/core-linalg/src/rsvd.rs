@@ -0,0 +1,199 @@
+use mdarray::DSlice;
+use num_complex::{Complex64, ComplexFloat};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Arc;
+use tensor4all_core_common::index::{DynId, Index, NoSymmSpace, Symmetry};
+use tensor4all_core_common::index_ops::sim;
+use tensor4all_core_common::tagset::DefaultTagSet;
+use tensor4all_core_tensor::{unfold_split, Storage, StorageScalar, TensorDynLen};
+
+use crate::backend::svd_backend;
+use crate::matrix::{adjoint, adjoint_matmul, from_f64, matmul, matrix_from_vec, matrix_to_vec, thin_qr};
+use crate::svd::{
+    compute_truncated_rank, default_svd_rtol, extract_usv_from_svd_decomp, SvdError, SvdOptions, SvdResult,
+};
+use faer_traits::ComplexField;
+
+/// Options for randomized SVD.
+#[derive(Debug, Clone, Copy)]
+pub struct RsvdOptions {
+    /// Target rank (maximum number of retained singular values).
+    pub rank: usize,
+    /// Number of random samples beyond `rank` used to capture the range of the matrix.
+    pub oversampling: usize,
+    /// Number of power iterations. More iterations improve the accuracy for slowly
+    /// decaying singular values at the cost of additional matrix products.
+    pub n_power_iter: usize,
+    /// Seed of the random test matrix.
+    pub seed: u64,
+    /// Truncation applied to the computed singular values.
+    /// `truncation.max_rank` is combined with `rank` (the smaller one is used).
+    pub truncation: SvdOptions,
+}
+
+impl RsvdOptions {
+    /// Create new randomized SVD options with the specified target rank.
+    ///
+    /// Defaults: oversampling 10, 2 power iterations, seed 0 and the default SVD truncation.
+    pub fn new(rank: usize) -> Self {
+        Self {
+            rank,
+            oversampling: 10,
+            n_power_iter: 2,
+            seed: 0,
+            truncation: SvdOptions::default(),
+        }
+    }
+
+    /// Set the oversampling.
+    pub fn with_oversampling(mut self, oversampling: usize) -> Self {
+        self.oversampling = oversampling;
+        self
+    }
+
+    /// Set the number of power iterations.
+    pub fn with_power_iterations(mut self, n_power_iter: usize) -> Self {
+        self.n_power_iter = n_power_iter;
+        self
+    }
+
+    /// Set the seed of the random test matrix.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the truncation applied to the computed singular values.
+    pub fn with_truncation(mut self, truncation: SvdOptions) -> Self {
+        self.truncation = truncation;
+        self
+    }
+}
+
+/// Compute a randomized truncated SVD of a tensor with arbitrary rank.
+///
+/// The tensor is unfolded into a matrix A (m×n) as in [`crate::svd_with`]. A random test
+/// matrix with `l = rank + oversampling` columns is used to find an orthonormal basis Q
+/// of the dominant range of A (refined by `n_power_iter` power iterations), and the SVD
+/// of the small matrix \(Q^H A\) gives the leading singular triplets. The cost is
+/// O(mnl) instead of O(mn min(m, n)) for the full SVD.
+///
+/// # Arguments
+/// * `t` - Input tensor with DenseF64 or DenseC64 storage
+/// * `left_inds` - Indices to place on the left (row) side of the unfolded matrix
+/// * `options` - Target rank, oversampling, power iterations and truncation
+///
+/// # Returns
+/// An [`SvdResult`] with the same index structure as [`crate::svd_truncated`].
+/// `singular_values` contains the `l` approximate leading singular values, and
+/// `discarded_weight` only accounts for those (not for the part of the spectrum that
+/// was never computed).
+///
+/// # Errors
+/// Returns `SvdError` if:
+/// - The tensor rank is < 2
+/// - Storage is not DenseF64 or DenseC64
+/// - `left_inds` is empty or contains all indices
+/// - `left_inds` contains indices not in the tensor or duplicates
+/// - The SVD computation fails
+/// - `options.rank` is zero or the truncation options are invalid
+#[allow(private_bounds)]
+pub fn rsvd<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    options: &RsvdOptions,
+) -> Result<SvdResult<Id, Symm>, SvdError>
+where
+    Id: Clone + std::hash::Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    T: StorageScalar + ComplexFloat + ComplexField + Default + From<<T as ComplexFloat>::Real>,
+    <T as ComplexFloat>::Real: Into<f64> + From<f64> + 'static,
+{
+    // Combine the target rank with the truncation options
+    let truncation = SvdOptions {
+        max_rank: Some(options.truncation.max_rank.map_or(options.rank, |r| r.min(options.rank))),
+        ..options.truncation
+    };
+    let rtol = truncation.rtol.unwrap_or_else(default_svd_rtol);
+    truncation.validate(rtol)?;
+
+    // Unfold tensor into matrix (returns DTensor<T, 2>)
+    let (a_tensor, _, m, n, left_indices, right_indices) = unfold_split::<Id, T, Symm>(t, left_inds)
+        .map_err(|e| anyhow::anyhow!("Failed to unfold tensor: {}", e))
+        .map_err(SvdError::ComputationError)?;
+    let a_vec = matrix_to_vec(&a_tensor, m, n);
+    let l = (options.rank + options.oversampling).min(m.min(n));
+
+    // Range finder: Q = orth(A Ω), refined by power iterations
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let omega: Vec<T> = (0..n * l).map(|_| from_f64(rng.gen_range(-1.0..1.0))).collect();
+    let (mut q, _) = thin_qr(&matmul(&a_vec, &omega, m, n, l), m, l);
+    for _ in 0..options.n_power_iter {
+        let (z, _) = thin_qr(&adjoint_matmul(&a_vec, &q, m, n, l), n, l);
+        (q, _) = thin_qr(&matmul(&a_vec, &z, m, n, l), m, l);
+    }
+
+    // B = Q^H A (l×n). Factor B^H = A^H Q = Q2 R2, so that B = R2^H Q2^H
+    let (q2, r2) = thin_qr(&adjoint_matmul(&a_vec, &q, m, n, l), n, l);
+
+    // SVD of the small l×l matrix R2^H = Ur Σ Vr^H
+    let mut small = matrix_from_vec(adjoint(&r2, l, l), l, l);
+    let small_slice: &mut DSlice<T, 2> = small.as_mut();
+    let decomp = svd_backend(small_slice).map_err(SvdError::ComputationError)?;
+    let (ur_vec, s_vec_full, vr_vec) = extract_usv_from_svd_decomp(decomp, l, l, l);
+
+    // A ≈ Q B = (Q Ur) Σ (Q2 Vr)^H
+    let u_vec_full = matmul(&q, &ur_vec, m, l, l);
+    let v_vec_full = matmul(&q2, &vr_vec, n, l, l);
+
+    // Compute retained rank from the truncation options
+    let r = compute_truncated_rank(&s_vec_full, rtol, &truncation);
+    let discarded_weight: f64 = s_vec_full[r..].iter().map(|&s| s * s).sum();
+    let s_vec: Vec<f64> = s_vec_full[..r].to_vec();
+    let u_vec: Vec<T> = u_vec_full.chunks(l).flat_map(|row| row[..r].iter().copied()).collect();
+    let v_vec: Vec<T> = v_vec_full.chunks(l).flat_map(|row| row[..r].iter().copied()).collect();
+
+    // Create bond index with "Link" tag (dimension r)
+    let bond_index: Index<Id, Symm, DefaultTagSet> = Index::new_link(r)
+        .map_err(|e| anyhow::anyhow!("Failed to create Link index: {:?}", e))
+        .map_err(SvdError::ComputationError)?;
+
+    // Create U tensor: [left_inds..., bond_index]
+    let mut u_indices = left_indices;
+    u_indices.push(bond_index.clone());
+    let u = TensorDynLen::from_indices(u_indices, T::dense_storage(u_vec));
+
+    // Create S tensor: [bond_index, sim(bond_index)] (diagonal)
+    let s_indices = vec![bond_index.clone(), sim(&bond_index)];
+    let s = TensorDynLen::from_indices(s_indices, Arc::new(Storage::new_diag_f64(s_vec)));
+
+    // Create V tensor: [right_inds..., bond_index]
+    let mut v_indices = right_indices;
+    v_indices.push(bond_index);
+    let v = TensorDynLen::from_indices(v_indices, T::dense_storage(v_vec));
+
+    Ok(SvdResult {
+        u,
+        s,
+        v,
+        singular_values: s_vec_full,
+        discarded_weight,
+    })
+}
+
+/// Compute a randomized truncated SVD of a complex tensor.
+///
+/// This is a convenience wrapper around the generic `rsvd` function for `Complex64` tensors.
+#[inline]
+pub fn rsvd_c64<Id, Symm>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    options: &RsvdOptions,
+) -> Result<SvdResult<Id, Symm>, SvdError>
+where
+    Id: Clone + std::hash::Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+{
+    rsvd::<Id, Symm, Complex64>(t, left_inds, options)
+}
@@ -19,21 +19,45 @@ pub enum SvdError {
     ComputationError(#[from] anyhow::Error),
     #[error("Invalid rtol value: {0}. rtol must be finite and non-negative.")]
     InvalidRtol(f64),
+    #[error("Invalid cutoff value: {0}. cutoff must be finite and non-negative.")]
+    InvalidCutoff(f64),
+    #[error("Invalid atol value: {0}. atol must be finite and non-negative.")]
+    InvalidAtol(f64),
+    #[error("Invalid rank bounds: min_rank = {min_rank}, max_rank = {max_rank}. max_rank must be at least max(min_rank, 1).")]
+    InvalidRankBounds { min_rank: usize, max_rank: usize },
 }
 
 /// Options for SVD decomposition with truncation control.
+///
+/// All criteria are applied together: the retained rank is the smallest rank allowed
+/// by `rtol`, `cutoff` and `atol`, clamped to `max_rank` and then raised to `min_rank`
+/// (but never above the full rank). At least one singular value is always kept.
 #[derive(Debug, Clone, Copy)]
 pub struct SvdOptions {
     /// Relative Frobenius error tolerance for truncation.
     /// If `None`, uses the global default rtol.
     /// The truncation guarantees: ||A - A_approx||_F / ||A||_F <= rtol.
     pub rtol: Option<f64>,
+    /// ITensors.jl-style cutoff on squared singular values.
+    /// The smallest singular values are discarded while
+    /// sum_{i>r} σ_i² / sum_i σ_i² <= cutoff.
+    pub cutoff: Option<f64>,
+    /// Absolute cutoff: singular values σ_i < atol are discarded.
+    pub atol: Option<f64>,
+    /// Maximum retained rank (bond dimension).
+    pub max_rank: Option<usize>,
+    /// Minimum retained rank, even if the other criteria would truncate further.
+    pub min_rank: Option<usize>,
 }
 
 impl Default for SvdOptions {
     fn default() -> Self {
         Self {
             rtol: None, // Use global default
+            cutoff: None,
+            atol: None,
+            max_rank: None,
+            min_rank: None,
         }
     }
 }
@@ -41,7 +65,95 @@ impl Default for SvdOptions {
 impl SvdOptions {
     /// Create new SVD options with the specified rtol.
     pub fn with_rtol(rtol: f64) -> Self {
-        Self { rtol: Some(rtol) }
+        Self {
+            rtol: Some(rtol),
+            ..Self::default()
+        }
+    }
+
+    /// Set the ITensors.jl-style cutoff on squared singular values.
+    pub fn with_cutoff(mut self, cutoff: f64) -> Self {
+        self.cutoff = Some(cutoff);
+        self
+    }
+
+    /// Set the absolute cutoff on singular values.
+    pub fn with_atol(mut self, atol: f64) -> Self {
+        self.atol = Some(atol);
+        self
+    }
+
+    /// Set the maximum retained rank.
+    pub fn with_max_rank(mut self, max_rank: usize) -> Self {
+        self.max_rank = Some(max_rank);
+        self
+    }
+
+    /// Set the minimum retained rank.
+    pub fn with_min_rank(mut self, min_rank: usize) -> Self {
+        self.min_rank = Some(min_rank);
+        self
+    }
+
+    /// Check that all tolerances and rank bounds are valid.
+    pub(crate) fn validate(&self, rtol: f64) -> Result<(), SvdError> {
+        if !rtol.is_finite() || rtol < 0.0 {
+            return Err(SvdError::InvalidRtol(rtol));
+        }
+        if let Some(cutoff) = self.cutoff {
+            if !cutoff.is_finite() || cutoff < 0.0 {
+                return Err(SvdError::InvalidCutoff(cutoff));
+            }
+        }
+        if let Some(atol) = self.atol {
+            if !atol.is_finite() || atol < 0.0 {
+                return Err(SvdError::InvalidAtol(atol));
+            }
+        }
+        if let Some(max_rank) = self.max_rank {
+            let min_rank = self.min_rank.unwrap_or(0);
+            if max_rank < min_rank.max(1) {
+                return Err(SvdError::InvalidRankBounds { min_rank, max_rank });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Result of a truncated SVD.
+///
+/// In addition to the (U, S, V) factors, this exposes the full singular value
+/// spectrum and the weight discarded by truncation.
+#[derive(Clone)]
+pub struct SvdResult<Id, Symm = NoSymmSpace> {
+    /// Left singular vectors with indices `[left_inds..., bond_index]`.
+    pub u: TensorDynLen<Id, Symm>,
+    /// Diagonal tensor of the retained singular values.
+    pub s: TensorDynLen<Id, Symm>,
+    /// Right singular vectors with indices `[right_inds..., bond_index]`.
+    pub v: TensorDynLen<Id, Symm>,
+    /// All singular values before truncation, in descending order.
+    pub singular_values: Vec<f64>,
+    /// Sum of the squared discarded singular values: sum_{i>r} σ_i².
+    pub discarded_weight: f64,
+}
+
+impl<Id, Symm> SvdResult<Id, Symm> {
+    /// Get the retained rank.
+    pub fn rank(&self) -> usize {
+        self.s.dims[0]
+    }
+
+    /// Get the relative Frobenius truncation error ||A - A_approx||_F / ||A||_F.
+    ///
+    /// Returns 0 for a zero matrix.
+    pub fn truncation_error(&self) -> f64 {
+        let total_sq_norm: f64 = self.singular_values.iter().map(|&s| s * s).sum();
+        if total_sq_norm == 0.0 {
+            0.0
+        } else {
+            (self.discarded_weight / total_sq_norm).sqrt()
+        }
     }
 }
 
@@ -120,6 +232,31 @@ fn compute_retained_rank(s_vec: &[f64], rtol: f64) -> usize {
     r.max(1)
 }
 
+/// Compute the retained rank from all truncation criteria in `options`.
+///
+/// `rtol` is the already resolved relative tolerance (option or global default).
+/// The ITensors.jl-style `cutoff` on squared singular values is the same criterion
+/// as `rtol` with `rtol² = cutoff`.
+///
+/// # Returns
+/// The retained rank `r` (at least 1, at most s_vec.len())
+pub(crate) fn compute_truncated_rank(s_vec: &[f64], rtol: f64, options: &SvdOptions) -> usize {
+    let mut r = compute_retained_rank(s_vec, rtol);
+    if let Some(cutoff) = options.cutoff {
+        r = r.min(compute_retained_rank(s_vec, cutoff.sqrt()));
+    }
+    if let Some(atol) = options.atol {
+        r = r.min(s_vec.iter().take_while(|&&s| s >= atol).count());
+    }
+    if let Some(max_rank) = options.max_rank {
+        r = r.min(max_rank);
+    }
+    if let Some(min_rank) = options.min_rank {
+        r = r.max(min_rank);
+    }
+    r.min(s_vec.len()).max(1)
+}
+
 /// Extract U, S, V from mdarray-linalg's SVDDecomp (which returns U, S, Vt).
 ///
 /// This helper function converts the backend's SVD result to our desired format:
@@ -138,7 +275,7 @@ fn compute_retained_rank(s_vec: &[f64], rtol: f64) -> usize {
 /// - `u_vec` is a vector of length `m * k` containing U matrix data
 /// - `s_vec` is a vector of length `k` containing singular values (real, f64)
 /// - `v_vec` is a vector of length `n * k` containing V matrix data
-fn extract_usv_from_svd_decomp<T>(
+pub(crate) fn extract_usv_from_svd_decomp<T>(
     decomp: SVDDecomp<T>,
     m: usize,
     n: usize,
@@ -269,6 +406,9 @@ where
 ///
 /// Truncation is performed based on the relative Frobenius error tolerance (rtol):
 /// The truncation guarantees: ||A - A_approx||_F / ||A||_F <= rtol.
+/// The remaining criteria in `options` (cutoff, atol, max_rank, min_rank) are applied
+/// as described in [`SvdOptions`]. Use [`svd_truncated`] to also obtain the full
+/// singular value spectrum and the discarded weight.
 ///
 /// For complex-valued matrices, the mathematical convention is:
 /// \[ A = U * Σ * V^H \]
@@ -280,14 +420,14 @@ where
 /// # Arguments
 /// * `t` - Input tensor with DenseF64 or DenseC64 storage
 /// * `left_inds` - Indices to place on the left (row) side of the unfolded matrix
-/// * `options` - SVD options including rtol for truncation control
+/// * `options` - SVD options including rtol, cutoff, atol and rank bounds for truncation control
 ///
 /// # Returns
 /// A tuple `(U, S, V)` where:
 /// - `U` is a tensor with indices `[left_inds..., bond_index]` and dimensions `[left_dims..., r]`
 /// - `S` is a r×r diagonal tensor with indices `[bond_index, bond_index]` (singular values are real)
 /// - `V` is a tensor with indices `[right_inds..., bond_index]` and dimensions `[right_dims..., r]`
-/// where `r` is the retained rank (≤ min(m, n)) determined by rtol truncation.
+/// where `r` is the retained rank (≤ min(m, n)) determined by the truncation options.
 ///
 /// Note: Singular values `S` are always real, even for complex input tensors.
 ///
@@ -298,7 +438,8 @@ where
 /// - `left_inds` is empty or contains all indices
 /// - `left_inds` contains indices not in the tensor or duplicates
 /// - The SVD computation fails
-/// - `options.rtol` is invalid (not finite or negative)
+/// - `options.rtol`, `options.cutoff` or `options.atol` is invalid (not finite or negative)
+/// - `options.max_rank` is smaller than `max(options.min_rank, 1)`
 #[allow(private_bounds)]
 pub fn svd_with<Id, Symm, T>(
     t: &TensorDynLen<Id, Symm>,
@@ -312,6 +453,30 @@ pub fn svd_with<Id, Symm, T>(
     ),
     SvdError,
 >
+where
+    Id: Clone + std::hash::Hash + Eq + From<DynId>,
+    Symm: Clone + Symmetry + From<NoSymmSpace>,
+    T: StorageScalar + ComplexFloat + ComplexField + Default + From<<T as ComplexFloat>::Real>,
+    <T as ComplexFloat>::Real: Into<f64> + 'static,
+{
+    let SvdResult { u, s, v, .. } = svd_truncated::<Id, Symm, T>(t, left_inds, options)?;
+    Ok((u, s, v))
+}
+
+/// Compute a truncated SVD of a tensor with arbitrary rank, returning an [`SvdResult`].
+///
+/// This is the same decomposition as [`svd_with`], but the result additionally
+/// contains the full singular value spectrum (before truncation) and the discarded
+/// weight sum_{i>r} σ_i², from which the truncation error can be computed.
+///
+/// # Errors
+/// Returns `SvdError` under the same conditions as [`svd_with`].
+#[allow(private_bounds)]
+pub fn svd_truncated<Id, Symm, T>(
+    t: &TensorDynLen<Id, Symm>,
+    left_inds: &[Index<Id, Symm>],
+    options: &SvdOptions,
+) -> Result<SvdResult<Id, Symm>, SvdError>
 where
     Id: Clone + std::hash::Hash + Eq + From<DynId>,
     Symm: Clone + Symmetry + From<NoSymmSpace>,
@@ -320,9 +485,7 @@ where
 {
     // Determine rtol to use
     let rtol = options.rtol.unwrap_or_else(default_svd_rtol);
-    if !rtol.is_finite() || rtol < 0.0 {
-        return Err(SvdError::InvalidRtol(rtol));
-    }
+    options.validate(rtol)?;
 
     // Unfold tensor into matrix (returns DTensor<T, 2>)
     let (mut a_tensor, _, m, n, left_indices, right_indices) = unfold_split::<Id, T, Symm>(t, left_inds)
@@ -338,8 +501,9 @@ where
     // Extract U, S, V from the decomposition (full rank k)
     let (u_vec_full, s_vec_full, v_vec_full) = extract_usv_from_svd_decomp(decomp, m, n, k);
 
-    // Compute retained rank based on rtol truncation
-    let r = compute_retained_rank(&s_vec_full, rtol);
+    // Compute retained rank from the truncation options
+    let r = compute_truncated_rank(&s_vec_full, rtol, options);
+    let discarded_weight: f64 = s_vec_full[r..].iter().map(|&s| s * s).sum();
 
     // Truncate to retained rank r
     let s_vec: Vec<f64> = s_vec_full[..r].to_vec();
@@ -384,7 +548,13 @@ where
     let v_storage = T::dense_storage(v_vec);
     let v = TensorDynLen::from_indices(v_indices, v_storage);
 
-    Ok((u, s, v))
+    Ok(SvdResult {
+        u,
+        s,
+        v,
+        singular_values: s_vec_full,
+        discarded_weight,
+    })
 }
 
 /// Compute SVD decomposition of a complex tensor with arbitrary rank, returning (U, S, V).
@@ -0,0 +1,124 @@
+use num_complex::Complex64;
+use std::sync::Arc;
+use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
+use tensor4all_core_linalg::{eigen, eigen_c64, EigenError};
+use tensor4all_core_tensor::storage::{DenseStorageC64, DenseStorageF64};
+use tensor4all_core_tensor::{Storage, StorageScalar, TensorDynLen};
+
+fn diag_values(t: &TensorDynLen<DynId>) -> Vec<f64> {
+    match t.storage.as_ref() {
+        Storage::DiagF64(diag) => diag.as_slice().to_vec(),
+        _ => panic!("D should be diagonal storage"),
+    }
+}
+
+#[test]
+fn test_eigen_real_symmetric() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    // [[2, 1], [1, 2]] has eigenvalues 3 and 1
+    let data = vec![2.0, 1.0, 1.0, 2.0];
+    let storage = Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data.clone())));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), j.clone()], vec![2, 2], storage);
+
+    let (d, u) = eigen::<DynId, _, f64>(&tensor, &[i.clone()], &[j.clone()]).expect("eigen should succeed");
+    let values = diag_values(&d);
+    assert!((values[0] - 3.0).abs() < 1e-12);
+    assert!((values[1] - 1.0).abs() < 1e-12);
+
+    // U has indices [j, bond]
+    assert_eq!(u.indices[0], j);
+    assert_eq!(u.dims, vec![2, 2]);
+    assert_eq!(d.indices[0], u.indices[1]);
+
+    // Reconstruct A = U D U^T
+    let u_data = f64::extract_dense(&u.storage).unwrap();
+    for r in 0..2 {
+        for c in 0..2 {
+            let a_rc: f64 = (0..2).map(|k| u_data[r * 2 + k] * values[k] * u_data[c * 2 + k]).sum();
+            assert!((a_rc - data[r * 2 + c]).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_eigen_complex_hermitian_with_paired_indices() {
+    // Two-site operator A(s1', s2', s1, s2) with left = [s1', s2'] and right = [s1, s2]
+    let s1p = Index::new_dyn(2);
+    let s2p = Index::new_dyn(2);
+    let s1 = Index::new_dyn(2);
+    let s2 = Index::new_dyn(2);
+
+    // Random-ish Hermitian 4x4 matrix
+    let n = 4;
+    let mut a = vec![Complex64::new(0.0, 0.0); n * n];
+    for r in 0..n {
+        for c in r..n {
+            let value = if r == c {
+                Complex64::new(r as f64 - 1.5, 0.0)
+            } else {
+                Complex64::new(0.3 * (r + c) as f64, 0.2 * (c - r) as f64)
+            };
+            a[r * n + c] = value;
+            a[c * n + r] = value.conj();
+        }
+    }
+    // Store with index order [s1, s1', s2, s2'] to exercise the pairing
+    let mut data = vec![Complex64::new(0.0, 0.0); 16];
+    for (r, c) in (0..n).flat_map(|r| (0..n).map(move |c| (r, c))) {
+        let (r1, r2) = (r / 2, r % 2);
+        let (c1, c2) = (c / 2, c % 2);
+        data[((c1 * 2 + r1) * 2 + c2) * 2 + r2] = a[r * n + c];
+    }
+    let storage = Arc::new(Storage::DenseC64(DenseStorageC64::from_vec(data)));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(
+        vec![s1.clone(), s1p.clone(), s2.clone(), s2p.clone()],
+        vec![2, 2, 2, 2],
+        storage,
+    );
+
+    let (d, u) = eigen_c64(&tensor, &[s1p.clone(), s2p.clone()], &[s1.clone(), s2.clone()])
+        .expect("eigen should succeed");
+    let values = diag_values(&d);
+    assert!(values.windows(2).all(|w| w[0] >= w[1]), "eigenvalues should be descending");
+    assert_eq!(u.indices[..2], [s1, s2]);
+
+    // Reconstruct A = U D U^H
+    let u_data = Complex64::extract_dense(&u.storage).unwrap();
+    for r in 0..n {
+        for c in 0..n {
+            let a_rc: Complex64 = (0..n)
+                .map(|k| u_data[r * n + k] * values[k] * u_data[c * n + k].conj())
+                .sum();
+            assert!((a_rc - a[r * n + c]).norm() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_eigen_errors() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let k = Index::new_dyn(3);
+
+    // Not Hermitian
+    let storage = Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(vec![1.0, 2.0, 0.0, 1.0])));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), j.clone()], vec![2, 2], storage);
+    assert!(matches!(
+        eigen::<DynId, _, f64>(&tensor, &[i.clone()], &[j.clone()]),
+        Err(EigenError::NotHermitian(_))
+    ));
+
+    // Paired indices with different dimensions
+    let storage = Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(vec![0.0; 6])));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), k.clone()], vec![2, 3], storage);
+    assert!(matches!(
+        eigen::<DynId, _, f64>(&tensor, &[i.clone()], &[k]),
+        Err(EigenError::ComputationError(_))
+    ));
+
+    // Indices not covering the tensor
+    let storage = Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(vec![0.0; 4])));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), j], vec![2, 2], storage);
+    assert!(eigen::<DynId, _, f64>(&tensor, &[i.clone()], &[i]).is_err());
+}
@@ -0,0 +1,70 @@
+use num_complex::Complex64;
+use std::sync::Arc;
+use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
+use tensor4all_core_linalg::{lq, lq_c64};
+use tensor4all_core_tensor::storage::{DenseStorageC64, DenseStorageF64};
+use tensor4all_core_tensor::{Storage, StorageScalar, TensorDynLen};
+
+#[test]
+fn test_lq_reconstruction() {
+    // 2×3 matrix [[1, 2, 3], [4, 5, 6]]
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(3);
+    let data = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let storage = Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data.clone())));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), j.clone()], vec![2, 3], storage);
+
+    let (l, q) = lq::<DynId, _, f64>(&tensor, &[i.clone()]).expect("LQ should succeed");
+
+    // Check dimensions: m=2, n=3, k=min(2,3)=2
+    assert_eq!(l.dims, vec![2, 2]);
+    assert_eq!(q.dims, vec![2, 3]);
+    assert_eq!(l.indices[1].id, q.indices[0].id);
+    assert!(l.indices[1].tags().has_tag("Link"));
+
+    // L is lower triangular
+    let l_data = f64::extract_dense(&l.storage).unwrap();
+    assert!(l_data[1].abs() < 1e-12);
+
+    // Q has orthonormal rows
+    let q_data = f64::extract_dense(&q.storage).unwrap();
+    for a in 0..2 {
+        for b in 0..2 {
+            let dot: f64 = (0..3).map(|c| q_data[a * 3 + c] * q_data[b * 3 + c]).sum();
+            let expected = if a == b { 1.0 } else { 0.0 };
+            assert!((dot - expected).abs() < 1e-12);
+        }
+    }
+
+    // L * Q = A
+    let reconstructed = l.contract(&q);
+    assert_eq!(reconstructed.indices, vec![i, j]);
+    let r_data = f64::extract_dense(&reconstructed.storage).unwrap();
+    for (x, y) in r_data.iter().zip(&data) {
+        assert!((x - y).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_lq_complex_rank3() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let k = Index::new_dyn(3);
+    let data: Vec<Complex64> = (0..12)
+        .map(|x| Complex64::new(x as f64 - 3.0, (x * x % 5) as f64))
+        .collect();
+    let storage = Arc::new(Storage::DenseC64(DenseStorageC64::from_vec(data.clone())));
+    let tensor: TensorDynLen<DynId> =
+        TensorDynLen::new(vec![i.clone(), j.clone(), k.clone()], vec![2, 2, 3], storage);
+
+    let (l, q) = lq_c64(&tensor, &[i.clone(), j.clone()]).expect("LQ should succeed");
+    assert_eq!(l.dims, vec![2, 2, 3]);
+    assert_eq!(q.dims, vec![3, 3]);
+
+    let reconstructed = l.contract(&q);
+    assert_eq!(reconstructed.indices, vec![i, j, k]);
+    let r_data = Complex64::extract_dense(&reconstructed.storage).unwrap();
+    for (x, y) in r_data.iter().zip(&data) {
+        assert!((x - y).norm() < 1e-12);
+    }
+}
@@ -0,0 +1,111 @@
+use num_complex::Complex64;
+use std::sync::Arc;
+use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
+use tensor4all_core_linalg::{exp, inv_sqrt, sqrt, EigenError};
+use tensor4all_core_tensor::storage::{DenseStorageC64, DenseStorageF64};
+use tensor4all_core_tensor::{Storage, StorageScalar, TensorDynLen};
+
+fn matrix_f64(i: &Index<DynId>, j: &Index<DynId>, data: Vec<f64>) -> TensorDynLen<DynId> {
+    let dims = vec![i.size(), j.size()];
+    let storage = Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data)));
+    TensorDynLen::new(vec![i.clone(), j.clone()], dims, storage)
+}
+
+#[test]
+fn test_exp_general_matrix() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    // exp([[0, 1], [0, 0]]) = [[1, 1], [0, 1]]
+    let tensor = matrix_f64(&i, &j, vec![0.0, 1.0, 0.0, 0.0]);
+    let result = exp::<DynId, _, f64>(&tensor, &[i.clone()], &[j.clone()]).expect("exp should succeed");
+    assert_eq!(result.indices, vec![i, j]);
+    let data = f64::extract_dense(&result.storage).unwrap();
+    for (x, expected) in data.iter().zip([1.0, 1.0, 0.0, 1.0]) {
+        assert!((x - expected).abs() < 1e-14);
+    }
+}
+
+#[test]
+fn test_exp_rejects_non_finite() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    for bad in [f64::INFINITY, f64::NAN] {
+        let tensor = matrix_f64(&i, &j, vec![0.0, bad, 0.0, 0.0]);
+        assert!(matches!(
+            exp::<DynId, _, f64>(&tensor, &[i.clone()], &[j.clone()]),
+            Err(EigenError::NotFinite)
+        ));
+    }
+}
+
+#[test]
+fn test_exp_time_evolution_gate() {
+    // exp(-iτX) = cos(τ) I - i sin(τ) X
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let tau = 2.7;
+    let minus_i_tau = Complex64::new(0.0, -tau);
+    let zero = Complex64::new(0.0, 0.0);
+    let storage = Arc::new(Storage::DenseC64(DenseStorageC64::from_vec(vec![
+        zero,
+        minus_i_tau,
+        minus_i_tau,
+        zero,
+    ])));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), j.clone()], vec![2, 2], storage);
+
+    let gate = exp::<DynId, _, Complex64>(&tensor, &[i], &[j]).expect("exp should succeed");
+    let data = Complex64::extract_dense(&gate.storage).unwrap();
+    let c = Complex64::new(tau.cos(), 0.0);
+    let s = Complex64::new(0.0, -tau.sin());
+    for (x, expected) in data.iter().zip([c, s, s, c]) {
+        assert!((x - expected).norm() < 1e-13);
+    }
+}
+
+#[test]
+fn test_sqrt_and_inv_sqrt() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    // Positive definite matrix with eigenvalues 4 and 1
+    let a = vec![2.5, 1.5, 1.5, 2.5];
+    let tensor = matrix_f64(&i, &j, a.clone());
+
+    let root = sqrt::<DynId, _, f64>(&tensor, &[i.clone()], &[j.clone()]).expect("sqrt should succeed");
+    let r = f64::extract_dense(&root.storage).unwrap();
+    // sqrt(A) = [[1.5, 0.5], [0.5, 1.5]]
+    for (x, expected) in r.iter().zip([1.5, 0.5, 0.5, 1.5]) {
+        assert!((x - expected).abs() < 1e-12);
+    }
+
+    let inv_root = inv_sqrt::<DynId, _, f64>(&tensor, &[i.clone()], &[j.clone()]).expect("inv_sqrt should succeed");
+    let s = f64::extract_dense(&inv_root.storage).unwrap();
+    // sqrt(A) * inv_sqrt(A) = I
+    for row in 0..2 {
+        for col in 0..2 {
+            let x: f64 = (0..2).map(|k| r[row * 2 + k] * s[k * 2 + col]).sum();
+            let expected = if row == col { 1.0 } else { 0.0 };
+            assert!((x - expected).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_sqrt_rejects_indefinite() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    // Eigenvalues 1 and -1
+    let tensor = matrix_f64(&i, &j, vec![0.0, 1.0, 1.0, 0.0]);
+    assert!(matches!(
+        sqrt::<DynId, _, f64>(&tensor, &[i.clone()], &[j.clone()]),
+        Err(EigenError::NotPositiveSemidefinite(_))
+    ));
+
+    // Singular matrix has no inverse square root
+    let tensor = matrix_f64(&i, &j, vec![1.0, 1.0, 1.0, 1.0]);
+    assert!(matches!(
+        inv_sqrt::<DynId, _, f64>(&tensor, &[i.clone()], &[j.clone()]),
+        Err(EigenError::NotPositiveDefinite(_))
+    ));
+    assert!(sqrt::<DynId, _, f64>(&tensor, &[i], &[j]).is_ok());
+}
@@ -0,0 +1,77 @@
+use num_complex::Complex64;
+use std::sync::Arc;
+use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
+use tensor4all_core_linalg::{polar, polar_c64};
+use tensor4all_core_tensor::storage::{DenseStorageC64, DenseStorageF64};
+use tensor4all_core_tensor::{Storage, StorageScalar, TensorDynLen};
+
+#[test]
+fn test_polar_real() {
+    let i = Index::new_dyn(3);
+    let j = Index::new_dyn(2);
+    let data = vec![1.0, 2.0, -1.0, 0.5, 3.0, 1.0];
+    let storage = Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data.clone())));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), j.clone()], vec![3, 2], storage);
+
+    let (u, p) = polar::<DynId, _, f64>(&tensor, &[i.clone()]).expect("polar should succeed");
+    assert_eq!(u.dims, vec![3, 2]);
+    assert_eq!(p.dims, vec![2, 2]);
+    assert_eq!(u.indices[1], p.indices[0]);
+    assert_eq!(p.indices[1], j);
+
+    // U has orthonormal columns
+    let u_data = f64::extract_dense(&u.storage).unwrap();
+    for a in 0..2 {
+        for b in 0..2 {
+            let dot: f64 = (0..3).map(|r| u_data[r * 2 + a] * u_data[r * 2 + b]).sum();
+            let expected = if a == b { 1.0 } else { 0.0 };
+            assert!((dot - expected).abs() < 1e-12);
+        }
+    }
+
+    // P is symmetric positive semidefinite
+    let p_data = f64::extract_dense(&p.storage).unwrap();
+    assert!((p_data[1] - p_data[2]).abs() < 1e-12);
+    assert!(p_data[0] >= 0.0 && p_data[3] >= 0.0);
+    assert!(p_data[0] * p_data[3] - p_data[1] * p_data[2] >= -1e-12);
+
+    // U * P = A
+    let reconstructed = u.contract(&p);
+    assert_eq!(reconstructed.indices, vec![i, j]);
+    let r_data = f64::extract_dense(&reconstructed.storage).unwrap();
+    for (x, y) in r_data.iter().zip(&data) {
+        assert!((x - y).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_polar_complex_wide() {
+    // m < n: U has orthonormal rows
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(3);
+    let data: Vec<Complex64> = (0..6)
+        .map(|x| Complex64::new((x + 1) as f64, (x % 3) as f64 - 1.0))
+        .collect();
+    let storage = Arc::new(Storage::DenseC64(DenseStorageC64::from_vec(data.clone())));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), j.clone()], vec![2, 3], storage);
+
+    let (u, p) = polar_c64(&tensor, &[i.clone()]).expect("polar should succeed");
+    assert_eq!(u.dims, vec![2, 3]);
+    assert_eq!(p.dims, vec![3, 3]);
+
+    let u_data = Complex64::extract_dense(&u.storage).unwrap();
+    for a in 0..2 {
+        for b in 0..2 {
+            let dot: Complex64 = (0..3).map(|c| u_data[a * 3 + c] * u_data[b * 3 + c].conj()).sum();
+            let expected = if a == b { 1.0 } else { 0.0 };
+            assert!((dot - expected).norm() < 1e-12);
+        }
+    }
+
+    let reconstructed = u.contract(&p);
+    assert_eq!(reconstructed.indices, vec![i, j]);
+    let r_data = Complex64::extract_dense(&reconstructed.storage).unwrap();
+    for (x, y) in r_data.iter().zip(&data) {
+        assert!((x - y).norm() < 1e-12);
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
+use tensor4all_core_linalg::{rsvd, svd_truncated, RsvdOptions, SvdError, SvdOptions};
+use tensor4all_core_tensor::storage::DenseStorageF64;
+use tensor4all_core_tensor::{Storage, StorageScalar, TensorDynLen};
+
+/// Create a 40×30 matrix with singular values decaying as 2^-k.
+fn decaying_matrix() -> (TensorDynLen<DynId>, Index<DynId>, Vec<f64>) {
+    let (m, n) = (40, 30);
+    let i = Index::new_dyn(m);
+    let j = Index::new_dyn(n);
+    let mut data = vec![0.0; m * n];
+    for k in 0..n {
+        let sigma = 0.5_f64.powi(k as i32);
+        for r in 0..m {
+            for c in 0..n {
+                let u = ((r * (k + 1)) as f64 * 0.37).sin();
+                let v = ((c * (k + 2)) as f64 * 0.23).cos();
+                data[r * n + c] += sigma * u * v;
+            }
+        }
+    }
+    let storage = Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data.clone())));
+    (TensorDynLen::new(vec![i.clone(), j], vec![m, n], storage), i, data)
+}
+
+#[test]
+fn test_rsvd_matches_svd() {
+    let (tensor, i, data) = decaying_matrix();
+
+    let options = RsvdOptions::new(8).with_oversampling(5).with_power_iterations(2).with_seed(42);
+    let result = rsvd::<DynId, _, f64>(&tensor, &[i.clone()], &options).expect("rSVD should succeed");
+    assert_eq!(result.rank(), 8);
+    assert_eq!(result.u.dims, vec![40, 8]);
+    assert_eq!(result.v.dims, vec![30, 8]);
+    assert_eq!(result.singular_values.len(), 13);
+
+    // Leading singular values agree with the exact SVD
+    let exact = svd_truncated::<DynId, _, f64>(&tensor, &[i], &SvdOptions::with_rtol(0.0))
+        .expect("SVD should succeed");
+    for (approx, exact) in result.singular_values[..8].iter().zip(&exact.singular_values) {
+        assert!((approx - exact).abs() < 1e-6 * exact.max(1.0), "{} vs {}", approx, exact);
+    }
+
+    // Reconstruction error is close to the optimal rank-8 error
+    let u = f64::extract_dense(&result.u.storage).unwrap();
+    let v = f64::extract_dense(&result.v.storage).unwrap();
+    let s = match result.s.storage.as_ref() {
+        Storage::DiagF64(diag) => diag.as_slice().to_vec(),
+        _ => panic!("S should be diagonal storage"),
+    };
+    let mut err_sq = 0.0;
+    for r in 0..40 {
+        for c in 0..30 {
+            let x: f64 = (0..8).map(|k| u[r * 8 + k] * s[k] * v[c * 8 + k]).sum();
+            err_sq += (x - data[r * 30 + c]).powi(2);
+        }
+    }
+    let optimal_sq: f64 = exact.singular_values[8..].iter().map(|s| s * s).sum();
+    assert!(err_sq.sqrt() < 2.0 * optimal_sq.sqrt() + 1e-12);
+}
+
+#[test]
+fn test_rsvd_truncation_and_errors() {
+    let (tensor, i, _) = decaying_matrix();
+
+    // rtol truncates below the target rank
+    let options = RsvdOptions::new(20).with_truncation(SvdOptions::with_rtol(1e-2));
+    let result = rsvd::<DynId, _, f64>(&tensor, &[i.clone()], &options).expect("rSVD should succeed");
+    assert!(result.rank() < 20);
+    assert!(result.truncation_error() <= 1e-2);
+
+    // Zero target rank is rejected
+    let options = RsvdOptions::new(0);
+    assert!(matches!(
+        rsvd::<DynId, _, f64>(&tensor, &[i], &options),
+        Err(SvdError::InvalidRankBounds { .. })
+    ));
+}
@@ -2,7 +2,8 @@ use num_complex::Complex64;
 use std::sync::Arc;
 use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
 use tensor4all_core_linalg::{
-    default_svd_rtol, set_default_svd_rtol, svd, svd_c64, svd_with, SvdOptions,
+    default_svd_rtol, set_default_svd_rtol, svd, svd_c64, svd_truncated, svd_with, SvdError,
+    SvdOptions,
 };
 use tensor4all_core_tensor::{Storage, TensorDynLen};
 
@@ -517,3 +518,107 @@ fn test_svd_uses_global_default() {
     // Restore original
     set_default_svd_rtol(original_rtol).expect("Should restore original rtol");
 }
+
+/// Create a square diagonal matrix tensor whose singular values are `diag`.
+fn diag_matrix(diag: &[f64]) -> (TensorDynLen<DynId>, Index<DynId>) {
+    let n = diag.len();
+    let i = Index::new_dyn(n);
+    let j = Index::new_dyn(n);
+    let mut data = vec![0.0; n * n];
+    for (k, &d) in diag.iter().enumerate() {
+        data[k * n + k] = d;
+    }
+    let storage = Arc::new(Storage::DenseF64(
+        tensor4all_core_tensor::storage::DenseStorageF64::from_vec(data),
+    ));
+    (TensorDynLen::new(vec![i.clone(), j], vec![n, n], storage), i)
+}
+
+#[test]
+fn test_svd_max_and_min_rank() {
+    let (tensor, i) = diag_matrix(&[4.0, 3.0, 2.0, 1.0]);
+
+    // max_rank caps the bond dimension
+    let options = SvdOptions::with_rtol(1e-14).with_max_rank(2);
+    let result = svd_truncated::<DynId, _, f64>(&tensor, &[i.clone()], &options).expect("SVD should succeed");
+    assert_eq!(result.rank(), 2);
+    assert_eq!(result.u.dims, vec![4, 2]);
+    assert_eq!(result.v.dims, vec![4, 2]);
+    assert!((result.discarded_weight - 5.0).abs() < 1e-12);
+    assert!((result.truncation_error() - (5.0_f64 / 30.0).sqrt()).abs() < 1e-12);
+
+    // The full spectrum is returned regardless of truncation
+    assert_eq!(result.singular_values.len(), 4);
+    for (s, expected) in result.singular_values.iter().zip([4.0, 3.0, 2.0, 1.0]) {
+        assert!((s - expected).abs() < 1e-12);
+    }
+
+    // min_rank keeps singular values that rtol would discard
+    let options = SvdOptions::with_rtol(0.9).with_min_rank(3);
+    let result = svd_truncated::<DynId, _, f64>(&tensor, &[i.clone()], &options).expect("SVD should succeed");
+    assert_eq!(result.rank(), 3);
+    assert!((result.discarded_weight - 1.0).abs() < 1e-12);
+
+    // min_rank never exceeds the full rank
+    let options = SvdOptions::with_rtol(1e-14).with_min_rank(10).with_max_rank(10);
+    let (u, s, _v) = svd_with::<DynId, _, f64>(&tensor, &[i], &options).expect("SVD should succeed");
+    assert_eq!(u.dims[1], 4);
+    assert_eq!(s.dims[0], 4);
+}
+
+#[test]
+fn test_svd_cutoff_and_atol() {
+    let (tensor, i) = diag_matrix(&[4.0, 3.0, 2.0, 1.0]);
+
+    // cutoff on squared singular values: 1/30 <= 0.04 < 5/30
+    let options = SvdOptions::with_rtol(1e-14).with_cutoff(0.04);
+    let result = svd_truncated::<DynId, _, f64>(&tensor, &[i.clone()], &options).expect("SVD should succeed");
+    assert_eq!(result.rank(), 3);
+    assert!((result.discarded_weight - 1.0).abs() < 1e-12);
+
+    // Absolute cutoff discards singular values below 2.5
+    let options = SvdOptions::with_rtol(1e-14).with_atol(2.5);
+    let result = svd_truncated::<DynId, _, f64>(&tensor, &[i.clone()], &options).expect("SVD should succeed");
+    assert_eq!(result.rank(), 2);
+    match result.s.storage.as_ref() {
+        Storage::DiagF64(diag) => {
+            assert!((diag.as_slice()[0] - 4.0).abs() < 1e-12);
+            assert!((diag.as_slice()[1] - 3.0).abs() < 1e-12);
+        }
+        _ => panic!("S should be diagonal storage"),
+    }
+
+    // At least one singular value is always kept
+    let options = SvdOptions::with_rtol(1e-14).with_atol(10.0);
+    let result = svd_truncated::<DynId, _, f64>(&tensor, &[i], &options).expect("SVD should succeed");
+    assert_eq!(result.rank(), 1);
+}
+
+#[test]
+fn test_svd_invalid_truncation_options() {
+    let (tensor, i) = diag_matrix(&[2.0, 1.0]);
+
+    let options = SvdOptions::default().with_cutoff(-1.0);
+    assert!(matches!(
+        svd_truncated::<DynId, _, f64>(&tensor, &[i.clone()], &options),
+        Err(SvdError::InvalidCutoff(_))
+    ));
+
+    let options = SvdOptions::default().with_atol(f64::NAN);
+    assert!(matches!(
+        svd_truncated::<DynId, _, f64>(&tensor, &[i.clone()], &options),
+        Err(SvdError::InvalidAtol(_))
+    ));
+
+    let options = SvdOptions::default().with_max_rank(0);
+    assert!(matches!(
+        svd_truncated::<DynId, _, f64>(&tensor, &[i.clone()], &options),
+        Err(SvdError::InvalidRankBounds { .. })
+    ));
+
+    let options = SvdOptions::default().with_min_rank(3).with_max_rank(2);
+    assert!(matches!(
+        svd_truncated::<DynId, _, f64>(&tensor, &[i], &options),
+        Err(SvdError::InvalidRankBounds { min_rank: 3, max_rank: 2 })
+    ));
+}
@@ -19,8 +19,17 @@ pub enum AnyScalar {
 impl SumFromStorage for AnyScalar {
     fn sum_from_storage(storage: &Storage) -> Self {
         match storage {
-            Storage::DenseF64(_) | Storage::DiagF64(_) => AnyScalar::F64(f64::sum_from_storage(storage)),
-            Storage::DenseC64(_) | Storage::DiagC64(_) => AnyScalar::C64(Complex64::sum_from_storage(storage)),
+            // Single-precision storage is summed in double precision
+            Storage::DenseF64(_)
+            | Storage::DiagF64(_)
+            | Storage::BlockSparseF64(_)
+            | Storage::DenseF32(_)
+            | Storage::DiagF32(_) => AnyScalar::F64(f64::sum_from_storage(storage)),
+            Storage::DenseC64(_)
+            | Storage::DiagC64(_)
+            | Storage::BlockSparseC64(_)
+            | Storage::DenseC32(_)
+            | Storage::DiagC32(_) => AnyScalar::C64(Complex64::sum_from_storage(storage)),
         }
     }
 }
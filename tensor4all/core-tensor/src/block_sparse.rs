@@ -0,0 +1,432 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ops::{Add, Mul};
+use num_complex::Complex64;
+use num_traits::Zero;
+use crate::gemm::{contract_dense, permute_data, GemmScalar};
+use crate::storage::Storage;
+
+/// Block-sparse storage.
+///
+/// Every axis is divided into blocks (e.g. the quantum number blocks of a `QNSpace`),
+/// and only nonzero blocks are stored, keyed by their block coordinates. Each block
+/// holds its elements densely in row-major order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockSparseStorage<T> {
+    block_dims: Vec<Vec<usize>>,
+    blocks: BTreeMap<Vec<usize>, Vec<T>>,
+}
+
+/// Block-sparse storage for f64 elements.
+pub type BlockSparseStorageF64 = BlockSparseStorage<f64>;
+
+/// Block-sparse storage for Complex64 elements.
+pub type BlockSparseStorageC64 = BlockSparseStorage<Complex64>;
+
+impl<T> BlockSparseStorage<T> {
+    /// Create an empty block-sparse storage (all blocks zero).
+    ///
+    /// `block_dims[k]` lists the block dimensions along axis `k`.
+    pub fn new(block_dims: Vec<Vec<usize>>) -> Self {
+        Self {
+            block_dims,
+            blocks: BTreeMap::new(),
+        }
+    }
+
+    /// Get the number of axes.
+    pub fn rank(&self) -> usize {
+        self.block_dims.len()
+    }
+
+    /// Get the block dimensions of every axis.
+    pub fn block_dims(&self) -> &[Vec<usize>] {
+        &self.block_dims
+    }
+
+    /// Get the total dimension of every axis.
+    pub fn dims(&self) -> Vec<usize> {
+        self.block_dims.iter().map(|b| b.iter().sum()).collect()
+    }
+
+    /// Get the shape of the block at the given block coordinates.
+    pub fn block_shape(&self, coords: &[usize]) -> Vec<usize> {
+        coords
+            .iter()
+            .zip(&self.block_dims)
+            .map(|(&c, dims)| dims[c])
+            .collect()
+    }
+
+    /// Get the number of stored (nonzero) blocks.
+    pub fn nblocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Get the number of stored elements.
+    pub fn len(&self) -> usize {
+        self.blocks.values().map(|b| b.len()).sum()
+    }
+
+    /// Check if no elements are stored.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Get the data of the block at the given block coordinates, if stored.
+    pub fn block(&self, coords: &[usize]) -> Option<&[T]> {
+        self.blocks.get(coords).map(|b| b.as_slice())
+    }
+
+    /// Get mutable data of the block at the given block coordinates, if stored.
+    pub fn block_mut(&mut self, coords: &[usize]) -> Option<&mut [T]> {
+        self.blocks.get_mut(coords).map(|b| b.as_mut_slice())
+    }
+
+    /// Iterate over stored blocks as `(block coordinates, data)`.
+    pub fn iter_blocks(&self) -> impl Iterator<Item = (&[usize], &[T])> {
+        self.blocks.iter().map(|(c, b)| (c.as_slice(), b.as_slice()))
+    }
+
+    /// Iterate over all stored elements (block by block).
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.blocks.values().flat_map(|b| b.iter())
+    }
+
+    /// Insert (or replace) a block.
+    ///
+    /// # Panics
+    /// Panics if the coordinates are out of range or the data length doesn't match
+    /// the block shape.
+    pub fn insert_block(&mut self, coords: Vec<usize>, data: Vec<T>) {
+        assert_eq!(coords.len(), self.rank(), "block coordinates must match the rank");
+        for (k, &c) in coords.iter().enumerate() {
+            assert!(
+                c < self.block_dims[k].len(),
+                "block coordinate {} out of range for axis {} with {} blocks",
+                c,
+                k,
+                self.block_dims[k].len()
+            );
+        }
+        let expected_len: usize = self.block_shape(&coords).iter().product();
+        assert_eq!(
+            data.len(),
+            expected_len,
+            "block data length {} does not match block shape {:?}",
+            data.len(),
+            self.block_shape(&coords)
+        );
+        self.blocks.insert(coords, data);
+    }
+
+    /// Apply a function to every stored element.
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> BlockSparseStorage<U> {
+        BlockSparseStorage {
+            block_dims: self.block_dims.clone(),
+            blocks: self
+                .blocks
+                .iter()
+                .map(|(c, b)| (c.clone(), b.iter().map(&f).collect()))
+                .collect(),
+        }
+    }
+}
+
+impl<T> BlockSparseStorage<T>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    /// Create a block-sparse storage from dense row-major data, keeping the blocks
+    /// for which `keep` returns `true`.
+    ///
+    /// # Panics
+    /// Panics if the data length doesn't match the total dimensions.
+    pub fn from_dense(block_dims: Vec<Vec<usize>>, data: &[T], mut keep: impl FnMut(&[usize]) -> bool) -> Self {
+        let mut result = Self::new(block_dims);
+        let dims = result.dims();
+        assert_eq!(
+            data.len(),
+            dims.iter().product::<usize>(),
+            "dense data length does not match dims {:?}",
+            dims
+        );
+        for coords in all_block_coords(&result.block_dims) {
+            if keep(&coords) {
+                let block = result.extract_block(&coords, data, &dims);
+                result.blocks.insert(coords, block);
+            }
+        }
+        result
+    }
+
+    /// Create a block-sparse storage with the given blocks set to zero.
+    pub fn zeros(block_dims: Vec<Vec<usize>>, coords: impl IntoIterator<Item = Vec<usize>>) -> Self {
+        let mut result = Self::new(block_dims);
+        for c in coords {
+            let len = result.block_shape(&c).iter().product();
+            result.insert_block(c, vec![T::zero(); len]);
+        }
+        result
+    }
+
+    /// Offsets of the block at `coords` along every axis.
+    fn block_offsets(&self, coords: &[usize]) -> Vec<usize> {
+        coords
+            .iter()
+            .zip(&self.block_dims)
+            .map(|(&c, dims)| dims[..c].iter().sum())
+            .collect()
+    }
+
+    /// Copy the elements of one block out of dense row-major data.
+    fn extract_block(&self, coords: &[usize], data: &[T], dims: &[usize]) -> Vec<T> {
+        let shape = self.block_shape(coords);
+        let offsets = self.block_offsets(coords);
+        let strides = row_major_strides(dims);
+        let mut block = Vec::with_capacity(shape.iter().product());
+        for_each_multi_index(&shape, |idx| {
+            let pos: usize = idx
+                .iter()
+                .zip(&offsets)
+                .zip(&strides)
+                .map(|((&i, &o), &s)| (i + o) * s)
+                .sum();
+            block.push(data[pos]);
+        });
+        block
+    }
+
+    /// Convert to dense row-major data, filling missing blocks with zeros.
+    pub fn to_dense_vec(&self) -> Vec<T> {
+        let dims = self.dims();
+        let strides = row_major_strides(&dims);
+        let mut dense = vec![T::zero(); dims.iter().product()];
+        for (coords, block) in &self.blocks {
+            let shape = self.block_shape(coords);
+            let offsets = self.block_offsets(coords);
+            let mut values = block.iter();
+            for_each_multi_index(&shape, |idx| {
+                let pos: usize = idx
+                    .iter()
+                    .zip(&offsets)
+                    .zip(&strides)
+                    .map(|((&i, &o), &s)| (i + o) * s)
+                    .sum();
+                dense[pos] = *values.next().expect("block data matches its shape");
+            });
+        }
+        dense
+    }
+
+    /// Permute the axes: new axis `i` is old axis `perm[i]`.
+    ///
+    /// Both the block coordinates and the data inside every block are permuted.
+    pub fn permute(&self, perm: &[usize]) -> Self {
+        assert_eq!(perm.len(), self.rank(), "permutation length must match rank");
+        let block_dims = perm.iter().map(|&p| self.block_dims[p].clone()).collect();
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|(coords, block)| {
+                let shape = self.block_shape(coords);
+                let new_coords = perm.iter().map(|&p| coords[p]).collect();
+                (new_coords, permute_data(block, &shape, perm))
+            })
+            .collect();
+        Self { block_dims, blocks }
+    }
+
+    /// Add two block-sparse storages with the same block structure.
+    ///
+    /// Blocks stored in only one of the operands are copied.
+    pub fn try_add(&self, other: &Self) -> Result<Self, String> {
+        if self.block_dims != other.block_dims {
+            return Err(format!(
+                "Block structures must match for addition: {:?} vs {:?}",
+                self.block_dims, other.block_dims
+            ));
+        }
+        let mut result = self.clone();
+        for (coords, block) in &other.blocks {
+            match result.blocks.get_mut(coords) {
+                Some(existing) => {
+                    for (x, &y) in existing.iter_mut().zip(block) {
+                        *x = *x + y;
+                    }
+                }
+                None => {
+                    result.blocks.insert(coords.clone(), block.clone());
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Sum all stored elements.
+    pub fn sum(&self) -> T {
+        self.iter().fold(T::zero(), |acc, &x| acc + x)
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: GemmScalar + Add<Output = T>> BlockSparseStorage<T> {
+    /// Contract with another block-sparse storage along the given axes.
+    ///
+    /// Only pairs of blocks whose coordinates agree on all contracted axes are
+    /// multiplied. The result axes are the free axes of `self` followed by the free
+    /// axes of `other`.
+    ///
+    /// # Panics
+    /// Panics if the block structures of contracted axes differ.
+    pub fn contract(&self, axes: &[usize], other: &Self, other_axes: &[usize]) -> Self {
+        assert_eq!(axes.len(), other_axes.len(), "number of contracted axes must match");
+        for (&a, &b) in axes.iter().zip(other_axes) {
+            assert_eq!(
+                self.block_dims[a], other.block_dims[b],
+                "block structure of contracted axes must match: axis {} vs axis {}",
+                a, b
+            );
+        }
+        let free_a: Vec<usize> = (0..self.rank()).filter(|k| !axes.contains(k)).collect();
+        let free_b: Vec<usize> = (0..other.rank()).filter(|k| !other_axes.contains(k)).collect();
+        let block_dims = free_a
+            .iter()
+            .map(|&k| self.block_dims[k].clone())
+            .chain(free_b.iter().map(|&k| other.block_dims[k].clone()))
+            .collect();
+        let mut result = Self::new(block_dims);
+
+        // Group the blocks of `other` by their coordinates on the contracted axes
+        let mut other_by_key: HashMap<Vec<usize>, Vec<&Vec<usize>>> = HashMap::new();
+        for coords in other.blocks.keys() {
+            let key = other_axes.iter().map(|&k| coords[k]).collect();
+            other_by_key.entry(key).or_default().push(coords);
+        }
+
+        for (coords_a, block_a) in &self.blocks {
+            let key: Vec<usize> = axes.iter().map(|&k| coords_a[k]).collect();
+            let Some(matches) = other_by_key.get(&key) else {
+                continue;
+            };
+            let shape_a = self.block_shape(coords_a);
+            for &coords_b in matches {
+                let shape_b = other.block_shape(coords_b);
+                let block_b = &other.blocks[coords_b];
+                let product = contract_dense(block_a, &shape_a, axes, block_b, &shape_b, other_axes);
+                let coords: Vec<usize> = free_a
+                    .iter()
+                    .map(|&k| coords_a[k])
+                    .chain(free_b.iter().map(|&k| coords_b[k]))
+                    .collect();
+                match result.blocks.get_mut(&coords) {
+                    Some(existing) => {
+                        for (x, y) in existing.iter_mut().zip(product) {
+                            *x = *x + y;
+                        }
+                    }
+                    None => {
+                        result.blocks.insert(coords, product);
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Scalar types that can be stored in block-sparse `Storage`.
+pub trait BlockSparseScalar: Copy + Zero + PartialEq + Add<Output = Self> + Mul<Output = Self> + 'static {
+    /// Wrap block-sparse data into the matching `Storage` variant.
+    fn block_sparse_storage(storage: BlockSparseStorage<Self>) -> Storage;
+}
+
+impl BlockSparseScalar for f64 {
+    fn block_sparse_storage(storage: BlockSparseStorage<Self>) -> Storage {
+        Storage::BlockSparseF64(storage)
+    }
+}
+
+impl BlockSparseScalar for Complex64 {
+    fn block_sparse_storage(storage: BlockSparseStorage<Self>) -> Storage {
+        Storage::BlockSparseC64(storage)
+    }
+}
+
+/// All block coordinates of a block structure, in row-major order.
+pub(crate) fn all_block_coords(block_dims: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let nblocks: Vec<usize> = block_dims.iter().map(|b| b.len()).collect();
+    let mut coords = Vec::new();
+    for_each_multi_index(&nblocks, |idx| coords.push(idx.to_vec()));
+    coords
+}
+
+/// Row-major strides for the given dimensions.
+fn row_major_strides(dims: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; dims.len()];
+    for k in (0..dims.len().saturating_sub(1)).rev() {
+        strides[k] = strides[k + 1] * dims[k + 1];
+    }
+    strides
+}
+
+/// Call `f` for every multi-index of the given shape, in row-major order.
+fn for_each_multi_index(shape: &[usize], mut f: impl FnMut(&[usize])) {
+    if shape.contains(&0) {
+        return;
+    }
+    let mut idx = vec![0; shape.len()];
+    loop {
+        f(&idx);
+        let mut k = shape.len();
+        loop {
+            if k == 0 {
+                return;
+            }
+            k -= 1;
+            idx[k] += 1;
+            if idx[k] < shape[k] {
+                break;
+            }
+            idx[k] = 0;
+        }
+    }
+}
+
+/// Serialized as the block structure and a list of `(block coordinates, data)` pairs.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for BlockSparseStorage<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let blocks: Vec<(&Vec<usize>, &Vec<T>)> = self.blocks.iter().collect();
+        let mut state = serializer.serialize_struct("BlockSparseStorage", 2)?;
+        state.serialize_field("block_dims", &self.block_dims)?;
+        state.serialize_field("blocks", &blocks)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for BlockSparseStorage<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "BlockSparseStorage")]
+        struct BlockSparseData<T> {
+            block_dims: Vec<Vec<usize>>,
+            blocks: Vec<(Vec<usize>, Vec<T>)>,
+        }
+
+        let data = BlockSparseData::<T>::deserialize(deserializer)?;
+        let mut result = Self::new(data.block_dims);
+        for (coords, block) in data.blocks {
+            let valid_coords = coords.len() == result.rank()
+                && coords.iter().zip(&result.block_dims).all(|(&c, dims)| c < dims.len());
+            if !valid_coords || block.len() != result.block_shape(&coords).iter().product::<usize>() {
+                return Err(D::Error::custom(format!("invalid block {:?}", coords)));
+            }
+            result.blocks.insert(coords, block);
+        }
+        Ok(result)
+    }
+}
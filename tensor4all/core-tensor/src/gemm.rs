@@ -0,0 +1,198 @@
+//! Dense tensor contraction by permute-then-GEMM.
+//!
+//! A contraction of two dense row-major tensors is mapped onto a single matrix
+//! multiplication C (m×n) = A (m×k) · B (k×n), where m (n) is the product of the free
+//! dimensions of the first (second) tensor and k the product of the contracted dimensions.
+//!
+//! Permutations are avoided where possible: if the contracted axes of an operand already
+//! form a contiguous leading or trailing block, the operand is passed to the GEMM kernel
+//! as a (possibly transposed) strided view of its data without copying.
+//! The matrix multiplication uses faer, or BLAS when the `blas` feature is enabled.
+
+use faer_traits::ComplexField;
+use mdarray::{DenseMapping, DynRank, View, Dense};
+use num_complex::{Complex32, Complex64};
+use num_traits::{One, Zero};
+
+#[cfg(feature = "blas")]
+use mdarray::{DTensor, Rank};
+#[cfg(feature = "blas")]
+use mdarray_linalg::matmul::{MatMul, MatMulBuilder};
+#[cfg(feature = "blas")]
+use mdarray_linalg_blas::Blas;
+
+/// Scalar types supported by the GEMM-based dense contraction.
+pub(crate) trait GemmScalar: Copy + Zero + One + ComplexField + 'static {
+    /// Multiply two contiguous row-major matrices with BLAS.
+    #[cfg(feature = "blas")]
+    fn blas_matmul(a: &DTensor<Self, 2>, b: &DTensor<Self, 2>) -> DTensor<Self, 2>;
+}
+
+impl GemmScalar for f64 {
+    #[cfg(feature = "blas")]
+    fn blas_matmul(a: &DTensor<Self, 2>, b: &DTensor<Self, 2>) -> DTensor<Self, 2> {
+        Blas.matmul(a, b).eval()
+    }
+}
+
+impl GemmScalar for Complex64 {
+    #[cfg(feature = "blas")]
+    fn blas_matmul(a: &DTensor<Self, 2>, b: &DTensor<Self, 2>) -> DTensor<Self, 2> {
+        Blas.matmul(a, b).eval()
+    }
+}
+
+impl GemmScalar for f32 {
+    #[cfg(feature = "blas")]
+    fn blas_matmul(a: &DTensor<Self, 2>, b: &DTensor<Self, 2>) -> DTensor<Self, 2> {
+        Blas.matmul(a, b).eval()
+    }
+}
+
+impl GemmScalar for Complex32 {
+    #[cfg(feature = "blas")]
+    fn blas_matmul(a: &DTensor<Self, 2>, b: &DTensor<Self, 2>) -> DTensor<Self, 2> {
+        Blas.matmul(a, b).eval()
+    }
+}
+
+/// How an operand enters the matrix multiplication.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// The data is already the required matrix in row-major order.
+    Direct,
+    /// The data is the transpose of the required matrix in row-major order.
+    Transposed,
+    /// The data has to be permuted first.
+    Permuted,
+}
+
+/// Determine how an operand can be viewed as a matrix with the contracted axes `axes`
+/// (in this order) as the inner dimension.
+///
+/// `inner_last` is true for the left operand (m×k) and false for the right operand (k×n).
+fn operand_layout(rank: usize, axes: &[usize], inner_last: bool) -> Layout {
+    let nc = axes.len();
+    let leading = axes.iter().copied().eq(0..nc);
+    let trailing = axes.iter().copied().eq(rank - nc..rank);
+    match (inner_last, trailing, leading) {
+        (true, true, _) | (false, _, true) => Layout::Direct,
+        (true, false, true) | (false, true, false) => Layout::Transposed,
+        _ => Layout::Permuted,
+    }
+}
+
+/// Permute dense row-major data: new axis `i` is old axis `perm[i]`.
+pub(crate) fn permute_data<T: Copy>(data: &[T], dims: &[usize], perm: &[usize]) -> Vec<T> {
+    let shape = DynRank::from_dims(dims);
+    let mapping = DenseMapping::new(shape);
+    let view: View<'_, T, DynRank, Dense> = unsafe { View::new_unchecked(data.as_ptr(), mapping) };
+    view.into_permuted(perm).to_tensor().into_vec()
+}
+
+/// Contract two dense row-major tensors along the paired axes `axes_a[i]` ↔ `axes_b[i]`.
+///
+/// The result axes are the free axes of `a` followed by the free axes of `b`, each in
+/// their original order.
+pub(crate) fn contract_dense<T: GemmScalar>(
+    a: &[T],
+    dims_a: &[usize],
+    axes_a: &[usize],
+    b: &[T],
+    dims_b: &[usize],
+    axes_b: &[usize],
+) -> Vec<T> {
+    assert_eq!(axes_a.len(), axes_b.len(), "number of contracted axes must match");
+    let free_a: Vec<usize> = (0..dims_a.len()).filter(|k| !axes_a.contains(k)).collect();
+    let free_b: Vec<usize> = (0..dims_b.len()).filter(|k| !axes_b.contains(k)).collect();
+    let m: usize = free_a.iter().map(|&k| dims_a[k]).product();
+    let n: usize = free_b.iter().map(|&k| dims_b[k]).product();
+    let k: usize = axes_a.iter().map(|&ax| dims_a[ax]).product();
+
+    // The order of the contracted axes is free. Try the order of `a` and the order of `b`
+    // and keep the one that requires the fewest elements to be permuted.
+    let mut pairs: Vec<(usize, usize)> = axes_a.iter().copied().zip(axes_b.iter().copied()).collect();
+    let mut candidates = Vec::with_capacity(2);
+    pairs.sort_unstable_by_key(|&(x, _)| x);
+    candidates.push(pairs.clone());
+    pairs.sort_unstable_by_key(|&(_, y)| y);
+    candidates.push(pairs);
+    let (_, layout_a, layout_b, order) = candidates
+        .into_iter()
+        .map(|order| {
+            let inner_a: Vec<usize> = order.iter().map(|&(x, _)| x).collect();
+            let inner_b: Vec<usize> = order.iter().map(|&(_, y)| y).collect();
+            let layout_a = operand_layout(dims_a.len(), &inner_a, true);
+            let layout_b = operand_layout(dims_b.len(), &inner_b, false);
+            let cost = if layout_a == Layout::Permuted { a.len() } else { 0 }
+                + if layout_b == Layout::Permuted { b.len() } else { 0 };
+            (cost, layout_a, layout_b, order)
+        })
+        .min_by_key(|&(cost, ..)| cost)
+        .expect("there are always two candidate orders");
+
+    let a_mat;
+    let (a_data, layout_a) = if layout_a == Layout::Permuted {
+        let perm: Vec<usize> = free_a.iter().copied().chain(order.iter().map(|&(x, _)| x)).collect();
+        a_mat = permute_data(a, dims_a, &perm);
+        (a_mat.as_slice(), Layout::Direct)
+    } else {
+        (a, layout_a)
+    };
+    let b_mat;
+    let (b_data, layout_b) = if layout_b == Layout::Permuted {
+        let perm: Vec<usize> = order.iter().map(|&(_, y)| y).chain(free_b.iter().copied()).collect();
+        b_mat = permute_data(b, dims_b, &perm);
+        (b_mat.as_slice(), Layout::Direct)
+    } else {
+        (b, layout_b)
+    };
+
+    gemm(a_data, layout_a, b_data, layout_b, m, k, n)
+}
+
+/// Multiply the m×k matrix stored in `a` by the k×n matrix stored in `b` (row-major result).
+///
+/// `Layout::Transposed` operands are stored as the row-major transpose (k×m resp. n×k).
+#[cfg(not(feature = "blas"))]
+fn gemm<T: GemmScalar>(a: &[T], layout_a: Layout, b: &[T], layout_b: Layout, m: usize, k: usize, n: usize) -> Vec<T> {
+    use faer::{Accum, MatMut, MatRef};
+
+    let lhs = match layout_a {
+        Layout::Transposed => MatRef::from_row_major_slice(a, k, m).transpose(),
+        _ => MatRef::from_row_major_slice(a, m, k),
+    };
+    let rhs = match layout_b {
+        Layout::Transposed => MatRef::from_row_major_slice(b, n, k).transpose(),
+        _ => MatRef::from_row_major_slice(b, k, n),
+    };
+    let mut c = vec![T::zero(); m * n];
+    faer::linalg::matmul::matmul(
+        MatMut::from_row_major_slice_mut(&mut c, m, n),
+        Accum::Replace,
+        lhs,
+        rhs,
+        T::one(),
+        faer::get_global_parallelism(),
+    );
+    c
+}
+
+/// Multiply the m×k matrix stored in `a` by the k×n matrix stored in `b` (row-major result).
+///
+/// `Layout::Transposed` operands are stored as the row-major transpose (k×m resp. n×k)
+/// and are copied into contiguous row-major matrices before calling BLAS.
+#[cfg(feature = "blas")]
+fn gemm<T: GemmScalar>(a: &[T], layout_a: Layout, b: &[T], layout_b: Layout, m: usize, k: usize, n: usize) -> Vec<T> {
+    fn to_matrix<T: Copy>(data: &[T], layout: Layout, rows: usize, cols: usize) -> DTensor<T, 2> {
+        let data = match layout {
+            Layout::Transposed => permute_data(data, &[cols, rows], &[1, 0]),
+            _ => data.to_vec(),
+        };
+        mdarray::Tensor::<T, Rank<1>>::from(data).into_shape([rows, cols])
+    }
+
+    let a_mat = to_matrix(a, layout_a, m, k);
+    let b_mat = to_matrix(b, layout_b, k, n);
+    T::blas_matmul(&a_mat, &b_mat).into_vec()
+}
@@ -1,10 +1,15 @@
 pub mod any_scalar;
+pub mod block_sparse;
+mod gemm;
+pub mod network;
 pub mod physical_indices;
 pub mod storage;
 pub mod tensor;
 
 pub use any_scalar::AnyScalar;
+pub use block_sparse::{BlockSparseScalar, BlockSparseStorage, BlockSparseStorageC64, BlockSparseStorageF64};
+pub use network::{contract_network, contract_network_with, contraction_path, ContractionPath, ContractionStrategy, OPTIMAL_MAX_TENSORS};
 pub use physical_indices::PhysicalIndices;
-pub use storage::{DenseStorageFactory, Storage, StorageScalar, SumFromStorage, make_mut_storage, mindim, storage_to_dtensor};
+pub use storage::{DenseStorage, DenseStorageFactory, DiagStorage, ScalarType, Storage, StorageElement, StorageScalar, SumFromStorage, make_mut_storage, mindim, storage_to_dtensor};
 pub use tensor::{TensorDynLen, TensorType, TensorAccess, compute_permutation_from_indices, is_diag_tensor, diag_tensor_dyn_len, diag_tensor_dyn_len_c64, unfold_split};
 
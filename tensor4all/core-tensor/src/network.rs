@@ -0,0 +1,351 @@
+//! Contraction of networks of tensors in an optimized pairwise order.
+//!
+//! Indices with the same ID that appear in two tensors are contracted; indices that
+//! appear in only one tensor remain open. The cost of a pairwise contraction is
+//! estimated as the product of the dimensions of all indices involved (the number of
+//! multiply-adds of the corresponding GEMM).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::Result;
+use tensor4all_core_common::index::Symmetry;
+use crate::storage::contract_storage;
+use crate::tensor::TensorDynLen;
+
+/// Maximum number of tensors for which the optimal contraction order is searched.
+///
+/// The exhaustive search visits O(3^n) subset splits.
+pub const OPTIMAL_MAX_TENSORS: usize = 10;
+
+/// Strategy for choosing the pairwise contraction order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContractionStrategy {
+    /// Optimal order for networks of at most [`OPTIMAL_MAX_TENSORS`] tensors, greedy otherwise.
+    #[default]
+    Auto,
+    /// Repeatedly contract the connected pair that shrinks the total size the most.
+    Greedy,
+    /// Exhaustive search for the order with the lowest estimated cost.
+    Optimal,
+}
+
+/// Pairwise contraction order of a tensor network.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractionPath {
+    /// Pairs of tensors to contract, in order.
+    ///
+    /// The input tensors are numbered `0..n`, and the result of step `s` gets number `n + s`.
+    pub steps: Vec<(usize, usize)>,
+    /// Estimated total cost (sum of the products of the dimensions involved in each step).
+    pub cost: f64,
+    /// Number of elements of the largest intermediate tensor.
+    pub max_intermediate_size: f64,
+}
+
+/// Index structure of a network: each tensor as a list of labels, with label dimensions.
+struct Network {
+    tensors: Vec<Vec<usize>>,
+    dims: Vec<f64>,
+}
+
+impl Network {
+    fn new<Id, Symm>(tensors: &[TensorDynLen<Id, Symm>]) -> Result<Self>
+    where
+        Id: Clone + std::hash::Hash + Eq,
+    {
+        let mut labels: HashMap<Id, usize> = HashMap::new();
+        let mut dims: Vec<usize> = Vec::new();
+        let mut counts: Vec<usize> = Vec::new();
+        let mut network = Vec::with_capacity(tensors.len());
+        for (t, tensor) in tensors.iter().enumerate() {
+            let mut tensor_labels = Vec::with_capacity(tensor.indices.len());
+            for (idx, &dim) in tensor.indices.iter().zip(&tensor.dims) {
+                let label = *labels.entry(idx.id.clone()).or_insert_with(|| {
+                    dims.push(dim);
+                    counts.push(0);
+                    dims.len() - 1
+                });
+                if dims[label] != dim {
+                    return Err(anyhow::anyhow!(
+                        "Dimension mismatch for a shared index in tensor {}: {} != {}",
+                        t,
+                        dim,
+                        dims[label]
+                    ));
+                }
+                counts[label] += 1;
+                if counts[label] > 2 {
+                    return Err(anyhow::anyhow!(
+                        "Index appears in more than two tensors (last in tensor {})",
+                        t
+                    ));
+                }
+                if tensor_labels.contains(&label) {
+                    return Err(anyhow::anyhow!("Duplicate index in tensor {}", t));
+                }
+                tensor_labels.push(label);
+            }
+            network.push(tensor_labels);
+        }
+        Ok(Self {
+            tensors: network,
+            dims: dims.into_iter().map(|d| d as f64).collect(),
+        })
+    }
+
+    /// Number of elements of a tensor with the given labels.
+    fn size(&self, labels: &[usize]) -> f64 {
+        labels.iter().map(|&l| self.dims[l]).product()
+    }
+
+    /// Cost of contracting tensors with labels `a` and `b`.
+    fn pair_cost(&self, a: &[usize], b: &[usize]) -> f64 {
+        self.size(a) * b.iter().filter(|l| !a.contains(l)).map(|&l| self.dims[l]).product::<f64>()
+    }
+}
+
+/// Labels of the result of contracting tensors with labels `a` and `b`.
+///
+/// Since every label appears in at most two tensors, shared labels are exactly the
+/// contracted ones.
+fn result_labels(a: &[usize], b: &[usize]) -> Vec<usize> {
+    a.iter()
+        .filter(|l| !b.contains(l))
+        .chain(b.iter().filter(|l| !a.contains(l)))
+        .copied()
+        .collect()
+}
+
+/// Find the contraction order of a network greedily.
+fn greedy_path(network: &Network) -> ContractionPath {
+    let n = network.tensors.len();
+    let mut live: Vec<(usize, Vec<usize>)> = network.tensors.iter().cloned().enumerate().collect();
+    let mut path = ContractionPath {
+        steps: Vec::with_capacity(n.saturating_sub(1)),
+        cost: 0.0,
+        max_intermediate_size: 0.0,
+    };
+
+    while live.len() > 1 {
+        // Among connected pairs, pick the one that reduces the total size the most
+        // (ties broken by cost). Without connected pairs, take the outer product
+        // of the two smallest tensors.
+        let mut best: Option<((f64, f64), usize, usize)> = None;
+        for i in 0..live.len() {
+            for j in i + 1..live.len() {
+                let (a, b) = (&live[i].1, &live[j].1);
+                if !a.iter().any(|l| b.contains(l)) {
+                    continue;
+                }
+                let result = result_labels(a, b);
+                let key = (
+                    network.size(&result) - network.size(a) - network.size(b),
+                    network.pair_cost(a, b),
+                );
+                let better = match best {
+                    Some((best_key, ..)) => key < best_key,
+                    None => true,
+                };
+                if better {
+                    best = Some((key, i, j));
+                }
+            }
+        }
+        let (i, j) = match best {
+            Some((_, i, j)) => (i, j),
+            None => {
+                let mut order: Vec<usize> = (0..live.len()).collect();
+                order.sort_by(|&x, &y| network.size(&live[x].1).total_cmp(&network.size(&live[y].1)));
+                (order[0].min(order[1]), order[0].max(order[1]))
+            }
+        };
+
+        let (id_b, b) = live.remove(j);
+        let (id_a, a) = live.remove(i);
+        let result = result_labels(&a, &b);
+        path.cost += network.pair_cost(&a, &b);
+        path.max_intermediate_size = path.max_intermediate_size.max(network.size(&result));
+        path.steps.push((id_a, id_b));
+        live.push((n + path.steps.len() - 1, result));
+    }
+    path
+}
+
+/// Find the contraction order with the lowest cost by dynamic programming over subsets.
+fn optimal_path(network: &Network) -> ContractionPath {
+    let n = network.tensors.len();
+    let full = (1usize << n) - 1;
+    let mut labels: Vec<Vec<usize>> = vec![Vec::new(); full + 1];
+    let mut cost = vec![f64::INFINITY; full + 1];
+    let mut split = vec![0usize; full + 1];
+
+    for set in 1..=full {
+        let lowest = set & set.wrapping_neg();
+        let rest = set ^ lowest;
+        let t = lowest.trailing_zeros() as usize;
+        labels[set] = result_labels(&labels[rest], &network.tensors[t]);
+        if rest == 0 {
+            cost[set] = 0.0;
+            continue;
+        }
+        // Enumerate splits (sub, set ^ sub) with `sub` containing the lowest element
+        let mut sub = rest;
+        loop {
+            let left = sub | lowest;
+            if left != set {
+                let right = set ^ left;
+                let c = cost[left] + cost[right] + network.pair_cost(&labels[left], &labels[right]);
+                if c < cost[set] {
+                    cost[set] = c;
+                    split[set] = left;
+                }
+            }
+            if sub == 0 {
+                break;
+            }
+            sub = (sub - 1) & rest;
+        }
+    }
+
+    fn build(set: usize, n: usize, split: &[usize], labels: &[Vec<usize>], network: &Network, path: &mut ContractionPath) -> usize {
+        if set.count_ones() == 1 {
+            return set.trailing_zeros() as usize;
+        }
+        let left = split[set];
+        let a = build(left, n, split, labels, network, path);
+        let b = build(set ^ left, n, split, labels, network, path);
+        path.max_intermediate_size = path.max_intermediate_size.max(network.size(&labels[set]));
+        path.steps.push((a, b));
+        n + path.steps.len() - 1
+    }
+
+    let mut path = ContractionPath {
+        steps: Vec::with_capacity(n - 1),
+        cost: cost[full],
+        max_intermediate_size: 0.0,
+    };
+    build(full, n, &split, &labels, network, &mut path);
+    path
+}
+
+/// Find a pairwise contraction order for a network of tensors.
+///
+/// # Errors
+/// Returns an error if:
+/// - `tensors` is empty
+/// - An index appears in more than two tensors or twice in one tensor
+/// - A shared index has different dimensions in the two tensors
+/// - `ContractionStrategy::Optimal` is requested for more than [`OPTIMAL_MAX_TENSORS`] tensors
+pub fn contraction_path<Id, Symm>(
+    tensors: &[TensorDynLen<Id, Symm>],
+    strategy: ContractionStrategy,
+) -> Result<ContractionPath>
+where
+    Id: Clone + std::hash::Hash + Eq,
+{
+    if tensors.is_empty() {
+        return Err(anyhow::anyhow!("Cannot contract an empty network"));
+    }
+    let network = Network::new(tensors)?;
+    let n = tensors.len();
+    let path = match strategy {
+        ContractionStrategy::Greedy => greedy_path(&network),
+        ContractionStrategy::Optimal if n > OPTIMAL_MAX_TENSORS => {
+            return Err(anyhow::anyhow!(
+                "Optimal contraction order is limited to {} tensors, got {}",
+                OPTIMAL_MAX_TENSORS,
+                n
+            ));
+        }
+        ContractionStrategy::Optimal => optimal_path(&network),
+        ContractionStrategy::Auto if n <= OPTIMAL_MAX_TENSORS => optimal_path(&network),
+        ContractionStrategy::Auto => greedy_path(&network),
+    };
+    Ok(path)
+}
+
+/// Contract two tensors along their common indices, or take the outer product if
+/// they have none.
+fn contract_pair<Id, Symm>(a: &TensorDynLen<Id, Symm>, b: &TensorDynLen<Id, Symm>) -> TensorDynLen<Id, Symm>
+where
+    Id: Clone + std::hash::Hash + Eq,
+    Symm: Clone + Symmetry,
+{
+    if a.indices.iter().any(|i| b.indices.iter().any(|j| i.id == j.id)) {
+        return a.contract(b);
+    }
+
+    // Outer product: a diagonal × diagonal contraction without common axes would
+    // multiply the diagonals elementwise, so densify first
+    let storage_a = if a.storage.is_diag() && b.storage.is_diag() {
+        Arc::new(a.storage.to_dense_storage(&a.dims))
+    } else {
+        a.storage.clone()
+    };
+    let mut indices = a.indices.clone();
+    indices.extend(b.indices.iter().cloned());
+    let mut dims = a.dims.clone();
+    dims.extend_from_slice(&b.dims);
+    let storage = contract_storage(&storage_a, &a.dims, &[], &b.storage, &b.dims, &[], &dims);
+    TensorDynLen::new(indices, dims, Arc::new(storage))
+}
+
+/// Contract a network of tensors in an order chosen by [`ContractionStrategy::Auto`].
+///
+/// Indices shared by two tensors (same ID) are contracted; the remaining indices form
+/// the indices of the result. Disconnected parts of the network are combined by outer
+/// products. The order of the result indices depends on the contraction order; use
+/// [`TensorDynLen::permute_indices`] to bring them into a specific order.
+///
+/// # Errors
+/// Returns an error under the same conditions as [`contraction_path`].
+///
+/// # Example
+/// ```
+/// use tensor4all_core_tensor::{contract_network, Storage, TensorDynLen};
+/// use tensor4all_core_tensor::storage::DenseStorageF64;
+/// use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
+/// use std::sync::Arc;
+///
+/// let i = Index::new_dyn(2);
+/// let j = Index::new_dyn(3);
+/// let k = Index::new_dyn(4);
+/// let dense = |n: usize| Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(vec![1.0; n])));
+/// let a: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), j.clone()], vec![2, 3], dense(6));
+/// let b: TensorDynLen<DynId> = TensorDynLen::new(vec![j.clone(), k.clone()], vec![3, 4], dense(12));
+/// let c: TensorDynLen<DynId> = TensorDynLen::new(vec![k.clone()], vec![4], dense(4));
+///
+/// let result = contract_network(&[a, b, c]).unwrap();
+/// assert_eq!(result.dims, vec![2]);
+/// ```
+pub fn contract_network<Id, Symm>(tensors: &[TensorDynLen<Id, Symm>]) -> Result<TensorDynLen<Id, Symm>>
+where
+    Id: Clone + std::hash::Hash + Eq,
+    Symm: Clone + Symmetry,
+{
+    contract_network_with(tensors, ContractionStrategy::Auto)
+}
+
+/// Contract a network of tensors in an order chosen by the given strategy.
+///
+/// See [`contract_network`].
+pub fn contract_network_with<Id, Symm>(
+    tensors: &[TensorDynLen<Id, Symm>],
+    strategy: ContractionStrategy,
+) -> Result<TensorDynLen<Id, Symm>>
+where
+    Id: Clone + std::hash::Hash + Eq,
+    Symm: Clone + Symmetry,
+{
+    let path = contraction_path(tensors, strategy)?;
+    let mut slots: Vec<Option<TensorDynLen<Id, Symm>>> = tensors.iter().cloned().map(Some).collect();
+    for &(a, b) in &path.steps {
+        let ta = slots[a].take().expect("each intermediate is contracted once");
+        let tb = slots[b].take().expect("each intermediate is contracted once");
+        slots.push(Some(contract_pair(&ta, &tb)));
+    }
+    slots
+        .pop()
+        .flatten()
+        .ok_or_else(|| anyhow::anyhow!("Contraction produced no result"))
+}
@@ -1,182 +1,129 @@
 use std::sync::Arc;
-use std::borrow::{Borrow, Cow};
+use std::borrow::Cow;
+use std::iter::Sum;
 use std::ops::{Add, Mul};
-use num_complex::Complex64;
-use mdarray::{DenseMapping, View, DynRank, Shape, Dense, Slice, DTensor, Rank};
-use mdarray_linalg::{matmul::{MatMul, ContractBuilder}, Naive};
-
-/// Dense storage for f64 elements.
-#[derive(Debug, Clone)]
-pub struct DenseStorageF64(Vec<f64>);
-
-impl DenseStorageF64 {
-    pub fn with_capacity(capacity: usize) -> Self {
-        Self(Vec::with_capacity(capacity))
-    }
+use num_complex::{Complex32, Complex64};
+use num_traits::Zero;
+use mdarray::{DenseMapping, View, DynRank, Shape, Dense, DTensor, Rank};
+use crate::block_sparse::{BlockSparseStorageC64, BlockSparseStorageF64};
+use crate::gemm::{contract_dense, GemmScalar};
+
+/// Element types of dense and diagonal storage.
+pub trait StorageElement: Copy + Zero + Add<Output = Self> + Mul<Output = Self> + Sum + 'static {
+    /// Wrap dense data into the matching `Storage` variant.
+    fn dense_variant(storage: DenseStorage<Self>) -> Storage;
+
+    /// Wrap diagonal data into the matching `Storage` variant.
+    fn diag_variant(storage: DiagStorage<Self>) -> Storage;
+}
 
-    pub fn from_vec(vec: Vec<f64>) -> Self {
-        Self(vec)
+impl StorageElement for f64 {
+    fn dense_variant(storage: DenseStorage<Self>) -> Storage {
+        Storage::DenseF64(storage)
     }
 
-    pub fn as_slice(&self) -> &[f64] {
-        &self.0
-    }
-
-    pub fn as_mut_slice(&mut self) -> &mut [f64] {
-        &mut self.0
+    fn diag_variant(storage: DiagStorage<Self>) -> Storage {
+        Storage::DiagF64(storage)
     }
+}
 
-    pub fn into_vec(self) -> Vec<f64> {
-        self.0
+impl StorageElement for Complex64 {
+    fn dense_variant(storage: DenseStorage<Self>) -> Storage {
+        Storage::DenseC64(storage)
     }
 
-    pub fn len(&self) -> usize {
-        self.0.len()
+    fn diag_variant(storage: DiagStorage<Self>) -> Storage {
+        Storage::DiagC64(storage)
     }
+}
 
-    pub fn capacity(&self) -> usize {
-        self.0.capacity()
+impl StorageElement for f32 {
+    fn dense_variant(storage: DenseStorage<Self>) -> Storage {
+        Storage::DenseF32(storage)
     }
 
-    pub fn push(&mut self, val: f64) {
-        self.0.push(val);
+    fn diag_variant(storage: DiagStorage<Self>) -> Storage {
+        Storage::DiagF32(storage)
     }
+}
 
-    pub fn extend_from_slice(&mut self, other: &[f64]) {
-        self.0.extend_from_slice(other);
+impl StorageElement for Complex32 {
+    fn dense_variant(storage: DenseStorage<Self>) -> Storage {
+        Storage::DenseC32(storage)
     }
 
-    pub fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
-        self.0.extend(iter);
+    fn diag_variant(storage: DiagStorage<Self>) -> Storage {
+        Storage::DiagC32(storage)
     }
+}
 
-    pub fn get(&self, i: usize) -> f64 {
-        self.0[i]
-    }
+/// Element type of a `Storage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScalarType {
+    F32,
+    F64,
+    C32,
+    C64,
+}
 
-    pub fn set(&mut self, i: usize, val: f64) {
-        self.0[i] = val;
+impl ScalarType {
+    /// Check if this is a complex type.
+    pub fn is_complex(self) -> bool {
+        matches!(self, Self::C32 | Self::C64)
     }
 
-    pub fn iter(&self) -> std::slice::Iter<'_, f64> {
-        self.0.iter()
+    /// Check if this is a single-precision type.
+    pub fn is_single(self) -> bool {
+        matches!(self, Self::F32 | Self::C32)
     }
 
-    /// Permute the dense storage data according to the given permutation.
-    pub fn permute(&self, dims: &[usize], perm: &[usize]) -> Self {
-        assert_eq!(
-            perm.len(),
-            dims.len(),
-            "permutation length must match dimensions length"
-        );
-        let expected_len: usize = dims.iter().product();
-        assert_eq!(
-            self.0.len(),
-            expected_len,
-            "DenseStorageF64 length {} does not match dims product {}. \
-             This likely indicates uninitialized storage (created with capacity but not filled).",
-            self.0.len(),
-            expected_len
-        );
-
-        // Create mdarray shape from dimensions
-        let shape = DynRank::from_dims(dims);
-        let mapping = DenseMapping::new(shape);
-
-        // Create a view over the vector data
-        let view: View<'_, f64, DynRank, Dense> = unsafe {
-            View::new_unchecked(self.0.as_ptr(), mapping)
-        };
-
-        // Permute the view
-        let permuted_view = view.into_permuted(perm);
-
-        // Convert to tensor and extract vector
-        let permuted_vec = permuted_view.to_tensor().into_vec();
-
-        Self::from_vec(permuted_vec)
+    /// The smallest type that represents both `self` and `other` without loss:
+    /// complex if either is complex, double precision if either is double precision.
+    pub fn promote(self, other: Self) -> Self {
+        match (self.is_complex() || other.is_complex(), self.is_single() && other.is_single()) {
+            (false, true) => Self::F32,
+            (false, false) => Self::F64,
+            (true, true) => Self::C32,
+            (true, false) => Self::C64,
+        }
     }
+}
 
-    /// Contract this dense storage with another dense storage.
-    pub fn contract(
-        &self,
-        dims: &[usize],
-        axes: &[usize],
-        other: &Self,
-        other_dims: &[usize],
-        other_axes: &[usize],
-    ) -> Self {
-        let expected_len: usize = dims.iter().product();
-        assert_eq!(
-            self.0.len(),
-            expected_len,
-            "DenseStorageF64 length {} does not match dims product {}. \
-             This likely indicates uninitialized storage (created with capacity but not filled).",
-            self.0.len(),
-            expected_len
-        );
-        let other_expected_len: usize = other_dims.iter().product();
-        assert_eq!(
-            other.0.len(),
-            other_expected_len,
-            "DenseStorageF64 (other) length {} does not match dims product {}. \
-             This likely indicates uninitialized storage (created with capacity but not filled).",
-            other.0.len(),
-            other_expected_len
-        );
-        // Create mdarray views (which can be used as slices)
-        let shape = DynRank::from_dims(dims);
-        let mapping = DenseMapping::new(shape);
-        let view: View<'_, f64, DynRank, Dense> = unsafe {
-            View::new_unchecked(self.0.as_ptr(), mapping)
-        };
-
-        let other_shape = DynRank::from_dims(other_dims);
-        let other_mapping = DenseMapping::new(other_shape);
-        let other_view: View<'_, f64, DynRank, Dense> = unsafe {
-            View::new_unchecked(other.0.as_ptr(), other_mapping)
-        };
+/// Dense storage in row-major order.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DenseStorage<T>(Vec<T>);
 
-        // Contract using mdarray-linalg
-        // View implements Borrow<Slice>, so we can use it directly
-        let slice: &Slice<f64, DynRank, Dense> = view.borrow();
-        let other_slice: &Slice<f64, DynRank, Dense> = other_view.borrow();
+/// Dense storage for f64 elements.
+pub type DenseStorageF64 = DenseStorage<f64>;
 
-        let result = Naive
-            .contract(
-                slice,
-                other_slice,
-                axes.to_vec(),
-                other_axes.to_vec(),
-            )
-            .eval();
+/// Dense storage for Complex64 elements.
+pub type DenseStorageC64 = DenseStorage<Complex64>;
 
-        Self::from_vec(result.into_vec())
-    }
-}
+/// Dense storage for f32 elements.
+pub type DenseStorageF32 = DenseStorage<f32>;
 
-/// Dense storage for Complex64 elements.
-#[derive(Debug, Clone)]
-pub struct DenseStorageC64(Vec<Complex64>);
+/// Dense storage for Complex32 elements.
+pub type DenseStorageC32 = DenseStorage<Complex32>;
 
-impl DenseStorageC64 {
+impl<T: Copy> DenseStorage<T> {
     pub fn with_capacity(capacity: usize) -> Self {
         Self(Vec::with_capacity(capacity))
     }
 
-    pub fn from_vec(vec: Vec<Complex64>) -> Self {
+    pub fn from_vec(vec: Vec<T>) -> Self {
         Self(vec)
     }
 
-    pub fn as_slice(&self) -> &[Complex64] {
+    pub fn as_slice(&self) -> &[T] {
         &self.0
     }
 
-    pub fn as_mut_slice(&mut self) -> &mut [Complex64] {
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
         &mut self.0
     }
 
-    pub fn into_vec(self) -> Vec<Complex64> {
+    pub fn into_vec(self) -> Vec<T> {
         self.0
     }
 
@@ -188,26 +135,35 @@ impl DenseStorageC64 {
         self.0.capacity()
     }
 
-    pub fn push(&mut self, val: Complex64) {
+    pub fn push(&mut self, val: T) {
         self.0.push(val);
     }
 
-    pub fn extend_from_slice(&mut self, other: &[Complex64]) {
+    pub fn extend_from_slice(&mut self, other: &[T]) {
         self.0.extend_from_slice(other);
     }
 
-    pub fn extend<I: IntoIterator<Item = Complex64>>(&mut self, iter: I) {
+    pub fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         self.0.extend(iter);
     }
 
-    pub fn get(&self, i: usize) -> Complex64 {
+    pub fn get(&self, i: usize) -> T {
         self.0[i]
     }
 
-    pub fn set(&mut self, i: usize, val: Complex64) {
+    pub fn set(&mut self, i: usize, val: T) {
         self.0[i] = val;
     }
 
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    /// Apply a function to every element, producing storage of another element type.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> DenseStorage<U> {
+        DenseStorage(self.0.iter().map(f).collect())
+    }
+
     /// Permute the dense storage data according to the given permutation.
     pub fn permute(&self, dims: &[usize], perm: &[usize]) -> Self {
         assert_eq!(
@@ -219,7 +175,7 @@ impl DenseStorageC64 {
         assert_eq!(
             self.0.len(),
             expected_len,
-            "DenseStorageC64 length {} does not match dims product {}. \
+            "DenseStorage length {} does not match dims product {}. \
              This likely indicates uninitialized storage (created with capacity but not filled).",
             self.0.len(),
             expected_len
@@ -230,7 +186,7 @@ impl DenseStorageC64 {
         let mapping = DenseMapping::new(shape);
 
         // Create a view over the vector data
-        let view: View<'_, Complex64, DynRank, Dense> = unsafe {
+        let view: View<'_, T, DynRank, Dense> = unsafe {
             View::new_unchecked(self.0.as_ptr(), mapping)
         };
 
@@ -242,7 +198,27 @@ impl DenseStorageC64 {
 
         Self::from_vec(permuted_vec)
     }
+}
 
+impl<T: StorageElement> DenseStorage<T> {
+    /// Add another dense storage element-wise.
+    ///
+    /// # Errors
+    /// Returns an error if the lengths don't match.
+    pub fn try_add(&self, other: &Self) -> Result<Self, String> {
+        if self.len() != other.len() {
+            return Err(format!(
+                "Storage lengths must match for addition: {} != {}",
+                self.len(),
+                other.len()
+            ));
+        }
+        Ok(Self(self.0.iter().zip(&other.0).map(|(&x, &y)| x + y).collect()))
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: StorageElement + GemmScalar> DenseStorage<T> {
     /// Contract this dense storage with another dense storage.
     pub fn contract(
         &self,
@@ -256,7 +232,7 @@ impl DenseStorageC64 {
         assert_eq!(
             self.0.len(),
             expected_len,
-            "DenseStorageC64 length {} does not match dims product {}. \
+            "DenseStorage length {} does not match dims product {}. \
              This likely indicates uninitialized storage (created with capacity but not filled).",
             self.0.len(),
             expected_len
@@ -265,60 +241,47 @@ impl DenseStorageC64 {
         assert_eq!(
             other.0.len(),
             other_expected_len,
-            "DenseStorageC64 (other) length {} does not match dims product {}. \
+            "DenseStorage (other) length {} does not match dims product {}. \
              This likely indicates uninitialized storage (created with capacity but not filled).",
             other.0.len(),
             other_expected_len
         );
-        // Create mdarray views (which can be used as slices)
-        let shape = DynRank::from_dims(dims);
-        let mapping = DenseMapping::new(shape);
-        let view: View<'_, Complex64, DynRank, Dense> = unsafe {
-            View::new_unchecked(self.0.as_ptr(), mapping)
-        };
+        // Contract by permute-then-GEMM
+        Self::from_vec(contract_dense(&self.0, dims, axes, &other.0, other_dims, other_axes))
+    }
+}
 
-        let other_shape = DynRank::from_dims(other_dims);
-        let other_mapping = DenseMapping::new(other_shape);
-        let other_view: View<'_, Complex64, DynRank, Dense> = unsafe {
-            View::new_unchecked(other.0.as_ptr(), other_mapping)
-        };
+/// Diagonal storage: the elements `t[i, i, ..., i]` of a tensor whose other elements are zero.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagStorage<T>(Vec<T>);
 
-        // Contract using mdarray-linalg
-        // View implements Borrow<Slice>, so we can use it directly
-        let slice: &Slice<Complex64, DynRank, Dense> = view.borrow();
-        let other_slice: &Slice<Complex64, DynRank, Dense> = other_view.borrow();
+/// Diagonal storage for f64 elements.
+pub type DiagStorageF64 = DiagStorage<f64>;
 
-        let result = Naive
-            .contract(
-                slice,
-                other_slice,
-                axes.to_vec(),
-                other_axes.to_vec(),
-            )
-            .eval();
+/// Diagonal storage for Complex64 elements.
+pub type DiagStorageC64 = DiagStorage<Complex64>;
 
-        Self::from_vec(result.into_vec())
-    }
-}
+/// Diagonal storage for f32 elements.
+pub type DiagStorageF32 = DiagStorage<f32>;
 
-/// Diagonal storage for f64 elements.
-#[derive(Debug, Clone)]
-pub struct DiagStorageF64(Vec<f64>);
+/// Diagonal storage for Complex32 elements.
+pub type DiagStorageC32 = DiagStorage<Complex32>;
 
-impl DiagStorageF64 {
-    pub fn from_vec(vec: Vec<f64>) -> Self {
+impl<T: Copy> DiagStorage<T> {
+    pub fn from_vec(vec: Vec<T>) -> Self {
         Self(vec)
     }
 
-    pub fn as_slice(&self) -> &[f64] {
+    pub fn as_slice(&self) -> &[T] {
         &self.0
     }
 
-    pub fn as_mut_slice(&mut self) -> &mut [f64] {
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
         &mut self.0
     }
 
-    pub fn into_vec(self) -> Vec<f64> {
+    pub fn into_vec(self) -> Vec<T> {
         self.0
     }
 
@@ -326,19 +289,26 @@ impl DiagStorageF64 {
         self.0.len()
     }
 
-    pub fn get(&self, i: usize) -> f64 {
+    pub fn get(&self, i: usize) -> T {
         self.0[i]
     }
 
-    pub fn set(&mut self, i: usize, val: f64) {
+    pub fn set(&mut self, i: usize, val: T) {
         self.0[i] = val;
     }
 
+    /// Apply a function to every element, producing storage of another element type.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> DiagStorage<U> {
+        DiagStorage(self.0.iter().map(f).collect())
+    }
+}
+
+impl<T: StorageElement> DiagStorage<T> {
     /// Convert diagonal storage to a dense vector representation.
     /// Creates a dense vector with diagonal elements set and off-diagonal elements as zero.
-    pub fn to_dense_vec(&self, dims: &[usize]) -> Vec<f64> {
+    pub fn to_dense_vec(&self, dims: &[usize]) -> Vec<T> {
         let total_size: usize = dims.iter().product();
-        let mut dense_vec = vec![0.0; total_size];
+        let mut dense_vec = vec![T::zero(); total_size];
         let mindim_val = mindim(dims);
         
         // Set diagonal elements
@@ -372,100 +342,23 @@ impl DiagStorageF64 {
         dense_vec
     }
 
-    /// Contract this diagonal storage with another diagonal storage.
-    /// Returns either a scalar (DenseStorageF64 with one element) or a diagonal storage.
-    pub fn contract_diag_diag(
-        &self,
-        dims: &[usize],
-        other: &Self,
-        other_dims: &[usize],
-        result_dims: &[usize],
-    ) -> Storage {
-        let mindim_a = mindim(dims);
-        let mindim_b = mindim(other_dims);
-        let min_len = mindim_a.min(mindim_b).min(self.0.len()).min(other.0.len());
-        
-        if result_dims.is_empty() {
-            // All indices contracted: compute inner product (scalar result)
-            let scalar: f64 = (0..min_len)
-                .map(|i| self.0[i] * other.0[i])
-                .sum();
-            Storage::DenseF64(DenseStorageF64::from_vec(vec![scalar]))
-        } else {
-            // Some indices remain: element-wise product (DiagTensor result)
-            let result_diag: Vec<f64> = (0..min_len)
-                .map(|i| self.0[i] * other.0[i])
-                .collect();
-            Storage::DiagF64(DiagStorageF64::from_vec(result_diag))
-        }
-    }
-}
-
-/// Diagonal storage for Complex64 elements.
-#[derive(Debug, Clone)]
-pub struct DiagStorageC64(Vec<Complex64>);
-
-impl DiagStorageC64 {
-    pub fn from_vec(vec: Vec<Complex64>) -> Self {
-        Self(vec)
-    }
-
-    pub fn as_slice(&self) -> &[Complex64] {
-        &self.0
-    }
-
-    pub fn as_mut_slice(&mut self) -> &mut [Complex64] {
-        &mut self.0
-    }
-
-    pub fn into_vec(self) -> Vec<Complex64> {
-        self.0
-    }
-
-    pub fn len(&self) -> usize {
-        self.0.len()
-    }
-
-    pub fn get(&self, i: usize) -> Complex64 {
-        self.0[i]
-    }
-
-    pub fn set(&mut self, i: usize, val: Complex64) {
-        self.0[i] = val;
-    }
-
-    /// Convert diagonal storage to a dense vector representation.
-    /// Creates a dense vector with diagonal elements set and off-diagonal elements as zero.
-    pub fn to_dense_vec(&self, dims: &[usize]) -> Vec<Complex64> {
-        let total_size: usize = dims.iter().product();
-        let mut dense_vec = vec![Complex64::new(0.0, 0.0); total_size];
-        let mindim_val = mindim(dims);
-        
-        // Same logic as DiagStorageF64 but for complex
-        let rank = dims.len();
-        if rank == 0 {
-            return vec![self.0[0]];
-        }
-        
-        let mut strides = vec![1; rank];
-        for i in (0..rank - 1).rev() {
-            strides[i] = strides[i + 1] * dims[i + 1];
-        }
-        
-        let diag_stride: usize = strides.iter().sum();
-        
-        for i in 0..mindim_val.min(self.0.len()) {
-            let linear_idx = i * diag_stride;
-            if linear_idx < total_size {
-                dense_vec[linear_idx] = self.0[i];
-            }
+    /// Add another diagonal storage element-wise.
+    ///
+    /// # Errors
+    /// Returns an error if the lengths don't match.
+    pub fn try_add(&self, other: &Self) -> Result<Self, String> {
+        if self.len() != other.len() {
+            return Err(format!(
+                "Storage lengths must match for addition: {} != {}",
+                self.len(),
+                other.len()
+            ));
         }
-        
-        dense_vec
+        Ok(Self(self.0.iter().zip(&other.0).map(|(&x, &y)| x + y).collect()))
     }
 
     /// Contract this diagonal storage with another diagonal storage.
-    /// Returns either a scalar (DenseStorageC64 with one element) or a diagonal storage.
+    /// Returns either a scalar (dense storage with one element) or a diagonal storage.
     pub fn contract_diag_diag(
         &self,
         dims: &[usize],
@@ -479,28 +372,36 @@ impl DiagStorageC64 {
         
         if result_dims.is_empty() {
             // All indices contracted: compute inner product (scalar result)
-            let scalar: Complex64 = (0..min_len)
+            let scalar: T = (0..min_len)
                 .map(|i| self.0[i] * other.0[i])
                 .sum();
-            Storage::DenseC64(DenseStorageC64::from_vec(vec![scalar]))
+            T::dense_variant(DenseStorage::from_vec(vec![scalar]))
         } else {
             // Some indices remain: element-wise product (DiagTensor result)
-            let result_diag: Vec<Complex64> = (0..min_len)
+            let result_diag: Vec<T> = (0..min_len)
                 .map(|i| self.0[i] * other.0[i])
                 .collect();
-            Storage::DiagC64(DiagStorageC64::from_vec(result_diag))
+            T::diag_variant(DiagStorage::from_vec(result_diag))
         }
     }
 }
 
 /// Storage backend for tensor data.
-/// Supports Dense and Diag storage for f64 and Complex64 element types.
+/// Supports Dense, Diag and BlockSparse storage for f64 and Complex64 element types,
+/// and Dense and Diag storage for the single-precision types f32 and Complex32.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Storage {
     DenseF64(DenseStorageF64),
     DenseC64(DenseStorageC64),
     DiagF64(DiagStorageF64),
     DiagC64(DiagStorageC64),
+    BlockSparseF64(BlockSparseStorageF64),
+    BlockSparseC64(BlockSparseStorageC64),
+    DenseF32(DenseStorageF32),
+    DenseC32(DenseStorageC32),
+    DiagF32(DiagStorageF32),
+    DiagC32(DiagStorageC32),
 }
 
 /// Type-driven constructor for `Storage`.
@@ -523,9 +424,22 @@ impl DenseStorageFactory for Complex64 {
     }
 }
 
+impl DenseStorageFactory for f32 {
+    fn new_dense(capacity: usize) -> Storage {
+        Storage::DenseF32(DenseStorageF32::with_capacity(capacity))
+    }
+}
+
+impl DenseStorageFactory for Complex32 {
+    fn new_dense(capacity: usize) -> Storage {
+        Storage::DenseC32(DenseStorageC32::with_capacity(capacity))
+    }
+}
+
 /// Types that can be computed as the result of a reduction over `Storage`.
 ///
 /// This lets callers write `let s: T = tensor.sum();` without matching on storage.
+/// Single-precision storage is accumulated in double precision.
 pub trait SumFromStorage: Sized {
     fn sum_from_storage(storage: &Storage) -> Self;
 }
@@ -537,6 +451,12 @@ impl SumFromStorage for f64 {
             Storage::DenseC64(v) => v.as_slice().iter().map(|z| z.re).sum(),
             Storage::DiagF64(v) => v.as_slice().iter().copied().sum(),
             Storage::DiagC64(v) => v.as_slice().iter().map(|z| z.re).sum(),
+            Storage::BlockSparseF64(v) => v.sum(),
+            Storage::BlockSparseC64(v) => v.sum().re,
+            Storage::DenseF32(v) => v.as_slice().iter().map(|&x| x as f64).sum(),
+            Storage::DenseC32(v) => v.as_slice().iter().map(|z| z.re as f64).sum(),
+            Storage::DiagF32(v) => v.as_slice().iter().map(|&x| x as f64).sum(),
+            Storage::DiagC32(v) => v.as_slice().iter().map(|z| z.re as f64).sum(),
         }
     }
 }
@@ -548,6 +468,11 @@ impl SumFromStorage for Complex64 {
             Storage::DenseC64(v) => v.as_slice().iter().copied().sum(),
             Storage::DiagF64(v) => Complex64::new(v.as_slice().iter().copied().sum(), 0.0),
             Storage::DiagC64(v) => v.as_slice().iter().copied().sum(),
+            Storage::BlockSparseF64(v) => Complex64::new(v.sum(), 0.0),
+            Storage::BlockSparseC64(v) => v.sum(),
+            Storage::DenseF32(_) | Storage::DiagF32(_) => Complex64::new(f64::sum_from_storage(storage), 0.0),
+            Storage::DenseC32(v) => v.as_slice().iter().map(|&z| c32_to_c64(z)).sum(),
+            Storage::DiagC32(v) => v.as_slice().iter().map(|&z| c32_to_c64(z)).sum(),
         }
     }
 }
@@ -576,18 +501,113 @@ impl Storage {
         Self::DiagC64(DiagStorageC64::from_vec(diag_data))
     }
 
+    /// Create a new DenseF32 storage with the given capacity.
+    pub fn new_dense_f32(capacity: usize) -> Self {
+        Self::DenseF32(DenseStorageF32::with_capacity(capacity))
+    }
+
+    /// Create a new DenseC32 storage with the given capacity.
+    pub fn new_dense_c32(capacity: usize) -> Self {
+        Self::DenseC32(DenseStorageC32::with_capacity(capacity))
+    }
+
+    /// Create a new DiagF32 storage with the given diagonal data.
+    pub fn new_diag_f32(diag_data: Vec<f32>) -> Self {
+        Self::DiagF32(DiagStorageF32::from_vec(diag_data))
+    }
+
+    /// Create a new DiagC32 storage with the given diagonal data.
+    pub fn new_diag_c32(diag_data: Vec<Complex32>) -> Self {
+        Self::DiagC32(DiagStorageC32::from_vec(diag_data))
+    }
+
+    /// Create a new BlockSparseF64 storage with the given block structure and no blocks.
+    pub fn new_block_sparse_f64(block_dims: Vec<Vec<usize>>) -> Self {
+        Self::BlockSparseF64(BlockSparseStorageF64::new(block_dims))
+    }
+
+    /// Create a new BlockSparseC64 storage with the given block structure and no blocks.
+    pub fn new_block_sparse_c64(block_dims: Vec<Vec<usize>>) -> Self {
+        Self::BlockSparseC64(BlockSparseStorageC64::new(block_dims))
+    }
+
     /// Check if this storage is a Diag storage type.
     pub fn is_diag(&self) -> bool {
-        matches!(self, Self::DiagF64(_) | Self::DiagC64(_))
+        matches!(self, Self::DiagF64(_) | Self::DiagC64(_) | Self::DiagF32(_) | Self::DiagC32(_))
+    }
+
+    /// Check if this storage is a BlockSparse storage type.
+    pub fn is_block_sparse(&self) -> bool {
+        matches!(self, Self::BlockSparseF64(_) | Self::BlockSparseC64(_))
     }
 
     /// Get the length of the storage (number of elements).
+    ///
+    /// For BlockSparse storage, this is the number of stored elements.
     pub fn len(&self) -> usize {
         match self {
             Self::DenseF64(v) => v.len(),
             Self::DenseC64(v) => v.len(),
             Self::DiagF64(v) => v.len(),
             Self::DiagC64(v) => v.len(),
+            Self::BlockSparseF64(v) => v.len(),
+            Self::BlockSparseC64(v) => v.len(),
+            Self::DenseF32(v) => v.len(),
+            Self::DenseC32(v) => v.len(),
+            Self::DiagF32(v) => v.len(),
+            Self::DiagC32(v) => v.len(),
+        }
+    }
+
+    /// Get the element type of the storage.
+    pub fn scalar_type(&self) -> ScalarType {
+        match self {
+            Self::DenseF64(_) | Self::DiagF64(_) | Self::BlockSparseF64(_) => ScalarType::F64,
+            Self::DenseC64(_) | Self::DiagC64(_) | Self::BlockSparseC64(_) => ScalarType::C64,
+            Self::DenseF32(_) | Self::DiagF32(_) => ScalarType::F32,
+            Self::DenseC32(_) | Self::DiagC32(_) => ScalarType::C32,
+        }
+    }
+
+    /// Check if the storage holds complex elements.
+    pub fn is_complex(&self) -> bool {
+        self.scalar_type().is_complex()
+    }
+
+    /// Convert the storage to another element type, keeping its layout.
+    ///
+    /// Real storage can be converted to any type. Complex storage can only be converted
+    /// to complex types; use [`Storage::extract_real_part`] to drop the imaginary part.
+    /// Converting to single precision rounds every element.
+    ///
+    /// # Panics
+    /// Panics if complex storage is converted to a real type, or if BlockSparse storage
+    /// is converted to a single-precision type (BlockSparse storage is f64 or Complex64 only).
+    pub fn to_scalar_type(&self, scalar_type: ScalarType) -> Storage {
+        assert!(
+            scalar_type.is_complex() || !self.is_complex(),
+            "Cannot convert complex storage to real type {:?}",
+            scalar_type
+        );
+        if self.scalar_type() == scalar_type {
+            return self.clone();
+        }
+        match self {
+            Storage::DenseF64(v) => convert_dense(v, |&x| Complex64::new(x, 0.0), scalar_type),
+            Storage::DenseC64(v) => convert_dense(v, |&z| z, scalar_type),
+            Storage::DenseF32(v) => convert_dense(v, |&x| Complex64::new(x as f64, 0.0), scalar_type),
+            Storage::DenseC32(v) => convert_dense(v, |&z| c32_to_c64(z), scalar_type),
+            Storage::DiagF64(d) => convert_diag(d, |&x| Complex64::new(x, 0.0), scalar_type),
+            Storage::DiagC64(d) => convert_diag(d, |&z| z, scalar_type),
+            Storage::DiagF32(d) => convert_diag(d, |&x| Complex64::new(x as f64, 0.0), scalar_type),
+            Storage::DiagC32(d) => convert_diag(d, |&z| c32_to_c64(z), scalar_type),
+            Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_) => {
+                assert!(
+                    !scalar_type.is_single(),
+                    "BlockSparse storage does not support single precision"
+                );
+                self.to_complex_storage()
+            }
         }
     }
 
@@ -601,9 +621,45 @@ impl Storage {
         Complex64::sum_from_storage(self)
     }
 
+    /// Sum of the squared absolute values of all elements (accumulated in f64).
+    ///
+    /// This is the squared Frobenius norm of the tensor: zeros that are not stored
+    /// (off-diagonal or missing blocks) do not contribute.
+    pub fn norm_sqr(&self) -> f64 {
+        match self {
+            Storage::DenseF64(v) => v.iter().map(|&x| x * x).sum(),
+            Storage::DenseC64(v) => v.iter().map(|z| z.norm_sqr()).sum(),
+            Storage::DenseF32(v) => v.iter().map(|&x| (x as f64).powi(2)).sum(),
+            Storage::DenseC32(v) => v.iter().map(|&z| c32_to_c64(z).norm_sqr()).sum(),
+            Storage::DiagF64(d) => d.as_slice().iter().map(|&x| x * x).sum(),
+            Storage::DiagC64(d) => d.as_slice().iter().map(|z| z.norm_sqr()).sum(),
+            Storage::DiagF32(d) => d.as_slice().iter().map(|&x| (x as f64).powi(2)).sum(),
+            Storage::DiagC32(d) => d.as_slice().iter().map(|&z| c32_to_c64(z).norm_sqr()).sum(),
+            Storage::BlockSparseF64(b) => b.map(|&x| x * x).sum(),
+            Storage::BlockSparseC64(b) => b.map(|z| z.norm_sqr()).sum(),
+        }
+    }
+
+    /// Complex conjugate of all elements. Real storage is returned unchanged.
+    pub fn conj(&self) -> Storage {
+        match self {
+            Storage::DenseC64(v) => Storage::DenseC64(v.map(|z| z.conj())),
+            Storage::DenseC32(v) => Storage::DenseC32(v.map(|z| z.conj())),
+            Storage::DiagC64(d) => Storage::DiagC64(d.map(|z| z.conj())),
+            Storage::DiagC32(d) => Storage::DiagC32(d.map(|z| z.conj())),
+            Storage::BlockSparseC64(b) => Storage::BlockSparseC64(b.map(|z| z.conj())),
+            Storage::DenseF64(_)
+            | Storage::DenseF32(_)
+            | Storage::DiagF64(_)
+            | Storage::DiagF32(_)
+            | Storage::BlockSparseF64(_) => self.clone(),
+        }
+    }
+
     /// Convert this storage to dense storage.
     /// For Diag storage, creates a Dense storage with diagonal elements set
     /// and off-diagonal elements as zero.
+    /// For BlockSparse storage, fills the missing blocks with zeros.
     /// For Dense storage, returns a copy.
     pub fn to_dense_storage(&self, dims: &[usize]) -> Storage {
         match self {
@@ -611,6 +667,12 @@ impl Storage {
             Storage::DenseC64(v) => Storage::DenseC64(DenseStorageC64::from_vec(v.as_slice().to_vec())),
             Storage::DiagF64(d) => Storage::DenseF64(DenseStorageF64::from_vec(d.to_dense_vec(dims))),
             Storage::DiagC64(d) => Storage::DenseC64(DenseStorageC64::from_vec(d.to_dense_vec(dims))),
+            Storage::BlockSparseF64(b) => Storage::DenseF64(DenseStorageF64::from_vec(b.to_dense_vec())),
+            Storage::BlockSparseC64(b) => Storage::DenseC64(DenseStorageC64::from_vec(b.to_dense_vec())),
+            Storage::DenseF32(v) => Storage::DenseF32(v.clone()),
+            Storage::DenseC32(v) => Storage::DenseC32(v.clone()),
+            Storage::DiagF32(d) => Storage::DenseF32(DenseStorageF32::from_vec(d.to_dense_vec(dims))),
+            Storage::DiagC32(d) => Storage::DenseC32(DenseStorageC32::from_vec(d.to_dense_vec(dims))),
         }
     }
 
@@ -619,14 +681,19 @@ impl Storage {
         match self {
             Storage::DenseF64(v) => Storage::DenseF64(v.permute(dims, perm)),
             Storage::DenseC64(v) => Storage::DenseC64(v.permute(dims, perm)),
+            Storage::DenseF32(v) => Storage::DenseF32(v.permute(dims, perm)),
+            Storage::DenseC32(v) => Storage::DenseC32(v.permute(dims, perm)),
             // For Diag storage, permute is trivial: data doesn't change, only index order changes
-            Storage::DiagF64(v) => Storage::DiagF64(v.clone()),
-            Storage::DiagC64(v) => Storage::DiagC64(v.clone()),
+            Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => self.clone(),
+            // For BlockSparse storage, both the block coordinates and the block data are permuted
+            Storage::BlockSparseF64(b) => Storage::BlockSparseF64(b.permute(perm)),
+            Storage::BlockSparseC64(b) => Storage::BlockSparseC64(b.permute(perm)),
         }
     }
 
     /// Extract real part from Complex64 storage as f64 storage.
     /// For f64 storage, returns a copy.
+    /// Complex32 and f32 storage are handled in the same way with f32 results.
     pub fn extract_real_part(&self) -> Storage {
         match self {
             Storage::DenseF64(v) => Storage::DenseF64(DenseStorageF64::from_vec(v.as_slice().to_vec())),
@@ -639,11 +706,17 @@ impl Storage {
                 let real_vec: Vec<f64> = d.as_slice().iter().map(|z| z.re).collect();
                 Storage::DiagF64(DiagStorageF64::from_vec(real_vec))
             }
+            Storage::BlockSparseF64(b) => Storage::BlockSparseF64(b.clone()),
+            Storage::BlockSparseC64(b) => Storage::BlockSparseF64(b.map(|z| z.re)),
+            Storage::DenseF32(_) | Storage::DiagF32(_) => self.clone(),
+            Storage::DenseC32(v) => Storage::DenseF32(v.map(|z| z.re)),
+            Storage::DiagC32(d) => Storage::DiagF32(d.map(|z| z.re)),
         }
     }
 
     /// Extract imaginary part from Complex64 storage as f64 storage.
     /// For f64 storage, returns zero storage (will be resized appropriately).
+    /// Complex32 and f32 storage are handled in the same way with f32 results.
     pub fn extract_imag_part(&self, dims: &[usize]) -> Storage {
         match self {
             Storage::DenseF64(_) => {
@@ -664,11 +737,19 @@ impl Storage {
                 let imag_vec: Vec<f64> = d.as_slice().iter().map(|z| z.im).collect();
                 Storage::DiagF64(DiagStorageF64::from_vec(imag_vec))
             }
+            // For real block-sparse storage, the imaginary part has the same blocks filled with zeros
+            Storage::BlockSparseF64(b) => Storage::BlockSparseF64(b.map(|_| 0.0)),
+            Storage::BlockSparseC64(b) => Storage::BlockSparseF64(b.map(|z| z.im)),
+            Storage::DenseF32(v) => Storage::DenseF32(v.map(|_| 0.0)),
+            Storage::DiagF32(_) => Storage::DiagF32(DiagStorageF32::from_vec(vec![0.0; mindim(dims)])),
+            Storage::DenseC32(v) => Storage::DenseF32(v.map(|z| z.im)),
+            Storage::DiagC32(d) => Storage::DiagF32(d.map(|z| z.im)),
         }
     }
 
     /// Convert f64 storage to Complex64 storage (real part only, imaginary part is zero).
     /// For Complex64 storage, returns a copy.
+    /// f32 storage is converted to Complex32 storage.
     pub fn to_complex_storage(&self) -> Storage {
         match self {
             Storage::DenseF64(v) => {
@@ -681,6 +762,11 @@ impl Storage {
             }
             Storage::DenseC64(v) => Storage::DenseC64(DenseStorageC64::from_vec(v.as_slice().to_vec())),
             Storage::DiagC64(d) => Storage::DiagC64(DiagStorageC64::from_vec(d.as_slice().to_vec())),
+            Storage::BlockSparseF64(b) => Storage::BlockSparseC64(b.map(|&x| Complex64::new(x, 0.0))),
+            Storage::BlockSparseC64(b) => Storage::BlockSparseC64(b.clone()),
+            Storage::DenseF32(v) => Storage::DenseC32(v.map(|&x| Complex32::new(x, 0.0))),
+            Storage::DiagF32(d) => Storage::DiagC32(d.map(|&x| Complex32::new(x, 0.0))),
+            Storage::DenseC32(_) | Storage::DiagC32(_) => self.clone(),
         }
     }
 
@@ -705,75 +791,70 @@ impl Storage {
                     .collect();
                 Storage::DiagC64(DiagStorageC64::from_vec(complex_vec))
             }
-            _ => panic!("Both storages must be the same type (DenseF64 or DiagF64)"),
+            (Storage::BlockSparseF64(real), Storage::BlockSparseF64(imag)) => {
+                let complex = real
+                    .map(|&r| Complex64::new(r, 0.0))
+                    .try_add(&imag.map(|&i| Complex64::new(0.0, i)))
+                    .expect("Block structures must match");
+                Storage::BlockSparseC64(complex)
+            }
+            (Storage::DenseF32(real), Storage::DenseF32(imag)) => {
+                assert_eq!(real.len(), imag.len(), "Storage lengths must match");
+                let complex_vec: Vec<Complex32> = real.as_slice().iter()
+                    .zip(imag.as_slice().iter())
+                    .map(|(&r, &i)| Complex32::new(r, i))
+                    .collect();
+                Storage::DenseC32(DenseStorageC32::from_vec(complex_vec))
+            }
+            (Storage::DiagF32(real), Storage::DiagF32(imag)) => {
+                assert_eq!(real.len(), imag.len(), "Storage lengths must match");
+                let complex_vec: Vec<Complex32> = real.as_slice().iter()
+                    .zip(imag.as_slice().iter())
+                    .map(|(&r, &i)| Complex32::new(r, i))
+                    .collect();
+                Storage::DiagC32(DiagStorageC32::from_vec(complex_vec))
+            }
+            _ => panic!("Both storages must be the same type (DenseF64, DiagF64, BlockSparseF64, DenseF32 or DiagF32)"),
         }
     }
 
     /// Add two storages element-wise, returning `Result` on error instead of panicking.
     ///
-    /// Both storages must have the same type and length.
+    /// Both storages must have the same layout (Dense, Diag or BlockSparse) and length.
+    /// If the element types differ, both storages are first promoted to the common type
+    /// (see [`ScalarType::promote`]).
     ///
     /// # Errors
     /// Returns an error if:
-    /// - Storage types don't match
+    /// - Storage layouts don't match
     /// - Storage lengths don't match
     pub fn try_add(&self, other: &Storage) -> Result<Storage, String> {
-        match (self, other) {
-            (Storage::DenseF64(a), Storage::DenseF64(b)) => {
-                if a.len() != b.len() {
-                    return Err(format!(
-                        "Storage lengths must match for addition: {} != {}",
-                        a.len(),
-                        b.len()
-                    ));
-                }
-                let sum_vec: Vec<f64> = a.as_slice().iter()
-                    .zip(b.as_slice().iter())
-                    .map(|(&x, &y)| x + y)
-                    .collect();
-                Ok(Storage::DenseF64(DenseStorageF64::from_vec(sum_vec)))
+        let (type_a, type_b) = (self.scalar_type(), other.scalar_type());
+        if type_a != type_b {
+            let common = type_a.promote(type_b);
+            if self.is_block_sparse() != other.is_block_sparse() || self.is_diag() != other.is_diag() {
+                return Err(format!(
+                    "Storage types must match for addition: {:?} vs {:?}",
+                    std::mem::discriminant(self),
+                    std::mem::discriminant(other)
+                ));
             }
-            (Storage::DenseC64(a), Storage::DenseC64(b)) => {
-                if a.len() != b.len() {
-                    return Err(format!(
-                        "Storage lengths must match for addition: {} != {}",
-                        a.len(),
-                        b.len()
-                    ));
-                }
-                let sum_vec: Vec<Complex64> = a.as_slice().iter()
-                    .zip(b.as_slice().iter())
-                    .map(|(&x, &y)| x + y)
-                    .collect();
-                Ok(Storage::DenseC64(DenseStorageC64::from_vec(sum_vec)))
-            }
-            (Storage::DiagF64(a), Storage::DiagF64(b)) => {
-                if a.len() != b.len() {
-                    return Err(format!(
-                        "Storage lengths must match for addition: {} != {}",
-                        a.len(),
-                        b.len()
-                    ));
-                }
-                let sum_vec: Vec<f64> = a.as_slice().iter()
-                    .zip(b.as_slice().iter())
-                    .map(|(&x, &y)| x + y)
-                    .collect();
-                Ok(Storage::DiagF64(DiagStorageF64::from_vec(sum_vec)))
+            return self.to_scalar_type(common).try_add(&other.to_scalar_type(common));
+        }
+        match (self, other) {
+            (Storage::DenseF64(a), Storage::DenseF64(b)) => Ok(Storage::DenseF64(a.try_add(b)?)),
+            (Storage::DenseC64(a), Storage::DenseC64(b)) => Ok(Storage::DenseC64(a.try_add(b)?)),
+            (Storage::DenseF32(a), Storage::DenseF32(b)) => Ok(Storage::DenseF32(a.try_add(b)?)),
+            (Storage::DenseC32(a), Storage::DenseC32(b)) => Ok(Storage::DenseC32(a.try_add(b)?)),
+            (Storage::DiagF64(a), Storage::DiagF64(b)) => Ok(Storage::DiagF64(a.try_add(b)?)),
+            (Storage::DiagC64(a), Storage::DiagC64(b)) => Ok(Storage::DiagC64(a.try_add(b)?)),
+            (Storage::DiagF32(a), Storage::DiagF32(b)) => Ok(Storage::DiagF32(a.try_add(b)?)),
+            (Storage::DiagC32(a), Storage::DiagC32(b)) => Ok(Storage::DiagC32(a.try_add(b)?)),
+            (Storage::BlockSparseF64(a), Storage::BlockSparseF64(b)) => {
+                Ok(Storage::BlockSparseF64(a.try_add(b)?))
             }
-            (Storage::DiagC64(a), Storage::DiagC64(b)) => {
-                if a.len() != b.len() {
-                    return Err(format!(
-                        "Storage lengths must match for addition: {} != {}",
-                        a.len(),
-                        b.len()
-                    ));
-                }
-                let sum_vec: Vec<Complex64> = a.as_slice().iter()
-                    .zip(b.as_slice().iter())
-                    .map(|(&x, &y)| x + y)
-                    .collect();
-                Ok(Storage::DiagC64(DiagStorageC64::from_vec(sum_vec)))
+            (Storage::BlockSparseC64(a), Storage::BlockSparseC64(b)) => {
+                Ok(Storage::BlockSparseC64(a.try_add(b)?))
             }
             _ => Err(format!(
                 "Storage types must match for addition: {:?} vs {:?}",
@@ -795,11 +876,47 @@ pub fn mindim(dims: &[usize]) -> usize {
     dims.iter().copied().min().unwrap_or(1)
 }
 
+fn c32_to_c64(z: Complex32) -> Complex64 {
+    Complex64::new(z.re as f64, z.im as f64)
+}
+
+fn c64_to_c32(z: Complex64) -> Complex32 {
+    Complex32::new(z.re as f32, z.im as f32)
+}
+
+/// Convert dense data to storage of the given type, going through Complex64.
+/// The imaginary part is dropped for real target types.
+fn convert_dense<T: Copy>(v: &DenseStorage<T>, to_c64: impl Fn(&T) -> Complex64, scalar_type: ScalarType) -> Storage {
+    match scalar_type {
+        ScalarType::F64 => Storage::DenseF64(v.map(|x| to_c64(x).re)),
+        ScalarType::C64 => Storage::DenseC64(v.map(to_c64)),
+        ScalarType::F32 => Storage::DenseF32(v.map(|x| to_c64(x).re as f32)),
+        ScalarType::C32 => Storage::DenseC32(v.map(|x| c64_to_c32(to_c64(x)))),
+    }
+}
+
+/// Convert diagonal data to storage of the given type, going through Complex64.
+/// The imaginary part is dropped for real target types.
+fn convert_diag<T: Copy>(d: &DiagStorage<T>, to_c64: impl Fn(&T) -> Complex64, scalar_type: ScalarType) -> Storage {
+    match scalar_type {
+        ScalarType::F64 => Storage::DiagF64(d.map(|x| to_c64(x).re)),
+        ScalarType::C64 => Storage::DiagC64(d.map(to_c64)),
+        ScalarType::F32 => Storage::DiagF32(d.map(|x| to_c64(x).re as f32)),
+        ScalarType::C32 => Storage::DiagC32(d.map(|x| c64_to_c32(to_c64(x)))),
+    }
+}
+
 /// Contract two storage tensors along specified axes.
 ///
 /// This is an internal helper function that contracts two `Storage` tensors.
 /// For Dense tensors, uses mdarray-linalg's contract method.
 /// For Diag tensors, implements specialized diagonal contraction.
+/// For BlockSparse tensors, only pairs of blocks that agree on the contracted
+/// axes are multiplied.
+/// If one of the operands has single precision and the element types differ, both
+/// operands are promoted to the common type (see [`ScalarType::promote`]) first.
+/// Mixed f64 × Complex64 contractions avoid the promotion by contracting the real and
+/// imaginary parts separately.
 ///
 /// # Arguments
 /// * `storage_a` - First tensor storage
@@ -838,6 +955,17 @@ pub fn contract_storage(
         );
     }
 
+    // Mixed precision: promote both operands to the common type
+    let (type_a, type_b) = (storage_a.scalar_type(), storage_b.scalar_type());
+    if type_a != type_b && (type_a.is_single() || type_b.is_single()) {
+        let common = type_a.promote(type_b);
+        return contract_storage(
+            &storage_a.to_scalar_type(common), dims_a, axes_a,
+            &storage_b.to_scalar_type(common), dims_b, axes_b,
+            result_dims
+        );
+    }
+
     match (storage_a, storage_b) {
         // Same type cases (existing - no change)
         (Storage::DenseF64(a), Storage::DenseF64(b)) => {
@@ -846,6 +974,12 @@ pub fn contract_storage(
         (Storage::DenseC64(a), Storage::DenseC64(b)) => {
             Storage::DenseC64(a.contract(dims_a, axes_a, b, dims_b, axes_b))
         }
+        (Storage::DenseF32(a), Storage::DenseF32(b)) => {
+            Storage::DenseF32(a.contract(dims_a, axes_a, b, dims_b, axes_b))
+        }
+        (Storage::DenseC32(a), Storage::DenseC32(b)) => {
+            Storage::DenseC32(a.contract(dims_a, axes_a, b, dims_b, axes_b))
+        }
         // DiagTensor × DiagTensor contraction
         (Storage::DiagF64(a), Storage::DiagF64(b)) => {
             a.contract_diag_diag(dims_a, b, dims_b, result_dims)
@@ -853,6 +987,12 @@ pub fn contract_storage(
         (Storage::DiagC64(a), Storage::DiagC64(b)) => {
             a.contract_diag_diag(dims_a, b, dims_b, result_dims)
         }
+        (Storage::DiagF32(a), Storage::DiagF32(b)) => {
+            a.contract_diag_diag(dims_a, b, dims_b, result_dims)
+        }
+        (Storage::DiagC32(a), Storage::DiagC32(b)) => {
+            a.contract_diag_diag(dims_a, b, dims_b, result_dims)
+        }
         
         // Mixed types: f64 × Complex64 (use real/imaginary separation)
         (Storage::DenseF64(_), Storage::DenseC64(_)) | (Storage::DiagF64(_), Storage::DiagC64(_)) => {
@@ -909,11 +1049,17 @@ pub fn contract_storage(
         }
         
         // DiagTensor × DenseTensor: convert Diag to Dense first, then handle mixed types
-        (Storage::DiagF64(_), Storage::DenseF64(_)) | (Storage::DiagC64(_), Storage::DenseC64(_)) => {
+        (Storage::DiagF64(_), Storage::DenseF64(_))
+        | (Storage::DiagC64(_), Storage::DenseC64(_))
+        | (Storage::DiagF32(_), Storage::DenseF32(_))
+        | (Storage::DiagC32(_), Storage::DenseC32(_)) => {
             let dense_a = storage_a.to_dense_storage(dims_a);
             contract_storage(&dense_a, dims_a, axes_a, storage_b, dims_b, axes_b, result_dims)
         }
-        (Storage::DenseF64(_), Storage::DiagF64(_)) | (Storage::DenseC64(_), Storage::DiagC64(_)) => {
+        (Storage::DenseF64(_), Storage::DiagF64(_))
+        | (Storage::DenseC64(_), Storage::DiagC64(_))
+        | (Storage::DenseF32(_), Storage::DiagF32(_))
+        | (Storage::DenseC32(_), Storage::DiagC32(_)) => {
             let dense_b = storage_b.to_dense_storage(dims_b);
             contract_storage(storage_a, dims_a, axes_a, &dense_b, dims_b, axes_b, result_dims)
         }
@@ -935,9 +1081,136 @@ pub fn contract_storage(
             let dense_a = storage_a.to_dense_storage(dims_a);
             contract_storage(&dense_a, dims_a, axes_a, storage_b, dims_b, axes_b, result_dims)
         }
+
+        // BlockSparseTensor × BlockSparseTensor: block-wise contraction
+        (Storage::BlockSparseF64(a), Storage::BlockSparseF64(b)) => {
+            Storage::BlockSparseF64(a.contract(axes_a, b, axes_b))
+        }
+        (Storage::BlockSparseC64(a), Storage::BlockSparseC64(b)) => {
+            Storage::BlockSparseC64(a.contract(axes_a, b, axes_b))
+        }
+        // Mixed BlockSparse types: promote the f64 side to Complex64
+        (Storage::BlockSparseF64(_), Storage::BlockSparseC64(_)) => {
+            let complex_a = storage_a.to_complex_storage();
+            contract_storage(&complex_a, dims_a, axes_a, storage_b, dims_b, axes_b, result_dims)
+        }
+        (Storage::BlockSparseC64(_), Storage::BlockSparseF64(_)) => {
+            let complex_b = storage_b.to_complex_storage();
+            contract_storage(storage_a, dims_a, axes_a, &complex_b, dims_b, axes_b, result_dims)
+        }
+        // BlockSparse with Dense or Diag: convert BlockSparse to Dense first
+        // (`TensorDynLen::contract` rejects these operands before getting here)
+        (Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_), _) => {
+            let dense_a = storage_a.to_dense_storage(dims_a);
+            contract_storage(&dense_a, dims_a, axes_a, storage_b, dims_b, axes_b, result_dims)
+        }
+        (_, Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)) => {
+            let dense_b = storage_b.to_dense_storage(dims_b);
+            contract_storage(storage_a, dims_a, axes_a, &dense_b, dims_b, axes_b, result_dims)
+        }
+        // Remaining single-precision cases have differing types and were promoted above
+        (Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_), _)
+        | (_, Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_)) => {
+            unreachable!("mixed-precision operands are promoted before contraction")
+        }
     }
 }
 
+/// Multiply two storage tensors element-wise, pairing the axes `axes_a[i]` ↔ `axes_b[i]`.
+///
+/// Unlike [`contract_storage`], the paired axes are not summed over:
+/// `c[a..., b_free...] = a[a...] * b[b...]`, where the paired axes of `b` take the values
+/// of the corresponding axes of `a`. The result has the axes of `a` followed by the
+/// unpaired axes of `b`. If all axes are paired this is the Hadamard product.
+///
+/// The operands are promoted to their common element type (see [`ScalarType::promote`])
+/// and the result is always dense.
+///
+/// # Panics
+/// Panics if the paired dimensions don't match.
+pub fn elementwise_product_storage(
+    storage_a: &Storage,
+    dims_a: &[usize],
+    axes_a: &[usize],
+    storage_b: &Storage,
+    dims_b: &[usize],
+    axes_b: &[usize],
+) -> Storage {
+    assert_eq!(axes_a.len(), axes_b.len(), "number of paired axes must match");
+    for (&a_axis, &b_axis) in axes_a.iter().zip(axes_b) {
+        assert_eq!(
+            dims_a[a_axis], dims_b[b_axis],
+            "Paired dimensions must match: dims_a[{}] = {} != dims_b[{}] = {}",
+            a_axis, dims_a[a_axis], b_axis, dims_b[b_axis]
+        );
+    }
+
+    let common = storage_a.scalar_type().promote(storage_b.scalar_type());
+    let dense_a = storage_a.to_scalar_type(common).to_dense_storage(dims_a);
+    let dense_b = storage_b.to_scalar_type(common).to_dense_storage(dims_b);
+    match (&dense_a, &dense_b) {
+        (Storage::DenseF64(a), Storage::DenseF64(b)) => Storage::DenseF64(DenseStorage::from_vec(
+            elementwise_product_dense(a.as_slice(), dims_a, axes_a, b.as_slice(), dims_b, axes_b),
+        )),
+        (Storage::DenseC64(a), Storage::DenseC64(b)) => Storage::DenseC64(DenseStorage::from_vec(
+            elementwise_product_dense(a.as_slice(), dims_a, axes_a, b.as_slice(), dims_b, axes_b),
+        )),
+        (Storage::DenseF32(a), Storage::DenseF32(b)) => Storage::DenseF32(DenseStorage::from_vec(
+            elementwise_product_dense(a.as_slice(), dims_a, axes_a, b.as_slice(), dims_b, axes_b),
+        )),
+        (Storage::DenseC32(a), Storage::DenseC32(b)) => Storage::DenseC32(DenseStorage::from_vec(
+            elementwise_product_dense(a.as_slice(), dims_a, axes_a, b.as_slice(), dims_b, axes_b),
+        )),
+        _ => unreachable!("both operands are dense with the common element type"),
+    }
+}
+
+/// Element-wise product of dense row-major data (see [`elementwise_product_storage`]).
+fn elementwise_product_dense<T: StorageElement>(
+    a: &[T],
+    dims_a: &[usize],
+    axes_a: &[usize],
+    b: &[T],
+    dims_b: &[usize],
+    axes_b: &[usize],
+) -> Vec<T> {
+    let mut strides_b = vec![1; dims_b.len()];
+    for k in (0..dims_b.len().saturating_sub(1)).rev() {
+        strides_b[k] = strides_b[k + 1] * dims_b[k + 1];
+    }
+
+    // Offset into `b` contributed by the paired axes, for every element of `a`
+    let paired_strides: Vec<usize> = (0..dims_a.len())
+        .map(|k| axes_a.iter().position(|&ax| ax == k).map_or(0, |s| strides_b[axes_b[s]]))
+        .collect();
+    let paired_offsets = row_major_offsets(dims_a, &paired_strides);
+
+    // Offset into `b` contributed by the unpaired axes
+    let free_b: Vec<usize> = (0..dims_b.len()).filter(|k| !axes_b.contains(k)).collect();
+    let free_dims: Vec<usize> = free_b.iter().map(|&k| dims_b[k]).collect();
+    let free_strides: Vec<usize> = free_b.iter().map(|&k| strides_b[k]).collect();
+    let free_offsets = row_major_offsets(&free_dims, &free_strides);
+
+    let mut result = Vec::with_capacity(a.len() * free_offsets.len());
+    for (&x, &paired) in a.iter().zip(&paired_offsets) {
+        result.extend(free_offsets.iter().map(|&free| x * b[paired + free]));
+    }
+    result
+}
+
+/// For every position of a row-major array with shape `dims`, the sum of its
+/// coordinates weighted with `strides`.
+fn row_major_offsets(dims: &[usize], strides: &[usize]) -> Vec<usize> {
+    let mut offsets = vec![0];
+    for (&dim, &stride) in dims.iter().zip(strides) {
+        offsets = offsets
+            .iter()
+            .flat_map(|&offset| (0..dim).map(move |i| offset + i * stride))
+            .collect();
+    }
+    offsets
+}
+
 /// Scalar types that can be extracted from and stored in `Storage`.
 ///
 /// This trait provides conversion methods between scalar types and `Storage`,
@@ -981,7 +1254,7 @@ pub trait StorageScalar: Copy + 'static {
 /// with the specified shape `[m, n]`. The data length must match `m * n`.
 ///
 /// # Arguments
-/// * `storage` - Dense storage (DenseF64 or DenseC64)
+/// * `storage` - Dense storage matching `T` (DenseF64, DenseC64, DenseF32 or DenseC32)
 /// * `shape` - Shape array `[m, n]`
 ///
 /// # Returns
@@ -1039,12 +1312,38 @@ impl StorageScalar for Complex64 {
     }
 }
 
+impl StorageScalar for f32 {
+    fn extract_dense_view<'a>(storage: &'a Storage) -> Result<&'a [Self], String> {
+        match storage {
+            Storage::DenseF32(ds) => Ok(ds.as_slice()),
+            _ => Err(format!("Expected DenseF32 storage, got {:?}", storage)),
+        }
+    }
+
+    fn dense_storage(data: Vec<Self>) -> Arc<Storage> {
+        Arc::new(Storage::DenseF32(DenseStorageF32::from_vec(data)))
+    }
+}
+
+impl StorageScalar for Complex32 {
+    fn extract_dense_view<'a>(storage: &'a Storage) -> Result<&'a [Self], String> {
+        match storage {
+            Storage::DenseC32(ds) => Ok(ds.as_slice()),
+            _ => Err(format!("Expected DenseC32 storage, got {:?}", storage)),
+        }
+    }
+
+    fn dense_storage(data: Vec<Self>) -> Arc<Storage> {
+        Arc::new(Storage::DenseC32(DenseStorageC32::from_vec(data)))
+    }
+}
+
 /// Add two storages element-wise.
-/// Both storages must have the same type and length.
+/// Both storages must have the same layout and length; differing element types are promoted.
 ///
 /// # Panics
 ///
-/// Panics if storage types don't match or lengths differ.
+/// Panics if storage layouts don't match or lengths differ.
 ///
 /// # Note
 ///
@@ -1054,46 +1353,13 @@ impl Add<&Storage> for &Storage {
     type Output = Storage;
 
     fn add(self, rhs: &Storage) -> Self::Output {
-        match (self, rhs) {
-            (Storage::DenseF64(a), Storage::DenseF64(b)) => {
-                assert_eq!(a.len(), b.len(), "Storage lengths must match for addition");
-                let sum_vec: Vec<f64> = a.as_slice().iter()
-                    .zip(b.as_slice().iter())
-                    .map(|(&x, &y)| x + y)
-                    .collect();
-                Storage::DenseF64(DenseStorageF64::from_vec(sum_vec))
-            }
-            (Storage::DenseC64(a), Storage::DenseC64(b)) => {
-                assert_eq!(a.len(), b.len(), "Storage lengths must match for addition");
-                let sum_vec: Vec<Complex64> = a.as_slice().iter()
-                    .zip(b.as_slice().iter())
-                    .map(|(&x, &y)| x + y)
-                    .collect();
-                Storage::DenseC64(DenseStorageC64::from_vec(sum_vec))
-            }
-            (Storage::DiagF64(a), Storage::DiagF64(b)) => {
-                assert_eq!(a.len(), b.len(), "Storage lengths must match for addition");
-                let sum_vec: Vec<f64> = a.as_slice().iter()
-                    .zip(b.as_slice().iter())
-                    .map(|(&x, &y)| x + y)
-                    .collect();
-                Storage::DiagF64(DiagStorageF64::from_vec(sum_vec))
-            }
-            (Storage::DiagC64(a), Storage::DiagC64(b)) => {
-                assert_eq!(a.len(), b.len(), "Storage lengths must match for addition");
-                let sum_vec: Vec<Complex64> = a.as_slice().iter()
-                    .zip(b.as_slice().iter())
-                    .map(|(&x, &y)| x + y)
-                    .collect();
-                Storage::DiagC64(DiagStorageC64::from_vec(sum_vec))
-            }
-            _ => panic!("Storage types must match for addition"),
-        }
+        self.try_add(rhs).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
 /// Multiply storage by a scalar (f64).
 /// For Complex64 storage, multiplies each element by the scalar (treated as real).
+/// Single-precision storage keeps its precision (the scalar is rounded to f32).
 impl Mul<f64> for &Storage {
     type Output = Storage;
 
@@ -1119,11 +1385,20 @@ impl Mul<f64> for &Storage {
                     .collect();
                 Storage::DiagC64(DiagStorageC64::from_vec(scaled_vec))
             }
+            Storage::BlockSparseF64(b) => Storage::BlockSparseF64(b.map(|&x| x * scalar)),
+            Storage::BlockSparseC64(b) => {
+                Storage::BlockSparseC64(b.map(|&z| z * Complex64::new(scalar, 0.0)))
+            }
+            Storage::DenseF32(v) => Storage::DenseF32(v.map(|&x| x * scalar as f32)),
+            Storage::DenseC32(v) => Storage::DenseC32(v.map(|&z| z * scalar as f32)),
+            Storage::DiagF32(d) => Storage::DiagF32(d.map(|&x| x * scalar as f32)),
+            Storage::DiagC32(d) => Storage::DiagC32(d.map(|&z| z * scalar as f32)),
         }
     }
 }
 
 /// Multiply storage by a scalar (Complex64).
+/// Single-precision storage becomes Complex32 storage (the scalar is rounded to Complex32).
 impl Mul<Complex64> for &Storage {
     type Output = Storage;
 
@@ -1155,6 +1430,29 @@ impl Mul<Complex64> for &Storage {
                     .collect();
                 Storage::DiagC64(DiagStorageC64::from_vec(scaled_vec))
             }
+            Storage::BlockSparseF64(b) => {
+                // Promote f64 to Complex64
+                Storage::BlockSparseC64(b.map(|&x| Complex64::new(x, 0.0) * scalar))
+            }
+            Storage::BlockSparseC64(b) => Storage::BlockSparseC64(b.map(|&z| z * scalar)),
+            Storage::DenseF32(v) => {
+                // Promote f32 to Complex32
+                let scalar = c64_to_c32(scalar);
+                Storage::DenseC32(v.map(|&x| Complex32::new(x, 0.0) * scalar))
+            }
+            Storage::DenseC32(v) => {
+                let scalar = c64_to_c32(scalar);
+                Storage::DenseC32(v.map(|&z| z * scalar))
+            }
+            Storage::DiagF32(d) => {
+                // Promote f32 to Complex32
+                let scalar = c64_to_c32(scalar);
+                Storage::DiagC32(d.map(|&x| Complex32::new(x, 0.0) * scalar))
+            }
+            Storage::DiagC32(d) => {
+                let scalar = c64_to_c32(scalar);
+                Storage::DiagC32(d.map(|&z| z * scalar))
+            }
         }
     }
 }
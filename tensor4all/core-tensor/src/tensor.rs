@@ -1,10 +1,15 @@
 use std::sync::Arc;
 use std::collections::HashSet;
-use std::ops::Mul;
+use std::ops::{Mul, Neg};
 use num_complex::Complex64;
 use tensor4all_core_common::index::{Index, NoSymmSpace, Symmetry};
 use tensor4all_core_common::index_ops::{common_inds, check_unique_indices};
-use crate::storage::{AnyScalar, Storage, StorageScalar, SumFromStorage, contract_storage, storage_to_dtensor};
+use tensor4all_core_common::qn::{QN, QNSpace};
+use crate::block_sparse::{all_block_coords, BlockSparseScalar, BlockSparseStorage, BlockSparseStorageF64};
+use crate::storage::{
+    AnyScalar, ScalarType, Storage, StorageScalar, SumFromStorage, contract_storage, elementwise_product_storage,
+    storage_to_dtensor,
+};
 use anyhow::Result;
 use mdarray::DTensor;
 
@@ -132,6 +137,7 @@ impl<Id, Symm> TensorDynLen<Id, Symm> {
     ///
     /// # Panics
     /// Panics if the storage is Diag and not all indices have the same dimension.
+    /// Panics if the storage is BlockSparse and its block structure doesn't match `dims`.
     /// Panics if there are duplicate indices (indices with the same ID).
     pub fn new(indices: Vec<Index<Id, Symm>>, dims: Vec<usize>, storage: Arc<Storage>) -> Self
     where
@@ -157,6 +163,15 @@ impl<Id, Symm> TensorDynLen<Id, Symm> {
                 );
             }
         }
+
+        // Validate BlockSparseTensor: block dimensions must add up to dims
+        if let Some(storage_dims) = block_sparse_dims(storage.as_ref()) {
+            assert_eq!(
+                storage_dims, dims,
+                "BlockSparse storage dims {:?} do not match tensor dims {:?}",
+                storage_dims, dims
+            );
+        }
         
         Self {
             indices,
@@ -196,6 +211,32 @@ impl<Id, Symm> TensorDynLen<Id, Symm> {
         f64::sum_from_storage(&self.storage)
     }
 
+    /// Frobenius norm: the square root of the sum of the squared absolute values
+    /// of all elements.
+    pub fn norm(&self) -> f64 {
+        self.storage.norm_sqr().sqrt()
+    }
+
+    /// Get the element type of the storage.
+    pub fn scalar_type(&self) -> ScalarType {
+        self.storage.scalar_type()
+    }
+
+    /// Convert the storage to another element type (see [`Storage::to_scalar_type`]).
+    ///
+    /// Converting to `ScalarType::F32` or `ScalarType::C32` halves the memory footprint.
+    pub fn to_scalar_type(&self, scalar_type: ScalarType) -> Self
+    where
+        Id: Clone,
+        Symm: Clone,
+    {
+        Self {
+            indices: self.indices.clone(),
+            dims: self.dims.clone(),
+            storage: Arc::new(self.storage.to_scalar_type(scalar_type)),
+        }
+    }
+
     /// Permute the tensor dimensions using the given new indices order.
     ///
     /// This is the main permutation method that takes the desired new indices
@@ -334,7 +375,9 @@ impl<Id, Symm> TensorDynLen<Id, Symm> {
     ///
     /// # Panics
     /// Panics if there are no common indices, if common indices have mismatched
-    /// dimensions, or if storage types don't match.
+    /// dimensions, if common quantum number indices are not dual to each other
+    /// (opposite directions and equal sectors, see [`Index::dag`]), or if one tensor
+    /// has BlockSparse storage and the other doesn't.
     ///
     /// # Example
     /// ```
@@ -401,6 +444,14 @@ impl<Id, Symm> TensorDynLen<Id, Symm> {
                 self.dims[pos_a],
                 other.dims[pos_b]
             );
+
+            // Verify the spaces are dual (opposite arrows for quantum number indices)
+            assert!(
+                self.indices[pos_a].symm.dag() == other.indices[pos_b].symm,
+                "Common index spaces are not dual: self axis {} vs other axis {}",
+                pos_a,
+                pos_b
+            );
         }
 
         // Get non-contracted indices
@@ -423,6 +474,10 @@ impl<Id, Symm> TensorDynLen<Id, Symm> {
             }
         }
 
+        if let Err(e) = check_block_sparse_operands(&self.storage, &other.storage) {
+            panic!("{}", e);
+        }
+
         // Perform contraction
         let result_storage = Arc::new(contract_storage(
             &self.storage,
@@ -455,7 +510,10 @@ impl<Id, Symm> TensorDynLen<Id, Symm> {
     /// A new tensor resulting from the contraction, or an error if:
     /// - Any specified index is not found in the respective tensor
     /// - Dimensions don't match for any pair
+    /// - The spaces of a pair are not dual to each other (for quantum number
+    ///   indices: opposite directions and equal sectors)
     /// - The same axis is specified multiple times in `self` or `other`
+    /// - One tensor has BlockSparse storage and the other doesn't
     ///
     /// # Example
     /// ```
@@ -532,6 +590,16 @@ impl<Id, Symm> TensorDynLen<Id, Symm> {
                 .context("contract_pairs: dimensions must match for each pair");
             }
 
+            // Verify the spaces are dual (opposite arrows for quantum number indices)
+            if self.indices[pos_a].symm.dag() != other.indices[pos_b].symm {
+                return Err(anyhow::anyhow!(
+                    "Spaces are not dual for pair: self[{}] vs other[{}]",
+                    pos_a,
+                    pos_b
+                ))
+                .context("contract_pairs: contracted indices must have dual spaces");
+            }
+
             // Check for duplicate axes in self
             if axes_a.contains(&pos_a) {
                 return Err(anyhow::anyhow!(
@@ -574,6 +642,8 @@ impl<Id, Symm> TensorDynLen<Id, Symm> {
             }
         }
 
+        check_block_sparse_operands(&self.storage, &other.storage).context("contract_pairs")?;
+
         // Perform contraction
         let result_storage = Arc::new(contract_storage(
             &self.storage,
@@ -678,6 +748,82 @@ where
     }
 }
 
+/// Implement multiplication by a real scalar.
+impl<Id, Symm> Mul<f64> for &TensorDynLen<Id, Symm>
+where
+    Id: Clone,
+    Symm: Clone,
+{
+    type Output = TensorDynLen<Id, Symm>;
+
+    fn mul(self, scalar: f64) -> Self::Output {
+        TensorDynLen {
+            indices: self.indices.clone(),
+            dims: self.dims.clone(),
+            storage: Arc::new(self.storage.as_ref() * scalar),
+        }
+    }
+}
+
+/// Implement multiplication by a complex scalar.
+impl<Id, Symm> Mul<Complex64> for &TensorDynLen<Id, Symm>
+where
+    Id: Clone,
+    Symm: Clone,
+{
+    type Output = TensorDynLen<Id, Symm>;
+
+    fn mul(self, scalar: Complex64) -> Self::Output {
+        TensorDynLen {
+            indices: self.indices.clone(),
+            dims: self.dims.clone(),
+            storage: Arc::new(self.storage.as_ref() * scalar),
+        }
+    }
+}
+
+/// Implement multiplication by an `AnyScalar`.
+impl<Id, Symm> Mul<AnyScalar> for &TensorDynLen<Id, Symm>
+where
+    Id: Clone,
+    Symm: Clone,
+{
+    type Output = TensorDynLen<Id, Symm>;
+
+    fn mul(self, scalar: AnyScalar) -> Self::Output {
+        TensorDynLen {
+            indices: self.indices.clone(),
+            dims: self.dims.clone(),
+            storage: Arc::new(self.storage.as_ref() * scalar),
+        }
+    }
+}
+
+/// Implement negation (multiplication by -1).
+impl<Id, Symm> Neg for &TensorDynLen<Id, Symm>
+where
+    Id: Clone,
+    Symm: Clone,
+{
+    type Output = TensorDynLen<Id, Symm>;
+
+    fn neg(self) -> Self::Output {
+        self * -1.0
+    }
+}
+
+/// Implement negation (owned version).
+impl<Id, Symm> Neg for TensorDynLen<Id, Symm>
+where
+    Id: Clone,
+    Symm: Clone,
+{
+    type Output = TensorDynLen<Id, Symm>;
+
+    fn neg(self) -> Self::Output {
+        &self * -1.0
+    }
+}
 
 /// Check if a tensor is a DiagTensor (has Diag storage).
 pub fn is_diag_tensor<Id, Symm>(tensor: &TensorDynLen<Id, Symm>) -> bool {
@@ -693,6 +839,7 @@ where
     ///
     /// The tensors must have the same index set (matched by ID). If the indices
     /// are in a different order, the other tensor will be permuted to match `self`.
+    /// If the storage layouts differ (e.g. Diag and Dense), the sum is dense.
     ///
     /// # Arguments
     /// * `other` - The tensor to add
@@ -701,7 +848,7 @@ where
     /// A new tensor representing `self + other`, or an error if:
     /// - The tensors have different index sets
     /// - The dimensions don't match
-    /// - Storage types are incompatible
+    /// - The block structures of two BlockSparse storages differ
     ///
     /// # Example
     /// ```
@@ -728,6 +875,39 @@ where
     /// // sum = [[2, 3, 4], [5, 6, 7]]
     /// ```
     pub fn add(&self, other: &Self) -> Result<Self> {
+        let other_aligned = self.align(other)?;
+
+        // Add storages using try_add (returns Result instead of panicking).
+        // Different layouts (e.g. Diag and Dense) are added densely.
+        let (a, b) = (self.storage.as_ref(), other_aligned.storage.as_ref());
+        let result_storage = if a.is_diag() != b.is_diag() || a.is_block_sparse() != b.is_block_sparse() {
+            a.to_dense_storage(&self.dims).try_add(&b.to_dense_storage(&self.dims))
+        } else {
+            a.try_add(b)
+        }
+        .map_err(|e| anyhow::anyhow!("Storage addition failed: {}", e))?;
+
+        Ok(Self {
+            indices: self.indices.clone(),
+            dims: self.dims.clone(),
+            storage: Arc::new(result_storage),
+        })
+    }
+
+    /// Subtract another tensor element-wise (`self - other`).
+    ///
+    /// Indices are matched by ID as in [`TensorDynLen::add`].
+    ///
+    /// # Errors
+    /// Returns an error if the index sets or dimensions don't match, or if the
+    /// block structures of two BlockSparse storages differ.
+    pub fn sub(&self, other: &Self) -> Result<Self> {
+        self.add(&-other)
+    }
+
+    /// Check that `other` has the same index set as `self` (matched by ID) and
+    /// return it with its indices permuted into the order of `self`.
+    fn align(&self, other: &Self) -> Result<Self> {
         // Validate that both tensors have the same number of indices
         if self.indices.len() != other.indices.len() {
             return Err(anyhow::anyhow!(
@@ -770,17 +950,239 @@ where
             ));
         }
 
-        // Add storages using try_add (returns Result instead of panicking)
-        let result_storage = self.storage.as_ref()
-            .try_add(other_aligned.storage.as_ref())
-            .map_err(|e| anyhow::anyhow!("Storage addition failed: {}", e))?;
+        Ok(other_aligned)
+    }
 
+    /// Complex conjugate of the tensor. The indices are unchanged.
+    pub fn conj(&self) -> Self {
+        Self {
+            indices: self.indices.clone(),
+            dims: self.dims.clone(),
+            storage: Arc::new(self.storage.conj()),
+        }
+    }
+
+    /// Hermitian conjugate of the tensor (ITensors.jl's `dag`).
+    ///
+    /// Conjugates the elements and replaces every index by its dual (see
+    /// [`Index::dag`]), which reverses the direction of quantum number indices.
+    /// For indices without symmetry this is the same as [`TensorDynLen::conj`].
+    pub fn dag(&self) -> Self {
+        Self {
+            indices: self.indices.iter().map(|idx| idx.dag()).collect(),
+            dims: self.dims.clone(),
+            storage: Arc::new(self.storage.conj()),
+        }
+    }
+
+    /// Inner product `⟨self, other⟩ = Σ conj(self) * other` over all elements.
+    ///
+    /// Indices are matched by ID as in [`TensorDynLen::add`].
+    ///
+    /// # Errors
+    /// Returns an error if the index sets or dimensions don't match.
+    pub fn inner(&self, other: &Self) -> Result<AnyScalar> {
+        let other_aligned = self.align(other)?;
+        let axes: Vec<usize> = (0..self.dims.len()).collect();
+        let result = contract_storage(
+            &self.storage.conj(),
+            &self.dims,
+            &axes,
+            other_aligned.storage.as_ref(),
+            &other_aligned.dims,
+            &axes,
+            &[],
+        );
+        Ok(AnyScalar::sum_from_storage(&result))
+    }
+
+    /// Element-wise product over shared indices.
+    ///
+    /// Shared indices (matched by ID) are kept instead of being summed over:
+    /// `C[i, j, k] = A[i, j] * B[j, k]`. The result has the indices of `self` followed
+    /// by the indices of `other` that `self` doesn't have. If both tensors have the
+    /// same index set, this is the Hadamard product. The result has dense storage.
+    ///
+    /// # Errors
+    /// Returns an error if shared indices have mismatched dimensions.
+    pub fn elementwise_product(&self, other: &Self) -> Result<Self> {
+        let mut axes_a = Vec::new();
+        let mut axes_b = Vec::new();
+        for (pos_b, idx_b) in other.indices.iter().enumerate() {
+            if let Some(pos_a) = self.indices.iter().position(|idx_a| idx_a.id == idx_b.id) {
+                if self.dims[pos_a] != other.dims[pos_b] {
+                    return Err(anyhow::anyhow!(
+                        "Dimension mismatch for shared index: self[{}] = {} != other[{}] = {}",
+                        pos_a,
+                        self.dims[pos_a],
+                        pos_b,
+                        other.dims[pos_b]
+                    ));
+                }
+                axes_a.push(pos_a);
+                axes_b.push(pos_b);
+            }
+        }
+
+        let storage = elementwise_product_storage(
+            &self.storage,
+            &self.dims,
+            &axes_a,
+            &other.storage,
+            &other.dims,
+            &axes_b,
+        );
+
+        let mut indices = self.indices.clone();
+        let mut dims = self.dims.clone();
+        for (pos_b, idx_b) in other.indices.iter().enumerate() {
+            if !axes_b.contains(&pos_b) {
+                indices.push(idx_b.clone());
+                dims.push(other.dims[pos_b]);
+            }
+        }
+        Ok(Self::new(indices, dims, Arc::new(storage)))
+    }
+
+    /// Apply a function to every element.
+    ///
+    /// Diag and BlockSparse tensors are converted to dense storage first, since `f`
+    /// does not need to map zero to zero.
+    ///
+    /// # Errors
+    /// Returns an error if the element type of the storage is not `T`.
+    pub fn map<T: StorageScalar>(&self, f: impl Fn(T) -> T) -> Result<Self> {
+        let dense;
+        let storage = if self.storage.is_diag() || self.storage.is_block_sparse() {
+            dense = self.storage.to_dense_storage(&self.dims);
+            &dense
+        } else {
+            self.storage.as_ref()
+        };
+        let data = T::extract_dense_view(storage).map_err(|e| anyhow::anyhow!("map: {}", e))?;
         Ok(Self {
             indices: self.indices.clone(),
             dims: self.dims.clone(),
-            storage: Arc::new(result_storage),
+            storage: T::dense_storage(data.iter().map(|&x| f(x)).collect()),
         })
     }
+
+    /// Check if two tensors are approximately equal (ITensors.jl's `isapprox`).
+    ///
+    /// Returns true if `‖self - other‖ <= max(atol, rtol * max(‖self‖, ‖other‖))`.
+    /// Indices are matched by ID; tensors with different index sets are not
+    /// approximately equal. Tensors with different storage layouts (e.g. Diag and
+    /// Dense) are compared elementwise.
+    pub fn isapprox(&self, other: &Self, atol: f64, rtol: f64) -> bool {
+        match self.sub(other) {
+            Ok(diff) => diff.norm() <= atol.max(rtol * self.norm().max(other.norm())),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Total dimensions of BlockSparse storage (`None` for other storage types).
+fn block_sparse_dims(storage: &Storage) -> Option<Vec<usize>> {
+    match storage {
+        Storage::BlockSparseF64(b) => Some(b.dims()),
+        Storage::BlockSparseC64(b) => Some(b.dims()),
+        _ => None,
+    }
+}
+
+/// Reject contractions of BlockSparse storage with Dense or Diag storage.
+///
+/// Contracting them would silently densify the result and lose the block
+/// structure; convert one side explicitly instead (e.g. with
+/// [`Storage::to_dense_storage`] or [`TensorDynLen::from_dense_with_flux`]).
+fn check_block_sparse_operands(a: &Storage, b: &Storage) -> Result<()> {
+    if a.is_block_sparse() != b.is_block_sparse() {
+        anyhow::bail!("cannot contract BlockSparse storage with Dense or Diag storage; convert one operand explicitly");
+    }
+    Ok(())
+}
+
+impl<Id> TensorDynLen<Id, QNSpace>
+where
+    Id: Clone + std::hash::Hash + Eq,
+{
+    /// Block coordinates of all blocks whose flux equals `flux`.
+    ///
+    /// The flux of a block is the sum of the quantum numbers of its index blocks,
+    /// with the sign given by each index direction.
+    fn blocks_with_flux(indices: &[Index<Id, QNSpace>], flux: QN) -> Result<Vec<Vec<usize>>> {
+        let block_dims: Vec<Vec<usize>> = indices.iter().map(|idx| idx.symm.block_dims()).collect();
+        let mut coords_with_flux = Vec::new();
+        for coords in all_block_coords(&block_dims) {
+            let mut block_flux = QN::default();
+            for (idx, &b) in indices.iter().zip(&coords) {
+                block_flux = block_flux.try_add(&idx.symm.flux_of_block(b))?;
+            }
+            if block_flux.try_add(&-flux)?.is_zero() {
+                coords_with_flux.push(coords);
+            }
+        }
+        Ok(coords_with_flux)
+    }
+
+    /// Create a block-sparse tensor of zeros with all blocks allowed by `flux`.
+    ///
+    /// # Errors
+    /// Returns an error if the quantum numbers of the indices and `flux` have
+    /// mismatching sectors.
+    pub fn zeros_with_flux(indices: Vec<Index<Id, QNSpace>>, flux: QN) -> Result<Self> {
+        let coords = Self::blocks_with_flux(&indices, flux)?;
+        let block_dims = indices.iter().map(|idx| idx.symm.block_dims()).collect();
+        let storage = BlockSparseStorageF64::zeros(block_dims, coords);
+        Ok(Self::from_indices(indices, Arc::new(Storage::BlockSparseF64(storage))))
+    }
+
+    /// Create a block-sparse tensor from dense row-major data.
+    ///
+    /// Only the blocks allowed by `flux` are stored.
+    ///
+    /// # Errors
+    /// Returns an error if the data length doesn't match the indices, if the quantum
+    /// numbers have mismatching sectors, or if `data` has nonzero elements outside
+    /// the blocks allowed by `flux`.
+    pub fn from_dense_with_flux<T: BlockSparseScalar>(
+        indices: Vec<Index<Id, QNSpace>>,
+        data: &[T],
+        flux: QN,
+    ) -> Result<Self> {
+        let dims: Vec<usize> = indices.iter().map(|idx| idx.size()).collect();
+        anyhow::ensure!(
+            data.len() == dims.iter().product::<usize>(),
+            "Data length {} does not match dims {:?}",
+            data.len(),
+            dims
+        );
+        let coords: HashSet<Vec<usize>> = Self::blocks_with_flux(&indices, flux)?.into_iter().collect();
+        let block_dims = indices.iter().map(|idx| idx.symm.block_dims()).collect();
+        let storage = BlockSparseStorage::from_dense(block_dims, data, |c| coords.contains(c));
+        anyhow::ensure!(
+            storage.to_dense_vec() == data,
+            "Data has nonzero elements outside the blocks with flux {}",
+            flux
+        );
+        Ok(Self::new(indices, dims, Arc::new(T::block_sparse_storage(storage))))
+    }
+
+    /// Get the flux (total quantum number) of the tensor.
+    ///
+    /// Computed from the first stored block. Returns `None` if the storage is not
+    /// block-sparse or has no stored blocks.
+    pub fn flux(&self) -> Option<QN> {
+        let coords = match self.storage.as_ref() {
+            Storage::BlockSparseF64(b) => b.iter_blocks().next()?.0.to_vec(),
+            Storage::BlockSparseC64(b) => b.iter_blocks().next()?.0.to_vec(),
+            _ => return None,
+        };
+        self.indices
+            .iter()
+            .zip(&coords)
+            .try_fold(QN::default(), |acc, (idx, &b)| acc.try_add(&idx.symm.flux_of_block(b)).ok())
+    }
 }
 
 impl<Id, Symm> Clone for TensorDynLen<Id, Symm>
@@ -797,6 +1199,94 @@ where
     }
 }
 
+/// Serialized as its indices, dimensions and storage.
+///
+/// Index IDs are preserved, so tensors saved together still share their
+/// common indices after loading.
+#[cfg(feature = "serde")]
+impl<Id, Symm> serde::Serialize for TensorDynLen<Id, Symm>
+where
+    Id: serde::Serialize,
+    Symm: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("TensorDynLen", 3)?;
+        state.serialize_field("indices", &self.indices)?;
+        state.serialize_field("dims", &self.dims)?;
+        state.serialize_field("storage", self.storage.as_ref())?;
+        state.end()
+    }
+}
+
+/// Deserialization validates the data instead of panicking like [`TensorDynLen::new`].
+#[cfg(feature = "serde")]
+impl<'de, Id, Symm> serde::Deserialize<'de> for TensorDynLen<Id, Symm>
+where
+    Id: serde::Deserialize<'de> + std::hash::Hash + Eq,
+    Symm: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "TensorDynLen")]
+        struct TensorData<Id, Symm> {
+            indices: Vec<Index<Id, Symm>>,
+            dims: Vec<usize>,
+            storage: Storage,
+        }
+
+        let data = TensorData::<Id, Symm>::deserialize(deserializer)?;
+        if data.indices.len() != data.dims.len() {
+            return Err(D::Error::custom(format!(
+                "indices and dims must have the same length, got {} and {}",
+                data.indices.len(),
+                data.dims.len()
+            )));
+        }
+        check_unique_indices(&data.indices).map_err(D::Error::custom)?;
+
+        if let Some(storage_dims) = block_sparse_dims(&data.storage) {
+            if storage_dims != data.dims {
+                return Err(D::Error::custom(format!(
+                    "block-sparse storage dims {:?} do not match dims {:?}",
+                    storage_dims, data.dims
+                )));
+            }
+            return Ok(Self {
+                indices: data.indices,
+                dims: data.dims,
+                storage: Arc::new(data.storage),
+            });
+        }
+
+        let expected_len = if data.storage.is_diag() {
+            if data.dims.windows(2).any(|w| w[0] != w[1]) {
+                return Err(D::Error::custom(
+                    "DiagTensor requires all indices to have the same dimension",
+                ));
+            }
+            data.dims.first().copied().unwrap_or(1)
+        } else {
+            data.dims.iter().product()
+        };
+        if data.storage.len() != expected_len {
+            return Err(D::Error::custom(format!(
+                "storage length {} does not match dims {:?}",
+                data.storage.len(),
+                data.dims
+            )));
+        }
+
+        Ok(Self {
+            indices: data.indices,
+            dims: data.dims,
+            storage: Arc::new(data.storage),
+        })
+    }
+}
+
 
 /// Create a DiagTensor with dynamic rank from diagonal data.
 ///
@@ -0,0 +1,199 @@
+use num_complex::Complex64;
+use std::sync::Arc;
+use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
+use tensor4all_core_common::qn::{Arrow, QNSpace, QN};
+use tensor4all_core_tensor::storage::DiagStorageF64;
+use tensor4all_core_tensor::{AnyScalar, Storage, StorageScalar, TensorDynLen};
+
+fn dense_f64(indices: Vec<Index<DynId>>, data: Vec<f64>) -> TensorDynLen<DynId> {
+    TensorDynLen::from_indices(indices, f64::dense_storage(data))
+}
+
+fn dense_c64(indices: Vec<Index<DynId>>, data: Vec<Complex64>) -> TensorDynLen<DynId> {
+    TensorDynLen::from_indices(indices, Complex64::dense_storage(data))
+}
+
+#[test]
+fn test_norm() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let t = dense_f64(vec![i.clone(), j.clone()], vec![1.0, 2.0, 2.0, 4.0]);
+    assert!((t.norm() - 5.0).abs() < 1e-12);
+
+    let z = dense_c64(vec![i.clone()], vec![Complex64::new(3.0, 4.0), Complex64::new(0.0, 0.0)]);
+    assert!((z.norm() - 5.0).abs() < 1e-12);
+
+    // Diag storage only stores the diagonal
+    let d: TensorDynLen<DynId> = TensorDynLen::from_indices(
+        vec![i, j],
+        Arc::new(Storage::DiagF64(DiagStorageF64::from_vec(vec![3.0, 4.0]))),
+    );
+    assert!((d.norm() - 5.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_conj_and_dag() {
+    let i = Index::new_dyn(2);
+    let z = dense_c64(vec![i.clone()], vec![Complex64::new(1.0, 2.0), Complex64::new(3.0, -4.0)]);
+
+    let expected = vec![Complex64::new(1.0, -2.0), Complex64::new(3.0, 4.0)];
+    assert_eq!(Complex64::extract_dense(&z.conj().storage).unwrap(), expected);
+    assert_eq!(Complex64::extract_dense(&z.dag().storage).unwrap(), expected);
+    assert_eq!(z.dag().indices, z.indices);
+}
+
+#[test]
+fn test_dag_flips_qn_arrows() {
+    let space = QNSpace::new(vec![(QN::u1(0), 1), (QN::u1(1), 1)], Arrow::Out);
+    let i: Index<DynId, QNSpace> = Index::new_dyn_with_space(space.clone());
+    let j: Index<DynId, QNSpace> = Index::new_dyn_with_space(space.dag());
+    let t = TensorDynLen::from_dense_with_flux(vec![i.clone(), j.clone()], &[1.0, 0.0, 0.0, 2.0], QN::u1(0)).unwrap();
+
+    let t_dag = t.dag();
+    assert_eq!(t_dag.indices, t.indices);
+    assert_eq!(t_dag.indices[0].dir(), Arrow::In);
+    assert_eq!(t_dag.indices[1].dir(), Arrow::Out);
+    assert!((t_dag.norm() - 5.0_f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn test_sub_matches_indices_by_id() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(3);
+    let a = dense_f64(vec![i.clone(), j.clone()], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    // Same data as `a`, stored in the order (j, i)
+    let b = dense_f64(vec![j.clone(), i.clone()], vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+
+    let diff = a.sub(&b).unwrap();
+    assert_eq!(diff.indices, a.indices);
+    assert_eq!(diff.norm(), 0.0);
+
+    let neg = -&a;
+    assert_eq!(f64::extract_dense(&neg.storage).unwrap(), vec![-1.0, -2.0, -3.0, -4.0, -5.0, -6.0]);
+
+    let k = Index::new_dyn(2);
+    let c = dense_f64(vec![i, k], vec![0.0; 4]);
+    assert!(a.sub(&c).is_err());
+}
+
+#[test]
+fn test_inner() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let a = dense_f64(vec![i.clone(), j.clone()], vec![1.0, 2.0, 3.0, 4.0]);
+    let b = dense_f64(vec![j.clone(), i.clone()], vec![1.0, 1.0, 0.0, 2.0]);
+    // b[i, j] = [[1, 0], [1, 2]]
+    assert_eq!(a.inner(&b).unwrap(), AnyScalar::F64(1.0 + 3.0 + 8.0));
+
+    // The first argument is conjugated
+    let z = dense_c64(vec![i.clone()], vec![Complex64::new(0.0, 1.0), Complex64::new(1.0, 0.0)]);
+    let w = dense_c64(vec![i.clone()], vec![Complex64::new(0.0, 1.0), Complex64::new(2.0, 0.0)]);
+    assert_eq!(z.inner(&w).unwrap(), AnyScalar::C64(Complex64::new(3.0, 0.0)));
+    assert_eq!(z.inner(&z).unwrap(), AnyScalar::C64(Complex64::new(2.0, 0.0)));
+
+    assert!(a.inner(&z).is_err());
+}
+
+#[test]
+fn test_map() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let a = dense_f64(vec![i.clone(), j.clone()], vec![1.0, 2.0, 3.0, 4.0]);
+    let squared = a.map(|x: f64| x * x).unwrap();
+    assert_eq!(f64::extract_dense(&squared.storage).unwrap(), vec![1.0, 4.0, 9.0, 16.0]);
+
+    // Diag storage is densified, since f(0) need not be zero
+    let d: TensorDynLen<DynId> = TensorDynLen::from_indices(
+        vec![i, j],
+        Arc::new(Storage::DiagF64(DiagStorageF64::from_vec(vec![1.0, 2.0]))),
+    );
+    let shifted = d.map(|x: f64| x + 1.0).unwrap();
+    assert_eq!(f64::extract_dense(&shifted.storage).unwrap(), vec![2.0, 1.0, 1.0, 3.0]);
+
+    // The element type must match
+    assert!(a.map(|z: Complex64| z.conj()).is_err());
+}
+
+#[test]
+fn test_elementwise_product() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let k = Index::new_dyn(3);
+    let a = dense_f64(vec![i.clone(), j.clone()], vec![1.0, 2.0, 3.0, 4.0]);
+    let b = dense_f64(vec![j.clone(), k.clone()], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    // C[i, j, k] = A[i, j] * B[j, k]
+    let c = a.elementwise_product(&b).unwrap();
+    assert_eq!(c.indices, vec![i.clone(), j.clone(), k.clone()]);
+    assert_eq!(c.dims, vec![2, 2, 3]);
+    assert_eq!(
+        f64::extract_dense(&c.storage).unwrap(),
+        vec![1.0, 2.0, 3.0, 8.0, 10.0, 12.0, 3.0, 6.0, 9.0, 16.0, 20.0, 24.0]
+    );
+
+    // Hadamard product with the same indices in a different order
+    let a_t = dense_f64(vec![j.clone(), i.clone()], vec![1.0, 3.0, 2.0, 4.0]);
+    let h = a.elementwise_product(&a_t).unwrap();
+    assert_eq!(h.indices, vec![i.clone(), j.clone()]);
+    assert_eq!(f64::extract_dense(&h.storage).unwrap(), vec![1.0, 4.0, 9.0, 16.0]);
+
+    // Mixed real/complex promotes to complex
+    let z = dense_c64(vec![j.clone()], vec![Complex64::new(0.0, 1.0), Complex64::new(2.0, 0.0)]);
+    let az = a.elementwise_product(&z).unwrap();
+    assert_eq!(
+        Complex64::extract_dense(&az.storage).unwrap(),
+        vec![
+            Complex64::new(0.0, 1.0),
+            Complex64::new(4.0, 0.0),
+            Complex64::new(0.0, 3.0),
+            Complex64::new(8.0, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn test_isapprox_and_scalar_mul() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let a = dense_f64(vec![i.clone(), j.clone()], vec![1.0, 2.0, 3.0, 4.0]);
+    let b = dense_f64(vec![j.clone(), i.clone()], vec![1.0, 3.0, 2.0, 4.0 + 1e-10]);
+
+    assert!(a.isapprox(&b, 1e-8, 0.0));
+    assert!(!a.isapprox(&b, 1e-12, 0.0));
+    assert!(a.isapprox(&b, 0.0, 1e-8));
+
+    let twice = &a * 2.0;
+    assert!(twice.isapprox(&a.add(&a).unwrap(), 1e-14, 0.0));
+    assert!(!twice.isapprox(&a, 1e-8, 1e-8));
+
+    let rotated = &a * Complex64::new(0.0, 1.0);
+    assert!((rotated.norm() - a.norm()).abs() < 1e-12);
+    assert!(rotated.isapprox(&(&a * AnyScalar::C64(Complex64::new(0.0, 1.0))), 1e-14, 0.0));
+
+    // Different index sets are never approximately equal
+    let k = Index::new_dyn(2);
+    let c = dense_f64(vec![i, k], vec![1.0, 2.0, 3.0, 4.0]);
+    assert!(!a.isapprox(&c, 1e10, 1e10));
+}
+
+#[test]
+fn test_isapprox_diag_vs_dense() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let d: TensorDynLen<DynId> = TensorDynLen::from_indices(
+        vec![i.clone(), j.clone()],
+        Arc::new(Storage::DiagF64(DiagStorageF64::from_vec(vec![3.0, 4.0]))),
+    );
+    let dense = dense_f64(vec![j.clone(), i.clone()], vec![3.0, 0.0, 0.0, 4.0]);
+
+    assert!(d.isapprox(&dense, 1e-14, 0.0));
+    assert!(dense.isapprox(&d, 1e-14, 0.0));
+    let diff = dense.sub(&d).unwrap();
+    assert!(!diff.storage.is_diag());
+    assert_eq!(diff.norm(), 0.0);
+
+    // An off-diagonal element is not part of the diagonal tensor
+    let off_diag = dense_f64(vec![i, j], vec![3.0, 1.0, 0.0, 4.0]);
+    assert!(!d.isapprox(&off_diag, 1e-8, 1e-8));
+    assert!((off_diag.sub(&d).unwrap().norm() - 1.0).abs() < 1e-14);
+}
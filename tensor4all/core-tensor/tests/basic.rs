@@ -14,7 +14,8 @@ fn test_storage_dense_f64() {
             assert_eq!(v.capacity(), 10);
         }
         Storage::DenseC64(_) => panic!("expected DenseF64"),
-        Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseF64"),
+        Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+            | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseF64"),
     }
 }
 
@@ -29,7 +30,8 @@ fn test_storage_dense_c64() {
             assert_eq!(v.capacity(), 10);
         }
         Storage::DenseF64(_) => panic!("expected DenseC64"),
-        Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseC64"),
+        Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+            | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseC64"),
     }
 }
 
@@ -38,7 +40,8 @@ fn test_storage_factory_f64() {
     let storage = <f64 as DenseStorageFactory>::new_dense(7);
     match storage {
             Storage::DenseF64(v) => assert_eq!(v.capacity(), 7),
-            Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseF64"),
+            Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+                | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseF64"),
     }
 }
 
@@ -47,7 +50,8 @@ fn test_storage_factory_c64() {
     let storage = <Complex64 as DenseStorageFactory>::new_dense(9);
     match storage {
             Storage::DenseC64(v) => assert_eq!(v.capacity(), 9),
-            Storage::DenseF64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseC64"),
+            Storage::DenseF64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+                | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseC64"),
     }
 }
 
@@ -67,7 +71,8 @@ fn test_cow_storage() {
                 v.push(1.0);
                 v.push(2.0);
             }
-            Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseF64"),
+            Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+                | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseF64"),
         }
     }
     
@@ -79,7 +84,8 @@ fn test_cow_storage() {
         Storage::DenseF64(v) => {
             assert_eq!(v.len(), 0);
         }
-        Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseF64"),
+        Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+            | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseF64"),
     }
     
     // storage1 should have the new data
@@ -89,7 +95,8 @@ fn test_cow_storage() {
             assert_eq!(v.get(0), 1.0);
             assert_eq!(v.get(1), 2.0);
         }
-        Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseF64"),
+        Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+            | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseF64"),
     }
 }
 
@@ -139,7 +146,8 @@ fn test_tensor_cow() {
             Storage::DenseF64(v) => {
                 v.push(42.0);
             }
-            Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseF64"),
+            Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+                | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseF64"),
         }
     }
     
@@ -151,7 +159,8 @@ fn test_tensor_cow() {
         Storage::DenseF64(v) => {
             assert_eq!(v.len(), 0);
         }
-        Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseF64"),
+        Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+            | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseF64"),
     }
     
     // tensor1's storage should have the new data
@@ -160,7 +169,8 @@ fn test_tensor_cow() {
             assert_eq!(v.len(), 1);
             assert_eq!(v.get(0), 42.0);
         }
-        Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseF64"),
+        Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+            | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseF64"),
     }
 }
 
@@ -173,7 +183,8 @@ fn test_tensor_sum_f64_no_match() {
         let s = make_mut_storage(&mut storage);
         match s {
             Storage::DenseF64(v) => v.extend([1.0, 2.0, 3.0].iter().copied()),
-            Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseF64"),
+            Storage::DenseC64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+                | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseF64"),
         }
     }
 
@@ -194,7 +205,8 @@ fn test_tensor_sum_c64() {
         let s = make_mut_storage(&mut storage);
         match s {
             Storage::DenseC64(v) => v.extend([Complex64::new(1.0, 2.0), Complex64::new(3.0, -1.0)]),
-            Storage::DenseF64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) => panic!("expected DenseC64"),
+            Storage::DenseF64(_) | Storage::DiagF64(_) | Storage::DiagC64(_) | Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_)
+                | Storage::DenseF32(_) | Storage::DenseC32(_) | Storage::DiagF32(_) | Storage::DiagC32(_) => panic!("expected DenseC64"),
         }
     }
 
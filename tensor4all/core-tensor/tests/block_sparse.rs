@@ -0,0 +1,205 @@
+use tensor4all_core_common::index::{DynId, Index};
+use tensor4all_core_common::qn::{Arrow, QNSpace, QN};
+use tensor4all_core_tensor::storage::DenseStorageF64;
+use tensor4all_core_tensor::{AnyScalar, BlockSparseStorageF64, Storage, TensorDynLen};
+use num_complex::Complex64;
+use std::sync::Arc;
+
+type QNIndex = Index<DynId, QNSpace>;
+
+/// Spin-1/2-like space: one state with charge 0, one with charge 1.
+fn site_space(dir: Arrow) -> QNSpace {
+    QNSpace::new(vec![(QN::u1(0), 1), (QN::u1(1), 1)], dir)
+}
+
+/// Link space with blocks of dimension 2 (charge 0) and 1 (charge 1).
+fn link_space(dir: Arrow) -> QNSpace {
+    QNSpace::new(vec![(QN::u1(0), 2), (QN::u1(1), 1)], dir)
+}
+
+fn dense_data(storage: &Storage, dims: &[usize]) -> Vec<f64> {
+    match storage.to_dense_storage(dims) {
+        Storage::DenseF64(d) => d.as_slice().to_vec(),
+        other => panic!("expected DenseF64, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_block_sparse_storage_roundtrip() {
+    let block_dims = vec![vec![1, 2], vec![2, 1]];
+    let data: Vec<f64> = (1..=9).map(|x| x as f64).collect();
+    let storage = BlockSparseStorageF64::from_dense(block_dims, &data, |c| c[0] == c[1]);
+
+    assert_eq!(storage.nblocks(), 2);
+    assert_eq!(storage.len(), 4);
+    assert_eq!(storage.block(&[0, 0]).unwrap(), &[1.0, 2.0]);
+    assert_eq!(storage.block(&[1, 1]).unwrap(), &[6.0, 9.0]);
+    assert!(storage.block(&[0, 1]).is_none());
+    assert_eq!(
+        storage.to_dense_vec(),
+        vec![1.0, 2.0, 0.0, 0.0, 0.0, 6.0, 0.0, 0.0, 9.0]
+    );
+}
+
+#[test]
+fn test_zeros_with_flux() {
+    let i = QNIndex::new_dyn_with_space(site_space(Arrow::Out));
+    let j = QNIndex::new_dyn_with_space(site_space(Arrow::In));
+
+    // Flux zero: only blocks where the charges of i and j agree are allowed
+    let t = TensorDynLen::zeros_with_flux(vec![i.clone(), j.clone()], QN::u1(0)).unwrap();
+    assert_eq!(t.dims, vec![2, 2]);
+    assert!(t.storage.is_block_sparse());
+    match t.storage.as_ref() {
+        Storage::BlockSparseF64(b) => {
+            assert_eq!(b.nblocks(), 2);
+            assert!(b.block(&[0, 0]).is_some());
+            assert!(b.block(&[1, 1]).is_some());
+        }
+        other => panic!("expected BlockSparseF64, got {:?}", other),
+    }
+    assert_eq!(t.flux(), Some(QN::u1(0)));
+
+    // Flux one: only the block (1, 0)
+    let t1 = TensorDynLen::zeros_with_flux(vec![i, j], QN::u1(1)).unwrap();
+    match t1.storage.as_ref() {
+        Storage::BlockSparseF64(b) => {
+            assert_eq!(b.nblocks(), 1);
+            assert!(b.block(&[1, 0]).is_some());
+        }
+        other => panic!("expected BlockSparseF64, got {:?}", other),
+    }
+    assert_eq!(t1.flux(), Some(QN::u1(1)));
+}
+
+#[test]
+fn test_from_dense_with_flux_rejects_wrong_flux() {
+    let i = QNIndex::new_dyn_with_space(site_space(Arrow::Out));
+    let j = QNIndex::new_dyn_with_space(site_space(Arrow::In));
+
+    let diag = vec![1.0, 0.0, 0.0, 2.0];
+    let t = TensorDynLen::from_dense_with_flux(vec![i.clone(), j.clone()], &diag, QN::u1(0)).unwrap();
+    assert_eq!(dense_data(&t.storage, &t.dims), diag);
+
+    let off_diag = vec![1.0, 3.0, 0.0, 2.0];
+    assert!(TensorDynLen::from_dense_with_flux(vec![i.clone(), j.clone()], &off_diag, QN::u1(0)).is_err());
+
+    // Mismatching QN sectors
+    assert!(TensorDynLen::from_dense_with_flux(vec![i, j], &diag, QN::zn(0, 2)).is_err());
+}
+
+#[test]
+fn test_block_sparse_permute() {
+    let i = QNIndex::new_dyn_with_space(site_space(Arrow::Out));
+    let l = QNIndex::new_dyn_with_space(link_space(Arrow::In));
+    let r = QNIndex::new_dyn_with_space(link_space(Arrow::Out));
+    let indices = vec![l.clone(), i.clone(), r.clone()];
+
+    let mut data = vec![0.0; 3 * 2 * 3];
+    for (n, x) in data.iter_mut().enumerate() {
+        *x = n as f64 + 1.0;
+    }
+    // Keep only the entries allowed by flux zero
+    let zeros = TensorDynLen::zeros_with_flux(indices.clone(), QN::u1(0)).unwrap();
+    let mask = match zeros.storage.as_ref() {
+        Storage::BlockSparseF64(b) => b.map(|_| 1.0).to_dense_vec(),
+        other => panic!("expected BlockSparseF64, got {:?}", other),
+    };
+    let data: Vec<f64> = data.iter().zip(&mask).map(|(x, m)| x * m).collect();
+
+    let t = TensorDynLen::from_dense_with_flux(indices.clone(), &data, QN::u1(0)).unwrap();
+    let permuted = t.permute_indices(&[r.clone(), l.clone(), i.clone()]);
+    assert!(permuted.storage.is_block_sparse());
+    assert_eq!(permuted.dims, vec![3, 3, 2]);
+    assert_eq!(permuted.flux(), Some(QN::u1(0)));
+
+    // Compare with the dense permutation
+    let dense = TensorDynLen::<DynId, QNSpace>::new(
+        indices,
+        vec![3, 2, 3],
+        Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data))),
+    );
+    let dense_permuted = dense.permute_indices(&[r, l, i]);
+    assert_eq!(
+        dense_data(&permuted.storage, &permuted.dims),
+        dense_data(&dense_permuted.storage, &dense_permuted.dims)
+    );
+}
+
+#[test]
+fn test_block_sparse_contract_matches_dense() {
+    let s1 = QNIndex::new_dyn_with_space(site_space(Arrow::Out));
+    let s2 = QNIndex::new_dyn_with_space(site_space(Arrow::Out));
+    let link = QNIndex::new_dyn_with_space(link_space(Arrow::Out));
+
+    // A(s1, link) with flux 1, B(link†, s2) with flux 0
+    let a_data = vec![0.0, 0.0, 1.5, 2.0, -1.0, 0.0];
+    let b_data = vec![3.0, 0.0, 4.0, 0.0, 0.0, 5.0];
+    let a = TensorDynLen::from_dense_with_flux(vec![s1.clone(), link.clone()], &a_data, QN::u1(1)).unwrap();
+    let b = TensorDynLen::from_dense_with_flux(vec![link.dag(), s2.clone()], &b_data, QN::u1(0)).unwrap();
+
+    let c = a.contract(&b);
+    assert!(c.storage.is_block_sparse());
+    assert_eq!(c.dims, vec![2, 2]);
+    assert_eq!(c.flux(), Some(QN::u1(1)));
+
+    let to_dense = |t: &TensorDynLen<DynId, QNSpace>| {
+        TensorDynLen::<DynId, QNSpace>::new(
+            t.indices.clone(),
+            t.dims.clone(),
+            Arc::new(t.storage.to_dense_storage(&t.dims)),
+        )
+    };
+    let c_dense = to_dense(&a).contract(&to_dense(&b));
+    assert_eq!(dense_data(&c.storage, &c.dims), dense_data(&c_dense.storage, &c_dense.dims));
+
+    // Mixed block-sparse × dense contraction would lose the block structure
+    let b_dense = to_dense(&b);
+    assert!(a.contract_pairs(&b_dense, &[(link.clone(), link.dag())]).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Common index spaces are not dual")]
+fn test_block_sparse_contract_rejects_same_arrows() {
+    let s1 = QNIndex::new_dyn_with_space(site_space(Arrow::Out));
+    let link = QNIndex::new_dyn_with_space(link_space(Arrow::Out));
+
+    let a = TensorDynLen::zeros_with_flux(vec![s1.clone(), link.clone()], QN::u1(1)).unwrap();
+    let b = TensorDynLen::zeros_with_flux(vec![link, s1.dag()], QN::u1(0)).unwrap();
+    a.contract(&b);
+}
+
+#[test]
+fn test_block_sparse_contract_pairs_rejects_mismatched_sectors() {
+    let s = QNIndex::new_dyn_with_space(site_space(Arrow::Out));
+    let l1 = QNIndex::new_dyn_with_space(link_space(Arrow::Out));
+    // Same total dimension as `l1`, but different sectors
+    let l2 = QNIndex::new_dyn_with_space(QNSpace::new(vec![(QN::u1(0), 1), (QN::u1(1), 2)], Arrow::In));
+
+    let a = TensorDynLen::zeros_with_flux(vec![s.clone(), l1.clone()], QN::u1(1)).unwrap();
+    let b = TensorDynLen::zeros_with_flux(vec![l2.clone(), s.dag()], QN::u1(0)).unwrap();
+    assert!(a.contract_pairs(&b, &[(l1, l2)]).is_err());
+}
+
+#[test]
+fn test_block_sparse_complex_promotion_and_sum() {
+    let i = QNIndex::new_dyn_with_space(site_space(Arrow::Out));
+    let j = QNIndex::new_dyn_with_space(site_space(Arrow::In));
+
+    let a = TensorDynLen::from_dense_with_flux(vec![i.clone(), j.clone()], &[1.0, 0.0, 0.0, 2.0], QN::u1(0)).unwrap();
+    let b_data = vec![
+        Complex64::new(0.0, 1.0),
+        Complex64::new(0.0, 0.0),
+        Complex64::new(0.0, 0.0),
+        Complex64::new(3.0, 0.0),
+    ];
+    let b = TensorDynLen::from_dense_with_flux(vec![i.dag(), j.dag()], &b_data, QN::u1(0)).unwrap();
+
+    // Full contraction: 1 * i + 2 * 3
+    let c = a.contract(&b);
+    assert_eq!(c.sum(), AnyScalar::C64(Complex64::new(6.0, 1.0)));
+
+    let sum = a.add(&a).unwrap();
+    assert!(sum.storage.is_block_sparse());
+    assert_eq!(sum.sum_f64(), 6.0);
+}
@@ -418,3 +418,114 @@ fn test_contract_pairs_empty_pairs() {
     }
 }
 
+
+/// Reference contraction by explicit summation over all multi-indices.
+fn reference_contract(
+    a: &[f64],
+    dims_a: &[usize],
+    axes_a: &[usize],
+    b: &[f64],
+    dims_b: &[usize],
+    axes_b: &[usize],
+) -> Vec<f64> {
+    let free_a: Vec<usize> = (0..dims_a.len()).filter(|k| !axes_a.contains(k)).collect();
+    let free_b: Vec<usize> = (0..dims_b.len()).filter(|k| !axes_b.contains(k)).collect();
+    let result_dims: Vec<usize> = free_a
+        .iter()
+        .map(|&k| dims_a[k])
+        .chain(free_b.iter().map(|&k| dims_b[k]))
+        .collect();
+    let contracted_dims: Vec<usize> = axes_a.iter().map(|&k| dims_a[k]).collect();
+    let unravel = |mut pos: usize, dims: &[usize]| -> Vec<usize> {
+        let mut idx = vec![0; dims.len()];
+        for k in (0..dims.len()).rev() {
+            idx[k] = pos % dims[k];
+            pos /= dims[k];
+        }
+        idx
+    };
+    let ravel = |idx: &[usize], dims: &[usize]| idx.iter().zip(dims).fold(0, |acc, (&i, &d)| acc * d + i);
+
+    let n_result: usize = result_dims.iter().product();
+    let n_contracted: usize = contracted_dims.iter().product();
+    (0..n_result)
+        .map(|r| {
+            let ridx = unravel(r, &result_dims);
+            (0..n_contracted)
+                .map(|c| {
+                    let cidx = unravel(c, &contracted_dims);
+                    let mut idx_a = vec![0; dims_a.len()];
+                    let mut idx_b = vec![0; dims_b.len()];
+                    for (p, &k) in free_a.iter().enumerate() {
+                        idx_a[k] = ridx[p];
+                    }
+                    for (p, &k) in free_b.iter().enumerate() {
+                        idx_b[k] = ridx[free_a.len() + p];
+                    }
+                    for (p, (&ka, &kb)) in axes_a.iter().zip(axes_b).enumerate() {
+                        idx_a[ka] = cidx[p];
+                        idx_b[kb] = cidx[p];
+                    }
+                    a[ravel(&idx_a, dims_a)] * b[ravel(&idx_b, dims_b)]
+                })
+                .sum()
+        })
+        .collect()
+}
+
+#[test]
+fn test_dense_contract_matches_reference() {
+    let dims_a = [2, 3, 4, 5];
+    let dims_b = [4, 3, 6];
+    let a: Vec<f64> = (0..120).map(|x| ((x * 7 % 11) as f64) - 5.0).collect();
+    let b: Vec<f64> = (0..72).map(|x| ((x * 5 % 13) as f64) * 0.5).collect();
+    let storage_a = DenseStorageF64::from_vec(a.clone());
+    let storage_b = DenseStorageF64::from_vec(b.clone());
+
+    // Contracted axes leading/trailing in various orders (exercises transposed views
+    // and permutations of either operand)
+    let cases: [(&[usize], &[usize]); 4] = [
+        (&[1, 2], &[1, 0]),
+        (&[2, 1], &[0, 1]),
+        (&[2], &[0]),
+        (&[1], &[1]),
+    ];
+    for (axes_a, axes_b) in cases {
+        let result = storage_a.contract(&dims_a, axes_a, &storage_b, &dims_b, axes_b);
+        let expected = reference_contract(&a, &dims_a, axes_a, &b, &dims_b, axes_b);
+        assert_eq!(result.len(), expected.len());
+        for (x, y) in result.iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-10, "axes {:?}/{:?}: {} != {}", axes_a, axes_b, x, y);
+        }
+    }
+
+    // Left operand with contracted axes leading, right operand with contracted axes trailing
+    let dims_c = [6, 4, 3];
+    let c: Vec<f64> = (0..72).map(|x| (x as f64).sin()).collect();
+    let storage_c = DenseStorageF64::from_vec(c.clone());
+    let dims_d = [4, 3, 2];
+    let d: Vec<f64> = (0..24).map(|x| (x as f64).cos()).collect();
+    let storage_d = DenseStorageF64::from_vec(d.clone());
+    let result = storage_d.contract(&dims_d, &[0, 1], &storage_c, &dims_c, &[1, 2]);
+    let expected = reference_contract(&d, &dims_d, &[0, 1], &c, &dims_c, &[1, 2]);
+    for (x, y) in result.iter().zip(&expected) {
+        assert!((x - y).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn test_dense_contract_c64_transposed() {
+    // A[j, i] · B[k, j] -> C[i, k]: both operands enter the GEMM transposed
+    let a: Vec<Complex64> = (0..6).map(|x| Complex64::new(x as f64, 1.0)).collect();
+    let b: Vec<Complex64> = (0..12).map(|x| Complex64::new(1.0, x as f64)).collect();
+    let storage_a = DenseStorageC64::from_vec(a.clone());
+    let storage_b = DenseStorageC64::from_vec(b.clone());
+    let result = storage_a.contract(&[3, 2], &[0], &storage_b, &[4, 3], &[1]);
+    assert_eq!(result.len(), 8);
+    for i in 0..2 {
+        for k in 0..4 {
+            let expected: Complex64 = (0..3).map(|j| a[j * 2 + i] * b[k * 3 + j]).sum();
+            assert!((result.get(i * 4 + k) - expected).norm() < 1e-12);
+        }
+    }
+}
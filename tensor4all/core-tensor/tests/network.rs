@@ -0,0 +1,138 @@
+use tensor4all_core_tensor::{
+    contract_network, contract_network_with, contraction_path, diag_tensor_dyn_len, ContractionStrategy, Storage,
+    StorageScalar, TensorDynLen,
+};
+use tensor4all_core_tensor::storage::DenseStorageF64;
+use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
+use std::sync::Arc;
+
+fn dense_tensor(indices: &[Index<DynId>], seed: usize) -> TensorDynLen<DynId> {
+    let dims: Vec<usize> = indices.iter().map(|i| i.size()).collect();
+    let n: usize = dims.iter().product();
+    let data: Vec<f64> = (0..n).map(|x| (((x + 1) * (seed + 3)) % 7) as f64 - 3.0).collect();
+    TensorDynLen::new(indices.to_vec(), dims, Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data))))
+}
+
+fn assert_tensors_close(a: &TensorDynLen<DynId>, b: &TensorDynLen<DynId>) {
+    let b = b.permute_indices(&a.indices);
+    let a_data = f64::extract_dense(&a.storage).unwrap();
+    let b_data = f64::extract_dense(&b.storage).unwrap();
+    assert_eq!(a_data.len(), b_data.len());
+    for (x, y) in a_data.iter().zip(&b_data) {
+        assert!((x - y).abs() < 1e-10 * x.abs().max(1.0), "{} != {}", x, y);
+    }
+}
+
+#[test]
+fn test_contract_network_matches_sequential() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(3);
+    let k = Index::new_dyn(4);
+    let l = Index::new_dyn(2);
+    let m = Index::new_dyn(3);
+    let a = dense_tensor(&[i.clone(), j.clone(), l.clone()], 0);
+    let b = dense_tensor(&[j.clone(), k.clone()], 1);
+    let c = dense_tensor(&[k.clone(), m.clone()], 2);
+    let d = dense_tensor(&[m.clone(), l.clone()], 3);
+
+    let expected = a.contract(&b).contract(&c).contract(&d);
+    assert_eq!(expected.dims, vec![2]);
+    let tensors = [a, b, c, d];
+    for strategy in [ContractionStrategy::Auto, ContractionStrategy::Greedy, ContractionStrategy::Optimal] {
+        let result = contract_network_with(&tensors, strategy).unwrap();
+        assert_eq!(result.indices, vec![i.clone()]);
+        assert_tensors_close(&expected, &result);
+    }
+}
+
+#[test]
+fn test_contraction_path_matrix_chain() {
+    // A[i, j] B[j, k] C[k, l] with dims 100, 2, 100, 2:
+    // (A B) C costs 100·2·100 + 100·100·2 = 40000, A (B C) costs 2·100·2 + 100·2·2 = 800
+    let i = Index::new_dyn(100);
+    let j = Index::new_dyn(2);
+    let k = Index::new_dyn(100);
+    let l = Index::new_dyn(2);
+    let tensors = [
+        dense_tensor(&[i.clone(), j.clone()], 0),
+        dense_tensor(&[j.clone(), k.clone()], 1),
+        dense_tensor(&[k.clone(), l.clone()], 2),
+    ];
+
+    for strategy in [ContractionStrategy::Greedy, ContractionStrategy::Optimal] {
+        let path = contraction_path(&tensors, strategy).unwrap();
+        assert_eq!(path.steps, vec![(1, 2), (0, 3)]);
+        assert_eq!(path.cost, 800.0);
+        assert_eq!(path.max_intermediate_size, 200.0);
+    }
+
+    let result = contract_network(&tensors).unwrap();
+    let expected = tensors[0].contract(&tensors[1]).contract(&tensors[2]);
+    assert_tensors_close(&expected, &result);
+}
+
+#[test]
+fn test_contract_network_ring_strategies_agree() {
+    // Ring of 8 tensors with an open index each
+    let bonds: Vec<Index<DynId>> = (0..8).map(|b| Index::new_dyn(2 + b % 3)).collect();
+    let sites: Vec<Index<DynId>> = (0..8).map(|_| Index::new_dyn(2)).collect();
+    let tensors: Vec<TensorDynLen<DynId>> = (0..8)
+        .map(|t| dense_tensor(&[bonds[t].clone(), sites[t].clone(), bonds[(t + 1) % 8].clone()], t))
+        .collect();
+
+    let greedy = contraction_path(&tensors, ContractionStrategy::Greedy).unwrap();
+    let optimal = contraction_path(&tensors, ContractionStrategy::Optimal).unwrap();
+    assert_eq!(greedy.steps.len(), 7);
+    assert_eq!(optimal.steps.len(), 7);
+    assert!(optimal.cost <= greedy.cost);
+
+    let result_greedy = contract_network_with(&tensors, ContractionStrategy::Greedy).unwrap();
+    let result_optimal = contract_network_with(&tensors, ContractionStrategy::Optimal).unwrap();
+    assert_eq!(result_optimal.dims.len(), 8);
+    assert_tensors_close(&result_optimal, &result_greedy);
+}
+
+#[test]
+fn test_contract_network_outer_product() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let k = Index::new_dyn(3);
+    let l = Index::new_dyn(3);
+    let a = diag_tensor_dyn_len(vec![i.clone(), j.clone()], vec![1.0, 2.0]);
+    let b = diag_tensor_dyn_len(vec![k.clone(), l.clone()], vec![3.0, 4.0, 5.0]);
+
+    let result = contract_network(&[a, b]).unwrap();
+    let result = result.permute_indices(&[i, j, k, l]);
+    assert_eq!(result.dims, vec![2, 2, 3, 3]);
+    let data = f64::extract_dense(&result.storage).unwrap();
+    // result[0, 0, 2, 2] = 1 · 5, result[1, 1, 1, 1] = 2 · 4, result[1, 0, 1, 1] = 0
+    assert_eq!(data[8], 5.0);
+    assert_eq!(data[18 + 9 + 3 + 1], 8.0);
+    assert_eq!(data[18 + 4], 0.0);
+    assert_eq!(data.iter().filter(|&&x| x != 0.0).count(), 6);
+}
+
+#[test]
+fn test_contract_network_single_and_errors() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(3);
+    let a = dense_tensor(&[i.clone(), j.clone()], 0);
+
+    // A single tensor is returned as is
+    let result = contract_network(std::slice::from_ref(&a)).unwrap();
+    assert_eq!(result.indices, a.indices);
+
+    // Empty network
+    let empty: [TensorDynLen<DynId>; 0] = [];
+    assert!(contract_network(&empty).is_err());
+
+    // Index shared by three tensors
+    let b = dense_tensor(&[j.clone()], 1);
+    let c = dense_tensor(&[j.clone()], 2);
+    assert!(contract_network(&[a.clone(), b, c]).is_err());
+
+    // Dimension mismatch for a shared index
+    let j_wrong = Index::new_with_size(j.id, 4);
+    let d = dense_tensor(&[j_wrong], 3);
+    assert!(contract_network(&[a, d]).is_err());
+}
@@ -0,0 +1,152 @@
+use num_complex::{Complex32, Complex64};
+use std::sync::Arc;
+use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
+use tensor4all_core_tensor::storage::{DenseStorageF32, DiagStorageF32};
+use tensor4all_core_tensor::{ScalarType, Storage, StorageScalar, TensorDynLen};
+
+fn dense_f32(indices: Vec<Index<DynId>>, data: Vec<f32>) -> TensorDynLen<DynId> {
+    TensorDynLen::from_indices(indices, f32::dense_storage(data))
+}
+
+#[test]
+fn test_scalar_type_promotion() {
+    use ScalarType::*;
+    assert_eq!(F32.promote(F32), F32);
+    assert_eq!(F32.promote(F64), F64);
+    assert_eq!(F32.promote(C32), C32);
+    assert_eq!(F32.promote(C64), C64);
+    assert_eq!(C32.promote(F64), C64);
+    assert_eq!(F64.promote(C64), C64);
+    assert_eq!(C32.promote(C32), C32);
+}
+
+#[test]
+fn test_contract_f32_stays_f32() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(3);
+    let k = Index::new_dyn(2);
+    let a = dense_f32(vec![i.clone(), j.clone()], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = dense_f32(vec![j.clone(), k.clone()], vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+
+    let c = a.contract(&b);
+    assert_eq!(c.scalar_type(), ScalarType::F32);
+    // C = A B = [[1+3, 2+3], [4+6, 5+6]]
+    assert_eq!(f32::extract_dense(&c.storage).unwrap(), vec![4.0, 5.0, 10.0, 11.0]);
+}
+
+#[test]
+fn test_contract_f32_with_f64_promotes() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let a = dense_f32(vec![i.clone(), j.clone()], vec![1.0, 2.0, 3.0, 4.0]);
+    let b: TensorDynLen<DynId> = TensorDynLen::from_indices(vec![j.clone()], f64::dense_storage(vec![0.5, 0.25]));
+
+    let c = a.contract(&b);
+    assert_eq!(c.scalar_type(), ScalarType::F64);
+    assert_eq!(f64::extract_dense(&c.storage).unwrap(), vec![1.0, 2.5]);
+}
+
+#[test]
+fn test_contract_f32_with_complex_promotes() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let a = dense_f32(vec![i.clone(), j.clone()], vec![1.0, 2.0, 3.0, 4.0]);
+
+    let b32: TensorDynLen<DynId> = TensorDynLen::from_indices(
+        vec![j.clone()],
+        Complex32::dense_storage(vec![Complex32::new(0.0, 1.0), Complex32::new(1.0, 0.0)]),
+    );
+    let c = a.contract(&b32);
+    assert_eq!(c.scalar_type(), ScalarType::C32);
+    assert_eq!(
+        Complex32::extract_dense(&c.storage).unwrap(),
+        vec![Complex32::new(2.0, 1.0), Complex32::new(4.0, 3.0)]
+    );
+
+    let b64: TensorDynLen<DynId> = TensorDynLen::from_indices(
+        vec![j.clone()],
+        Complex64::dense_storage(vec![Complex64::new(0.0, 1.0), Complex64::new(1.0, 0.0)]),
+    );
+    let c = a.contract(&b64);
+    assert_eq!(c.scalar_type(), ScalarType::C64);
+    assert_eq!(
+        Complex64::extract_dense(&c.storage).unwrap(),
+        vec![Complex64::new(2.0, 1.0), Complex64::new(4.0, 3.0)]
+    );
+}
+
+#[test]
+fn test_contract_diag_f32() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let k = Index::new_dyn(2);
+    let d: TensorDynLen<DynId> = TensorDynLen::from_indices(
+        vec![i.clone(), j.clone()],
+        Arc::new(Storage::DiagF32(DiagStorageF32::from_vec(vec![2.0, 3.0]))),
+    );
+    let a = dense_f32(vec![j.clone(), k.clone()], vec![1.0, 2.0, 3.0, 4.0]);
+
+    let c = d.contract(&a);
+    assert_eq!(c.scalar_type(), ScalarType::F32);
+    assert_eq!(f32::extract_dense(&c.storage).unwrap(), vec![2.0, 4.0, 9.0, 12.0]);
+
+    // Diag × Diag keeps the diagonal layout
+    let e: TensorDynLen<DynId> = TensorDynLen::from_indices(
+        vec![j.clone(), k.clone()],
+        Arc::new(Storage::DiagF32(DiagStorageF32::from_vec(vec![1.0, -1.0]))),
+    );
+    let f = d.contract(&e);
+    match f.storage.as_ref() {
+        Storage::DiagF32(s) => assert_eq!(s.as_slice(), &[2.0, -3.0]),
+        other => panic!("expected DiagF32 storage, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_add_promotes_scalar_type() {
+    let a = Storage::DenseF32(DenseStorageF32::from_vec(vec![1.0, 2.0]));
+    let b = Storage::DenseF64(tensor4all_core_tensor::storage::DenseStorageF64::from_vec(vec![0.5, 0.5]));
+    let sum = a.try_add(&b).unwrap();
+    assert_eq!(sum.scalar_type(), ScalarType::F64);
+    assert_eq!(f64::extract_dense(&sum).unwrap(), vec![1.5, 2.5]);
+
+    let c = Storage::new_diag_c32(vec![Complex32::new(0.0, 1.0)]);
+    let d = Storage::new_diag_f32(vec![1.0]);
+    match c.try_add(&d).unwrap() {
+        Storage::DiagC32(s) => assert_eq!(s.as_slice(), &[Complex32::new(1.0, 1.0)]),
+        other => panic!("expected DiagC32 storage, got {:?}", other),
+    }
+
+    // Layouts must still match
+    let diag = Storage::new_diag_f64(vec![1.0, 2.0]);
+    assert!(a.try_add(&diag).is_err());
+}
+
+#[test]
+fn test_to_scalar_type_and_scaling() {
+    let i = Index::new_dyn(3);
+    let t: TensorDynLen<DynId> = TensorDynLen::from_indices(vec![i.clone()], f64::dense_storage(vec![1.0, 2.0, 3.0]));
+
+    let t32 = t.to_scalar_type(ScalarType::F32);
+    assert_eq!(f32::extract_dense(&t32.storage).unwrap(), vec![1.0, 2.0, 3.0]);
+    assert_eq!(t32.sum_f64(), 6.0);
+
+    // Real scaling keeps single precision, complex scaling promotes to Complex32
+    assert_eq!((t32.storage.as_ref() * 2.0).scalar_type(), ScalarType::F32);
+    let scaled = t32.storage.as_ref() * Complex64::new(0.0, 1.0);
+    assert_eq!(
+        Complex32::extract_dense(&scaled).unwrap(),
+        vec![Complex32::new(0.0, 1.0), Complex32::new(0.0, 2.0), Complex32::new(0.0, 3.0)]
+    );
+
+    let back = t32.to_scalar_type(ScalarType::C64);
+    assert_eq!(back.scalar_type(), ScalarType::C64);
+    assert_eq!(back.sum(), Complex64::new(6.0, 0.0).into());
+}
+
+#[test]
+#[should_panic(expected = "Cannot convert complex storage to real type")]
+fn test_to_scalar_type_complex_to_real_panics() {
+    let s = Storage::new_diag_c32(vec![Complex32::new(1.0, 1.0)]);
+    let _ = s.to_scalar_type(ScalarType::F64);
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "serde")]
+
+use num_complex::Complex64;
+use std::sync::Arc;
+use tensor4all_core_common::index::{DefaultIndex as Index, DynId};
+use tensor4all_core_tensor::storage::{DenseStorageC64, DenseStorageF64, DiagStorageC64, DiagStorageF64};
+use tensor4all_core_tensor::{Storage, TensorDynLen};
+
+fn roundtrip(tensor: &TensorDynLen<DynId>) -> TensorDynLen<DynId> {
+    let json = serde_json::to_string(tensor).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn test_storage_roundtrip_all_variants() {
+    let storages = vec![
+        Storage::DenseF64(DenseStorageF64::from_vec(vec![1.0, -2.5, 3.0])),
+        Storage::DenseC64(DenseStorageC64::from_vec(vec![Complex64::new(1.0, -1.0), Complex64::new(0.5, 2.0)])),
+        Storage::DiagF64(DiagStorageF64::from_vec(vec![4.0, 5.0])),
+        Storage::DiagC64(DiagStorageC64::from_vec(vec![Complex64::new(0.0, 1.0)])),
+    ];
+
+    for storage in &storages {
+        let json = serde_json::to_string(storage).unwrap();
+        let restored: Storage = serde_json::from_str(&json).unwrap();
+        match (storage, &restored) {
+            (Storage::DenseF64(a), Storage::DenseF64(b)) => assert_eq!(a.as_slice(), b.as_slice()),
+            (Storage::DenseC64(a), Storage::DenseC64(b)) => assert_eq!(a.as_slice(), b.as_slice()),
+            (Storage::DiagF64(a), Storage::DiagF64(b)) => assert_eq!(a.as_slice(), b.as_slice()),
+            (Storage::DiagC64(a), Storage::DiagC64(b)) => assert_eq!(a.as_slice(), b.as_slice()),
+            _ => panic!("storage variant changed during roundtrip"),
+        }
+    }
+}
+
+#[test]
+fn test_tensor_roundtrip_preserves_index_ids() {
+    let i = Index::new_dyn_with_tag(2, "Site").unwrap();
+    let j = Index::new_dyn(3);
+    let data: Vec<f64> = (0..6).map(|x| x as f64).collect();
+    let storage = Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(data.clone())));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), j.clone()], vec![2, 3], storage);
+
+    let restored = roundtrip(&tensor);
+    assert_eq!(restored.indices, vec![i.clone(), j]);
+    assert_eq!(restored.indices[0].tags(), i.tags());
+    assert_eq!(restored.dims, vec![2, 3]);
+    match restored.storage.as_ref() {
+        Storage::DenseF64(v) => assert_eq!(v.as_slice(), data.as_slice()),
+        _ => panic!("expected DenseF64"),
+    }
+}
+
+#[test]
+fn test_tensor_roundtrip_diag() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(2);
+    let storage = Arc::new(Storage::DiagC64(DiagStorageC64::from_vec(vec![
+        Complex64::new(1.0, 0.0),
+        Complex64::new(0.0, -1.0),
+    ])));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i, j], vec![2, 2], storage);
+
+    let restored = roundtrip(&tensor);
+    assert!(restored.storage.is_diag());
+    assert_eq!(restored.indices, tensor.indices);
+}
+
+#[test]
+fn test_tensor_deserialize_rejects_invalid_data() {
+    let i = Index::new_dyn(2);
+    let j = Index::new_dyn(3);
+    let storage = Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(vec![0.0; 6])));
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(vec![i.clone(), j], vec![2, 3], storage);
+    let json = serde_json::to_string(&tensor).unwrap();
+
+    // Storage length does not match dims
+    let bad = json.replace(r#""dims":[2,3]"#, r#""dims":[2,2]"#);
+    assert_ne!(bad, json);
+    assert!(serde_json::from_str::<TensorDynLen<DynId>>(&bad).is_err());
+
+    // Duplicate index IDs
+    let index = serde_json::to_string(&i).unwrap();
+    let bad = format!(
+        r#"{{"indices":[{},{}],"dims":[2,2],"storage":{{"DenseF64":[0.0,0.0,0.0,0.0]}}}}"#,
+        index, index
+    );
+    assert!(serde_json::from_str::<TensorDynLen<DynId>>(&bad).is_err());
+}
+
+#[test]
+fn test_tensor_roundtrip_block_sparse() {
+    use tensor4all_core_common::index::Index as GenericIndex;
+    use tensor4all_core_common::qn::{Arrow, QNSpace, QN};
+
+    let space = QNSpace::new(vec![(QN::u1(0), 1), (QN::u1(1), 2)], Arrow::Out);
+    let i = GenericIndex::<DynId, QNSpace>::new_dyn_with_space(space.clone());
+    let j = GenericIndex::<DynId, QNSpace>::new_dyn_with_space(space.dag());
+    let data = vec![1.0, 0.0, 0.0, 0.0, 2.0, 3.0, 0.0, 4.0, 5.0];
+    let tensor = TensorDynLen::from_dense_with_flux(vec![i, j], &data, QN::u1(0)).unwrap();
+
+    let json = serde_json::to_string(&tensor).unwrap();
+    let restored: TensorDynLen<DynId, QNSpace> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.indices, tensor.indices);
+    assert_eq!(restored.flux(), Some(QN::u1(0)));
+    match (tensor.storage.as_ref(), restored.storage.as_ref()) {
+        (Storage::BlockSparseF64(a), Storage::BlockSparseF64(b)) => assert_eq!(a, b),
+        _ => panic!("expected BlockSparseF64"),
+    }
+
+    // Block structure does not match dims
+    let bad = json.replace(r#""dims":[3,3]"#, r#""dims":[3,2]"#);
+    assert_ne!(bad, json);
+    assert!(serde_json::from_str::<TensorDynLen<DynId, QNSpace>>(&bad).is_err());
+}
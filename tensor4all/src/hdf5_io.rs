@@ -0,0 +1,526 @@
+//! HDF5 I/O compatible with ITensors.jl and ITensorMPS.jl
+//!
+//! Objects are written with the same group layout ITensors.jl uses for
+//! `Index`, `ITensor` and `MPS`, so files can be exchanged with Julia:
+//!
+//! ```julia
+//! using ITensors, ITensorMPS, HDF5
+//! f = h5open("data.h5", "r")
+//! psi = read(f, "psi", MPS)
+//! ```
+//!
+//! Conventions:
+//! - ITensors.jl index IDs are `UInt64`. The lower 64 bits of a [`DynId`] are
+//!   written as `id` and the upper 64 bits as an extra `id_high` dataset, which
+//!   Julia ignores; IDs of files without `id_high` are zero-extended.
+//! - Indices carry no direction here; the ITensors.jl `dir` of an index is
+//!   available through [`read_index_with_dir`] and [`write_index_with_dir`].
+//! - Tensor data is stored column-major as in Julia and transposed to and
+//!   from the row-major layout used here.
+//! - Prime levels and quantum number spaces are not supported.
+
+use anyhow::{anyhow, bail, Result};
+use hdf5::types::VarLenUnicode;
+use hdf5::{Group, H5Type};
+use num_complex::{Complex32, Complex64};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tensor4all_core_common::index::{DefaultIndex, DynId, NoSymmSpace};
+use tensor4all_core_common::qn::Arrow;
+use tensor4all_core_common::tagset::DefaultTagSet;
+use tensor4all_core_tensor::storage::{
+    DenseStorageC32, DenseStorageC64, DenseStorageF32, DenseStorageF64, DiagStorageC32, DiagStorageC64,
+    DiagStorageF32, DiagStorageF64,
+};
+use tensor4all_core_tensor::{Storage, TensorDynLen};
+use tensor4all_tensortrain::{AbstractTensorTrain, TTScalar, Tensor3, TensorTrain};
+
+/// Index type stored in HDF5 files.
+pub type H5Index = DefaultIndex<DynId, NoSymmSpace>;
+
+/// Scalar types that can be stored in ITensors.jl-compatible files.
+pub trait H5Scalar: TTScalar + H5Type {
+    /// Julia name of the element type (e.g. `Float64`).
+    const JULIA_TYPE: &'static str;
+}
+
+impl H5Scalar for f64 {
+    const JULIA_TYPE: &'static str = "Float64";
+}
+
+impl H5Scalar for Complex64 {
+    const JULIA_TYPE: &'static str = "ComplexF64";
+}
+
+impl H5Scalar for f32 {
+    const JULIA_TYPE: &'static str = "Float32";
+}
+
+impl H5Scalar for Complex32 {
+    const JULIA_TYPE: &'static str = "ComplexF32";
+}
+
+// ---------------------------------------------------------------------------
+// Low-level helpers
+// ---------------------------------------------------------------------------
+
+fn to_varlen(s: &str) -> Result<VarLenUnicode> {
+    s.parse::<VarLenUnicode>()
+        .map_err(|e| anyhow!("invalid HDF5 string {:?}: {:?}", s, e))
+}
+
+fn write_attr_str(group: &Group, name: &str, value: &str) -> Result<()> {
+    group
+        .new_attr::<VarLenUnicode>()
+        .create(name)?
+        .write_scalar(&to_varlen(value)?)?;
+    Ok(())
+}
+
+fn read_attr_str(group: &Group, name: &str) -> Result<String> {
+    Ok(group.attr(name)?.read_scalar::<VarLenUnicode>()?.as_str().to_string())
+}
+
+fn write_attr_i64(group: &Group, name: &str, value: i64) -> Result<()> {
+    group.new_attr::<i64>().create(name)?.write_scalar(&value)?;
+    Ok(())
+}
+
+fn write_scalar<T: H5Type>(group: &Group, name: &str, value: &T) -> Result<()> {
+    group.new_dataset::<T>().create(name)?.write_scalar(value)?;
+    Ok(())
+}
+
+fn read_scalar<T: H5Type>(group: &Group, name: &str) -> Result<T> {
+    Ok(group.dataset(name)?.read_scalar::<T>()?)
+}
+
+fn write_str(group: &Group, name: &str, value: &str) -> Result<()> {
+    write_scalar(group, name, &to_varlen(value)?)
+}
+
+fn read_str(group: &Group, name: &str) -> Result<String> {
+    Ok(read_scalar::<VarLenUnicode>(group, name)?.as_str().to_string())
+}
+
+fn write_slice<T: H5Type>(group: &Group, name: &str, data: &[T]) -> Result<()> {
+    group.new_dataset_builder().with_data(data).create(name)?;
+    Ok(())
+}
+
+fn read_vec<T: H5Type>(group: &Group, name: &str) -> Result<Vec<T>> {
+    Ok(group.dataset(name)?.read_raw::<T>()?)
+}
+
+/// Create a group tagged with the ITensors.jl type name and version 1.
+fn create_typed_group(parent: &Group, name: &str, type_name: &str) -> Result<Group> {
+    let group = parent.create_group(name)?;
+    write_attr_str(&group, "type", type_name)?;
+    write_attr_i64(&group, "version", 1)?;
+    Ok(group)
+}
+
+/// Open a group and check its ITensors.jl type name.
+fn open_typed_group(parent: &Group, name: &str, type_name: &str) -> Result<Group> {
+    let group = parent.group(name)?;
+    let found = read_attr_str(&group, "type")?;
+    if found != type_name {
+        bail!("HDF5 group {:?} has type {:?}, expected {:?}", name, found, type_name);
+    }
+    Ok(group)
+}
+
+/// Reorder row-major `data` with shape `dims` into column-major order.
+fn row_to_column_major<T: Copy>(data: &[T], dims: &[usize]) -> Vec<T> {
+    // Row-major data over `dims` is column-major data over the reversed dims,
+    // and column-major order over `dims` is row-major order over the reversed dims.
+    let reversed: Vec<usize> = dims.iter().rev().copied().collect();
+    let identity: Vec<usize> = (0..dims.len()).collect();
+    column_to_row_major(data, &reversed, &identity)
+}
+
+/// Reorder column-major `data` with shape `dims` into row-major order over
+/// the axes `perm` (output axis `k` is input axis `perm[k]`).
+fn column_to_row_major<T: Copy>(data: &[T], dims: &[usize], perm: &[usize]) -> Vec<T> {
+    let rank = dims.len();
+    let mut strides = vec![1usize; rank];
+    for k in 1..rank {
+        strides[k] = strides[k - 1] * dims[k - 1];
+    }
+    let out_dims: Vec<usize> = perm.iter().map(|&p| dims[p]).collect();
+    let total: usize = dims.iter().product();
+
+    let mut out = Vec::with_capacity(total);
+    let mut idx = vec![0usize; rank];
+    for _ in 0..total {
+        let offset: usize = idx.iter().zip(perm).map(|(&i, &p)| i * strides[p]).sum();
+        out.push(data[offset]);
+        for k in (0..rank).rev() {
+            idx[k] += 1;
+            if idx[k] < out_dims[k] {
+                break;
+            }
+            idx[k] = 0;
+        }
+    }
+    out
+}
+
+/// Product of dimensions, checking the data length.
+fn check_len(len: usize, dims: &[usize], what: &str) -> Result<()> {
+    let expected: usize = dims.iter().product();
+    if len != expected {
+        bail!("{} has {} elements, expected {} for dims {:?}", what, len, expected, dims);
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Index
+// ---------------------------------------------------------------------------
+
+fn write_tagset(parent: &Group, name: &str, tags: &DefaultTagSet) -> Result<()> {
+    let group = create_typed_group(parent, name, "TagSet")?;
+    let tagstring = tags.iter().map(|t| t.as_str()).collect::<Vec<_>>().join(",");
+    write_str(&group, "tags", &tagstring)
+}
+
+fn read_tagset(parent: &Group, name: &str) -> Result<DefaultTagSet> {
+    let group = open_typed_group(parent, name, "TagSet")?;
+    let tagstring = read_str(&group, "tags")?;
+    DefaultTagSet::from_str(&tagstring).map_err(|e| anyhow!("invalid tags {:?}: {:?}", tagstring, e))
+}
+
+/// Write an index as an ITensors.jl `Index` group with direction `Neither`.
+pub fn write_index(parent: &Group, name: &str, index: &H5Index) -> Result<()> {
+    write_index_with_dir(parent, name, index, None)
+}
+
+/// Write an index as an ITensors.jl `Index` group with the given direction.
+///
+/// `None` is written as ITensors.jl's `Neither`.
+pub fn write_index_with_dir(parent: &Group, name: &str, index: &H5Index, dir: Option<Arrow>) -> Result<()> {
+    let group = create_typed_group(parent, name, "Index")?;
+    write_scalar(&group, "id", &(index.id.0 as u64))?;
+    write_scalar(&group, "id_high", &((index.id.0 >> 64) as u64))?;
+    write_scalar(&group, "dim", &(index.size() as i64))?;
+    write_scalar(&group, "dir", &dir.map_or(0, Arrow::sign))?;
+    write_tagset(&group, "tags", index.tags())?;
+    write_scalar(&group, "plev", &0i64)?;
+    write_attr_str(&group, "space_type", "Int")
+}
+
+/// Read an index from an ITensors.jl `Index` group.
+///
+/// The direction is checked but dropped; use [`read_index_with_dir`] to keep it.
+pub fn read_index(parent: &Group, name: &str) -> Result<H5Index> {
+    read_index_with_dir(parent, name).map(|(index, _)| index)
+}
+
+/// Read an index and its direction from an ITensors.jl `Index` group.
+///
+/// The direction is `None` for ITensors.jl's `Neither`.
+pub fn read_index_with_dir(parent: &Group, name: &str) -> Result<(H5Index, Option<Arrow>)> {
+    let group = open_typed_group(parent, name, "Index")?;
+    let space_type = read_attr_str(&group, "space_type")?;
+    if space_type != "Int" {
+        bail!("index {:?} has space type {:?}; only \"Int\" is supported", name, space_type);
+    }
+    let plev: i64 = read_scalar(&group, "plev")?;
+    if plev != 0 {
+        bail!("index {:?} has prime level {}; primed indices are not supported", name, plev);
+    }
+    let dir = match read_scalar::<i64>(&group, "dir")? {
+        -1 => Some(Arrow::In),
+        0 => None,
+        1 => Some(Arrow::Out),
+        d => bail!("index {:?} has invalid direction {}", name, d),
+    };
+    let low: u64 = read_scalar(&group, "id")?;
+    let high: u64 = if group.link_exists("id_high") {
+        read_scalar(&group, "id_high")?
+    } else {
+        0
+    };
+    let id = DynId(((high as u128) << 64) | low as u128);
+    let dim: i64 = read_scalar(&group, "dim")?;
+    let tags = read_tagset(&group, "tags")?;
+    Ok((H5Index::new_with_size_and_tags(id, dim as usize, tags), dir))
+}
+
+fn write_index_set(parent: &Group, name: &str, indices: &[H5Index]) -> Result<()> {
+    let group = create_typed_group(parent, name, "IndexSet")?;
+    write_scalar(&group, "length", &(indices.len() as i64))?;
+    for (n, index) in indices.iter().enumerate() {
+        write_index(&group, &format!("index_{}", n + 1), index)?;
+    }
+    Ok(())
+}
+
+fn read_index_set(parent: &Group, name: &str) -> Result<Vec<H5Index>> {
+    let group = open_typed_group(parent, name, "IndexSet")?;
+    let len: i64 = read_scalar(&group, "length")?;
+    (1..=len)
+        .map(|n| read_index(&group, &format!("index_{}", n)))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// ITensor
+// ---------------------------------------------------------------------------
+
+fn dense_type<T: H5Scalar>() -> String {
+    format!("Dense{{{}}}", T::JULIA_TYPE)
+}
+
+fn diag_type<T: H5Scalar>() -> String {
+    format!("Diag{{{},Vector{{{}}}}}", T::JULIA_TYPE, T::JULIA_TYPE)
+}
+
+fn write_storage<T: H5Scalar>(parent: &Group, type_name: &str, data: &[T]) -> Result<()> {
+    let group = create_typed_group(parent, "storage", type_name)?;
+    write_slice(&group, "data", data)
+}
+
+/// Write an `ITensor` group with dense column-major data.
+fn write_dense_itensor<T: H5Scalar>(
+    parent: &Group,
+    name: &str,
+    indices: &[H5Index],
+    column_major: &[T],
+) -> Result<()> {
+    let group = create_typed_group(parent, name, "ITensor")?;
+    write_index_set(&group, "inds", indices)?;
+    write_storage(&group, &dense_type::<T>(), column_major)
+}
+
+/// Read the dense column-major data of an `ITensor` group.
+fn read_dense_itensor<T: H5Scalar>(parent: &Group, name: &str) -> Result<(Vec<H5Index>, Vec<T>)> {
+    let group = open_typed_group(parent, name, "ITensor")?;
+    let indices = read_index_set(&group, "inds")?;
+    let storage = open_typed_group(&group, "storage", &dense_type::<T>())?;
+    let data = read_vec::<T>(&storage, "data")?;
+    let dims: Vec<usize> = indices.iter().map(|i| i.size()).collect();
+    check_len(data.len(), &dims, name)?;
+    Ok((indices, data))
+}
+
+/// Write a tensor as an ITensors.jl `ITensor` group.
+///
+/// Dense storage is written as `Dense{T}` and diagonal storage as
+/// `Diag{T,Vector{T}}`.
+///
+/// # Errors
+/// Returns an error for block-sparse storage, since quantum number indices
+/// are not supported and writing it densely would lose the block structure.
+pub fn write_tensor(parent: &Group, name: &str, tensor: &TensorDynLen<DynId>) -> Result<()> {
+    if tensor.storage.is_block_sparse() {
+        bail!("cannot write block-sparse ITensor {:?}: quantum number indices are not supported", name);
+    }
+    let group = create_typed_group(parent, name, "ITensor")?;
+    write_index_set(&group, "inds", &tensor.indices)?;
+    match tensor.storage.as_ref() {
+        Storage::DenseF64(s) => write_storage(
+            &group,
+            &dense_type::<f64>(),
+            &row_to_column_major(s.as_slice(), &tensor.dims),
+        ),
+        Storage::DenseC64(s) => write_storage(
+            &group,
+            &dense_type::<Complex64>(),
+            &row_to_column_major(s.as_slice(), &tensor.dims),
+        ),
+        Storage::DiagF64(s) => write_storage(&group, &diag_type::<f64>(), s.as_slice()),
+        Storage::DiagC64(s) => write_storage(&group, &diag_type::<Complex64>(), s.as_slice()),
+        Storage::BlockSparseF64(_) | Storage::BlockSparseC64(_) => unreachable!("rejected above"),
+        Storage::DenseF32(s) => write_storage(
+            &group,
+            &dense_type::<f32>(),
+            &row_to_column_major(s.as_slice(), &tensor.dims),
+        ),
+        Storage::DenseC32(s) => write_storage(
+            &group,
+            &dense_type::<Complex32>(),
+            &row_to_column_major(s.as_slice(), &tensor.dims),
+        ),
+        Storage::DiagF32(s) => write_storage(&group, &diag_type::<f32>(), s.as_slice()),
+        Storage::DiagC32(s) => write_storage(&group, &diag_type::<Complex32>(), s.as_slice()),
+    }
+}
+
+/// Read a tensor from an ITensors.jl `ITensor` group.
+///
+/// Supports `Dense` and `Diag` storage with `Float64`, `ComplexF64`, `Float32` or
+/// `ComplexF32` elements.
+pub fn read_tensor(parent: &Group, name: &str) -> Result<TensorDynLen<DynId>> {
+    let group = open_typed_group(parent, name, "ITensor")?;
+    let indices = read_index_set(&group, "inds")?;
+    let dims: Vec<usize> = indices.iter().map(|i| i.size()).collect();
+
+    let mut seen = HashSet::new();
+    if !indices.iter().all(|i| seen.insert(i.id)) {
+        bail!("ITensor {:?} has duplicate indices", name);
+    }
+
+    let storage_group = group.group("storage")?;
+    let storage_type = read_attr_str(&storage_group, "type")?;
+    let is_diag = storage_type.starts_with("Diag");
+    if is_diag && dims.windows(2).any(|w| w[0] != w[1]) {
+        bail!("diagonal ITensor {:?} has indices of different dimensions: {:?}", name, dims);
+    }
+    let diag_len = [dims.first().copied().unwrap_or(1)];
+    let identity: Vec<usize> = (0..dims.len()).collect();
+
+    let storage = if storage_type == dense_type::<f64>() {
+        let data = read_vec::<f64>(&storage_group, "data")?;
+        check_len(data.len(), &dims, name)?;
+        Storage::DenseF64(DenseStorageF64::from_vec(column_to_row_major(&data, &dims, &identity)))
+    } else if storage_type == dense_type::<Complex64>() {
+        let data = read_vec::<Complex64>(&storage_group, "data")?;
+        check_len(data.len(), &dims, name)?;
+        Storage::DenseC64(DenseStorageC64::from_vec(column_to_row_major(&data, &dims, &identity)))
+    } else if storage_type == diag_type::<f64>() {
+        let data = read_vec::<f64>(&storage_group, "data")?;
+        check_len(data.len(), &diag_len, name)?;
+        Storage::DiagF64(DiagStorageF64::from_vec(data))
+    } else if storage_type == diag_type::<Complex64>() {
+        let data = read_vec::<Complex64>(&storage_group, "data")?;
+        check_len(data.len(), &diag_len, name)?;
+        Storage::DiagC64(DiagStorageC64::from_vec(data))
+    } else if storage_type == dense_type::<f32>() {
+        let data = read_vec::<f32>(&storage_group, "data")?;
+        check_len(data.len(), &dims, name)?;
+        Storage::DenseF32(DenseStorageF32::from_vec(column_to_row_major(&data, &dims, &identity)))
+    } else if storage_type == dense_type::<Complex32>() {
+        let data = read_vec::<Complex32>(&storage_group, "data")?;
+        check_len(data.len(), &dims, name)?;
+        Storage::DenseC32(DenseStorageC32::from_vec(column_to_row_major(&data, &dims, &identity)))
+    } else if storage_type == diag_type::<f32>() {
+        let data = read_vec::<f32>(&storage_group, "data")?;
+        check_len(data.len(), &diag_len, name)?;
+        Storage::DiagF32(DiagStorageF32::from_vec(data))
+    } else if storage_type == diag_type::<Complex32>() {
+        let data = read_vec::<Complex32>(&storage_group, "data")?;
+        check_len(data.len(), &diag_len, name)?;
+        Storage::DiagC32(DiagStorageC32::from_vec(data))
+    } else {
+        bail!("unsupported ITensor storage type {:?}", storage_type);
+    };
+
+    Ok(TensorDynLen::new(indices, dims, Arc::new(storage)))
+}
+
+// ---------------------------------------------------------------------------
+// MPS
+// ---------------------------------------------------------------------------
+
+/// Write a tensor train as an ITensorMPS.jl `MPS` group.
+///
+/// `sites` are the site indices of the MPS; new link indices tagged
+/// `"Link,l=n"` are created for the bonds. Boundary bonds of dimension 1 are
+/// omitted, as in ITensorMPS.jl.
+pub fn write_tensor_train<T: H5Scalar>(
+    parent: &Group,
+    name: &str,
+    tt: &TensorTrain<T>,
+    sites: &[H5Index],
+) -> Result<()> {
+    let n = tt.len();
+    if sites.len() != n {
+        bail!("got {} site indices for a tensor train of length {}", sites.len(), n);
+    }
+    let tensors = tt.site_tensors();
+    if n > 0 && (tensors[0].left_dim() != 1 || tensors[n - 1].right_dim() != 1) {
+        bail!("boundary bond dimensions of the tensor train must be 1");
+    }
+    for (i, (tensor, site)) in tensors.iter().zip(sites).enumerate() {
+        if tensor.site_dim() != site.size() {
+            bail!(
+                "site index {} has dimension {}, but the tensor has site dimension {}",
+                i,
+                site.size(),
+                tensor.site_dim()
+            );
+        }
+    }
+
+    let links: Vec<H5Index> = (0..n.saturating_sub(1))
+        .map(|b| {
+            let tags = DefaultTagSet::from_str(&format!("Link,l={}", b + 1))
+                .map_err(|e| anyhow!("invalid link tags: {:?}", e))?;
+            Ok(H5Index::new_dyn_with_tags(tensors[b].right_dim(), tags))
+        })
+        .collect::<Result<_>>()?;
+
+    let group = create_typed_group(parent, name, "MPS")?;
+    write_scalar(&group, "length", &(n as i64))?;
+    write_scalar(&group, "rlim", &(n as i64 + 1))?;
+    write_scalar(&group, "llim", &0i64)?;
+    for (i, tensor) in tensors.iter().enumerate() {
+        let mut indices = Vec::with_capacity(3);
+        if i > 0 {
+            indices.push(links[i - 1].clone());
+        }
+        indices.push(sites[i].clone());
+        if i + 1 < n {
+            indices.push(links[i].clone());
+        }
+        // Boundary dimensions are 1, so the column-major layout over (l, s, r)
+        // matches the layout over the written indices
+        let dims = [tensor.left_dim(), tensor.site_dim(), tensor.right_dim()];
+        let data = row_to_column_major(&tensor.as_left_matrix().0, &dims);
+        write_dense_itensor(&group, &format!("MPS[{}]", i + 1), &indices, &data)?;
+    }
+    Ok(())
+}
+
+/// Read a tensor train from an ITensorMPS.jl `MPS` group.
+///
+/// Returns the tensor train and its site indices. Link indices are identified
+/// as the indices shared between neighbouring tensors.
+pub fn read_tensor_train<T: H5Scalar>(parent: &Group, name: &str) -> Result<(TensorTrain<T>, Vec<H5Index>)> {
+    let group = open_typed_group(parent, name, "MPS")?;
+    let n: i64 = read_scalar(&group, "length")?;
+    let site_data = (1..=n)
+        .map(|i| read_dense_itensor::<T>(&group, &format!("MPS[{}]", i)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let n = site_data.len();
+    let mut tensors = Vec::with_capacity(n);
+    let mut sites = Vec::with_capacity(n);
+    for i in 0..n {
+        let (indices, data) = &site_data[i];
+        let shared_with = |j: usize| indices.iter().position(|idx| site_data[j].0.contains(idx));
+        let left = if i > 0 { shared_with(i - 1) } else { None };
+        let right = if i + 1 < n { shared_with(i + 1) } else { None };
+        if (i > 0 && left.is_none()) || (i + 1 < n && right.is_none()) {
+            bail!("MPS tensor {} does not share a link index with its neighbours", i + 1);
+        }
+
+        let site_positions: Vec<usize> = (0..indices.len())
+            .filter(|&p| Some(p) != left && Some(p) != right)
+            .collect();
+        if site_positions.len() != 1 {
+            bail!(
+                "MPS tensor {} has {} site indices, expected exactly one",
+                i + 1,
+                site_positions.len()
+            );
+        }
+        let s = site_positions[0];
+
+        let dims: Vec<usize> = indices.iter().map(|idx| idx.size()).collect();
+        let perm: Vec<usize> = left.into_iter().chain([s]).chain(right).collect();
+        let left_dim = left.map_or(1, |p| dims[p]);
+        let right_dim = right.map_or(1, |p| dims[p]);
+        tensors.push(Tensor3::from_data(
+            column_to_row_major(data, &dims, &perm),
+            left_dim,
+            dims[s],
+            right_dim,
+        ));
+        sites.push(indices[s].clone());
+    }
+
+    Ok((TensorTrain::new(tensors)?, sites))
+}
@@ -7,3 +7,7 @@ pub use tensor4all_core_tensor::*;
 // Re-export everything from core-linalg (when enabled)
 #[cfg(feature = "linalg")]
 pub use tensor4all_core_linalg::*;
+
+// ITensors.jl-compatible HDF5 I/O (when enabled)
+#[cfg(feature = "hdf5")]
+pub mod hdf5_io;
@@ -0,0 +1,148 @@
+#![cfg(feature = "hdf5")]
+
+use num_complex::Complex64;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tensor4all::hdf5_io::{
+    read_index, read_index_with_dir, read_tensor, read_tensor_train, write_index, write_index_with_dir,
+    write_tensor, write_tensor_train, H5Index,
+};
+use tensor4all::qn::Arrow;
+use tensor4all::storage::{DenseStorageC64, DenseStorageF64};
+use tensor4all::{DynId, Storage, TensorDynLen};
+use tensor4all_tensortrain::{AbstractTensorTrain, Tensor3, TensorTrain};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("tensor4all_{}_{}.h5", name, std::process::id()))
+}
+
+#[test]
+fn test_index_roundtrip() {
+    let path = temp_path("index");
+    let index = H5Index::new_dyn_with_tag(4, "Site").unwrap();
+    {
+        let file = hdf5::File::create(&path).unwrap();
+        write_index(&file, "i", &index).unwrap();
+    }
+    let file = hdf5::File::open(&path).unwrap();
+    let restored = read_index(&file, "i").unwrap();
+    assert_eq!(restored.id, index.id);
+    assert_eq!(restored.size(), 4);
+    assert!(restored.tags().has_tag("Site"));
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_index_dir_and_id_roundtrip() {
+    let path = temp_path("index_dir");
+    let index = H5Index::new_with_size(DynId(u128::MAX - 7), 3);
+    {
+        let file = hdf5::File::create(&path).unwrap();
+        write_index_with_dir(&file, "i", &index, Some(Arrow::In)).unwrap();
+        write_index(&file, "j", &index).unwrap();
+
+        // Julia reads only the lower 64 bits
+        let low: u64 = file.dataset("i/id").unwrap().read_scalar().unwrap();
+        assert_eq!(low, u64::MAX - 7);
+    }
+    let file = hdf5::File::open(&path).unwrap();
+    let (restored, dir) = read_index_with_dir(&file, "i").unwrap();
+    assert_eq!(restored.id, index.id);
+    assert_eq!(dir, Some(Arrow::In));
+    assert_eq!(read_index_with_dir(&file, "j").unwrap().1, None);
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_tensor_roundtrip_dense() {
+    let path = temp_path("tensor");
+    let i = H5Index::new_dyn_with_tag(2, "i").unwrap();
+    let j = H5Index::new_dyn_with_tag(3, "j").unwrap();
+    let data: Vec<Complex64> = (0..6).map(|x| Complex64::new(x as f64, -(x as f64))).collect();
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(
+        vec![i, j],
+        vec![2, 3],
+        Arc::new(Storage::DenseC64(DenseStorageC64::from_vec(data.clone()))),
+    );
+    {
+        let file = hdf5::File::create(&path).unwrap();
+        write_tensor(&file, "T", &tensor).unwrap();
+
+        // Julia reads the data column-major: T[i=1, j=2] is the third element
+        let stored: Vec<Complex64> = file.dataset("T/storage/data").unwrap().read_raw().unwrap();
+        assert_eq!(stored[2], data[1]);
+    }
+    let file = hdf5::File::open(&path).unwrap();
+    let restored = read_tensor(&file, "T").unwrap();
+    assert_eq!(restored.dims, vec![2, 3]);
+    match restored.storage.as_ref() {
+        Storage::DenseC64(s) => assert_eq!(s.as_slice(), data.as_slice()),
+        _ => panic!("expected DenseC64"),
+    }
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_tensor_rejects_wrong_type() {
+    let path = temp_path("wrong_type");
+    let i = H5Index::new_dyn(2);
+    {
+        let file = hdf5::File::create(&path).unwrap();
+        write_index(&file, "i", &i).unwrap();
+        let tensor: TensorDynLen<DynId> = TensorDynLen::new(
+            vec![i],
+            vec![2],
+            Arc::new(Storage::DenseF64(DenseStorageF64::from_vec(vec![1.0, 2.0]))),
+        );
+        write_tensor(&file, "T", &tensor).unwrap();
+    }
+    let file = hdf5::File::open(&path).unwrap();
+    assert!(read_tensor(&file, "i").is_err());
+    assert!(read_index(&file, "T").is_err());
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_tensor_rejects_block_sparse() {
+    let path = temp_path("block_sparse");
+    let i = H5Index::new_dyn(3);
+    let tensor: TensorDynLen<DynId> = TensorDynLen::new(
+        vec![i],
+        vec![3],
+        Arc::new(Storage::new_block_sparse_f64(vec![vec![1, 2]])),
+    );
+    let file = hdf5::File::create(&path).unwrap();
+    assert!(write_tensor(&file, "T", &tensor).is_err());
+    assert!(!file.link_exists("T"));
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_tensor_train_roundtrip() {
+    let path = temp_path("mps");
+    let tensors = vec![
+        Tensor3::from_data((0..6).map(|x| x as f64).collect(), 1, 3, 2),
+        Tensor3::from_data((0..12).map(|x| 0.5 * x as f64).collect(), 2, 2, 3),
+        Tensor3::from_data((0..6).map(|x| -(x as f64)).collect(), 3, 2, 1),
+    ];
+    let tt = TensorTrain::new(tensors).unwrap();
+    let sites: Vec<H5Index> = [3, 2, 2]
+        .iter()
+        .map(|&d| H5Index::new_dyn_with_tag(d, "Site").unwrap())
+        .collect();
+    {
+        let file = hdf5::File::create(&path).unwrap();
+        write_tensor_train(&file, "psi", &tt, &sites).unwrap();
+    }
+    let file = hdf5::File::open(&path).unwrap();
+    let (restored, restored_sites) = read_tensor_train::<f64>(&file, "psi").unwrap();
+    assert_eq!(restored.len(), 3);
+    assert_eq!(restored.link_dims(), tt.link_dims());
+    for (a, b) in restored_sites.iter().zip(&sites) {
+        assert_eq!(a.id, b.id);
+    }
+    for idx in [vec![0, 0, 0], vec![2, 1, 0], vec![1, 0, 1]] {
+        assert_eq!(restored.evaluate(&idx).unwrap(), tt.evaluate(&idx).unwrap());
+    }
+    std::fs::remove_file(&path).ok();
+}